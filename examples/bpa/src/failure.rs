@@ -0,0 +1,121 @@
+use std::fmt::Display;
+use std::fmt::Write as _;
+use std::io::ErrorKind;
+use std::process::ExitCode;
+
+use bpa_rs::ReconstructError;
+use clap::ValueEnum;
+
+/// How failures are reported on stderr.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// A sentence for people.
+    #[default]
+    Text,
+    /// One JSON object per failure, for scripts.
+    Json,
+}
+
+/// The ways a command can fail, each with its own exit status.
+///
+/// Status 1 is left for `diff` finding differences, and clap exits with 2
+/// when the arguments are invalid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// An input file is missing, unreadable or of an unsupported format, or
+    /// the arguments cannot be used with it.
+    BadInput,
+    /// An input file was read but its contents could not be parsed.
+    Parse,
+    /// No seed triangle was found, so no mesh was generated.
+    NoSeed,
+    /// The reconstruction failed for another reason, such as the radius.
+    Reconstruct,
+    /// The output file could not be written.
+    Write,
+    /// A triangle or time budget stopped the reconstruction. The partial mesh
+    /// was written.
+    Budget,
+}
+
+impl Failure {
+    const fn code(self) -> u8 {
+        match self {
+            Self::BadInput => 2,
+            Self::Parse => 3,
+            Self::NoSeed => 4,
+            Self::Reconstruct => 5,
+            Self::Write => 6,
+            Self::Budget => 7,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::BadInput => "bad_input",
+            Self::Parse => "parse",
+            Self::NoSeed => "no_seed",
+            Self::Reconstruct => "reconstruct",
+            Self::Write => "write",
+            Self::Budget => "budget",
+        }
+    }
+
+    /// Classifies an error reading an input file.
+    pub fn reading(e: &std::io::Error) -> Self {
+        match e.kind() {
+            ErrorKind::NotFound
+            | ErrorKind::PermissionDenied
+            | ErrorKind::InvalidInput
+            | ErrorKind::IsADirectory => Self::BadInput,
+            _ => Self::Parse,
+        }
+    }
+
+    /// Classifies an error returned by a reconstruction.
+    pub const fn reconstructing(e: &ReconstructError) -> Self {
+        match e {
+            ReconstructError::NoSeed(_) => Self::NoSeed,
+            _ => Self::Reconstruct,
+        }
+    }
+}
+
+/// Reports failures on stderr in the chosen format.
+#[derive(Clone, Copy, Debug)]
+pub struct Reporter {
+    pub format: ErrorFormat,
+}
+
+impl Reporter {
+    /// Reports the failure, returning its exit status.
+    pub fn fail(self, failure: Failure, message: impl Display) -> ExitCode {
+        match self.format {
+            ErrorFormat::Text => eprintln!("{message}"),
+            ErrorFormat::Json => eprintln!(
+                r#"{{"error":"{}","code":{},"message":{}}}"#,
+                failure.name(),
+                failure.code(),
+                json_string(&message.to_string())
+            ),
+        }
+        ExitCode::from(failure.code())
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", u32::from(c));
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,768 @@
+#![deny(clippy::all)]
+#![warn(clippy::cargo)]
+#![warn(clippy::complexity)]
+#![warn(clippy::pedantic)]
+#![warn(clippy::nursery)]
+#![warn(clippy::perf)]
+#![warn(missing_debug_implementations)]
+#![warn(missing_docs)]
+//! Command line tools for the Ball Pivoting Algorithm
+//!
+//! Exit status: 0 on success, 1 when `diff` finds differences, 2 for bad
+//! arguments or input files, 3 for unparsable input, 4 when no seed triangle
+//! is found, 5 when the reconstruction fails otherwise, 6 when the output
+//! cannot be written, and 7 when a budget stops the reconstruction early.
+
+mod failure;
+mod transform;
+
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use bpa_rs::config::DebugArtifacts;
+use bpa_rs::config::DebugOutput;
+use bpa_rs::config::Traversal;
+use bpa_rs::config::Winding;
+use bpa_rs::diff::diff;
+use bpa_rs::diff::distance;
+use bpa_rs::io::Encoding;
+use bpa_rs::io::FaceMetric;
+use bpa_rs::io::SaveOptions;
+use bpa_rs::io::VertexColors;
+use bpa_rs::io::load_cloud;
+use bpa_rs::io::load_ply_with_colors;
+use bpa_rs::io::load_point_cloud;
+use bpa_rs::io::load_stl;
+use bpa_rs::io::save_mesh;
+use bpa_rs::io::save_obj_with_options;
+use bpa_rs::io::save_points_and_normals;
+use bpa_rs::io::save_quality_ply;
+use bpa_rs::provenance::Provenance;
+use bpa_rs::render::Camera;
+use bpa_rs::render::render_png;
+use bpa_rs::report::Budget;
+use bpa_rs::report::Report;
+use bpa_rs::sampling::sample_surface;
+use bpa_rs::spacing::estimate_radius;
+use bpa_rs::units::Unit;
+use bpa_rs::units::scale;
+use bpa_rs::{Point, ReconstructionConfig, Triangle, reconstruct_with_config};
+use clap::ArgAction;
+use clap::Args;
+use clap::Parser;
+use clap::Subcommand;
+use clap::ValueEnum;
+use glam::Vec3;
+use log::LevelFilter;
+use log::info;
+
+use crate::failure::ErrorFormat;
+use crate::failure::Failure;
+use crate::failure::Reporter;
+use crate::transform::Transform;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    #[arg(
+        long = "verbose",
+        short = 'v',
+        action = ArgAction::Count,
+        global = true,
+        help = "log progress, repeat for more detail"
+    )]
+    verbose: u8,
+    #[arg(
+        long = "quiet",
+        short = 'q',
+        global = true,
+        conflicts_with = "verbose",
+        help = "print only errors and the result"
+    )]
+    quiet: bool,
+    #[arg(
+        long = "error-format",
+        value_enum,
+        default_value_t = ErrorFormat::Text,
+        global = true,
+        help = "how failures are reported on stderr"
+    )]
+    error_format: ErrorFormat,
+}
+
+impl Cli {
+    // RUST_LOG, when set, takes precedence.
+    const fn log_level(&self) -> LevelFilter {
+        if self.quiet {
+            return LevelFilter::Error;
+        }
+        match self.verbose {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Info,
+            2 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Measure how far a mesh is from a reference mesh, such as a
+    /// reconstruction from an earlier scan, for checking scanner and
+    /// processing changes. The meshes need not share vertices.
+    Compare {
+        #[arg(help = "the mesh to check")]
+        mesh: PathBuf,
+        #[arg(help = "the reference mesh")]
+        reference: PathBuf,
+        #[arg(
+            long = "metric",
+            value_enum,
+            value_delimiter = ',',
+            default_values_t = [Distance::Hausdorff, Distance::Chamfer],
+            help = "the distances to print"
+        )]
+        metrics: Vec<Distance>,
+        #[arg(long = "json", help = "print the distances as a JSON object")]
+        json: bool,
+    },
+    /// Convert a mesh or a point cloud to another format, without running a
+    /// reconstruction. An STL mesh can be written as .stl, .ply, .obj, .mesh
+    /// or .msh, and a xyz, pts or ply point cloud as ply, with its normals.
+    Convert {
+        #[arg(help = "the mesh or point cloud")]
+        input: PathBuf,
+        #[arg(help = "the file to write, in the format given by its extension")]
+        output: PathBuf,
+        #[arg(
+            long = "ascii",
+            help = "write a mesh as an ascii stl or ply file instead of binary"
+        )]
+        ascii: bool,
+    },
+    /// Compare two STL meshes, exiting with status 1 when they differ.
+    Diff {
+        #[arg(help = "the original mesh")]
+        a: PathBuf,
+        #[arg(help = "the mesh to compare with it")]
+        b: PathBuf,
+        #[arg(
+            long = "tolerance",
+            default_value_t = 1e-5,
+            help = "how far a vertex may move and still match"
+        )]
+        tolerance: f32,
+    },
+    /// Suggest ball radii from the spacing of the points in a xyz or ply
+    /// file, without running a reconstruction.
+    EstimateRadius {
+        #[arg(help = "the point cloud")]
+        input: PathBuf,
+        #[arg(
+            long = "k",
+            default_value_t = 8,
+            help = "the number of neighbours measured around each point"
+        )]
+        k: usize,
+        #[arg(
+            long = "sample",
+            default_value_t = 100_000,
+            help = "the most points to measure"
+        )]
+        sample: usize,
+    },
+    /// Reconstruct a mesh from a xyz or ply point cloud, and print statistics
+    /// describing it. The mesh is written in the format of the output
+    /// extension: .stl, .ply, .obj, with the colours of a ply cloud, or a
+    /// MEDIT or Gmsh surface for .mesh or .msh.
+    Reconstruct(ReconstructArgs),
+    /// Scatter points evenly over the surface of an STL mesh, writing them
+    /// as a ply point cloud with the normals of their triangles. For making
+    /// synthetic clouds, and clouds to compare with a mesh.
+    Sample {
+        #[arg(help = "the mesh")]
+        mesh: PathBuf,
+        #[arg(
+            long = "count",
+            short = 'n',
+            value_parser = parse_count,
+            default_value = "100_000",
+            help = "the number of points, underscores allowed"
+        )]
+        count: usize,
+        #[arg(
+            long = "output",
+            short = 'o',
+            help = "the ply file, by default the mesh with a .ply extension"
+        )]
+        output: Option<PathBuf>,
+        #[arg(
+            long = "seed",
+            default_value_t = 0,
+            help = "the seed of the random placement, for repeatable clouds"
+        )]
+        seed: u64,
+    },
+}
+
+#[derive(Args, Debug)]
+struct ReconstructArgs {
+    #[arg(help = "the point cloud")]
+    input: PathBuf,
+    #[arg(
+        long = "radius",
+        short = 'r',
+        value_parser = parse_radius,
+        allow_negative_numbers = true,
+        help = "the ball radius"
+    )]
+    radius: f32,
+    #[arg(
+        long = "output",
+        short = 'o',
+        help = "the mesh file, by default the input with a .stl extension"
+    )]
+    output: Option<PathBuf>,
+    #[arg(
+        long = "ascii",
+        help = "write an ascii stl or ply file instead of binary"
+    )]
+    ascii: bool,
+    #[arg(
+        long = "unit",
+        value_name = "UNIT",
+        help = "scale the mesh into mm, cm, m or in, recording the unit in the file"
+    )]
+    unit: Option<Unit>,
+    #[arg(
+        long = "input-unit",
+        value_name = "UNIT",
+        default_value_t = Unit::Meter,
+        requires = "unit",
+        help = "the unit of the point cloud, after any --scale"
+    )]
+    input_unit: Unit,
+    #[arg(
+        long = "traversal",
+        value_enum,
+        default_value_t = Growth::DepthFirst,
+        help = "the order the edges of the front are pivoted in"
+    )]
+    traversal: Growth,
+    #[arg(long = "clockwise", help = "emit triangles with clockwise winding")]
+    clockwise: bool,
+    #[arg(
+        long = "max-triangles",
+        help = "stop after this many triangles, writing the partial mesh"
+    )]
+    max_triangles: Option<usize>,
+    #[arg(
+        long = "timeout",
+        value_name = "SECS",
+        value_parser = parse_timeout,
+        help = "stop after this many seconds, writing the partial mesh"
+    )]
+    timeout: Option<Duration>,
+    #[arg(
+        long = "keep-largest-component",
+        help = "keep only the largest connected piece of the mesh"
+    )]
+    keep_largest_component: bool,
+    #[arg(
+        long = "min-component-faces",
+        value_name = "N",
+        help = "remove the connected pieces of the mesh with fewer than N triangles"
+    )]
+    min_component_faces: Option<usize>,
+    #[arg(
+        long = "debug-dir",
+        value_name = "PATH",
+        help = "write debugging files for each step to this directory"
+    )]
+    debug_dir: Option<PathBuf>,
+    #[arg(
+        long = "debug-artifacts",
+        value_name = "LIST",
+        value_parser = parse_artifacts,
+        default_value = "seed,front,candidates",
+        requires = "debug_dir",
+        help = "the debugging files to write, from seed, front and candidates"
+    )]
+    debug_artifacts: DebugArtifacts,
+    #[arg(
+        long = "inspect",
+        value_name = "METRIC",
+        value_enum,
+        help = "also write the mesh to a .quality.ply file, its faces coloured by this metric"
+    )]
+    inspect: Option<Metric>,
+    #[arg(
+        long = "thumbnail",
+        value_name = "PATH",
+        help = "also draw the mesh into this png image, for previews"
+    )]
+    thumbnail: Option<PathBuf>,
+    #[command(flatten)]
+    transform: Transform,
+}
+
+// The orders the front can be grown in.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Growth {
+    /// The newest edge first, as in the original implementation.
+    DepthFirst,
+    /// The oldest edge first, spreading evenly from the seed.
+    BreadthFirst,
+}
+
+// The distances between two meshes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Distance {
+    /// The furthest either mesh is from the other.
+    Hausdorff,
+    /// The mean distance of each mesh from the other, summed.
+    Chamfer,
+}
+
+// The measures a mesh can be coloured by for inspection.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Metric {
+    /// How far each triangle is from equilateral.
+    AspectRatio,
+    /// The area of each triangle.
+    Area,
+    /// How far the input points nearest each triangle are from it.
+    Distance,
+}
+
+fn parse_radius(arg: &str) -> Result<f32, String> {
+    let radius: f32 = arg.parse().map_err(|e| format!("{e}"))?;
+    if radius.is_finite() && radius > 0.0 {
+        Ok(radius)
+    } else {
+        Err(String::from("the ball radius must be a positive number"))
+    }
+}
+
+fn parse_artifacts(arg: &str) -> Result<DebugArtifacts, String> {
+    let mut artifacts = DebugArtifacts::default();
+    for name in arg.split(',') {
+        match name.trim() {
+            "seed" => artifacts.seed = true,
+            "front" => artifacts.front = true,
+            "candidates" => artifacts.candidates = true,
+            other => {
+                return Err(format!(
+                    "unknown artifact {other}, expected seed, front or candidates"
+                ));
+            }
+        }
+    }
+    Ok(artifacts)
+}
+
+fn parse_count(arg: &str) -> Result<usize, String> {
+    arg.replace('_', "").parse().map_err(|e| format!("{e}"))
+}
+
+fn parse_timeout(arg: &str) -> Result<Duration, String> {
+    let secs: f64 = arg.parse().map_err(|e| format!("{e}"))?;
+    Duration::try_from_secs_f64(secs).map_err(|e| format!("{e}"))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    env_logger::Builder::new()
+        .filter_level(cli.log_level())
+        .parse_default_env()
+        .init();
+
+    let reporter = Reporter {
+        format: cli.error_format,
+    };
+    match cli.command {
+        Command::Compare {
+            mesh,
+            reference,
+            metrics,
+            json,
+        } => run_compare(reporter, &mesh, &reference, &metrics, json),
+        Command::Convert {
+            input,
+            output,
+            ascii,
+        } => run_convert(reporter, &input, &output, ascii, cli.quiet),
+        Command::Diff { a, b, tolerance } => run_diff(reporter, &a, &b, tolerance),
+        Command::EstimateRadius { input, k, sample } => {
+            run_estimate_radius(reporter, &input, k, sample)
+        }
+        Command::Reconstruct(args) => run_reconstruct(reporter, &args, cli.quiet),
+        Command::Sample {
+            mesh,
+            count,
+            output,
+            seed,
+        } => {
+            let output = output.unwrap_or_else(|| mesh.with_extension("ply"));
+            run_sample(reporter, &mesh, count, &output, seed, cli.quiet)
+        }
+    }
+}
+
+fn run_compare(
+    reporter: Reporter,
+    mesh: &PathBuf,
+    reference: &PathBuf,
+    metrics: &[Distance],
+    json: bool,
+) -> ExitCode {
+    let (mesh, reference) = match (load_stl(mesh), load_stl(reference)) {
+        (Ok(mesh), Ok(reference)) => (mesh, reference),
+        (Err(e), _) | (_, Err(e)) => {
+            return reporter.fail(
+                Failure::reading(&e),
+                format!("Exception occurred reading a mesh: {e}"),
+            );
+        }
+    };
+
+    let Some(measured) = distance(&mesh, &reference) else {
+        return reporter.fail(Failure::BadInput, "Cannot compare a mesh without triangles");
+    };
+    let values: Vec<(&str, f32)> = metrics
+        .iter()
+        .map(|metric| match metric {
+            Distance::Hausdorff => ("hausdorff", measured.hausdorff),
+            Distance::Chamfer => ("chamfer", measured.chamfer),
+        })
+        .collect();
+    if json {
+        let fields: Vec<String> = values
+            .iter()
+            .map(|(name, value)| format!(r#""{name}":{value}"#))
+            .collect();
+        println!("{{{}}}", fields.join(","));
+    } else {
+        for (name, value) in values {
+            println!("{name}: {value}");
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_convert(
+    reporter: Reporter,
+    input: &Path,
+    output: &Path,
+    ascii: bool,
+    quiet: bool,
+) -> ExitCode {
+    if has_extension(input, "stl") {
+        let triangles = match load_stl(&input.to_path_buf()) {
+            Ok(triangles) => triangles,
+            Err(e) => {
+                return reporter.fail(
+                    Failure::reading(&e),
+                    format!("Exception occurred reading a mesh: {e}"),
+                );
+            }
+        };
+        let mut options = SaveOptions::default();
+        if ascii {
+            options = options.with_encoding(Encoding::Ascii);
+        }
+        if let Err(e) = save_mesh(output, &triangles, &options) {
+            let failure = if e.kind() == ErrorKind::InvalidInput {
+                Failure::BadInput
+            } else {
+                Failure::Write
+            };
+            return reporter.fail(
+                failure,
+                format!("Exception occurred while writing to file. {e}"),
+            );
+        }
+        if !quiet {
+            println!("triangles: {}", triangles.len());
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    if !has_extension(output, "ply") {
+        return reporter.fail(
+            Failure::BadInput,
+            format!(
+                "Cannot write a point cloud to {}, only to ply. Reconstruct it to write a mesh.",
+                output.display()
+            ),
+        );
+    }
+    let cloud = match load_point_cloud(input) {
+        Ok(cloud) => cloud,
+        Err(e) => {
+            return reporter.fail(
+                Failure::reading(&e),
+                format!("Exception occurred reading the point cloud: {e}"),
+            );
+        }
+    };
+    if let Err(e) = save_points_and_normals(&output.to_path_buf(), &cloud.to_points()) {
+        return reporter.fail(
+            Failure::Write,
+            format!("Exception occurred while writing to file. {e}"),
+        );
+    }
+    if !quiet {
+        println!("points: {}", cloud.len());
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_diff(reporter: Reporter, a: &PathBuf, b: &PathBuf, tolerance: f32) -> ExitCode {
+    let (a, b) = match (load_stl(a), load_stl(b)) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(e), _) | (_, Err(e)) => {
+            return reporter.fail(
+                Failure::reading(&e),
+                format!("Exception occurred reading a mesh: {e}"),
+            );
+        }
+    };
+
+    let result = diff(&a, &b, tolerance);
+    println!("unchanged: {}", result.unchanged);
+    println!("moved: {}", result.moved.len());
+    println!("removed: {}", result.removed.len());
+    println!("added: {}", result.added.len());
+    println!("max vertex deviation: {}", result.max_deviation);
+    if result.is_unchanged() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn run_estimate_radius(reporter: Reporter, input: &Path, k: usize, sample: usize) -> ExitCode {
+    let points = match load_cloud(input) {
+        Ok(points) => points,
+        Err(e) => {
+            return reporter.fail(
+                Failure::reading(&e),
+                format!("Exception occurred reading the point cloud: {e}"),
+            );
+        }
+    };
+
+    let Some(estimate) = estimate_radius(&points, k, sample) else {
+        return reporter.fail(
+            Failure::BadInput,
+            format!(
+                "Cannot estimate a radius from {} points with --k {k} and --sample {sample}",
+                points.len()
+            ),
+        );
+    };
+    println!("sampled points: {}", estimate.sampled);
+    println!("min radius: {}", estimate.min);
+    println!("typical radius: {}", estimate.typical);
+    println!("max radius: {}", estimate.max);
+    ExitCode::SUCCESS
+}
+
+fn run_reconstruct(reporter: Reporter, args: &ReconstructArgs, quiet: bool) -> ExitCode {
+    let loaded = if has_extension(&args.input, "ply") {
+        load_ply_with_colors(&args.input)
+    } else {
+        load_cloud(&args.input).map(|points| (points, None))
+    };
+    let (mut points, colors) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            return reporter.fail(
+                Failure::reading(&e),
+                format!("Exception occurred reading the point cloud: {e}"),
+            );
+        }
+    };
+    args.transform.apply(&mut points);
+
+    let config = ReconstructionConfig {
+        traversal: match args.traversal {
+            Growth::DepthFirst => Traversal::Lifo,
+            Growth::BreadthFirst => Traversal::Fifo,
+        },
+        winding: if args.clockwise {
+            Winding::Clockwise
+        } else {
+            Winding::CounterClockwise
+        },
+        max_triangles: args.max_triangles,
+        time_budget: args.timeout,
+        keep_largest_component: args.keep_largest_component,
+        min_component_faces: args.min_component_faces,
+        debug: args.debug_dir.clone().map(|dir| DebugOutput {
+            dir,
+            artifacts: args.debug_artifacts,
+        }),
+        ..ReconstructionConfig::new(args.radius)
+    };
+    let reconstruction = match reconstruct_with_config(&points, &config) {
+        Ok(reconstruction) => reconstruction,
+        Err(e) => {
+            return reporter.fail(
+                Failure::reconstructing(&e),
+                format!("Exception occurred reconstructing the surface: {e}"),
+            );
+        }
+    };
+    info!("reconstruction complete... saving");
+    let mut triangles = reconstruction.triangles;
+    if let Some(unit) = args.unit {
+        // The points are scaled too, so colours are still found by position.
+        let factor = args.input_unit.scale_to(unit);
+        scale(&mut triangles, factor);
+        for p in &mut points {
+            p.pos *= factor;
+        }
+    }
+    let provenance = args
+        .unit
+        .map(|unit| Provenance::new(&config).with_unit(unit));
+    let output = args
+        .output
+        .clone()
+        .unwrap_or_else(|| args.input.with_extension("stl"));
+    let saved = save_outputs(args, &output, &triangles, &points, colors, provenance);
+    if let Err(e) = saved {
+        return reporter.fail(
+            Failure::Write,
+            format!("Exception occurred while writing to file. {e}"),
+        );
+    }
+    let report = &reconstruction.report;
+    if !quiet {
+        print_stats(report, &args.transform);
+    }
+
+    // The partial mesh is still written, but scripts can tell it apart.
+    match report.budget_exceeded {
+        Some(Budget::Triangles) => reporter.fail(
+            Failure::Budget,
+            "Warning: stopped at the triangle limit, the mesh is partial",
+        ),
+        Some(Budget::Time) => reporter.fail(
+            Failure::Budget,
+            "Warning: stopped at the timeout, the mesh is partial",
+        ),
+        None => ExitCode::SUCCESS,
+    }
+}
+
+fn run_sample(
+    reporter: Reporter,
+    mesh: &PathBuf,
+    count: usize,
+    output: &Path,
+    seed: u64,
+    quiet: bool,
+) -> ExitCode {
+    if !has_extension(output, "ply") {
+        return reporter.fail(
+            Failure::BadInput,
+            format!(
+                "Cannot write a point cloud to {}, only to ply.",
+                output.display()
+            ),
+        );
+    }
+    let triangles = match load_stl(mesh) {
+        Ok(triangles) => triangles,
+        Err(e) => {
+            return reporter.fail(
+                Failure::reading(&e),
+                format!("Exception occurred reading a mesh: {e}"),
+            );
+        }
+    };
+
+    let points = sample_surface(&triangles, count, seed);
+    if points.is_empty() && count > 0 {
+        return reporter.fail(Failure::BadInput, "Cannot sample a mesh without area");
+    }
+    if let Err(e) = save_points_and_normals(&output.to_path_buf(), &points) {
+        return reporter.fail(
+            Failure::Write,
+            format!("Exception occurred while writing to file. {e}"),
+        );
+    }
+    if !quiet {
+        println!("points: {}", points.len());
+    }
+    ExitCode::SUCCESS
+}
+
+// Writes the mesh in the format given by the extension of `output`, and the
+// inspection file and thumbnail when asked for.
+fn save_outputs(
+    args: &ReconstructArgs,
+    output: &Path,
+    triangles: &[Triangle],
+    points: &[Point],
+    colors: Option<Vec<Vec3>>,
+    provenance: Option<Provenance>,
+) -> std::io::Result<()> {
+    let mut options = SaveOptions::default();
+    if args.ascii {
+        options = options.with_encoding(Encoding::Ascii);
+    }
+    if let Some(provenance) = provenance {
+        options = options.with_provenance(provenance);
+    }
+    match colors {
+        Some(colors) if has_extension(output, "obj") => {
+            let colors = VertexColors::new(points, &colors);
+            save_obj_with_options(output, triangles, Some(&colors), &options)?;
+        }
+        _ => save_mesh(output, triangles, &options)?,
+    }
+
+    if let Some(metric) = args.inspect {
+        let metric = match metric {
+            Metric::AspectRatio => FaceMetric::AspectRatio,
+            Metric::Area => FaceMetric::Area,
+            Metric::Distance => FaceMetric::Distance(points),
+        };
+        save_quality_ply(&output.with_extension("quality.ply"), triangles, metric)?;
+    }
+    if let Some(thumbnail) = &args.thumbnail {
+        // Seen from above, at a corner of the bounding box.
+        if let Some(camera) = Camera::framing(triangles, Vec3::new(-1.0, -1.0, -1.0)) {
+            if let Some(parent) = thumbnail.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(thumbnail, render_png(triangles, &camera, (512, 512)))?;
+        }
+    }
+    Ok(())
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+fn print_stats(report: &Report, transform: &Transform) {
+    println!("points: {}", report.points);
+    println!("transform: {transform}");
+    println!("radius: {}", report.radius);
+    println!("triangles: {}", report.triangles);
+    println!("orphans: {}", report.orphans.len());
+    if report.removed_components > 0 {
+        println!("removed components: {}", report.removed_components);
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use bpa_rs::Point;
+use clap::Args;
+use glam::Quat;
+use glam::Vec3;
+
+/// Changes applied to the cloud before reconstruction: scaling, then
+/// rotation, then translation.
+#[derive(Args, Clone, Copy, Debug)]
+pub struct Transform {
+    #[arg(
+        long = "scale",
+        value_parser = parse_scale,
+        default_value_t = 1.0,
+        help = "multiply the coordinates by this factor, for unit fixes"
+    )]
+    scale: f32,
+    #[arg(
+        long = "rotate",
+        value_parser = parse_rotate,
+        value_name = "AXIS,DEG",
+        help = "rotate around the x, y or z axis by this many degrees"
+    )]
+    rotate: Option<Rotation>,
+    #[arg(
+        long = "translate",
+        value_parser = parse_translate,
+        value_name = "X,Y,Z",
+        allow_hyphen_values = true,
+        help = "add this offset to the coordinates"
+    )]
+    translate: Option<Vec3>,
+}
+
+/// A rotation around a coordinate axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Rotation {
+    axis: char,
+    degrees: f32,
+}
+
+impl Rotation {
+    fn quat(self) -> Quat {
+        let angle = self.degrees.to_radians();
+        match self.axis {
+            'x' => Quat::from_rotation_x(angle),
+            'y' => Quat::from_rotation_y(angle),
+            _ => Quat::from_rotation_z(angle),
+        }
+    }
+}
+
+impl Transform {
+    /// True when the points are left as they are.
+    pub fn is_identity(&self) -> bool {
+        #[allow(clippy::float_cmp)]
+        let unscaled = self.scale == 1.0;
+        unscaled && self.rotate.is_none() && self.translate.is_none()
+    }
+
+    /// Moves the points in place. Normals are rotated with them.
+    pub fn apply(&self, points: &mut [Point]) {
+        if self.is_identity() {
+            return;
+        }
+        let rotation = self.rotate.map_or(Quat::IDENTITY, Rotation::quat);
+        let translation = self.translate.unwrap_or(Vec3::ZERO);
+        for p in points {
+            p.pos = rotation * (p.pos * self.scale) + translation;
+            p.normal = rotation * p.normal;
+        }
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut steps = vec![];
+        #[allow(clippy::float_cmp)]
+        if self.scale != 1.0 {
+            steps.push(format!("scale {}", self.scale));
+        }
+        if let Some(Rotation { axis, degrees }) = self.rotate {
+            steps.push(format!("rotate {axis} {degrees}°"));
+        }
+        if let Some(Vec3 { x, y, z }) = self.translate {
+            steps.push(format!("translate {x},{y},{z}"));
+        }
+        if steps.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", steps.join(", "))
+        }
+    }
+}
+
+fn parse_scale(arg: &str) -> Result<f32, String> {
+    let scale: f32 = arg.parse().map_err(|e| format!("{e}"))?;
+    if scale.is_finite() && scale > 0.0 {
+        Ok(scale)
+    } else {
+        Err(String::from("the scale must be a positive number"))
+    }
+}
+
+fn parse_rotate(arg: &str) -> Result<Rotation, String> {
+    let (axis, degrees) = arg
+        .split_once(',')
+        .ok_or_else(|| String::from("expected an axis and an angle, such as z,90"))?;
+    let axis = match axis.trim().to_ascii_lowercase().as_str() {
+        "x" => 'x',
+        "y" => 'y',
+        "z" => 'z',
+        other => return Err(format!("unknown axis {other}, expected x, y or z")),
+    };
+    let degrees: f32 = degrees.trim().parse().map_err(|e| format!("{e}"))?;
+    if degrees.is_finite() {
+        Ok(Rotation { axis, degrees })
+    } else {
+        Err(String::from("the angle must be a finite number"))
+    }
+}
+
+fn parse_translate(arg: &str) -> Result<Vec3, String> {
+    let values = arg
+        .split(',')
+        .map(|v| v.trim().parse::<f32>().map_err(|e| format!("{e}")))
+        .collect::<Result<Vec<f32>, String>>()?;
+    match values[..] {
+        [x, y, z] if values.iter().all(|v| v.is_finite()) => Ok(Vec3::new(x, y, z)),
+        [_, _, _] => Err(String::from("the offset must be finite")),
+        _ => Err(String::from(
+            "expected three comma separated values, such as 0,0,1",
+        )),
+    }
+}
@@ -7,49 +7,193 @@
 #![warn(missing_debug_implementations)]
 #![warn(missing_docs)]
 //! Convert a point cloud (.ply) file into a STL mesh
+//!
+//! Exit status: 0 on success, 2 for bad arguments or input files, 3 for
+//! unparsable input, 4 when no seed triangle is found, 5 when the
+//! reconstruction fails otherwise, and 6 when the output cannot be written.
+
+// Shared with the bpa tool, so the exit statuses match.
+#[path = "../../bpa/src/failure.rs"]
+#[allow(dead_code)]
+mod failure;
 
 use std::path::PathBuf;
+use std::process::ExitCode;
 
+use bpa_rs::config::Winding;
+use bpa_rs::io::save_orphans;
 use bpa_rs::io::save_triangles;
-use bpa_rs::{Point, reconstruct};
+use bpa_rs::io::save_triangles_ascii;
+use bpa_rs::io::save_triangles_with_provenance;
+use bpa_rs::provenance::Provenance;
+use bpa_rs::{Point, ReconstructionConfig, reconstruct_with_config};
+use clap::ArgAction;
 use clap::Parser;
+use log::LevelFilter;
 use log::info;
 
+use crate::failure::ErrorFormat;
+use crate::failure::Failure;
+use crate::failure::Reporter;
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about)]
+#[allow(clippy::struct_excessive_bools)]
 struct Cli {
     #[arg(long = "input", short = 'i', help = "point cloud file")]
     input: PathBuf,
-    #[clap(long = "radius", short = 'r')]
+    #[clap(
+        long = "radius",
+        short = 'r',
+        value_parser = parse_radius,
+        allow_negative_numbers = true
+    )]
     radius: f32,
     #[clap(long="output", help="output mesh file mesh", short='o', default_value=None)]
     output: Option<PathBuf>,
+    #[clap(
+        long = "orphans",
+        help = "write the points left out of the mesh to this ply file"
+    )]
+    orphans: Option<PathBuf>,
+    #[clap(long = "clockwise", help = "emit triangles with clockwise winding")]
+    clockwise: bool,
+    #[clap(
+        long = "provenance",
+        help = "record the version, radius, input hash and time in the stl header"
+    )]
+    provenance: bool,
+    #[clap(
+        long = "ascii",
+        help = "write an ascii stl file instead of binary",
+        conflicts_with = "provenance"
+    )]
+    ascii: bool,
+    #[clap(
+        long = "verbose",
+        short = 'v',
+        action = ArgAction::Count,
+        help = "log progress, repeat for more detail"
+    )]
+    verbose: u8,
+    #[clap(
+        long = "quiet",
+        short = 'q',
+        conflicts_with = "verbose",
+        help = "log only errors"
+    )]
+    quiet: bool,
+    #[clap(
+        long = "error-format",
+        value_enum,
+        default_value_t = ErrorFormat::Text,
+        help = "how failures are reported on stderr"
+    )]
+    error_format: ErrorFormat,
 }
 
-fn main() -> std::io::Result<()> {
-    env_logger::init();
-    info!("starting up");
+impl Cli {
+    // RUST_LOG, when set, takes precedence.
+    const fn log_level(&self) -> LevelFilter {
+        if self.quiet {
+            return LevelFilter::Error;
+        }
+        match self.verbose {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Info,
+            2 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
+}
+
+fn parse_radius(arg: &str) -> Result<f32, String> {
+    let radius: f32 = arg.parse().map_err(|e| format!("{e}"))?;
+    if radius.is_finite() && radius > 0.0 {
+        Ok(radius)
+    } else {
+        Err(String::from("the ball radius must be a positive number"))
+    }
+}
 
+fn main() -> ExitCode {
     let args = Cli::parse();
+    env_logger::Builder::new()
+        .filter_level(args.log_level())
+        .parse_default_env()
+        .init();
+    info!("starting up");
+    let reporter = Reporter {
+        format: args.error_format,
+    };
+
     let output = args.output.clone().unwrap_or_else(|| {
         let mut path = args.input.clone();
         path.set_extension("stl");
         path
     });
 
-    let points: Vec<Point> = bpa_rs::io::load_ply(&args.input)?;
+    let points: Vec<Point> = match bpa_rs::io::load_ply(&args.input) {
+        Ok(points) => points,
+        Err(e) => {
+            return reporter.fail(
+                Failure::reading(&e),
+                format!("Exception occurred reading the point cloud: {e}"),
+            );
+        }
+    };
 
-    match reconstruct(&points, args.radius) {
-        Some(triangles) => {
+    let config = ReconstructionConfig {
+        winding: if args.clockwise {
+            Winding::Clockwise
+        } else {
+            Winding::CounterClockwise
+        },
+        ..ReconstructionConfig::new(args.radius)
+    };
+    match reconstruct_with_config(&points, &config) {
+        Ok(reconstruction) => {
             info!("reconstruction complete... saving");
-            if let Err(e) = save_triangles(&output, &triangles) {
-                eprintln!("Exception occurred while writing to file. {e}");
+            let saved = if args.provenance {
+                Provenance::new(&config)
+                    .with_input(&args.input)
+                    .and_then(|provenance| {
+                        save_triangles_with_provenance(
+                            &output,
+                            &reconstruction.triangles,
+                            &provenance,
+                        )
+                    })
+            } else if args.ascii {
+                save_triangles_ascii(&output, &reconstruction.triangles)
+            } else {
+                save_triangles(&output, &reconstruction.triangles)
+            };
+            if let Err(e) = saved {
+                return reporter.fail(
+                    Failure::Write,
+                    format!("Exception occurred while writing to file. {e}"),
+                );
             }
+            let report = &reconstruction.report;
+            info!(
+                "{} of {} points are not in the mesh",
+                report.orphans.len(),
+                report.points
+            );
+            if let Some(path) = &args.orphans
+                && let Err(e) = save_orphans(path, &points, report)
+            {
+                return reporter.fail(
+                    Failure::Write,
+                    format!("Exception occurred while writing orphans to file. {e}"),
+                );
+            }
+            ExitCode::SUCCESS
         }
-        None => {
-            eprintln!("Exception occurred reconstructing the surface");
-        }
+        Err(e) => reporter.fail(
+            Failure::reconstructing(&e),
+            format!("Exception occurred reconstructing the surface: {e}"),
+        ),
     }
-
-    Ok(())
 }
@@ -9,13 +9,25 @@
 #![allow(clippy::many_single_char_names)]
 #![doc = include_str!("../README.md")]
 
+// Shared with the bpa tool, so the exit statuses match.
+#[path = "../../bpa/src/failure.rs"]
+#[allow(dead_code)]
+mod failure;
+
 use std::path::PathBuf;
+use std::process::ExitCode;
 
 use bpa_rs::io::load_xyz;
 use bpa_rs::io::save_triangles;
-use bpa_rs::reconstruct;
+use bpa_rs::{ReconstructionConfig, reconstruct_with_config};
+use clap::ArgAction;
 use clap::Parser;
-use clap::arg;
+use log::LevelFilter;
+use log::info;
+
+use crate::failure::ErrorFormat;
+use crate::failure::Failure;
+use crate::failure::Reporter;
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
@@ -26,17 +38,75 @@ static ALLOC: dhat::Alloc = dhat::Alloc;
 struct Cli {
     #[arg(long = "input", short = 'i', help = "point cloud file")]
     input: PathBuf,
-    #[clap(long = "radius", short = 'r')]
+    #[clap(
+        long = "radius",
+        short = 'r',
+        value_parser = parse_radius,
+        allow_negative_numbers = true
+    )]
     radius: f32,
     #[clap(long="output", help="output mesh file mesh", short='o', default_value=None)]
     output: Option<PathBuf>,
+    #[clap(
+        long = "verbose",
+        short = 'v',
+        action = ArgAction::Count,
+        help = "log progress, repeat for more detail"
+    )]
+    verbose: u8,
+    #[clap(
+        long = "quiet",
+        short = 'q',
+        conflicts_with = "verbose",
+        help = "log only errors"
+    )]
+    quiet: bool,
+    #[clap(
+        long = "error-format",
+        value_enum,
+        default_value_t = ErrorFormat::Text,
+        help = "how failures are reported on stderr"
+    )]
+    error_format: ErrorFormat,
+}
+
+impl Cli {
+    // RUST_LOG, when set, takes precedence.
+    const fn log_level(&self) -> LevelFilter {
+        if self.quiet {
+            return LevelFilter::Error;
+        }
+        match self.verbose {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Info,
+            2 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
 }
 
-fn main() -> std::io::Result<()> {
+fn parse_radius(arg: &str) -> Result<f32, String> {
+    let radius: f32 = arg.parse().map_err(|e| format!("{e}"))?;
+    if radius.is_finite() && radius > 0.0 {
+        Ok(radius)
+    } else {
+        Err(String::from("the ball radius must be a positive number"))
+    }
+}
+
+fn main() -> ExitCode {
     #[cfg(feature = "dhat-heap")]
     let _profiler = dhat::Profiler::new_heap();
 
     let args = Cli::parse();
+    env_logger::Builder::new()
+        .filter_level(args.log_level())
+        .parse_default_env()
+        .init();
+    info!("starting up");
+    let reporter = Reporter {
+        format: args.error_format,
+    };
 
     let output = args.output.clone().unwrap_or_else(|| {
         let mut path = args.input.clone();
@@ -44,18 +114,32 @@ fn main() -> std::io::Result<()> {
         path
     });
 
-    let points = load_xyz(&args.input)?;
+    let points = match load_xyz(&args.input) {
+        Ok(points) => points,
+        Err(e) => {
+            return reporter.fail(
+                Failure::reading(&e),
+                format!("Exception occurred reading the point cloud: {e}"),
+            );
+        }
+    };
 
-    match reconstruct(&points, args.radius) {
-        Some(triangles) => {
-            if let Err(e) = save_triangles(&output, &triangles) {
-                eprintln!("Exception occurred while writing to file. {e}");
+    let reconstruction =
+        match reconstruct_with_config(&points, &ReconstructionConfig::new(args.radius)) {
+            Ok(reconstruction) => reconstruction,
+            Err(e) => {
+                return reporter.fail(
+                    Failure::reconstructing(&e),
+                    format!("Exception occurred reconstructing the surface: {e}"),
+                );
             }
-        }
-        None => {
-            eprintln!("Exception occurred reconstructing the surface");
-        }
+        };
+    info!("reconstruction complete... saving");
+    if let Err(e) = save_triangles(&output, &reconstruction.triangles) {
+        return reporter.fail(
+            Failure::Write,
+            format!("Exception occurred while writing to file. {e}"),
+        );
     }
-
-    Ok(())
+    ExitCode::SUCCESS
 }
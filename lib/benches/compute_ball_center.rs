@@ -1,3 +1,6 @@
+#![allow(clippy::excessive_precision)]
+#![allow(clippy::unreadable_literal)]
+
 use core::cell::RefCell;
 use std::hint::black_box;
 use std::rc::Rc;
@@ -5,9 +8,9 @@ use std::rc::Rc;
 use criterion::{Criterion, criterion_group, criterion_main};
 use glam::Vec3;
 
-use bpa_rs::grid::compute_ball_center;
-use bpa_rs::mesh::MeshFace;
-use bpa_rs::mesh::MeshPoint;
+use bpa_rs::pipeline::MeshFace;
+use bpa_rs::pipeline::MeshPoint;
+use bpa_rs::pipeline::compute_ball_center;
 
 pub fn isosceles(criterion: &mut Criterion) {
     let a = Rc::new(RefCell::new(MeshPoint::new(Vec3::new(0.0, 0.0, 0.0))));
@@ -20,7 +20,7 @@ fn create_spherical_cloud(slices: i32, stacks: i32) -> Vec<Point> {
             let x = (r * yaw.sin()) as f32;
             let y = (r * yaw.cos()) as f32;
 
-            let v = Vec3::new(x as f32, y as f32, z as f32);
+            let v = Vec3::new(x, y, z as f32);
             // This makes no sense, but the original C++ code does this
             // could there be a implicit clone?.
             let normal = v - Vec3::new(0.0, 0.0, 0.0).normalize();
@@ -0,0 +1,512 @@
+use core::borrow::Borrow;
+use std::collections::BTreeMap;
+
+use glam::DMat3;
+use glam::DMat4;
+use glam::DVec3;
+use glam::DVec4;
+use glam::IVec3;
+use glam::Vec3;
+
+use crate::Point;
+use crate::spacing::NeighbourIndex;
+
+/// The shape of the surface around a point, from [`estimate_curvature()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Curvature {
+    /// The larger principal curvature, the reciprocal of the radius of the
+    /// most tightly bent circle through the point. Positive where the
+    /// surface bends away from the normal, as on a sphere with outward
+    /// normals.
+    pub k1: f32,
+    /// The smaller principal curvature.
+    pub k2: f32,
+    /// The smallest eigenvalue of the covariance of the neighbourhood over
+    /// their sum: zero on a plane, and up to one third where the points are
+    /// scattered evenly. High on edges, corners and noise.
+    pub variation: f32,
+}
+
+impl Curvature {
+    /// The mean of the principal curvatures.
+    #[must_use]
+    pub const fn mean(&self) -> f32 {
+        f32::midpoint(self.k1, self.k2)
+    }
+
+    /// The product of the principal curvatures: positive on domes and bowls,
+    /// negative on saddles and zero on cylinders.
+    #[must_use]
+    pub const fn gaussian(&self) -> f32 {
+        self.k1 * self.k2
+    }
+}
+
+/// Estimates the curvature of the surface at each point from its `k`
+/// nearest neighbours, in the order of the input.
+///
+/// The principal curvatures come from a quadric fitted to the neighbours in
+/// the tangent plane of each point's normal. Points without a normal use the
+/// normal of their neighbourhood, and then the sign of the curvature is
+/// arbitrary. Where the neighbours lie on a line no quadric can be fitted,
+/// and the principal curvatures are zero.
+///
+/// Returns `None` when `k` is less than three, or there are no more than `k`
+/// points.
+#[must_use]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = points.len()))
+)]
+pub fn estimate_curvature<P: Borrow<Point>>(points: &[P], k: usize) -> Option<Vec<Curvature>> {
+    if k < 3 || points.len() <= k {
+        return None;
+    }
+    let positions: Vec<Vec3> = points.iter().map(|p| p.borrow().pos).collect();
+    let index = NeighbourIndex::new(&positions);
+
+    Some(
+        points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                let neighbours: Vec<DVec3> = index
+                    .nearest(i, k)
+                    .iter()
+                    .map(|&(_, j)| positions[j].as_dvec3())
+                    .collect();
+                curvature_at(
+                    point.borrow().pos.as_dvec3(),
+                    point.borrow().normal.as_dvec3(),
+                    &neighbours,
+                )
+            })
+            .collect(),
+    )
+}
+
+fn curvature_at(p: DVec3, normal: DVec3, neighbours: &[DVec3]) -> Curvature {
+    #[allow(clippy::cast_precision_loss)]
+    let count = (neighbours.len() + 1) as f64;
+    let centroid = (neighbours.iter().sum::<DVec3>() + p) / count;
+    let covariance = neighbours
+        .iter()
+        .chain([&p])
+        .map(|q| {
+            let d = *q - centroid;
+            DMat3::from_cols(d * d.x, d * d.y, d * d.z)
+        })
+        .fold(DMat3::ZERO, |a, b| a + b)
+        / count;
+    let [smallest, middle, largest] = eigenvalues(&covariance);
+    let total = smallest + middle + largest;
+    let variation = if total > 0.0 { smallest / total } else { 0.0 };
+
+    let normal = normal
+        .try_normalize()
+        .unwrap_or_else(|| eigenvector(&covariance, smallest));
+    let (k1, k2) = principal_curvatures(p, normal, neighbours);
+    #[allow(clippy::cast_possible_truncation)]
+    Curvature {
+        k1: k1 as f32,
+        k2: k2 as f32,
+        variation: variation as f32,
+    }
+}
+
+// Fits z = a x² + b x y + c y² to the neighbours in the tangent frame, with
+// the coordinates scaled by the size of the neighbourhood to keep the normal
+// equations well conditioned.
+fn principal_curvatures(p: DVec3, normal: DVec3, neighbours: &[DVec3]) -> (f64, f64) {
+    let scale = neighbours.iter().map(|q| q.distance(p)).fold(0.0, f64::max);
+    if scale <= 0.0 {
+        return (0.0, 0.0);
+    }
+    let (u, v) = normal.any_orthonormal_pair();
+    let mut m = DMat3::ZERO;
+    let mut rhs = DVec3::ZERO;
+    for q in neighbours {
+        let d = (*q - p) / scale;
+        let (x, y, z) = (d.dot(u), d.dot(v), d.dot(normal));
+        let f = DVec3::new(x * x, x * y, y * y);
+        m += DMat3::from_cols(f * f.x, f * f.y, f * f.z);
+        rhs += f * z;
+    }
+    if m.determinant().abs() < 1e-12 {
+        return (0.0, 0.0);
+    }
+    let DVec3 { x: a, y: b, z: c } = m.inverse() * rhs;
+
+    // The eigenvalues of the shape operator [[2a, b], [b, 2c]], negated so
+    // that surfaces bending away from the normal are positive.
+    let mean = -(a + c) / scale;
+    let spread = (a - c).hypot(b) / scale;
+    (mean + spread, mean - spread)
+}
+
+// The eigenvalues of a symmetric matrix, smallest first.
+fn eigenvalues(m: &DMat3) -> [f64; 3] {
+    let off = m.x_axis.y.mul_add(
+        m.x_axis.y,
+        m.x_axis.z.mul_add(m.x_axis.z, m.y_axis.z * m.y_axis.z),
+    );
+    let diagonal = [m.x_axis.x, m.y_axis.y, m.z_axis.z];
+    if off <= 0.0 {
+        let mut values = diagonal;
+        values.sort_by(f64::total_cmp);
+        return values;
+    }
+    let q = diagonal.iter().sum::<f64>() / 3.0;
+    let p2 = 2.0f64.mul_add(off, diagonal.iter().map(|d| (d - q) * (d - q)).sum::<f64>());
+    let p = (p2 / 6.0).sqrt();
+    let b = (*m - DMat3::from_diagonal(DVec3::splat(q))) / p;
+    let phi = (b.determinant() / 2.0).clamp(-1.0, 1.0).acos() / 3.0;
+    let largest = (2.0 * p).mul_add(phi.cos(), q);
+    let smallest = (2.0 * p).mul_add((phi + core::f64::consts::TAU / 3.0).cos(), q);
+    [smallest, 3.0f64.mul_add(q, -largest - smallest), largest]
+}
+
+// A unit eigenvector of a symmetric matrix for `value`, from the longest
+// cross product of the rows of `m - value I`.
+fn eigenvector(m: &DMat3, value: f64) -> DVec3 {
+    let r = *m - DMat3::from_diagonal(DVec3::splat(value));
+    [
+        r.x_axis.cross(r.y_axis),
+        r.x_axis.cross(r.z_axis),
+        r.y_axis.cross(r.z_axis),
+    ]
+    .into_iter()
+    .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+    .and_then(DVec3::try_normalize)
+    .unwrap_or(DVec3::Z)
+}
+
+/// The number of neighbours measured around each point by
+/// [`density_stats()`], as `bpa estimate-radius` does by default.
+pub const DENSITY_NEIGHBOURS: usize = 8;
+
+// The cells of `density_stats` divide the longest side of the cloud into
+// this many.
+const CELLS_PER_AXIS: f32 = 8.0;
+
+// The bins of the spacing histogram.
+const HISTOGRAM_BINS: usize = 16;
+
+/// Summary statistics of the spacing of a set of points: the mean distance
+/// from each point to its nearest neighbours.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpacingStats {
+    /// The number of points measured.
+    pub points: usize,
+    /// The smallest spacing.
+    pub min: f32,
+    /// The median spacing.
+    pub median: f32,
+    /// The 95th percentile. Gaps wider than this are rare.
+    pub p95: f32,
+    /// The largest spacing.
+    pub max: f32,
+}
+
+impl SpacingStats {
+    // Returns `None` when there are no spacings.
+    fn new(mut spacings: Vec<f32>) -> Option<Self> {
+        if spacings.is_empty() {
+            return None;
+        }
+        spacings.sort_by(f32::total_cmp);
+        let percentile = |p: usize| spacings[(spacings.len() - 1) * p / 100];
+        Some(Self {
+            points: spacings.len(),
+            min: spacings[0],
+            median: percentile(50),
+            p95: percentile(95),
+            max: spacings[spacings.len() - 1],
+        })
+    }
+}
+
+/// The spacing of the points in one cell of a coarse grid over the cloud.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CellDensity {
+    /// The smallest corner of the cell.
+    pub lower: Vec3,
+    /// The spacing of the points in the cell.
+    pub spacing: SpacingStats,
+}
+
+/// How densely and evenly a cloud is sampled, from [`density_stats()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DensityStats {
+    /// The spacing over the whole cloud.
+    pub global: SpacingStats,
+    /// The number of points in each of equal bins of spacing, from
+    /// `global.min` to `global.max`.
+    pub histogram: Vec<usize>,
+    /// The width of each bin of the histogram.
+    pub bin_width: f32,
+    /// The length of the sides of the cells.
+    pub cell_size: f32,
+    /// The spacing in each cell holding points. A cell whose median is well
+    /// above the global median is sparsely sampled, and likely to leave holes
+    /// at a radius chosen for the rest of the cloud.
+    pub cells: Vec<CellDensity>,
+}
+
+/// Measures the spacing of the points, over the whole cloud and in cubic
+/// cells an eighth of its longest side, from the mean distance to the
+/// [`DENSITY_NEIGHBOURS`] nearest neighbours of every point.
+///
+/// Returns `None` when there are no more than [`DENSITY_NEIGHBOURS`] points.
+#[must_use]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = points.len()))
+)]
+pub fn density_stats<P: Borrow<Point>>(points: &[P]) -> Option<DensityStats> {
+    if points.len() <= DENSITY_NEIGHBOURS {
+        return None;
+    }
+    let positions: Vec<Vec3> = points.iter().map(|p| p.borrow().pos).collect();
+    let index = NeighbourIndex::new(&positions);
+    let spacings: Vec<f32> = (0..positions.len())
+        .map(|i| index.spacing(i, DENSITY_NEIGHBOURS).unwrap_or(0.0))
+        .collect();
+    let global = SpacingStats::new(spacings.clone())?;
+
+    #[allow(clippy::cast_precision_loss)]
+    let bin_width = (global.max - global.min) / HISTOGRAM_BINS as f32;
+    let mut histogram = vec![0; HISTOGRAM_BINS];
+    for s in &spacings {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let bin = if bin_width > 0.0 {
+            (((s - global.min) / bin_width) as usize).min(HISTOGRAM_BINS - 1)
+        } else {
+            0
+        };
+        histogram[bin] += 1;
+    }
+
+    let lower = positions.iter().copied().fold(Vec3::INFINITY, Vec3::min);
+    let upper = positions
+        .iter()
+        .copied()
+        .fold(Vec3::NEG_INFINITY, Vec3::max);
+    let longest = (upper - lower).max_element();
+    let cell_size = if longest > 0.0 {
+        longest / CELLS_PER_AXIS
+    } else {
+        1.0
+    };
+    let mut by_cell: BTreeMap<[i32; 3], Vec<f32>> = BTreeMap::new();
+    for (p, s) in positions.iter().zip(&spacings) {
+        let cell = ((*p - lower) / cell_size).floor().as_ivec3();
+        by_cell.entry(cell.to_array()).or_default().push(*s);
+    }
+    let cells = by_cell
+        .into_iter()
+        .filter_map(|(cell, spacings)| {
+            Some(CellDensity {
+                lower: lower + IVec3::from_array(cell).as_vec3() * cell_size,
+                spacing: SpacingStats::new(spacings)?,
+            })
+        })
+        .collect();
+
+    Some(DensityStats {
+        global,
+        histogram,
+        bin_width,
+        cell_size,
+        cells,
+    })
+}
+
+// The most Gauss-Newton steps taken by `fit_sphere`.
+const SPHERE_ITERATIONS: usize = 32;
+
+/// A sphere fitted to points by [`fit_sphere()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SphereFit {
+    /// The center of the sphere.
+    pub center: Vec3,
+    /// The radius of the sphere.
+    pub radius: f32,
+    /// The distance of each point from the sphere, in the order of the
+    /// input: positive outside it and negative inside.
+    pub residuals: Vec<f32>,
+}
+
+impl SphereFit {
+    /// The root mean square of the residuals.
+    #[must_use]
+    pub fn rms(&self) -> f32 {
+        rms(&self.residuals)
+    }
+
+    /// The largest distance of a point from the sphere.
+    #[must_use]
+    pub fn max_residual(&self) -> f32 {
+        max_residual(&self.residuals)
+    }
+}
+
+/// A plane fitted to points by [`fit_plane()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaneFit {
+    /// The centroid of the points, which lies on the plane.
+    pub point: Vec3,
+    /// The unit normal of the plane. Its sign is arbitrary.
+    pub normal: Vec3,
+    /// The distance of each point from the plane, in the order of the input,
+    /// positive on the side the normal faces.
+    pub residuals: Vec<f32>,
+}
+
+impl PlaneFit {
+    /// The root mean square of the residuals.
+    #[must_use]
+    pub fn rms(&self) -> f32 {
+        rms(&self.residuals)
+    }
+
+    /// The largest distance of a point from the plane.
+    #[must_use]
+    pub fn max_residual(&self) -> f32 {
+        max_residual(&self.residuals)
+    }
+}
+
+/// Fits a sphere to the points, such as the scan of a calibration sphere or
+/// the vertices of its mesh, minimising the squares of their distances from
+/// it. A cap of the sphere is enough.
+///
+/// Returns `None` when there are fewer than four points, or they lie on a
+/// plane or a line.
+#[must_use]
+pub fn fit_sphere(points: &[Vec3]) -> Option<SphereFit> {
+    if points.len() < 4 {
+        return None;
+    }
+    let (centroid, scale, q) = normalized(points)?;
+
+    // An algebraic fit of |q|² = 2 c·q + d to start from.
+    let mut m = DMat4::ZERO;
+    let mut rhs = DVec4::ZERO;
+    for q in &q {
+        let row = (*q * 2.0).extend(1.0);
+        m += DMat4::from_cols(row * row.x, row * row.y, row * row.z, row * row.w);
+        rhs += row * q.length_squared();
+    }
+    if m.determinant().abs() < 1e-12 {
+        return None;
+    }
+    let solution = m.inverse() * rhs;
+    let mut center = solution.truncate();
+    let radius_squared = solution.w + center.length_squared();
+    if radius_squared <= 0.0 {
+        return None;
+    }
+    let mut radius = radius_squared.sqrt();
+
+    // Refined to minimise the distances themselves.
+    for _ in 0..SPHERE_ITERATIONS {
+        let mut jtj = DMat4::ZERO;
+        let mut jtr = DVec4::ZERO;
+        for q in &q {
+            let d = *q - center;
+            let length = d.length();
+            if length <= 0.0 {
+                continue;
+            }
+            let j = (-d / length).extend(-1.0);
+            jtj += DMat4::from_cols(j * j.x, j * j.y, j * j.z, j * j.w);
+            jtr += j * (length - radius);
+        }
+        if jtj.determinant().abs() < 1e-12 {
+            break;
+        }
+        let step = jtj.inverse() * jtr;
+        center -= step.truncate();
+        radius -= step.w;
+        if step.length_squared() < 1e-24 {
+            break;
+        }
+    }
+
+    let center = centroid + center * scale;
+    let radius = radius * scale;
+    #[allow(clippy::cast_possible_truncation)]
+    Some(SphereFit {
+        center: center.as_vec3(),
+        radius: radius as f32,
+        residuals: points
+            .iter()
+            .map(|p| (p.as_dvec3().distance(center) - radius) as f32)
+            .collect(),
+    })
+}
+
+/// Fits a plane to the points, such as the scan of a calibration plate or
+/// the vertices of its mesh, minimising the squares of their distances from
+/// it.
+///
+/// Returns `None` when there are fewer than three points, or they lie on a
+/// line.
+#[must_use]
+pub fn fit_plane(points: &[Vec3]) -> Option<PlaneFit> {
+    if points.len() < 3 {
+        return None;
+    }
+    let (centroid, _, q) = normalized(points)?;
+    let covariance = q
+        .iter()
+        .map(|d| DMat3::from_cols(*d * d.x, *d * d.y, *d * d.z))
+        .fold(DMat3::ZERO, |a, b| a + b);
+    let [smallest, middle, _] = eigenvalues(&covariance);
+    if middle <= 1e-12 {
+        return None;
+    }
+    let normal = eigenvector(&covariance, smallest);
+    #[allow(clippy::cast_possible_truncation)]
+    Some(PlaneFit {
+        point: centroid.as_vec3(),
+        normal: normal.as_vec3(),
+        residuals: points
+            .iter()
+            .map(|p| (p.as_dvec3() - centroid).dot(normal) as f32)
+            .collect(),
+    })
+}
+
+// The centroid of the points, their largest distance from it, and their
+// offsets from it over that distance, which keep the fits well conditioned.
+// `None` when the points coincide.
+fn normalized(points: &[Vec3]) -> Option<(DVec3, f64, Vec<DVec3>)> {
+    #[allow(clippy::cast_precision_loss)]
+    let centroid = points.iter().map(|p| p.as_dvec3()).sum::<DVec3>() / points.len() as f64;
+    let scale = points
+        .iter()
+        .map(|p| p.as_dvec3().distance(centroid))
+        .fold(0.0, f64::max);
+    if scale <= 0.0 || !scale.is_finite() {
+        return None;
+    }
+    let q = points
+        .iter()
+        .map(|p| (p.as_dvec3() - centroid) / scale)
+        .collect();
+    Some((centroid, scale, q))
+}
+
+fn rms(residuals: &[f32]) -> f32 {
+    if residuals.is_empty() {
+        return 0.0;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    (residuals.iter().map(|r| r * r).sum::<f32>() / residuals.len() as f32).sqrt()
+}
+
+fn max_residual(residuals: &[f32]) -> f32 {
+    residuals.iter().map(|r| r.abs()).fold(0.0, f32::max)
+}
@@ -0,0 +1,112 @@
+#[cfg(feature = "ply")]
+use std::io::BufReader;
+#[cfg(feature = "ply")]
+use std::io::Cursor;
+use std::path::Path;
+
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+#[cfg(feature = "ply")]
+use tokio::io::AsyncReadExt;
+#[cfg(feature = "stl")]
+use tokio::io::AsyncWrite;
+#[cfg(feature = "stl")]
+use tokio::io::AsyncWriteExt;
+
+use crate::Point;
+#[cfg(feature = "stl")]
+use crate::Triangle;
+use crate::io;
+use crate::io::XyzCloud;
+use crate::io::XyzOptions;
+#[cfg(feature = "stl")]
+use crate::io::stl_facet;
+
+/// Return a point cloud read line by line from `reader`, in the format
+/// read by [`io::load_xyz()`].
+///
+/// # Errors
+///   When reading fails, the input is not valid UTF-8, or a line has a value
+///   that cannot be read.
+pub async fn read_xyz<R: AsyncRead + Unpin>(reader: R) -> std::io::Result<Vec<Point>> {
+    let options = XyzOptions::default();
+    let mut lines = tokio::io::BufReader::new(reader).lines();
+    let mut cloud = XyzCloud::new(&options);
+    let mut number = 0;
+    while let Some(line) = lines.next_line().await? {
+        number += 1;
+        cloud
+            .parse_line(&line, &options)
+            .map_err(|e| io::invalid_line(number, &e))?;
+    }
+    Ok(cloud.points)
+}
+
+/// Return a point cloud stored in a `.xyz` or `.pts` file.
+///
+/// # Errors
+///   If the file cannot be opened or read, or a line has a value that cannot
+///   be read.
+pub async fn load_xyz(path: &Path) -> std::io::Result<Vec<Point>> {
+    read_xyz(tokio::fs::File::open(path).await?).await
+}
+
+/// Return a point cloud read from an ascii or binary PLY stream.
+///
+/// The whole stream is buffered before it is parsed.
+///
+/// # Errors
+///   When reading fails, the header cannot be parsed, or a record is
+///   truncated or unreadable.
+#[cfg(feature = "ply")]
+pub async fn read_ply<R: AsyncRead + Unpin>(mut reader: R) -> std::io::Result<Vec<Point>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+    io::read_ply(BufReader::new(Cursor::new(bytes)))
+}
+
+/// Return a point cloud stored in an ascii or binary PLY file.
+///
+/// # Errors
+///   If the file cannot be opened or read, the header cannot be parsed, or a
+///   record is truncated or unreadable.
+#[cfg(feature = "ply")]
+pub async fn load_ply(path: &Path) -> std::io::Result<Vec<Point>> {
+    read_ply(tokio::fs::File::open(path).await?).await
+}
+
+/// Write triangles to `writer` as a binary STL file.
+///
+/// # Errors
+///   When writing fails, or there are more triangles than the stl format
+///   allows.
+#[cfg(feature = "stl")]
+pub async fn write_stl<W: AsyncWrite + Unpin>(
+    writer: W,
+    triangles: &[Triangle],
+) -> std::io::Result<()> {
+    let count = u32::try_from(triangles.len()).map_err(|_| {
+        std::io::Error::other("stl file format cannot contain more than 4,294,967,295 triangles")
+    })?;
+
+    let mut writer = tokio::io::BufWriter::new(writer);
+    writer.write_all(&[b' '; 80]).await?;
+    writer.write_all(&count.to_le_bytes()).await?;
+    for t in triangles {
+        writer.write_all(&stl_facet(t)).await?;
+    }
+    writer.flush().await
+}
+
+/// Write triangles to file.
+///
+/// # Errors
+///   When the file cannot be created or written to, or there are more
+///   triangles than the stl format allows.
+#[cfg(feature = "stl")]
+pub async fn save_triangles(path: &Path, triangles: &[Triangle]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    write_stl(tokio::fs::File::create(path).await?, triangles).await
+}
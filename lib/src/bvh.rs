@@ -0,0 +1,325 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use glam::Vec3;
+
+use crate::Triangle;
+use crate::math::mul_add;
+
+// The most triangles held by a leaf.
+const LEAF_SIZE: usize = 4;
+
+/// The point of a mesh closest to a query point, from
+/// [`Bvh::closest_point()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Closest {
+    /// The closest point, on the surface of the mesh.
+    pub point: Vec3,
+    /// The distance from the query point.
+    pub distance: f32,
+    /// The index of the triangle holding the closest point.
+    pub face: usize,
+}
+
+/// Where a ray first meets a mesh, from [`Bvh::raycast()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hit {
+    /// The distance along the ray, in multiples of its direction.
+    pub distance: f32,
+    /// The index of the triangle hit.
+    pub face: usize,
+    /// The weights of the triangle's three vertices at the hit, which sum to
+    /// one. Interpolate vertex attributes, such as colours, with them.
+    pub barycentric: Vec3,
+}
+
+impl Hit {
+    /// The point hit, on a ray from `origin` along `direction`.
+    #[must_use]
+    pub fn point(&self, origin: Vec3, direction: Vec3) -> Vec3 {
+        origin + direction * self.distance
+    }
+}
+
+/// A bounding volume hierarchy over the triangles of a mesh, answering
+/// closest point, distance and ray queries in logarithmic time.
+///
+/// Faces are identified by their index in the slice the tree was built from.
+#[derive(Clone, Debug)]
+pub struct Bvh {
+    triangles: Vec<Triangle>,
+    // The face indices, ordered so that each leaf holds a contiguous run.
+    faces: Vec<usize>,
+    // The root is the first node.
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    min: Vec3,
+    max: Vec3,
+    kind: NodeKind,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NodeKind {
+    // A run of `faces`.
+    Leaf { start: usize, end: usize },
+    // The indices of the two children in `nodes`.
+    Inner { left: usize, right: usize },
+}
+
+impl Bvh {
+    /// Builds the tree, splitting the triangles at the median of their
+    /// centroids along the longest axis.
+    #[must_use]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(triangles = triangles.len()))
+    )]
+    pub fn new(triangles: &[Triangle]) -> Self {
+        let mut bvh = Self {
+            triangles: triangles.to_vec(),
+            faces: (0..triangles.len()).collect(),
+            nodes: vec![],
+        };
+        if !triangles.is_empty() {
+            bvh.build(0, triangles.len());
+        }
+        bvh
+    }
+
+    // Adds the node covering `faces[start..end]`, returning its index.
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let (min, max) = self.faces[start..end]
+            .iter()
+            .flat_map(|&f| self.triangles[f].0)
+            .fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            min,
+            max,
+            kind: NodeKind::Leaf { start, end },
+        });
+        if end - start <= LEAF_SIZE {
+            return index;
+        }
+
+        let extent = max - min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        let triangles = &self.triangles;
+        let mid = start + (end - start) / 2;
+        self.faces[start..end].select_nth_unstable_by(mid - start, |&a, &b| {
+            centroid(&triangles[a])[axis].total_cmp(&centroid(&triangles[b])[axis])
+        });
+        let left = self.build(start, mid);
+        let right = self.build(mid, end);
+        self.nodes[index].kind = NodeKind::Inner { left, right };
+        index
+    }
+
+    /// The triangles the tree was built from.
+    #[must_use]
+    pub fn triangles(&self) -> &[Triangle] {
+        &self.triangles
+    }
+
+    /// The point of the mesh closest to `p`, or `None` when the mesh is
+    /// empty.
+    #[must_use]
+    pub fn closest_point(&self, p: Vec3) -> Option<Closest> {
+        let mut best: Option<Closest> = None;
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let Some(node) = self.nodes.get(index) else {
+                continue;
+            };
+            let bound = best.map_or(f32::INFINITY, |b| b.distance);
+            if distance_to_box(p, node.min, node.max) > bound {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { start, end } => {
+                    for &face in &self.faces[start..end] {
+                        let point = closest_on_triangle(p, &self.triangles[face]);
+                        let distance = p.distance(point);
+                        if best.is_none_or(|b| distance < b.distance) {
+                            best = Some(Closest {
+                                point,
+                                distance,
+                                face,
+                            });
+                        }
+                    }
+                }
+                NodeKind::Inner { left, right } => {
+                    // Visit the nearer child first, so the bound tightens sooner.
+                    let near = |i: usize| {
+                        let n = &self.nodes[i];
+                        distance_to_box(p, n.min, n.max)
+                    };
+                    if near(left) < near(right) {
+                        stack.extend([right, left]);
+                    } else {
+                        stack.extend([left, right]);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// The unsigned distance from `p` to the mesh, or `None` when the mesh
+    /// is empty.
+    #[must_use]
+    pub fn distance(&self, p: Vec3) -> Option<f32> {
+        self.closest_point(p).map(|c| c.distance)
+    }
+
+    /// The first triangle met by the ray from `origin` along `direction`,
+    /// from either side. Returns `None` when the ray misses the mesh.
+    #[must_use]
+    pub fn raycast(&self, origin: Vec3, direction: Vec3) -> Option<Hit> {
+        let inverse = direction.recip();
+        let mut best: Option<Hit> = None;
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let Some(node) = self.nodes.get(index) else {
+                continue;
+            };
+            let bound = best.map_or(f32::INFINITY, |b| b.distance);
+            if !ray_meets_box(origin, inverse, node.min, node.max, bound) {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { start, end } => {
+                    for &face in &self.faces[start..end] {
+                        if let Some((distance, u, v)) =
+                            ray_triangle(origin, direction, &self.triangles[face])
+                            && best.is_none_or(|b| distance < b.distance)
+                        {
+                            best = Some(Hit {
+                                distance,
+                                face,
+                                barycentric: Vec3::new(1.0 - u - v, u, v),
+                            });
+                        }
+                    }
+                }
+                NodeKind::Inner { left, right } => stack.extend([left, right]),
+            }
+        }
+        best
+    }
+
+    /// True when the segment from `from` to `to` crosses no triangle, as when
+    /// a scanner at `from` could have seen the point `to`.
+    ///
+    /// Triangles within `tolerance` of either end are ignored, so a point on
+    /// the mesh is visible from a scanner that can see it.
+    #[must_use]
+    pub fn is_visible(&self, from: Vec3, to: Vec3, tolerance: f32) -> bool {
+        let length = from.distance(to);
+        // The part of the segment that is checked.
+        let span = mul_add(tolerance, -2.0, length);
+        if span <= 0.0 {
+            return true;
+        }
+        let direction = (to - from) / length;
+        let start = from + direction * tolerance;
+        self.raycast(start, direction)
+            .is_none_or(|hit| hit.distance >= span)
+    }
+}
+
+fn centroid(t: &Triangle) -> Vec3 {
+    (t.0[0] + t.0[1] + t.0[2]) / 3.0
+}
+
+fn distance_to_box(p: Vec3, min: Vec3, max: Vec3) -> f32 {
+    p.distance(p.clamp(min, max))
+}
+
+// The slab test, for hits nearer than `limit`.
+fn ray_meets_box(origin: Vec3, inverse: Vec3, min: Vec3, max: Vec3, limit: f32) -> bool {
+    let a = (min - origin) * inverse;
+    let b = (max - origin) * inverse;
+    // NaN, from a zero direction component on a slab face, compares false and
+    // is ignored by min and max.
+    let near = a.min(b).max_element().max(0.0);
+    let far = a.max(b).min_element().min(limit);
+    near <= far
+}
+
+// The Möller–Trumbore intersection, returning the distance along the ray and
+// the weights of the second and third vertices.
+fn ray_triangle(origin: Vec3, direction: Vec3, t: &Triangle) -> Option<(f32, f32, f32)> {
+    let [a, b, c] = t.0;
+    let ab = b - a;
+    let ac = c - a;
+    let p = direction.cross(ac);
+    let det = ab.dot(p);
+    if det.abs() < f32::EPSILON * ab.length() * ac.length() * direction.length() {
+        return None;
+    }
+    let s = origin - a;
+    let u = s.dot(p) / det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(ab);
+    let v = direction.dot(q) / det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let distance = ac.dot(q) / det;
+    (distance >= 0.0).then_some((distance, u, v))
+}
+
+// The point of the triangle closest to `p`, after Ericson, Real-Time
+// Collision Detection, 5.1.5.
+fn closest_on_triangle(p: Vec3, t: &Triangle) -> Vec3 {
+    let [a, b, c] = t.0;
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+    let vc = mul_add(d1, d4, -(d3 * d2));
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+    let vb = mul_add(d5, d2, -(d1 * d6));
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+    let va = mul_add(d3, d6, -(d5 * d4));
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
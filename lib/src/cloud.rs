@@ -0,0 +1,91 @@
+use core::borrow::Borrow;
+
+use glam::Vec3;
+
+use crate::Point;
+
+// Read access to the input points, whatever their layout.
+pub trait Cloud {
+    fn len(&self) -> usize;
+    fn pos(&self, i: usize) -> Vec3;
+    fn normal(&self, i: usize) -> Vec3;
+
+    // True when the point only bounds the ball, and is never meshed.
+    fn is_context(&self, _i: usize) -> bool {
+        false
+    }
+
+    // The positions, when they are held in a slice.
+    #[cfg(feature = "rayon")]
+    fn positions(&self) -> Option<&[Vec3]> {
+        None
+    }
+}
+
+impl<P: Borrow<Point>> Cloud for [P] {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn pos(&self, i: usize) -> Vec3 {
+        self[i].borrow().pos
+    }
+
+    fn normal(&self, i: usize) -> Vec3 {
+        self[i].borrow().normal
+    }
+}
+
+// Positions and normals held in separate slices of the same length.
+pub struct Soa<'a> {
+    pub positions: &'a [Vec3],
+    pub normals: &'a [Vec3],
+}
+
+impl Cloud for Soa<'_> {
+    fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    fn pos(&self, i: usize) -> Vec3 {
+        self.positions[i]
+    }
+
+    fn normal(&self, i: usize) -> Vec3 {
+        self.normals[i]
+    }
+
+    #[cfg(feature = "rayon")]
+    fn positions(&self) -> Option<&[Vec3]> {
+        Some(self.positions)
+    }
+}
+
+// A cloud with some of its points marked as context only.
+pub struct WithContext<'a, C: ?Sized> {
+    pub cloud: &'a C,
+    pub context: &'a [bool],
+}
+
+impl<C: Cloud + ?Sized> Cloud for WithContext<'_, C> {
+    fn len(&self) -> usize {
+        self.cloud.len()
+    }
+
+    fn pos(&self, i: usize) -> Vec3 {
+        self.cloud.pos(i)
+    }
+
+    fn normal(&self, i: usize) -> Vec3 {
+        self.cloud.normal(i)
+    }
+
+    fn is_context(&self, i: usize) -> bool {
+        self.context[i]
+    }
+
+    #[cfg(feature = "rayon")]
+    fn positions(&self) -> Option<&[Vec3]> {
+        self.cloud.positions()
+    }
+}
@@ -0,0 +1,205 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+
+use glam::IVec3;
+use glam::Vec2;
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructError;
+use crate::cloud::Cloud;
+use crate::math::sqrt;
+
+// The largest quantized offset within a cell.
+const STEPS: f32 = u16::MAX as f32;
+
+// The largest component of an encoded normal.
+const SNORM: f32 = i16::MAX as f32;
+
+// An encoded normal for a point without one. Encoding never produces it, as
+// components are clamped to ±`i16::MAX`.
+const NO_NORMAL: [i16; 2] = [i16::MIN, i16::MIN];
+
+/// A point cloud held in about 10 bytes a point, rather than the 24 of a
+/// [`Point`], for clouds too large to keep in memory at full precision.
+///
+/// The points are bucketed into cubes two radii wide, as the reconstruction
+/// does, and each position is stored as three 16 bit offsets within its
+/// cube. Normals are stored as two 16 bit components of an octahedral
+/// encoding. Points are dequantized as the reconstruction reads them, so
+/// the grid and every geometric test work on ordinary coordinates.
+///
+/// The points are held in order of their cube, not in the order they were
+/// given in. [`CompactCloud::get()`] returns them in the stored order, and
+/// the point indices in a report refer to it.
+#[derive(Clone, Debug)]
+pub struct CompactCloud {
+    lower: Vec3,
+    cell_size: f32,
+    // The runs of `positions` in each occupied cell, in order.
+    blocks: Vec<Block>,
+    positions: Vec<[u16; 3]>,
+    normals: Vec<[i16; 2]>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Block {
+    cell: IVec3,
+    // The index of the first point in the cell.
+    start: usize,
+}
+
+impl CompactCloud {
+    /// Quantizes the points for a ball of the given radius.
+    ///
+    /// Positions are kept to within [`CompactCloud::precision()`] of the
+    /// originals. A normal that is zero or not finite is kept as unknown,
+    /// and the others are kept as unit vectors.
+    ///
+    /// # Errors
+    ///   When the radius is not positive and finite.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(points = points.len(), radius))
+    )]
+    pub fn new<P: Borrow<Point>>(points: &[P], radius: f32) -> Result<Self, ReconstructError> {
+        if !(radius.is_finite() && radius > 0.0) {
+            return Err(ReconstructError::InvalidRadius(radius));
+        }
+        let cell_size = 2.0 * radius;
+        let lower = points
+            .iter()
+            .map(|p| p.borrow().pos)
+            .fold(Vec3::INFINITY, Vec3::min);
+        #[allow(clippy::cast_possible_truncation)]
+        let cell_of = |pos: Vec3| ((pos - lower) / cell_size).floor().as_ivec3();
+
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        order.sort_by_key(|&i| cell_of(points[i].borrow().pos).to_array());
+
+        let mut cloud = Self {
+            lower,
+            cell_size,
+            blocks: vec![],
+            positions: Vec::with_capacity(points.len()),
+            normals: Vec::with_capacity(points.len()),
+        };
+        for i in order {
+            let Point { pos, normal } = points[i].borrow();
+            let cell = cell_of(*pos);
+            if cloud.blocks.last().is_none_or(|b| b.cell != cell) {
+                cloud.blocks.push(Block {
+                    cell,
+                    start: cloud.positions.len(),
+                });
+            }
+            let offset = (*pos - cloud.origin(cell)) / cell_size * STEPS;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            cloud.positions.push(
+                offset
+                    .round()
+                    .clamp(Vec3::ZERO, Vec3::splat(STEPS))
+                    .to_array()
+                    .map(|q| q as u16),
+            );
+            cloud.normals.push(encode_normal(*normal));
+        }
+        Ok(cloud)
+    }
+
+    /// The number of points.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// True when there are no points.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// The `i`th point, dequantized, or `None` when `i` is out of bounds.
+    #[must_use]
+    pub fn get(&self, i: usize) -> Option<Point> {
+        (i < self.len()).then(|| Point {
+            pos: self.position(i),
+            normal: decode_normal(self.normals[i]),
+        })
+    }
+
+    /// The points, dequantized, in the stored order.
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.len()).filter_map(|i| self.get(i))
+    }
+
+    /// The largest distance between a stored position and the original.
+    #[must_use]
+    pub fn precision(&self) -> f32 {
+        // Half a step along each axis.
+        self.cell_size / STEPS * 0.5 * sqrt(3.0)
+    }
+
+    /// The memory held by the cloud, in bytes.
+    #[must_use]
+    pub const fn resident_bytes(&self) -> usize {
+        size_of::<Self>()
+            + self.blocks.capacity() * size_of::<Block>()
+            + self.positions.capacity() * size_of::<[u16; 3]>()
+            + self.normals.capacity() * size_of::<[i16; 2]>()
+    }
+
+    fn origin(&self, cell: IVec3) -> Vec3 {
+        self.lower + cell.as_vec3() * self.cell_size
+    }
+
+    fn position(&self, i: usize) -> Vec3 {
+        let block = self.blocks[self.blocks.partition_point(|b| b.start <= i) - 1];
+        let offset = Vec3::from_array(self.positions[i].map(f32::from));
+        self.origin(block.cell) + offset * (self.cell_size / STEPS)
+    }
+}
+
+impl Cloud for CompactCloud {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn pos(&self, i: usize) -> Vec3 {
+        self.position(i)
+    }
+
+    fn normal(&self, i: usize) -> Vec3 {
+        decode_normal(self.normals[i])
+    }
+}
+
+// Projects the unit sphere onto an octahedron, unfolded into a square.
+fn encode_normal(normal: Vec3) -> [i16; 2] {
+    let Some(n) = normal.try_normalize() else {
+        return NO_NORMAL;
+    };
+    let n = n / n.abs().element_sum();
+    let folded = if n.z < 0.0 {
+        (Vec2::ONE - Vec2::new(n.y, n.x).abs()) * Vec2::new(n.x, n.y).signum()
+    } else {
+        Vec2::new(n.x, n.y)
+    };
+    #[allow(clippy::cast_possible_truncation)]
+    (folded * SNORM)
+        .round()
+        .clamp(Vec2::splat(-SNORM), Vec2::splat(SNORM))
+        .to_array()
+        .map(|c| c as i16)
+}
+
+fn decode_normal(encoded: [i16; 2]) -> Vec3 {
+    if encoded == NO_NORMAL {
+        return Vec3::ZERO;
+    }
+    let [x, y] = encoded.map(|c| f32::from(c) / SNORM);
+    let z = 1.0 - x.abs() - y.abs();
+    let t = (-z).max(0.0);
+    Vec3::new(x - t.copysign(x), y - t.copysign(y), z).normalize()
+}
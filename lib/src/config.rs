@@ -0,0 +1,405 @@
+#[cfg(feature = "std")]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+use alloc::sync::Arc;
+use core::fmt;
+
+use glam::Vec3;
+
+use crate::postprocess::SliverOptions;
+use crate::report::Progress;
+
+/// The default limit on the number of cells in the grid.
+///
+/// Each empty cell costs a few dozen bytes, so this allows for a grid of
+/// around 2.5 GB.
+pub const DEFAULT_MAX_GRID_CELLS: usize = 1 << 26;
+
+/// The default tolerance of the test for an empty ball, as a fraction of
+/// the squared radius.
+pub const DEFAULT_EMPTY_BALL_TOLERANCE: f32 = 1e-4;
+
+/// The order in which active edges are taken from the front.
+///
+/// The order shapes the growing front, and so the holes and boundaries
+/// left where a scan is incomplete.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Traversal {
+    /// Pivot the most recently created edge first (depth-first growth).
+    ///
+    /// This matches the original C++ implementation.
+    #[default]
+    Lifo,
+    /// Pivot the oldest edge first (breadth-first growth).
+    Fifo,
+}
+
+/// The winding order of the output triangles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Winding {
+    /// Counter-clockwise when viewed from the side the point normals face.
+    #[default]
+    CounterClockwise,
+    /// Clockwise when viewed from the side the point normals face. The facet
+    /// normals written to STL files are flipped too.
+    Clockwise,
+}
+
+/// Where the search for a seed triangle starts.
+///
+/// A reconstruction grows from a single seed, so on a noisy cloud the seed
+/// decides which part of the surface is covered.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SeedStrategy {
+    /// The cells of the grid in order, from the lowest corner of the
+    /// bounding box.
+    #[default]
+    ScanOrder,
+    /// The cells holding the most points first, where the surface is most
+    /// likely to be well sampled.
+    DensestCellFirst,
+    /// The cells in a random order, drawn from this seed. The same seed
+    /// gives the same order.
+    Random(u64),
+    /// The cells nearest this position first.
+    Near(Vec3),
+}
+
+/// How the geometric decisions of the algorithm are computed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Predicates {
+    /// In `f32` throughout. Nearly collinear points and nearly tangent balls
+    /// can be decided either way by rounding.
+    #[default]
+    Fast,
+    /// In `f32`, recomputed in `f64` when the result is within the rounding
+    /// error of the decision it settles: whether a ball rests on three
+    /// points, and which pivot angle is the smallest. Slower only on
+    /// near-degenerate configurations.
+    Robust,
+}
+
+/// Where this crate departs from the C++ implementation it was ported from,
+/// which behaviour to follow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompatMode {
+    /// The behaviour of this crate: the test for an empty ball allows for
+    /// rounding in proportion to the radius, a face found twice is output
+    /// once, and a front edge duplicating an active one is merged into it.
+    #[default]
+    Improved,
+    /// The behaviour of the C++ implementation, for comparing meshes with it
+    /// while migrating: a point is inside the ball when nearer its center
+    /// than `r² - 1e-4`, in the squared units of the cloud, a face found
+    /// twice is output twice, and duplicate front edges are each pivoted.
+    ///
+    /// The options with fields of their own are left as set; see
+    /// [`ReconstructionConfig::cpp()`] for those of the C++ implementation.
+    /// The meshes match where `f32` arithmetic agrees, which fused
+    /// multiply-adds and the order of neighbours tied in distance can
+    /// upset.
+    Cpp,
+}
+
+/// Checks made while pivoting that are not in the original paper.
+///
+/// All are enabled by default. On unoriented or noisy clouds they can reject
+/// valid surface, so each can be disabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Heuristics {
+    /// Reject points whose normal faces away from the new triangle.
+    pub normal_half_space: bool,
+    /// Reject points when the ball center lies beneath the new triangle.
+    pub ball_above_triangle: bool,
+    /// Reject points already joined to the edge by an inner edge.
+    ///
+    /// Without this check the front can keep re-covering the mesh, so set a
+    /// budget when disabling it.
+    pub existing_inner_edge: bool,
+}
+
+impl Default for Heuristics {
+    fn default() -> Self {
+        Self {
+            normal_half_space: true,
+            ball_above_triangle: true,
+            existing_inner_edge: true,
+        }
+    }
+}
+
+/// What to do with a point whose normal faces away from the triangle the
+/// ball would make with it, when [`Heuristics::normal_half_space`] is set.
+///
+/// Such points mark normals that disagree with the surface grown around
+/// them, from a noisy or badly oriented normal estimate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NormalDisagreement {
+    /// Reject the point, counting it in
+    /// [`crate::report::PivotRejections::normal_half_space`].
+    #[default]
+    Reject,
+    /// Accept the point, listing it in
+    /// [`crate::report::Report::flagged_points`].
+    Flag,
+    /// Accept the point, and flip its normal to agree with the triangle for
+    /// the pivots that follow.
+    Flip,
+}
+
+/// Retry policy used when no seed triangle is found.
+///
+/// Each retry multiplies the radius by `factor`, up to `max_radius`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RadiusEscalation {
+    /// Growth of the radius on each retry. Must be greater than one.
+    pub factor: f32,
+    /// The largest radius to try.
+    pub max_radius: f32,
+}
+
+impl RadiusEscalation {
+    // The radius of the next attempt, if any.
+    pub(crate) fn next(self, radius: f32) -> Option<f32> {
+        let next = (radius * self.factor).min(self.max_radius);
+        (self.factor > 1.0 && next > radius).then_some(next)
+    }
+}
+
+/// The longest edge allowed in an output triangle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaxEdgeLength {
+    /// A length in the units of the points.
+    Absolute(f32),
+    /// A multiple of the ball radius, following the radius as it escalates.
+    Radius(f32),
+}
+
+impl MaxEdgeLength {
+    // The length for a ball of this radius.
+    pub(crate) const fn resolve(self, radius: f32) -> f32 {
+        match self {
+            Self::Absolute(length) => length,
+            Self::Radius(multiple) => multiple * radius,
+        }
+    }
+}
+
+/// The debugging files to write, see [`DebugOutput`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DebugArtifacts {
+    /// Each seed triangle, as `seed_N.stl`.
+    pub seed: bool,
+    /// The active edges of the front before each pivot, as `N_front.stl`,
+    /// and the boundary edges once the front is exhausted, as
+    /// `boundary_edges.stl`. Edges are written as degenerate triangles.
+    pub front: bool,
+    /// The points considered by each pivot, as `N_candidates.ply`.
+    pub candidates: bool,
+}
+
+/// Debugging files written while reconstructing, numbered by pivot.
+///
+/// Meant for small clouds: a file is written for every pivot.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugOutput {
+    /// The directory the files are written to, created if needed.
+    pub dir: PathBuf,
+    /// The files to write.
+    pub artifacts: DebugArtifacts,
+}
+
+/// Called with the state of a reconstruction as it runs, see
+/// [`ReconstructionConfig::progress`].
+#[derive(Clone)]
+pub struct ProgressCallback(pub Arc<dyn Fn(&Progress) + Send + Sync>);
+
+impl ProgressCallback {
+    /// Wraps the closure.
+    pub fn new(callback: impl Fn(&Progress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+}
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// Parameters controlling a reconstruction.
+#[derive(Clone, Debug)]
+pub struct ReconstructionConfig {
+    /// Radius of the pivoting ball.
+    pub radius: f32,
+    /// Order in which active edges are pivoted.
+    pub traversal: Traversal,
+    /// Stop once this many triangles have been output.
+    ///
+    /// The seed triangle is always output.
+    pub max_triangles: Option<usize>,
+    /// Stop once this much time has passed since the reconstruction started.
+    ///
+    /// Requires the `std` feature, for the clock.
+    #[cfg(feature = "std")]
+    pub time_budget: Option<Duration>,
+    /// Retry with a larger radius when no seed triangle is found.
+    pub radius_escalation: Option<RadiusEscalation>,
+    /// Once the front is exhausted, pivot again from the boundary edges with
+    /// the radius multiplied by this factor, bridging pinholes a little too
+    /// wide for the ball without coarsening the rest of the mesh. The factor
+    /// bounds the holes bridged; factors of one or less are ignored.
+    ///
+    /// Triangles found this way are counted in
+    /// [`crate::report::Report::bridged_triangles`].
+    pub boundary_pass: Option<f32>,
+    /// Checks made while pivoting that are not in the original paper.
+    pub heuristics: Heuristics,
+    /// What to do with a point whose normal disagrees with the surface grown
+    /// around it. Seed triangles must always agree with their normals.
+    pub normal_disagreement: NormalDisagreement,
+    /// How the ball centers and pivot angles are computed.
+    pub predicates: Predicates,
+    /// Where the search for a seed triangle starts.
+    pub seed_strategy: SeedStrategy,
+    /// The most points considered around a pivot or a seed. Beyond this the
+    /// nearest are kept, bounding the cost of each pivot in dense blobs.
+    ///
+    /// The ball is then only checked to be empty of the points kept.
+    pub max_neighbors: Option<usize>,
+    /// The sharpest fold allowed between a new triangle and the triangle
+    /// across its pivot edge, as the angle between their normals in
+    /// radians. Pivots folding further are rejected, which keeps the front
+    /// from doubling back on thin, noisy structures.
+    pub max_dihedral_angle: Option<f32>,
+    /// The longest edge of a triangle. The ball can span edges up to its
+    /// diameter, bridging sparse regions and concavities with long thin
+    /// triangles; with a shorter limit those are left as holes instead.
+    pub max_edge_length: Option<MaxEdgeLength>,
+    /// How far inside the ball a point may lie and still count as on its
+    /// surface, as a fraction of the squared radius. Rounding puts the
+    /// points the ball rests on slightly inside it. Being relative to the
+    /// radius, the same cloud in millimetres or in metres gives the same
+    /// mesh. Must be at least 0 and less than 1.
+    pub empty_ball_tolerance: f32,
+    /// Follow this crate or the C++ implementation where they differ.
+    pub compat: CompatMode,
+    /// The winding order of the output triangles.
+    pub winding: Winding,
+    /// Orient the finished mesh consistently, with closed pieces facing
+    /// outward. See [`crate::postprocess::orient()`].
+    ///
+    /// This needs the whole mesh, so it is ignored when streaming to a file.
+    pub orient_outward: bool,
+    /// Keep only the largest edge-connected piece of the finished mesh. See
+    /// [`crate::postprocess::keep_largest_component()`].
+    ///
+    /// This needs the whole mesh, so it is ignored when streaming to a file.
+    pub keep_largest_component: bool,
+    /// Remove the pieces of the finished mesh with fewer triangles than
+    /// this, such as shells around stray blobs of noise. See
+    /// [`crate::postprocess::remove_small_components()`].
+    ///
+    /// This needs the whole mesh, so it is ignored when streaming to a file.
+    pub min_component_faces: Option<usize>,
+    /// Collapse or flip the edges of needle and cap triangles in the
+    /// finished mesh. See [`crate::postprocess::remove_slivers()`].
+    ///
+    /// This needs the whole mesh, so it is ignored when streaming to a file.
+    pub remove_slivers: Option<SliverOptions>,
+    /// Record the ball center and pivot angle of each triangle in
+    /// [`crate::report::Report::pivots`], for judging how confidently each
+    /// part of the mesh was found.
+    ///
+    /// Not recorded by the slabs of a parallel reconstruction.
+    pub record_pivots: bool,
+    /// The largest number of grid cells to allocate. The grid has a cell for
+    /// every cube two radii wide in the bounding box of the points, so a tiny
+    /// radius on a large cloud can need an absurd amount of memory.
+    pub max_grid_cells: usize,
+    /// Record every step of the reconstruction to this file, as newline
+    /// delimited JSON: the seed, each pivot with the candidates considered,
+    /// the glue cases taken and the boundary and stalled edges found.
+    ///
+    /// Meant for debugging and visualisation; the file can be large. When it
+    /// cannot be written a warning is logged and the reconstruction goes on
+    /// untraced. Ignored by the slabs of a parallel reconstruction.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub trace: Option<PathBuf>,
+    /// Write debugging files to a directory while reconstructing. When they
+    /// cannot be written a warning is logged and the reconstruction goes on
+    /// without them. Ignored by the slabs of a parallel reconstruction.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub debug: Option<DebugOutput>,
+    /// Called every few thousand pivots, and once the front is exhausted,
+    /// with the size of the reconstruction's state and an estimate of the
+    /// memory it holds. Hosts can use it to watch a long run, or to decide
+    /// whether to admit another. Ignored by the slabs of a parallel
+    /// reconstruction.
+    pub progress: Option<ProgressCallback>,
+}
+
+impl ReconstructionConfig {
+    /// Default parameters for the given ball radius.
+    #[must_use]
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            traversal: Traversal::default(),
+            max_triangles: None,
+            #[cfg(feature = "std")]
+            time_budget: None,
+            radius_escalation: None,
+            boundary_pass: None,
+            heuristics: Heuristics::default(),
+            normal_disagreement: NormalDisagreement::default(),
+            predicates: Predicates::default(),
+            seed_strategy: SeedStrategy::default(),
+            max_neighbors: None,
+            max_dihedral_angle: None,
+            max_edge_length: None,
+            empty_ball_tolerance: DEFAULT_EMPTY_BALL_TOLERANCE,
+            compat: CompatMode::default(),
+            winding: Winding::default(),
+            orient_outward: false,
+            keep_largest_component: false,
+            min_component_faces: None,
+            remove_slivers: None,
+            record_pivots: false,
+            max_grid_cells: DEFAULT_MAX_GRID_CELLS,
+            #[cfg(feature = "std")]
+            trace: None,
+            #[cfg(feature = "std")]
+            debug: None,
+            progress: None,
+        }
+    }
+
+    /// The parameters of the C++ implementation for the given ball radius,
+    /// with [`CompatMode::Cpp`]: depth-first traversal, every heuristic,
+    /// `f32` predicates and a scan for the seed, and no limits or clean-ups.
+    #[must_use]
+    pub fn cpp(radius: f32) -> Self {
+        Self {
+            traversal: Traversal::Lifo,
+            heuristics: Heuristics {
+                normal_half_space: true,
+                ball_above_triangle: true,
+                existing_inner_edge: true,
+            },
+            predicates: Predicates::Fast,
+            seed_strategy: SeedStrategy::ScanOrder,
+            compat: CompatMode::Cpp,
+            ..Self::new(radius)
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use glam::IVec3;
+use glam::Vec3;
+
+use crate::Triangle;
+use crate::bvh::Bvh;
+
+/// The differences between two meshes, from [`diff()`].
+///
+/// Triangles are compared by their vertices, allowing each vertex to move by
+/// the tolerance, and keeping their winding. The order of the triangles and
+/// the vertex each triangle starts with are ignored.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshDiff {
+    /// Triangles found in both meshes.
+    pub unchanged: usize,
+    /// Indices of the triangles of the first mesh, paired with those of the
+    /// second, that share an edge but whose third vertex moved further than
+    /// the tolerance.
+    pub moved: Vec<(usize, usize)>,
+    /// Indices of the triangles of the first mesh with no counterpart in the
+    /// second.
+    pub removed: Vec<usize>,
+    /// Indices of the triangles of the second mesh with no counterpart in the
+    /// first.
+    pub added: Vec<usize>,
+    /// The largest distance between corresponding vertices of unchanged and
+    /// moved triangles.
+    pub max_deviation: f32,
+}
+
+impl MeshDiff {
+    /// True when every triangle is unchanged, within the tolerance.
+    #[must_use]
+    pub const fn is_unchanged(&self) -> bool {
+        self.moved.is_empty() && self.removed.is_empty() && self.added.is_empty()
+    }
+}
+
+/// Compares two meshes, allowing vertices to differ by `tolerance`.
+///
+/// A tolerance of zero requires vertices to match exactly. Vertices of `b`
+/// closer together than the tolerance are treated as one, so it should be
+/// well below the spacing of the vertices.
+#[must_use]
+pub fn diff(a: &[Triangle], b: &[Triangle], tolerance: f32) -> MeshDiff {
+    // Weld the vertices of `b`, then find each vertex of `a` among them.
+    let mut vertices = VertexIndex::new(tolerance);
+    let b_faces: Vec<[usize; 3]> = b.iter().map(|t| t.0.map(|v| vertices.insert(v))).collect();
+    let a_faces: Vec<[Option<usize>; 3]> =
+        a.iter().map(|t| t.0.map(|v| vertices.find(v))).collect();
+
+    let mut result = MeshDiff::default();
+    let deviate = |max: &mut f32, from: Vec3, to: usize| {
+        *max = max.max(from.distance(vertices.positions[to]));
+    };
+
+    // The unmatched triangles of `b`, by their rotation starting with the
+    // smallest vertex index.
+    let mut unmatched: HashMap<[usize; 3], Vec<usize>> = HashMap::new();
+    for (j, face) in b_faces.iter().enumerate() {
+        unmatched.entry(canonical(*face)).or_default().push(j);
+    }
+
+    let mut remaining = vec![];
+    for (i, face) in a_faces.iter().enumerate() {
+        let matched = match face {
+            [Some(u), Some(v), Some(w)] => unmatched
+                .get_mut(&canonical([*u, *v, *w]))
+                .and_then(Vec::pop),
+            _ => None,
+        };
+        if matched.is_some() {
+            for (k, v) in face.iter().enumerate() {
+                if let Some(v) = v {
+                    deviate(&mut result.max_deviation, a[i].0[k], *v);
+                }
+            }
+            result.unchanged += 1;
+        } else {
+            remaining.push(i);
+        }
+    }
+
+    // The remaining triangles of `b`, by their directed edges.
+    let mut taken = vec![true; b.len()];
+    let mut by_edge: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for j in unmatched.into_values().flatten() {
+        taken[j] = false;
+        let [u, v, w] = b_faces[j];
+        for edge in [(u, v), (v, w), (w, u)] {
+            by_edge.entry(edge).or_default().push(j);
+        }
+    }
+
+    for i in remaining {
+        let face = a_faces[i];
+        let partner = (0..3).find_map(|k| {
+            let (u, v) = (face[k]?, face[(k + 1) % 3]?);
+            let j = by_edge.get(&(u, v))?.iter().copied().find(|&j| !taken[j])?;
+            Some((k, j))
+        });
+        match partner {
+            Some((k, j)) => {
+                taken[j] = true;
+                // Compare the third vertex with the one opposite the edge.
+                let start = b_faces[j].iter().position(|&v| Some(v) == face[k]);
+                let start = start.unwrap_or(0);
+                for offset in 0..3 {
+                    deviate(
+                        &mut result.max_deviation,
+                        a[i].0[(k + offset) % 3],
+                        b_faces[j][(start + offset) % 3],
+                    );
+                }
+                result.moved.push((i, j));
+            }
+            None => result.removed.push(i),
+        }
+    }
+    result.added = (0..b.len()).filter(|&j| !taken[j]).collect();
+    result
+}
+
+/// How far apart the surfaces of two meshes are, from [`distance()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshDistance {
+    /// The Hausdorff distance: the furthest any sample of either mesh lies
+    /// from the other mesh.
+    pub hausdorff: f32,
+    /// The Chamfer distance: the mean distance of the samples of the first
+    /// mesh from the second, plus the mean distance of the samples of the
+    /// second from the first.
+    pub chamfer: f32,
+}
+
+/// Measures how far apart the surfaces of two meshes are, for checking a
+/// reconstruction against a reference that need not share its vertices.
+///
+/// Each mesh is sampled at the vertices and centroid of each of its
+/// triangles, and each sample measured to the closest point of the other
+/// mesh. Returns `None` when either mesh has no triangles.
+#[must_use]
+pub fn distance(a: &[Triangle], b: &[Triangle]) -> Option<MeshDistance> {
+    let (a_max, a_mean) = one_sided(a, &Bvh::new(b))?;
+    let (b_max, b_mean) = one_sided(b, &Bvh::new(a))?;
+    Some(MeshDistance {
+        hausdorff: a_max.max(b_max),
+        chamfer: a_mean + b_mean,
+    })
+}
+
+// The largest and the mean distance of the samples of `from` from the mesh
+// in `to`.
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation)]
+fn one_sided(from: &[Triangle], to: &Bvh) -> Option<(f32, f32)> {
+    let (mut max, mut sum, mut samples) = (0_f32, 0_f64, 0_usize);
+    for t in from {
+        let [a, b, c] = t.0;
+        for p in [a, b, c, (a + b + c) / 3.0] {
+            let d = to.distance(p)?;
+            max = max.max(d);
+            sum += f64::from(d);
+            samples += 1;
+        }
+    }
+    (samples > 0).then(|| (max, (sum / samples as f64) as f32))
+}
+
+// Rotates the face so that it starts with its smallest index.
+fn canonical(face: [usize; 3]) -> [usize; 3] {
+    let mut face = face;
+    let first = (0..3).min_by_key(|&i| face[i]).unwrap_or(0);
+    face.rotate_left(first);
+    face
+}
+
+// Distinct vertices, found by position within a tolerance.
+pub(crate) struct VertexIndex {
+    tolerance: f32,
+    positions: Vec<Vec3>,
+    cells: HashMap<IVec3, Vec<usize>>,
+    exact: HashMap<[u32; 3], usize>,
+}
+
+impl VertexIndex {
+    pub(crate) fn new(tolerance: f32) -> Self {
+        Self {
+            tolerance,
+            positions: vec![],
+            cells: HashMap::new(),
+            exact: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, v: Vec3) -> IVec3 {
+        (v / self.tolerance).floor().as_ivec3()
+    }
+
+    // The index of the vertex, adding it when it is new.
+    pub(crate) fn insert(&mut self, v: Vec3) -> usize {
+        if let Some(i) = self.find(v) {
+            return i;
+        }
+        let i = self.positions.len();
+        self.positions.push(v);
+        if self.tolerance > 0.0 {
+            let cell = self.cell(v);
+            self.cells.entry(cell).or_default().push(i);
+        } else {
+            self.exact.insert(v.to_array().map(f32::to_bits), i);
+        }
+        i
+    }
+
+    // The nearest vertex within the tolerance.
+    fn find(&self, v: Vec3) -> Option<usize> {
+        if self.tolerance <= 0.0 {
+            return self.exact.get(&v.to_array().map(f32::to_bits)).copied();
+        }
+        let cell = self.cell(v);
+        let mut nearest: Option<(f32, usize)> = None;
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    let Some(ids) = self.cells.get(&cell.wrapping_add(IVec3::new(x, y, z))) else {
+                        continue;
+                    };
+                    for &i in ids {
+                        let d = v.distance(self.positions[i]);
+                        if d <= self.tolerance && nearest.is_none_or(|(best, _)| d < best) {
+                            nearest = Some((d, i));
+                        }
+                    }
+                }
+            }
+        }
+        nearest.map(|(_, i)| i)
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use core::convert::Infallible;
+use core::error::Error;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+#[cfg(feature = "std")]
+use glam::Vec3;
+use log::warn;
+
+use crate::Triangle;
+#[cfg(feature = "std")]
+use crate::config::DebugOutput;
+use crate::front::Front;
+#[cfg(feature = "std")]
+use crate::io::save_points;
+#[cfg(feature = "std")]
+use crate::io::save_triangles_ascii;
+use crate::trace::Candidate;
+
+// Takes a step, abandoning the debug files when writing fails.
+pub fn write(
+    dumps: &mut Option<Dumps>,
+    step: impl FnOnce(&mut Dumps) -> Result<(), Box<dyn Error>>,
+) {
+    if let Some(d) = dumps
+        && let Err(e) = step(d)
+    {
+        warn!("Abandoning the debug files after failing to write one: {e}");
+        *dumps = None;
+    }
+}
+
+// Writes the debugging files chosen in `ReconstructionConfig::debug`.
+#[cfg(feature = "std")]
+pub struct Dumps {
+    output: DebugOutput,
+    seeds: usize,
+    pivots: usize,
+}
+
+#[cfg(feature = "std")]
+impl Dumps {
+    pub fn create(output: &DebugOutput) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&output.dir)?;
+        Ok(Self {
+            output: output.clone(),
+            seeds: 0,
+            pivots: 0,
+        })
+    }
+
+    pub const fn wants_candidates(&self) -> bool {
+        self.output.artifacts.candidates
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.output.dir.join(name)
+    }
+
+    pub fn seed(&mut self, triangle: Triangle) -> Result<(), Box<dyn Error>> {
+        self.seeds += 1;
+        if self.output.artifacts.seed {
+            save_triangles_ascii(&self.path(&format!("seed_{}.stl", self.seeds)), &[triangle])?;
+        }
+        Ok(())
+    }
+
+    // Starts a pivot, writing the front as it stands.
+    pub fn front(&mut self, front: &Front) -> Result<(), Box<dyn Error>> {
+        self.pivots += 1;
+        if self.output.artifacts.front {
+            let edges: Vec<Triangle> = front
+                .iter()
+                .map(|e| {
+                    let (a, b) = (e.borrow().a.borrow().pos, e.borrow().b.borrow().pos);
+                    Triangle([a, a, b])
+                })
+                .collect();
+            save_triangles_ascii(&self.path(&format!("{:06}_front.stl", self.pivots)), &edges)?;
+        }
+        Ok(())
+    }
+
+    pub fn candidates(&self, candidates: &[Candidate]) -> Result<(), Box<dyn Error>> {
+        if self.output.artifacts.candidates {
+            let points: Vec<Vec3> = candidates.iter().map(|c| c.pos).collect();
+            save_points(
+                &self.path(&format!("{:06}_candidates.ply", self.pivots)),
+                &points,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn boundary(&self, edges: &[Triangle]) -> Result<(), Box<dyn Error>> {
+        if self.output.artifacts.front {
+            save_triangles_ascii(&self.path("boundary_edges.stl"), edges)?;
+        }
+        Ok(())
+    }
+}
+
+// The files are written to a directory, so without `std` none are.
+#[cfg(not(feature = "std"))]
+pub struct Dumps(Infallible);
+
+#[cfg(not(feature = "std"))]
+impl Dumps {
+    pub const fn wants_candidates(&self) -> bool {
+        match self.0 {}
+    }
+
+    pub fn seed(&self, _: Triangle) -> Result<(), Box<dyn Error>> {
+        match self.0 {}
+    }
+
+    pub fn front(&self, _: &Front) -> Result<(), Box<dyn Error>> {
+        match self.0 {}
+    }
+
+    pub fn candidates(&self, _: &[Candidate]) -> Result<(), Box<dyn Error>> {
+        match self.0 {}
+    }
+
+    pub fn boundary(&self, _: &[Triangle]) -> Result<(), Box<dyn Error>> {
+        match self.0 {}
+    }
+}
@@ -0,0 +1,123 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::report::Diagnostics;
+
+/// The reasons a reconstruction can fail.
+#[derive(Debug)]
+pub enum ReconstructError {
+    /// The ball radius is zero, negative or not finite.
+    InvalidRadius(f32),
+    /// The
+    /// [`empty_ball_tolerance`](crate::ReconstructionConfig::empty_ball_tolerance)
+    /// is negative, not finite, or allows a point at the center of the ball.
+    InvalidEmptyBallTolerance(f32),
+    /// The grid would have more than
+    /// [`max_grid_cells`](crate::ReconstructionConfig::max_grid_cells) cells.
+    GridTooLarge {
+        /// Number of cells needed, or `None` when the count overflows.
+        cells: Option<usize>,
+        /// The limit on the number of cells.
+        max_cells: usize,
+        /// The smallest radius that stays within the limit.
+        min_radius: f32,
+    },
+    /// Too few points were supplied to form a triangle.
+    InsufficientPoints {
+        /// Number of points supplied.
+        got: usize,
+        /// Number of points required.
+        need: usize,
+    },
+    /// No seed triangle was found. The diagnostics describe the search.
+    NoSeed(Box<Diagnostics>),
+    /// The positions and normals supplied separately differ in length.
+    MismatchedLengths {
+        /// Number of positions supplied.
+        positions: usize,
+        /// Number of normals supplied.
+        normals: usize,
+    },
+    /// The labels supplied for a segmented cloud differ in number from the
+    /// points.
+    MismatchedLabels {
+        /// Number of points supplied.
+        points: usize,
+        /// Number of labels supplied.
+        labels: usize,
+    },
+    /// The context mask supplied for a cloud differs in length from the
+    /// points.
+    MismatchedMask {
+        /// Number of points supplied.
+        points: usize,
+        /// Length of the mask supplied.
+        mask: usize,
+    },
+}
+
+impl fmt::Display for ReconstructError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRadius(radius) => {
+                write!(f, "the radius must be positive and finite, got {radius}")
+            }
+            Self::InvalidEmptyBallTolerance(tolerance) => {
+                write!(
+                    f,
+                    "the empty ball tolerance must be at least 0 and less than 1, got {tolerance}"
+                )
+            }
+            Self::GridTooLarge {
+                cells,
+                max_cells,
+                min_radius,
+            } => {
+                match cells {
+                    Some(cells) => write!(f, "the grid needs {cells} cells")?,
+                    None => write!(f, "the grid needs too many cells to count")?,
+                }
+                write!(
+                    f,
+                    ", over the limit of {max_cells}; use a radius of at least {min_radius}"
+                )
+            }
+            Self::InsufficientPoints { got, need } => {
+                write!(f, "at least {need} points are needed, got {got}")
+            }
+            Self::MismatchedLengths { positions, normals } => {
+                write!(f, "got {positions} positions but {normals} normals")
+            }
+            Self::MismatchedLabels { points, labels } => {
+                write!(f, "got {points} points but {labels} labels")
+            }
+            Self::MismatchedMask { points, mask } => {
+                write!(f, "got {points} points but a mask of {mask}")
+            }
+            Self::NoSeed(diagnostics) => {
+                let rejections = diagnostics.seed_rejections;
+                write!(
+                    f,
+                    "no seed triangle found: scanned {} cells, {} isolated points; \
+                     rejected {} candidates facing away from the cell normal, \
+                     {} too large for the ball, {} with points inside the ball",
+                    diagnostics.cells_scanned,
+                    diagnostics.isolated_points,
+                    rejections.normal,
+                    rejections.no_ball_center,
+                    rejections.ball_not_empty,
+                )?;
+                if let Some(spacing) = diagnostics.densest_cell_spacing {
+                    write!(
+                        f,
+                        "; the densest points are {spacing} apart, \
+                         try a radius larger than that"
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl core::error::Error for ReconstructError {}
@@ -0,0 +1,234 @@
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use crate::config::Traversal;
+use crate::mesh::EdgeStatus;
+use crate::mesh::MeshEdge;
+
+// A slot in the intrusive linked list.
+#[derive(Debug)]
+struct Slot {
+    edge: Option<Rc<RefCell<MeshEdge>>>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// The set of active edges still to be pivoted.
+///
+/// Edges are stored in a slab backed doubly linked list, so an edge whose
+/// status changes is unlinked immediately rather than lingering until it
+/// reaches the end of the queue. Only active edges are ever held, and only
+/// one from each point to each other point.
+#[derive(Debug)]
+pub struct Front {
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+    // The slot of the active edge between each pair of points, keyed by the
+    // identity of its start and end.
+    keys: BTreeMap<(usize, usize), usize>,
+    merged: usize,
+    merge_duplicates: bool,
+    head: Option<usize>,
+    tail: Option<usize>,
+    active: usize,
+    traversal: Traversal,
+}
+
+impl Front {
+    /// An empty front.
+    #[must_use]
+    pub const fn new(traversal: Traversal) -> Self {
+        Self {
+            slots: vec![],
+            free: vec![],
+            keys: BTreeMap::new(),
+            merged: 0,
+            merge_duplicates: true,
+            head: None,
+            tail: None,
+            active: 0,
+            traversal,
+        }
+    }
+
+    /// Number of active edges on the front.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.active
+    }
+
+    /// True when no active edges remain.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.active == 0
+    }
+
+    /// An estimate of the bytes held by the front, not counting the edges.
+    #[must_use]
+    pub fn memory_bytes(&self) -> usize {
+        // A map entry holds its key and value, and about as much again in
+        // the tree around it.
+        self.slots.capacity() * size_of::<Slot>()
+            + self.free.capacity() * size_of::<usize>()
+            + self.keys.len() * 2 * size_of::<((usize, usize), usize)>()
+    }
+
+    /// Keeps edges with the same start and end as an active edge on the
+    /// front, as the C++ implementation did, rather than merging them.
+    #[must_use]
+    pub const fn with_merge_duplicates(mut self, merge_duplicates: bool) -> Self {
+        self.merge_duplicates = merge_duplicates;
+        self
+    }
+
+    /// Edges merged into an active edge with the same start and end, see
+    /// [`Self::push()`].
+    #[must_use]
+    pub const fn merged(&self) -> usize {
+        self.merged
+    }
+
+    /// Appends an active edge.
+    ///
+    /// When an active edge from the same point to the same point is already
+    /// on the front, as complex glue sequences can leave, the two would be
+    /// pivoted into overlapping faces. Unless turned off with
+    /// [`Self::with_merge_duplicates()`], the new edge is merged into the old
+    /// one instead: it is marked as a boundary and left off the front.
+    pub fn push(&mut self, edge: &Rc<RefCell<MeshEdge>>) {
+        debug_assert!(edge.borrow().status == EdgeStatus::Active);
+        debug_assert!(edge.borrow().front_slot.is_none());
+        let key = key(edge);
+        if self.merge_duplicates && self.keys.contains_key(&key) {
+            edge.borrow_mut().status = EdgeStatus::Boundary;
+            self.merged += 1;
+            return;
+        }
+        let slot = Slot {
+            edge: Some(edge.clone()),
+            prev: self.tail,
+            next: None,
+        };
+        let index = if let Some(index) = self.free.pop() {
+            self.slots[index] = slot;
+            index
+        } else {
+            self.slots.push(slot);
+            self.slots.len() - 1
+        };
+        match self.tail {
+            Some(tail) => self.slots[tail].next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+        if self.merge_duplicates {
+            self.keys.insert(key, index);
+        }
+        edge.borrow_mut().front_slot = Some(index);
+        self.active += 1;
+    }
+
+    /// The next edge to pivot, as selected by the traversal strategy.
+    ///
+    /// The edge stays on the front until its status changes.
+    #[must_use]
+    pub fn active_edge(&self) -> Option<Rc<RefCell<MeshEdge>>> {
+        let index = match self.traversal {
+            Traversal::Lifo => self.tail,
+            Traversal::Fifo => self.head,
+        }?;
+        self.slots[index].edge.clone()
+    }
+
+    /// Marks the edge as inner, removing it from the front.
+    pub fn remove(&mut self, edge: &Rc<RefCell<MeshEdge>>) {
+        self.set_status(edge, EdgeStatus::Inner);
+    }
+
+    /// Marks the edge as a boundary, removing it from the front.
+    pub fn mark_boundary(&mut self, edge: &Rc<RefCell<MeshEdge>>) {
+        self.set_status(edge, EdgeStatus::Boundary);
+    }
+
+    fn set_status(&mut self, edge: &Rc<RefCell<MeshEdge>>, status: EdgeStatus) {
+        let slot = {
+            let mut inner = edge.borrow_mut();
+            inner.status = status;
+            inner.front_slot.take()
+        };
+        if let Some(index) = slot {
+            self.unlink(index);
+        }
+    }
+
+    fn unlink(&mut self, index: usize) {
+        if let Some(edge) = &self.slots[index].edge {
+            self.keys.remove(&key(edge));
+        }
+        let Slot { prev, next, .. } = self.slots[index];
+        match prev {
+            Some(prev) => self.slots[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.slots[next].prev = prev,
+            None => self.tail = prev,
+        }
+        self.slots[index] = Slot {
+            edge: None,
+            prev: None,
+            next: None,
+        };
+        self.free.push(index);
+        self.active -= 1;
+    }
+
+    /// The active edges, grouped into the loops their links form.
+    ///
+    /// Each loop starts at its oldest edge and follows the `next` links,
+    /// ending when they return to the start or leave the front. The loops
+    /// are ordered by their oldest edge.
+    #[cfg(feature = "unstable")]
+    #[must_use]
+    pub fn loops(&self) -> Vec<Vec<Rc<RefCell<MeshEdge>>>> {
+        let mut seen = vec![false; self.slots.len()];
+        let mut loops = vec![];
+        for edge in self.iter() {
+            let mut walk = vec![];
+            let mut cursor = Some(edge.clone());
+            while let Some(e) = cursor.take() {
+                let Some(slot) = e.borrow().front_slot.filter(|&slot| !seen[slot]) else {
+                    break;
+                };
+                seen[slot] = true;
+                cursor.clone_from(&e.borrow().next);
+                walk.push(e);
+            }
+            if !walk.is_empty() {
+                loops.push(walk);
+            }
+        }
+        loops
+    }
+
+    /// Iterates over the active edges, oldest first.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub fn iter(&self) -> impl Iterator<Item = &Rc<RefCell<MeshEdge>>> {
+        let mut cursor = self.head;
+        core::iter::from_fn(move || {
+            let slot = &self.slots[cursor?];
+            cursor = slot.next;
+            slot.edge.as_ref()
+        })
+    }
+}
+
+// The identities of the start and end of the edge.
+fn key(edge: &Rc<RefCell<MeshEdge>>) -> (usize, usize) {
+    let edge = edge.borrow();
+    (Rc::as_ptr(&edge.a) as usize, Rc::as_ptr(&edge.b) as usize)
+}
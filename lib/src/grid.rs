@@ -1,94 +1,469 @@
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
 use core::cell::RefCell;
 use core::f32;
-use core::panic;
+use core::ops::Div;
+#[cfg(feature = "debug-dumps")]
 use std::fmt::Write;
-use std::ops::Div;
+#[cfg(feature = "debug-dumps")]
 use std::path::PathBuf;
-use std::rc::Rc;
-use std::vec;
 
+use glam::DVec3;
 use glam::IVec3;
 use glam::Vec3;
+use glam::Vec4;
 use glam::ivec3;
 
 use crate::Cell;
-use crate::DEBUG;
+use crate::ReconstructError;
+use crate::ReconstructionConfig;
+use crate::cloud::Cloud;
+use crate::config::CompatMode;
+use crate::config::DEFAULT_EMPTY_BALL_TOLERANCE;
+use crate::config::Heuristics;
+use crate::config::NormalDisagreement;
+use crate::config::Predicates;
+use crate::config::SeedStrategy;
+use crate::front::Front;
+#[cfg(feature = "debug-dumps")]
 use crate::io::save_points;
+#[cfg(feature = "debug-dumps")]
 use crate::io::save_triangles_ascii;
+use crate::math::SplitMix64;
+use crate::math::acos;
+use crate::math::atan2_f64;
+use crate::math::mul_add;
+use crate::math::mul_add_f64;
+use crate::math::powi;
+use crate::math::sqrt;
+use crate::math::sqrt_f64;
 use crate::mesh::EdgeStatus;
 use crate::mesh::MeshEdge;
 use crate::mesh::MeshFace;
 use crate::mesh::MeshPoint;
+use crate::report::Diagnostics;
+use crate::report::PivotRejections;
+use crate::trace::Candidate;
+use crate::trace::Outcome;
+use crate::trace::note;
+use crate::validate;
 
 use crate::Point;
 use crate::Triangle;
 
+/// The point cloud, bucketed into cubes two radii wide.
 #[derive(Clone, Debug)]
-pub(crate) struct Grid {
+pub struct Grid {
     cell_size: f32,
     dims: IVec3,
     cells: Vec<Cell>,
+    // The sum of the normals in each cell, kept up to date as points are
+    // inserted so the seed search needn't recompute cell averages.
+    normal_sums: Vec<Vec3>,
+    // The number of points in the cells.
+    points: usize,
     lower: Vec3,
-    // upper: Vec3,
+    upper: Vec3,
+    predicates: Predicates,
+    max_neighbors: Option<usize>,
+    max_dihedral_angle: Option<f32>,
+    max_edge_length: Option<f32>,
+    normal_disagreement: NormalDisagreement,
+    empty_ball_tolerance: f32,
+    compat: CompatMode,
 }
 
 impl Grid {
-    pub fn new(points: &[Point], radius: f32) -> Self {
-        let cell_size = 2_f32 * radius;
-        let mut lower = points.first().expect("Vec with no points").pos;
-        let mut upper = points.first().expect("Vec with no points(2)").pos;
-        for p in points {
-            for i in 0..3 {
-                lower[i] = lower[i].min(p.pos[i]);
-                upper[i] = upper[i].max(p.pos[i]);
+    /// Buckets the points for a ball of the given radius.
+    ///
+    /// # Errors
+    ///   For the reasons given by [`crate::reconstruct_with_config()`]
+    ///   before the search for a seed, with the default limit on the number
+    ///   of cells.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub fn new<P: core::borrow::Borrow<Point>>(
+        points: &[P],
+        radius: f32,
+    ) -> Result<Self, ReconstructError> {
+        validate(points, &ReconstructionConfig::new(radius))?;
+        Self::from_cloud(points, radius)
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    pub(crate) fn from_cloud<C: Cloud + ?Sized>(
+        points: &C,
+        radius: f32,
+    ) -> Result<Self, ReconstructError> {
+        let (lower, upper) =
+            bounds(points).ok_or(ReconstructError::InsufficientPoints { got: 0, need: 1 })?;
+        let mut grid = Self::empty(lower, upper, radius)?;
+        for index in 0..points.len() {
+            let pos = points.pos(index);
+            let offset = grid.offset(grid.cell_index(&pos));
+            grid.insert(
+                offset,
+                index,
+                pos,
+                points.normal(index),
+                points.is_context(index),
+            );
+        }
+        Ok(grid)
+    }
+
+    // The bounds are reduced and the cells of the points found on every
+    // thread. Only the points themselves, which cannot be shared between
+    // threads, are created serially.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn from_cloud<C: Cloud + ?Sized>(
+        points: &C,
+        radius: f32,
+    ) -> Result<Self, ReconstructError> {
+        use rayon::prelude::*;
+
+        if points.len() == 0 {
+            return Err(ReconstructError::InsufficientPoints { got: 0, need: 1 });
+        }
+        // A cloud that is not a slice of positions may not be shareable
+        // between threads, so its positions are copied out first.
+        let copied: Vec<Vec3>;
+        let positions = if let Some(positions) = points.positions() {
+            positions
+        } else {
+            copied = (0..points.len()).map(|i| points.pos(i)).collect();
+            &copied
+        };
+
+        let empty = || (Vec3::INFINITY, Vec3::NEG_INFINITY);
+        let (lower, upper) = positions
+            .par_iter()
+            .fold(empty, |(lower, upper), pos| {
+                (lower.min(*pos), upper.max(*pos))
+            })
+            .reduce(empty, |(a, b), (c, d)| (a.min(c), b.max(d)));
+        let mut grid = Self::empty(lower, upper, radius)?;
+
+        let (cell_size, dims) = (grid.cell_size, grid.dims);
+        let offsets: Vec<usize> = positions
+            .par_iter()
+            .map(|pos| offset(cell_of(pos, lower, cell_size, dims), dims))
+            .collect();
+        for (index, (offset, pos)) in offsets.into_iter().zip(positions).enumerate() {
+            grid.insert(
+                offset,
+                index,
+                *pos,
+                points.normal(index),
+                points.is_context(index),
+            );
+        }
+        Ok(grid)
+    }
+
+    /// Sets how the seed search and the pivots compute ball centers and
+    /// pivot angles.
+    #[must_use]
+    pub const fn with_predicates(mut self, predicates: Predicates) -> Self {
+        self.predicates = predicates;
+        self
+    }
+
+    /// Limits the points considered around a pivot or a seed to the nearest
+    /// `max_neighbors`.
+    #[must_use]
+    pub const fn with_max_neighbors(mut self, max_neighbors: Option<usize>) -> Self {
+        self.max_neighbors = max_neighbors;
+        self
+    }
+
+    /// Rejects pivots folding the new triangle more than `max_dihedral_angle`
+    /// radians away from the triangle across the pivot edge.
+    #[must_use]
+    pub const fn with_max_dihedral_angle(mut self, max_dihedral_angle: Option<f32>) -> Self {
+        self.max_dihedral_angle = max_dihedral_angle;
+        self
+    }
+
+    /// Sets what a pivot does with a point whose normal faces away from the
+    /// new triangle, when [`Heuristics::normal_half_space`] is set.
+    #[must_use]
+    pub const fn with_normal_disagreement(mut self, policy: NormalDisagreement) -> Self {
+        self.normal_disagreement = policy;
+        self
+    }
+
+    /// Rejects seeds and pivots making an edge longer than `max_edge_length`.
+    #[must_use]
+    pub const fn with_max_edge_length(mut self, max_edge_length: Option<f32>) -> Self {
+        self.max_edge_length = max_edge_length;
+        self
+    }
+
+    /// The points less than a cell width, two radii, from `point`, nearest
+    /// first. Points the same distance away keep the order of the cells
+    /// they are in. Limited as set by [`Self::with_max_neighbors()`].
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    #[must_use]
+    pub fn neighbors_by_distance(&self, point: Vec3) -> Vec<Rc<RefCell<MeshPoint>>> {
+        let mut neighborhood = self.spherical_neighborhood(&point, &[]);
+        sort_by_distance(&mut neighborhood, point);
+        neighborhood
+    }
+
+    /// The corners of the smallest box holding every point, lowest first.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    #[must_use]
+    pub const fn bounds(&self) -> (Vec3, Vec3) {
+        (self.lower, self.upper)
+    }
+
+    /// The number of cells along each axis. Points beyond the last cell on
+    /// an axis are kept in it.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    #[must_use]
+    pub const fn dims(&self) -> IVec3 {
+        self.dims
+    }
+
+    /// An estimate of the bytes held by the grid and its points, not counting
+    /// the edges of the mesh they refer to.
+    #[must_use]
+    pub const fn memory_bytes(&self) -> usize {
+        // An `Rc` allocation holds two counts alongside its value.
+        let point = 2 * size_of::<usize>() + size_of::<RefCell<MeshPoint>>();
+        self.cells.len() * (size_of::<Cell>() + size_of::<Vec3>())
+            + self.points * (point + size_of::<Rc<RefCell<MeshPoint>>>())
+    }
+
+    /// The number of cells holding at least one point.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    #[must_use]
+    pub fn occupied_cells(&self) -> usize {
+        self.cells.iter().filter(|cell| !cell.is_empty()).count()
+    }
+
+    /// The number of cells holding each number of points: entry `n` counts
+    /// the cells with `n` points, from the empty cells up to the fullest.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    #[must_use]
+    pub fn histogram_points_per_cell(&self) -> Vec<usize> {
+        let fullest = self.cells.iter().map(Vec::len).max().unwrap_or(0);
+        let mut histogram = vec![0; fullest + 1];
+        for cell in &self.cells {
+            histogram[cell.len()] += 1;
+        }
+        histogram
+    }
+
+    /// Counts a point as on the surface of the ball, rather than inside it,
+    /// when within `tolerance` times the squared radius of it.
+    #[must_use]
+    pub const fn with_empty_ball_tolerance(mut self, tolerance: f32) -> Self {
+        self.empty_ball_tolerance = tolerance;
+        self
+    }
+
+    /// Tests balls for emptiness as the C++ implementation did, with
+    /// [`CompatMode::Cpp`].
+    #[must_use]
+    pub const fn with_compat(mut self, compat: CompatMode) -> Self {
+        self.compat = compat;
+        self
+    }
+
+    // True when no point is inside the ball, as tested by the implementation
+    // followed.
+    fn is_empty_ball(&self, ball_center: &Vec3, points: &PackedPositions, radius: f32) -> bool {
+        match self.compat {
+            CompatMode::Improved => {
+                ball_is_empty(ball_center, points, radius, self.empty_ball_tolerance)
             }
+            CompatMode::Cpp => none_within(ball_center, points, mul_add(radius, radius, -1e-4)),
         }
+    }
 
-        let ceil_float = (upper - lower).ceil().div(cell_size);
-        let candidate_dim: IVec3 = ivec3(
-            ceil_float[0] as i32,
-            ceil_float[1] as i32,
-            ceil_float[2] as i32,
-        );
-        let dims = candidate_dim.max(ivec3(1, 1, 1));
-        let cells = vec![Cell::default(); (dims.x * dims.y * dims.z) as usize];
+    // True when an edge from `a` to `b` is longer than allowed.
+    fn too_long(&self, a: Vec3, b: Vec3) -> bool {
+        self.max_edge_length
+            .is_some_and(|max| a.distance_squared(b) > max * max)
+    }
 
+    // The same points, with their place in the mesh, bucketed for a ball of
+    // another radius.
+    pub(crate) fn rebucketed(&self, radius: f32) -> Result<Self, ReconstructError> {
         let mut grid = Self {
-            cell_size,
+            predicates: self.predicates,
+            max_neighbors: self.max_neighbors,
+            max_dihedral_angle: self.max_dihedral_angle,
+            max_edge_length: self.max_edge_length,
+            normal_disagreement: self.normal_disagreement,
+            empty_ball_tolerance: self.empty_ball_tolerance,
+            compat: self.compat,
+            ..Self::empty(self.lower, self.upper, radius)?
+        };
+        for point in self.cells.iter().flatten() {
+            let (pos, normal) = (point.borrow().pos, point.borrow().normal);
+            let offset = grid.offset(grid.cell_index(&pos));
+            grid.normal_sums[offset] += normal;
+            grid.cells[offset].push(point.clone());
+            grid.points += 1;
+        }
+        Ok(grid)
+    }
+
+    // A grid without points spanning `lower` to `upper`.
+    fn empty(lower: Vec3, upper: Vec3, radius: f32) -> Result<Self, ReconstructError> {
+        let (dims, cell_count) =
+            grid_dims(upper - lower, radius).ok_or_else(|| ReconstructError::GridTooLarge {
+                cells: None,
+                max_cells: usize::MAX,
+                min_radius: min_radius_for(upper - lower, usize::MAX),
+            })?;
+        Ok(Self {
+            cell_size: 2_f32 * radius,
             dims,
-            cells,
+            cells: vec![Cell::default(); cell_count],
+            normal_sums: vec![Vec3::ZERO; cell_count],
+            points: 0,
             lower,
-            // upper,
-        };
+            upper,
+            predicates: Predicates::default(),
+            max_neighbors: None,
+            max_dihedral_angle: None,
+            max_edge_length: None,
+            normal_disagreement: NormalDisagreement::Reject,
+            empty_ball_tolerance: DEFAULT_EMPTY_BALL_TOLERANCE,
+            compat: CompatMode::Improved,
+        })
+    }
 
-        for p in points {
-            let actual_cell = grid.cell(grid.cell_index(&p.pos));
-            actual_cell.push(Rc::new(RefCell::new(MeshPoint::from(p))));
-        }
+    // Adds the point with this index in the cloud to the cell at `offset`.
+    fn insert(&mut self, offset: usize, index: usize, pos: Vec3, normal: Vec3, context: bool) {
+        self.normal_sums[offset] += normal;
+        self.cells[offset].push(Rc::new(RefCell::new(MeshPoint {
+            context,
+            index,
+            ..MeshPoint::from(&Point { pos, normal })
+        })));
+        self.points += 1;
+    }
 
-        grid
+    // The mean normal of the points in the cell at `offset`, or zero when
+    // they cancel.
+    pub(crate) fn average_normal(&self, offset: usize) -> Vec3 {
+        self.normal_sums[offset].normalize_or_zero()
+    }
+
+    // Reverses the normal of a point, keeping the normals of its cell
+    // summed for seed searches.
+    pub(crate) fn flip_normal(&mut self, point: &Rc<RefCell<MeshPoint>>) {
+        let mut point = point.borrow_mut();
+        let offset = self.offset(self.cell_index(&point.pos));
+        self.normal_sums[offset] -= 2.0 * point.normal;
+        point.normal = -point.normal;
     }
 
     fn cell_index(&self, point: &Vec3) -> IVec3 {
-        let diff = (point - self.lower) / self.cell_size;
-        let index = ivec3(diff.x as i32, diff.y as i32, diff.z as i32);
-        index.clamp(ivec3(0, 0, 0), self.dims - 1)
+        cell_of(point, self.lower, self.cell_size, self.dims)
+    }
+
+    fn cell(&self, index: IVec3) -> &Cell {
+        &self.cells[self.offset(index)]
+    }
+
+    fn offset(&self, index: IVec3) -> usize {
+        offset(index, self.dims)
+    }
+
+    // Mean distance from the points in the most populated cell to their
+    // nearest neighbours. At most 64 points are sampled.
+    pub(crate) fn densest_cell_spacing(&self) -> Option<f32> {
+        let cell = self.cells.iter().max_by_key(|cell| cell.len())?;
+        let nearest: Vec<f32> = cell
+            .iter()
+            .take(64)
+            .filter_map(|p| self.nearest_distance(p))
+            .collect();
+        #[allow(clippy::cast_precision_loss)]
+        (!nearest.is_empty()).then(|| nearest.iter().sum::<f32>() / nearest.len() as f32)
     }
 
-    fn cell(&mut self, index: IVec3) -> &mut Cell {
-        let index = index.z * self.dims.x * self.dims.y + index.y * self.dims.x + index.x;
-        &mut self.cells[index as usize]
+    // The distance from `p` to the nearest other point, searching shells of
+    // cells outward until no nearer point can lie beyond them.
+    fn nearest_distance(&self, p: &Rc<RefCell<MeshPoint>>) -> Option<f32> {
+        let pos = p.borrow().pos;
+        let center = self.cell_index(&pos);
+        let mut best: Option<f32> = None;
+        for ring in 0..=self.dims.max_element() {
+            for x in -ring..=ring {
+                for y in -ring..=ring {
+                    for z in -ring..=ring {
+                        let step = ivec3(x, y, z);
+                        let index = center + step;
+                        if step.abs().max_element() != ring
+                            || index.cmplt(IVec3::ZERO).any()
+                            || index.cmpge(self.dims).any()
+                        {
+                            continue;
+                        }
+                        for q in self.cell(index).iter().filter(|q| !Rc::ptr_eq(p, q)) {
+                            let d = q.borrow().pos.distance(pos);
+                            best = Some(best.map_or(d, |best| best.min(d)));
+                        }
+                    }
+                }
+            }
+            // Every point within `ring` cells of `p` has been seen.
+            #[allow(clippy::cast_precision_loss)]
+            if best.is_some_and(|best| best <= ring as f32 * self.cell_size) {
+                break;
+            }
+        }
+        best
+    }
+
+    // Indices of the points that are not vertices of the mesh, in input order.
+    // Context points are never meshed, so are left out.
+    pub(crate) fn unused_points(&self) -> Vec<usize> {
+        let mut unused: Vec<usize> = self
+            .cells
+            .iter()
+            .flatten()
+            .filter(|p| !p.borrow().used && !p.borrow().context)
+            .map(|p| p.borrow().index)
+            .collect();
+        unused.sort_unstable();
+        unused
+    }
+
+    // The grid point at exactly this position.
+    #[cfg(feature = "std")]
+    pub(crate) fn point_at(&self, pos: &Vec3) -> Option<Rc<RefCell<MeshPoint>>> {
+        let index = self.cell_index(pos);
+        self.cell(index)
+            .iter()
+            .find(|p| p.borrow().pos == *pos)
+            .cloned()
     }
 
     fn spherical_neighborhood(
-        &mut self,
+        &self,
         point: &Vec3,
-        ignore: &[Vec3],
+        ignore: &[&Rc<RefCell<MeshPoint>>],
     ) -> Vec<Rc<RefCell<MeshPoint>>> {
-        let center_index = self.cell_index(point);
+        let mut candidates = Vec::new();
+        self.gather_candidates(self.cell_index(point), &mut candidates);
+        self.nearby(point, ignore, &candidates)
+    }
+
+    // Replaces the contents of `candidates` with the points of the cell at
+    // `center_index` and of the cells around it: everything that may lie
+    // within a cell width of a point in that cell.
+    fn gather_candidates(&self, center_index: IVec3, candidates: &mut Vec<Rc<RefCell<MeshPoint>>>) {
+        candidates.clear();
         // Just an estimate.
-        let capacity = self.cell(center_index).len() * 27;
-        let mut result = Vec::with_capacity(capacity);
+        candidates.reserve(self.cell(center_index).len() * 27);
         for x_off in [-1, 0, 1] {
             for y_off in [-1, 0, 1] {
                 for z_off in [-1, 0, 1] {
@@ -99,24 +474,112 @@ impl Grid {
                     {
                         continue;
                     }
-
-                    // TODO cell_size is defined at the top, to appease the borrow checker
-                    let cell_size = self.cell_size;
-                    for p in self.cell(index) {
-                        let p_pos = p.borrow().pos;
-                        if (p_pos - point).length_squared() < cell_size * cell_size
-                            && !ignore.contains(&p_pos)
-                        {
-                            result.push(p.clone());
-                        }
-                    }
+                    candidates.extend(self.cell(index).iter().cloned());
                 }
             }
         }
+    }
+
+    // The candidates within a cell width of `point`, in the order they were
+    // gathered, less those in `ignore`.
+    fn nearby(
+        &self,
+        point: &Vec3,
+        ignore: &[&Rc<RefCell<MeshPoint>>],
+        candidates: &[Rc<RefCell<MeshPoint>>],
+    ) -> Vec<Rc<RefCell<MeshPoint>>> {
+        let cell_size = self.cell_size;
+        let mut result: Vec<Rc<RefCell<MeshPoint>>> = candidates
+            .iter()
+            .filter(|p| {
+                (p.borrow().pos - point).length_squared() < cell_size * cell_size
+                    && !ignore.iter().any(|q| Rc::ptr_eq(p, q))
+            })
+            .cloned()
+            .collect();
+        if let Some(max) = self.max_neighbors
+            && result.len() > max
+        {
+            let distance = |p: &Rc<RefCell<MeshPoint>>| p.borrow().pos.distance_squared(*point);
+            result.select_nth_unstable_by(max, |a, b| distance(a).total_cmp(&distance(b)));
+            result.truncate(max);
+        }
         result
     }
 }
 
+// The cell of a grid holding `point`, with points outside it clamped to the
+// nearest cell.
+#[allow(clippy::cast_possible_truncation)]
+fn cell_of(point: &Vec3, lower: Vec3, cell_size: f32, dims: IVec3) -> IVec3 {
+    let diff = (point - lower) / cell_size;
+    let index = ivec3(diff.x as i32, diff.y as i32, diff.z as i32);
+    index.clamp(ivec3(0, 0, 0), dims - 1)
+}
+
+// Sorts the points nearest `point` first, keeping ties in order.
+fn sort_by_distance(points: &mut [Rc<RefCell<MeshPoint>>], point: Vec3) {
+    let distance = |p: &Rc<RefCell<MeshPoint>>| p.borrow().pos.distance_squared(point);
+    points.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+}
+
+// The position of a cell in the cells of a grid.
+fn offset(index: IVec3, dims: IVec3) -> usize {
+    // In usize, as the cell count may not fit in an i32.
+    let [x, y, z] = index.to_array().map(|i| i.unsigned_abs() as usize);
+    let [dx, dy, _] = dims.to_array().map(|i| i.unsigned_abs() as usize);
+    (z * dy + y) * dx + x
+}
+
+// The smallest box holding every point.
+pub fn bounds<C: Cloud + ?Sized>(points: &C) -> Option<(Vec3, Vec3)> {
+    let first = (points.len() > 0).then(|| points.pos(0))?;
+    Some(
+        (1..points.len())
+            .map(|i| points.pos(i))
+            .fold((first, first), |(lower, upper), pos| {
+                (lower.min(pos), upper.max(pos))
+            }),
+    )
+}
+
+// The number of cells along each axis, and in total, of a grid spanning
+// `extent`, in whole cells so that the layout does not depend on the units
+// of the cloud. `None` when an axis does not fit in an i32, or the total in
+// a usize.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn grid_dims(extent: Vec3, radius: f32) -> Option<(IVec3, usize)> {
+    let counts = extent.div(2_f32 * radius).ceil();
+    let mut dims = ivec3(1, 1, 1);
+    let mut total = 1_usize;
+    for i in 0..3 {
+        if counts[i].is_nan() || counts[i] >= powi(2.0, 31) {
+            return None;
+        }
+        dims[i] = (counts[i] as i32).max(1);
+        total = total.checked_mul(dims[i] as usize)?;
+    }
+    Some((dims, total))
+}
+
+// The smallest radius, found by bisection, whose grid spanning `extent` has
+// at most `max_cells` cells.
+pub fn min_radius_for(extent: Vec3, max_cells: usize) -> f32 {
+    let fits = |radius| grid_dims(extent, radius).is_some_and(|(_, total)| total <= max_cells);
+    // A single cell spans everything.
+    let mut hi = (extent.max_element() / 2.0).max(f32::MIN_POSITIVE);
+    let mut lo = 0.0_f32;
+    for _ in 0..64 {
+        let mid = f32::midpoint(lo, hi);
+        if fits(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi
+}
+
 /// Computes the circumcenter of a triangle in 3D space.
 ///
 /// The circumcenter is the center of the circle that passes through all three
@@ -136,56 +599,252 @@ pub fn compute_ball_center(f: &MeshFace, radius: f32) -> Option<Vec3> {
 
     let circum_circle_center = f.0[0].borrow().pos + to_circum_circle_center;
 
-    let height_squared = radius.mul_add(
+    let height_squared = mul_add(
+        radius,
         radius,
         -to_circum_circle_center.dot(to_circum_circle_center),
     );
-    if height_squared.is_sign_negative() {
+    // NaN when the vertices are collinear or coincident.
+    if height_squared.is_nan() || height_squared.is_sign_negative() {
         return None;
     }
 
-    Some(circum_circle_center + f.normal() * height_squared.sqrt())
+    Some(circum_circle_center + f.normal() * sqrt(height_squared))
 }
 
-fn ball_is_empty(ball_center: &Vec3, points: &[Rc<RefCell<MeshPoint>>], radius: f32) -> bool {
-    let threshold = radius.mul_add(radius, -1e-4);
-    !points.iter().any(|p| {
-        let length_squared = (p.borrow().pos - ball_center).length_squared();
-        // TODO epsilon
-        length_squared < threshold
-    })
+// Relative rounding error in `f32` beyond which a geometric decision is
+// recomputed in `f64`, with `Predicates::Robust`.
+const UNCERTAINTY: f32 = 64.0 * f32::EPSILON;
+
+// Pivot angles closer than this may compare the wrong way round in `f32`.
+const ANGLE_UNCERTAINTY: f32 = 1e-4;
+
+// Within this of no turn or of half a turn, `acos()` has lost half its
+// precision and the direction of the turn is unreliable.
+const TURN_UNCERTAINTY: f32 = 1e-3;
+
+/// Like [`compute_ball_center()`], but recomputed in `f64` when rounding in
+/// `f32` may decide the result: when the vertices are nearly collinear, or
+/// the ball is nearly too small to rest on them.
+#[must_use]
+pub fn compute_ball_center_robust(f: &MeshFace, radius: f32) -> Option<Vec3> {
+    let [a, b, c] = f.0.each_ref().map(|p| p.borrow().pos);
+    if ball_center_is_uncertain(a, b, c, radius) {
+        precise_ball_center(a.as_dvec3(), b.as_dvec3(), c.as_dvec3(), f64::from(radius))
+            .map(DVec3::as_vec3)
+    } else {
+        compute_ball_center(f, radius)
+    }
 }
 
-pub(crate) struct SeedResult {
-    pub(crate) f: MeshFace,
-    pub(crate) ball_center: Vec3,
+fn ball_center(f: &MeshFace, radius: f32, predicates: Predicates) -> Option<Vec3> {
+    match predicates {
+        Predicates::Fast => compute_ball_center(f, radius),
+        Predicates::Robust => compute_ball_center_robust(f, radius),
+    }
 }
 
-pub(crate) fn find_seed_triangle(grid: &Grid, radius: f32) -> Option<SeedResult> {
-    for cell in &grid.cells {
-        let avg_normal = cell
-            .iter()
-            .fold(Vec3::new(0.0, 0.0, 0.0), |acc, p| acc + p.borrow().normal)
-            .normalize();
+fn ball_center_is_uncertain(a: Vec3, b: Vec3, c: Vec3, radius: f32) -> bool {
+    let (ab, ac) = (b - a, c - a);
+    let lengths_squared = ab.length_squared() * ac.length_squared();
+    // The square of the sine of the angle at `a`.
+    let sine_squared = ab.cross(ac).length_squared() / lengths_squared;
+    if sine_squared.is_nan() || sine_squared <= UNCERTAINTY * UNCERTAINTY {
+        return true;
+    }
+    // The error in the circumradius grows as the triangle flattens.
+    let circumradius_squared = (b - c).length_squared() / (4.0 * sine_squared);
+    mul_add(radius, -radius, circumradius_squared).abs() * sine_squared
+        <= UNCERTAINTY * radius * radius
+}
+
+// `compute_ball_center()` in `f64`.
+fn precise_ball_center(a: DVec3, b: DVec3, c: DVec3, radius: f64) -> Option<DVec3> {
+    let (ab, ac) = (b - a, c - a);
+    let ab_cross_ac = ab.cross(ac);
+    let to_circum_circle_center = (ab_cross_ac.cross(ab) * ac.dot(ac)
+        + ac.cross(ab_cross_ac) * ab.dot(ab))
+        / (2.0 * ab_cross_ac.dot(ab_cross_ac));
+    let height_squared = mul_add_f64(
+        radius,
+        radius,
+        -to_circum_circle_center.dot(to_circum_circle_center),
+    );
+    if height_squared.is_nan() || height_squared.is_sign_negative() {
+        return None;
+    }
+    let normal = (a - b).cross(a - c).normalize();
+    Some(a + to_circum_circle_center + normal * sqrt_f64(height_squared))
+}
+
+// The angle the ball turns through about the edge from `a` to `b`, from
+// `old_center` to resting on `p`, in `f64`.
+fn precise_pivot_angle(a: Vec3, b: Vec3, p: Vec3, old_center: Vec3, radius: f32) -> Option<f64> {
+    let [a, b, p] = [a, b, p].map(Vec3::as_dvec3);
+    let new_center = precise_ball_center(b, a, p, f64::from(radius))?;
+    let m = (a + b) / 2.0;
+    let old_center_vec = (old_center.as_dvec3() - m).normalize();
+    let new_center_vec = (new_center - m).normalize();
+    let turn = new_center_vec.cross(old_center_vec);
+    let mut angle = atan2_f64(turn.length(), new_center_vec.dot(old_center_vec));
+    if turn.dot(a - b) < 0.0 {
+        angle += core::f64::consts::PI;
+    }
+    Some(angle)
+}
+
+/// Neighborhood positions, stored contiguously per axis.
+///
+/// Each axis is padded with infinity to a multiple of four, so the emptiness
+/// test can run four lanes at a time without a scalar tail.
+#[derive(Debug, Default)]
+pub struct PackedPositions {
+    x: Vec<f32>,
+    y: Vec<f32>,
+    z: Vec<f32>,
+}
+
+impl PackedPositions {
+    pub(crate) fn new(points: &[Rc<RefCell<MeshPoint>>]) -> Self {
+        let len = points.len().next_multiple_of(4);
+        let mut packed = Self {
+            x: Vec::with_capacity(len),
+            y: Vec::with_capacity(len),
+            z: Vec::with_capacity(len),
+        };
+        for p in points {
+            let pos = p.borrow().pos;
+            packed.x.push(pos.x);
+            packed.y.push(pos.y);
+            packed.z.push(pos.z);
+        }
+        for axis in [&mut packed.x, &mut packed.y, &mut packed.z] {
+            axis.resize(len, f32::INFINITY);
+        }
+        packed
+    }
+}
+
+// True when no point is inside the ball, by more than `tolerance` times the
+// squared radius.
+pub fn ball_is_empty(
+    ball_center: &Vec3,
+    points: &PackedPositions,
+    radius: f32,
+    tolerance: f32,
+) -> bool {
+    let radius_squared = radius * radius;
+    none_within(
+        ball_center,
+        points,
+        mul_add(radius_squared, -tolerance, radius_squared),
+    )
+}
+
+// True when no point is nearer `center` than the square root of
+// `distance_squared`.
+fn none_within(center: &Vec3, points: &PackedPositions, distance_squared: f32) -> bool {
+    let threshold = Vec4::splat(distance_squared);
+    let cx = Vec4::splat(center.x);
+    let cy = Vec4::splat(center.y);
+    let cz = Vec4::splat(center.z);
+    !points
+        .x
+        .chunks_exact(4)
+        .zip(points.y.chunks_exact(4))
+        .zip(points.z.chunks_exact(4))
+        .any(|((x, y), z)| {
+            let dx = Vec4::from_slice(x) - cx;
+            let dy = Vec4::from_slice(y) - cy;
+            let dz = Vec4::from_slice(z) - cz;
+            let length_squared = dx * dx + dy * dy + dz * dz;
+            length_squared.cmplt(threshold).any()
+        })
+}
+
+/// The first triangle of a mesh.
+#[derive(Debug)]
+pub struct SeedResult {
+    /// The triangle.
+    pub f: MeshFace,
+    /// Center of the empty ball touching its vertices.
+    pub ball_center: Vec3,
+}
+
+/// Searches the grid for a triangle whose ball contains no other points, and
+/// marks its vertices as used.
+///
+/// Counts the cells scanned and the candidates rejected in `diagnostics`.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub fn find_seed_triangle(
+    grid: &Grid,
+    radius: f32,
+    diagnostics: &mut Diagnostics,
+) -> Option<SeedResult> {
+    find_seed_triangle_with(grid, radius, SeedStrategy::ScanOrder, diagnostics)
+}
+
+/// Like [`find_seed_triangle()`], visiting the cells in the order given by
+/// `strategy`. Only occupied cells are counted as scanned, except in scan
+/// order.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+pub fn find_seed_triangle_with(
+    grid: &Grid,
+    radius: f32,
+    strategy: SeedStrategy,
+    diagnostics: &mut Diagnostics,
+) -> Option<SeedResult> {
+    find_seed_triangle_until(grid, radius, strategy, diagnostics, &|| false)
+}
+
+// Like `find_seed_triangle_with()`, giving up when `stop` returns true before
+// a cell is scanned.
+pub fn find_seed_triangle_until(
+    grid: &Grid,
+    radius: f32,
+    strategy: SeedStrategy,
+    diagnostics: &mut Diagnostics,
+    stop: &dyn Fn() -> bool,
+) -> Option<SeedResult> {
+    let order = seed_cell_order(grid, strategy);
+    let offsets: &mut dyn Iterator<Item = usize> = match &order {
+        Some(order) => &mut order.iter().copied(),
+        None => &mut (0..grid.cells.len()),
+    };
+    // The points around the last cell gathered, shared by every point in it.
+    let mut candidates = Vec::new();
+    let mut gathered = None;
+    for offset in offsets {
+        if stop() {
+            return None;
+        }
+        diagnostics.cells_scanned += 1;
+        let cell = &grid.cells[offset];
+        let avg_normal = grid.average_normal(offset);
 
         for p1 in cell {
-            let mut neighborhood = grid
-                .clone()
-                .spherical_neighborhood(&p1.borrow().pos, &[p1.borrow().pos]);
+            if p1.borrow().context {
+                continue;
+            }
+            let pos = p1.borrow().pos;
+            let index = grid.cell_index(&pos);
+            if gathered != Some(index) {
+                grid.gather_candidates(index, &mut candidates);
+                gathered = Some(index);
+            }
+            let mut neighborhood = grid.nearby(&pos, &[p1], &candidates);
 
-            neighborhood.sort_by(|a, b| {
-                if (a.borrow().pos - p1.borrow().pos).length_squared()
-                    < (b.borrow().pos - p1.borrow().pos).length_squared()
-                {
-                    std::cmp::Ordering::Less
-                } else {
-                    std::cmp::Ordering::Greater
-                }
-            });
+            sort_by_distance(&mut neighborhood, pos);
 
+            if neighborhood.len() < 2 {
+                diagnostics.isolated_points += 1;
+            }
+
+            let packed = PackedPositions::new(&neighborhood);
             for p2 in neighborhood.clone() {
                 for p3 in &neighborhood {
-                    if p2.as_ptr() == p3.as_ptr() {
+                    if p2.as_ptr() == p3.as_ptr() || p2.borrow().context || p3.borrow().context {
                         continue;
                     }
 
@@ -194,17 +853,25 @@ pub(crate) fn find_seed_triangle(grid: &Grid, radius: f32) -> Option<SeedResult>
                     let f = MeshFace([p1.clone(), p2.clone(), p3.clone()]);
 
                     if f.normal().dot(avg_normal) < 0.0 {
+                        diagnostics.seed_rejections.normal += 1;
                         continue;
                     }
-                    let ball_center = compute_ball_center(&f, radius);
-                    if let Some(ball_center) = ball_center
-                        && ball_is_empty(&ball_center, &neighborhood, radius)
-                    {
+                    let [a, b, c] = f.0.each_ref().map(|p| p.borrow().pos);
+                    if grid.too_long(a, b) || grid.too_long(b, c) || grid.too_long(c, a) {
+                        diagnostics.seed_rejections.edge_length += 1;
+                        continue;
+                    }
+                    let Some(ball_center) = ball_center(&f, radius, grid.predicates) else {
+                        diagnostics.seed_rejections.no_ball_center += 1;
+                        continue;
+                    };
+                    if grid.is_empty_ball(&ball_center, &packed, radius) {
                         p1.borrow_mut().used = true;
                         p2.borrow_mut().used = true;
                         p3.borrow_mut().used = true;
                         return Some(SeedResult { f, ball_center });
                     }
+                    diagnostics.seed_rejections.ball_not_empty += 1;
                 }
             }
         }
@@ -212,64 +879,123 @@ pub(crate) fn find_seed_triangle(grid: &Grid, radius: f32) -> Option<SeedResult>
     None
 }
 
-pub(crate) fn get_active_edge(
-    front: &mut Vec<Rc<RefCell<MeshEdge>>>,
-) -> Option<Rc<RefCell<MeshEdge>>> {
-    loop {
-        {
-            match front.last() {
-                None => {
-                    // exit loop
-                    return None;
-                }
-                Some(e) => {
-                    if e.borrow().status == EdgeStatus::Active {
-                        return Some(e.clone());
-                    }
-                }
+// The offsets of the occupied cells in the order `strategy` visits them, or
+// `None` for scan order.
+fn seed_cell_order(grid: &Grid, strategy: SeedStrategy) -> Option<Vec<usize>> {
+    let occupied = || (0..grid.cells.len()).filter(|&i| !grid.cells[i].is_empty());
+    match strategy {
+        SeedStrategy::ScanOrder => None,
+        SeedStrategy::DensestCellFirst => {
+            let mut order: Vec<usize> = occupied().collect();
+            order.sort_by_key(|&i| core::cmp::Reverse(grid.cells[i].len()));
+            Some(order)
+        }
+        SeedStrategy::Random(seed) => {
+            let mut order: Vec<usize> = occupied().collect();
+            // A Fisher-Yates shuffle driven by SplitMix64.
+            let mut rng = SplitMix64(seed);
+            for i in (1..order.len()).rev() {
+                #[allow(clippy::cast_possible_truncation)]
+                order.swap(i, (rng.next_u64() % (i as u64 + 1)) as usize);
             }
-            // cleanup non-active edges from front
-            front.pop();
+            Some(order)
+        }
+        SeedStrategy::Near(hint) => {
+            let distance = |i: usize| {
+                grid.cells[i]
+                    .iter()
+                    .map(|p| p.borrow().pos.distance_squared(hint))
+                    .fold(f32::INFINITY, f32::min)
+            };
+            let mut order: Vec<(f32, usize)> = occupied().map(|i| (distance(i), i)).collect();
+            order.sort_by(|a, b| a.0.total_cmp(&b.0));
+            Some(order.into_iter().map(|(_, i)| i).collect())
         }
     }
 }
 
+/// The point reached by pivoting the ball around an edge.
 #[derive(Debug)]
-pub(crate) struct PivotResult {
-    pub(crate) p: Rc<RefCell<MeshPoint>>,
-    pub(crate) center: Vec3,
+pub struct PivotResult {
+    /// The point the ball touches.
+    pub p: Rc<RefCell<MeshPoint>>,
+    /// Center of the ball touching the point and the edge.
+    pub center: Vec3,
+    /// The angle the ball turned through about the edge to reach the
+    /// point, in radians.
+    pub angle: f32,
 }
 
+#[cfg(feature = "debug-dumps")]
 thread_local! {
   static COUNTER: std::cell::Cell<i32> = const { std::cell::Cell::new(0) };
   static COUNTER2: std::cell::Cell<i32> = const { std::cell::Cell::new(0) };
 }
 
-pub(crate) fn ball_pivot(
+/// Pivots the ball around an edge, returning the first point it touches.
+///
+/// Counts the candidates rejected in `rejections`.
+///
+/// # Panics
+///  (Debug ONLY) File system issues when saving the debug output.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub fn ball_pivot(
     e: &Rc<RefCell<MeshEdge>>,
-    grid: &mut Grid,
+    grid: &Grid,
     radius: f32,
+    heuristics: Heuristics,
+    rejections: &mut PivotRejections,
+) -> Option<PivotResult> {
+    traced_ball_pivot(e, grid, radius, heuristics, rejections, None)
+}
+
+// `ball_pivot()`, noting every point considered in `candidates`.
+#[allow(clippy::too_many_lines)]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+pub fn traced_ball_pivot(
+    e: &Rc<RefCell<MeshEdge>>,
+    grid: &Grid,
+    radius: f32,
+    heuristics: Heuristics,
+    rejections: &mut PivotRejections,
+    mut candidates: Option<&mut Vec<Candidate>>,
 ) -> Option<PivotResult> {
     let m = (e.borrow().a.borrow().pos + e.borrow().b.borrow().pos) / 2.0;
     let old_center_vec = (e.borrow().center - m).normalize();
-
-    let neighborhood = grid.spherical_neighborhood(
-        &m,
-        &[
-            e.borrow().a.borrow().pos,
-            e.borrow().b.borrow().pos,
-            e.borrow().opposite.borrow().pos,
-        ],
-    );
-
+    let predicates = grid.predicates;
+    let max_dihedral_angle = grid.max_dihedral_angle;
+    let old_face_normal = {
+        let edge = e.borrow();
+        Triangle([
+            edge.a.borrow().pos,
+            edge.b.borrow().pos,
+            edge.opposite.borrow().pos,
+        ])
+        .normal()
+    };
+    // The pivot angle onto a point in `f64`, when the `f32` angles are too
+    // close to call.
+    let precise_angle = |p: &Rc<RefCell<MeshPoint>>| {
+        let edge = e.borrow();
+        let (a, b) = (edge.a.borrow().pos, edge.b.borrow().pos);
+        precise_pivot_angle(a, b, p.borrow().pos, edge.center, radius)
+    };
+
+    let neighborhood = {
+        let edge = e.borrow();
+        grid.spherical_neighborhood(&m, &[&edge.a, &edge.b, &edge.opposite])
+    };
+
+    #[cfg(feature = "debug-dumps")]
     if let Err(e) = COUNTER.try_with(|counter| {
         counter.set(counter.get() + 1);
     }) {
         // Elsewhere COUNTER's destructor has been called!!!``
-        eprintln!("Access error incrementing debug counter: {e:?}");
+        log::warn!("Access error incrementing debug counter: {e:?}");
     }
 
-    if DEBUG {
+    #[cfg(feature = "debug-dumps")]
+    {
         save_triangles_ascii(
             &PathBuf::from(format!("{}_pivot_edge.stl", COUNTER.get())),
             &[Triangle([
@@ -292,16 +1018,14 @@ pub(crate) fn ball_pivot(
     }
 
     let mut smallest_angle = f32::MAX;
+    let mut smallest_is_uncertain = false;
     let mut point_with_smallest_angle = None;
     let mut center_of_smallest = Vec3::ZERO;
+    #[cfg(feature = "debug-dumps")]
     let mut ss = String::new();
 
-    if DEBUG {
-        let mut ss = String::new();
-    }
-
-    if DEBUG {
-        let mut ss = String::new();
+    #[cfg(feature = "debug-dumps")]
+    {
         writeln!(
             ss,
             "{}.pivoting edge a={} b={} op={}. testing {} neighbors",
@@ -314,10 +1038,25 @@ pub(crate) fn ball_pivot(
         .expect("could not write debug");
     }
 
+    #[cfg(feature = "debug-dumps")]
     let mut i = 0;
+    #[cfg(feature = "debug-dumps")]
     let mut smallest_number = 0;
     'next_neighborhood: for p in &neighborhood {
-        i += 1;
+        #[cfg(feature = "debug-dumps")]
+        {
+            i += 1;
+        }
+        // Context points stay in the neighborhood, so that the ball cannot
+        // pass through them, but are never touched.
+        if p.borrow().context {
+            note(
+                &mut candidates,
+                p.borrow().pos,
+                Outcome::Rejected("context_only"),
+            );
+            continue;
+        }
         let new_face_normal = Triangle([
             e.borrow().b.borrow().pos,
             e.borrow().a.borrow().pos,
@@ -327,15 +1066,58 @@ pub(crate) fn ball_pivot(
 
         // this check is not in the paper: all points' normals must point into the
         // same half-space
-        if new_face_normal.dot(p.borrow().normal) < 0.0 {
+        if heuristics.normal_half_space
+            && grid.normal_disagreement == NormalDisagreement::Reject
+            && new_face_normal.dot(p.borrow().normal) < 0.0
+        {
+            rejections.normal_half_space += 1;
+            note(
+                &mut candidates,
+                p.borrow().pos,
+                Outcome::Rejected("normal_half_space"),
+            );
+            continue;
+        }
+
+        if grid.too_long(p.borrow().pos, e.borrow().a.borrow().pos)
+            || grid.too_long(p.borrow().pos, e.borrow().b.borrow().pos)
+        {
+            rejections.edge_length += 1;
+            note(
+                &mut candidates,
+                p.borrow().pos,
+                Outcome::Rejected("edge_length"),
+            );
             continue;
         }
 
-        let Some(c) = compute_ball_center(
+        // this check is not in the paper: the new triangle must not fold too
+        // far over the triangle across the edge
+        if max_dihedral_angle
+            .is_some_and(|max| acos(new_face_normal.dot(old_face_normal).clamp(-1.0, 1.0)) > max)
+        {
+            rejections.dihedral_angle += 1;
+            note(
+                &mut candidates,
+                p.borrow().pos,
+                Outcome::Rejected("dihedral_angle"),
+            );
+            continue;
+        }
+
+        let Some(c) = ball_center(
             &MeshFace([e.borrow().b.clone(), e.borrow().a.clone(), p.clone()]),
             radius,
+            predicates,
         ) else {
-            if DEBUG {
+            rejections.no_ball_center += 1;
+            note(
+                &mut candidates,
+                p.borrow().pos,
+                Outcome::Rejected("no_ball_center"),
+            );
+            #[cfg(feature = "debug-dumps")]
+            {
                 writeln!(
                     &mut ss,
                     "{i}.     {:?} center computation failed",
@@ -346,12 +1128,13 @@ pub(crate) fn ball_pivot(
             continue;
         };
 
-        if DEBUG {
+        #[cfg(feature = "debug-dumps")]
+        {
             if let Err(e) = COUNTER2.try_with(|counter2| {
                 counter2.set(counter2.get() + 1);
             }) {
                 // Elsewhere COUNTER2's destructor has been called!!!``
-                eprintln!("Access error incrementing debug counter: {e:?}");
+                log::warn!("Access error incrementing debug counter: {e:?}");
             }
             save_triangles_ascii(
                 &PathBuf::from(format!("{}_{}_face.stl", COUNTER.get(), COUNTER2.get())),
@@ -377,8 +1160,15 @@ pub(crate) fn ball_pivot(
         // triangle
         let new_center_vec = (c - m).normalize();
         let new_center_face_dot = (new_center_vec).dot(new_face_normal);
-        if new_center_face_dot < 0_f32 {
-            if DEBUG {
+        if heuristics.ball_above_triangle && new_center_face_dot < 0_f32 {
+            rejections.ball_above_triangle += 1;
+            note(
+                &mut candidates,
+                p.borrow().pos,
+                Outcome::Rejected("ball_above_triangle"),
+            );
+            #[cfg(feature = "debug-dumps")]
+            {
                 writeln!(
                     &mut ss,
                     "{i}.    {:?} ball center {c:?} underneath triangle",
@@ -397,39 +1187,70 @@ pub(crate) fn ball_pivot(
             } else {
                 &ee.borrow().a
             };
-            if ee.borrow().status == EdgeStatus::Inner
+            if heuristics.existing_inner_edge
+                && ee.borrow().status == EdgeStatus::Inner
                 && (other_point.as_ptr() == e.borrow().a.as_ptr()
                     || other_point.as_ptr() == e.borrow().b.as_ptr())
             {
-                if DEBUG {
+                #[cfg(feature = "debug-dumps")]
+                {
                     writeln!(&mut ss, "{i}.    {:?} inner edge exists", p.borrow().pos)
                         .expect("could to write debug");
                 }
+                rejections.existing_inner_edge += 1;
+                note(
+                    &mut candidates,
+                    p.borrow().pos,
+                    Outcome::Rejected("existing_inner_edge"),
+                );
                 // This was a GOTO into the original c++ source.
                 continue 'next_neighborhood;
             }
         }
 
-        let mut angle = (old_center_vec).dot(new_center_vec).clamp(-1.0, 1.0).acos();
+        let mut angle = acos(old_center_vec.dot(new_center_vec).clamp(-1.0, 1.0));
+        let is_uncertain =
+            !(TURN_UNCERTAINTY..=core::f32::consts::PI - TURN_UNCERTAINTY).contains(&angle);
         if new_center_vec
             .cross(old_center_vec)
             .dot(e.borrow().a.borrow().pos - e.borrow().b.borrow().pos)
             < 0.0_f32
         {
-            angle += std::f32::consts::PI;
+            angle += core::f32::consts::PI;
         }
-        if angle < smallest_angle {
-            if DEBUG {
+        note(&mut candidates, p.borrow().pos, Outcome::Angle(angle));
+        let is_smallest = match &point_with_smallest_angle {
+            Some(smallest)
+                if predicates == Predicates::Robust
+                    && (is_uncertain
+                        || smallest_is_uncertain
+                        || (angle - smallest_angle).abs() <= ANGLE_UNCERTAINTY) =>
+            {
+                match (precise_angle(p), precise_angle(smallest)) {
+                    (Some(precise), Some(smallest)) => precise < smallest,
+                    _ => angle < smallest_angle,
+                }
+            }
+            _ => angle < smallest_angle,
+        };
+        if is_smallest {
+            #[cfg(feature = "debug-dumps")]
+            {
                 writeln!(&mut ss, "ball pivot angle < smallest angle")
                     .expect("could not write debug");
             }
             smallest_angle = angle;
+            smallest_is_uncertain = is_uncertain;
             point_with_smallest_angle = Some(p.clone());
             center_of_smallest = c;
-            smallest_number = i;
+            #[cfg(feature = "debug-dumps")]
+            {
+                smallest_number = i;
+            }
         }
 
-        if DEBUG {
+        #[cfg(feature = "debug-dumps")]
+        {
             writeln!(
                     &mut ss,
                     "{i}.   {}  center {c:?} angle {angle:?} next center face dot {new_center_face_dot}",
@@ -439,31 +1260,38 @@ pub(crate) fn ball_pivot(
         }
     }
 
-    if smallest_angle != f32::MAX {
-        if ball_is_empty(&center_of_smallest, &neighborhood, radius) {
-            if DEBUG {
+    if let Some(point_with_smallest_angle) = point_with_smallest_angle {
+        if grid.is_empty_ball(
+            &center_of_smallest,
+            &PackedPositions::new(&neighborhood),
+            radius,
+        ) {
+            #[cfg(feature = "debug-dumps")]
+            {
                 writeln!(&mut ss, "       picking point {smallest_number}")
                     .expect("Could not render debug");
-                match &point_with_smallest_angle {
-                    Some(candidate_point) => {
-                        save_points(
-                            &PathBuf::from(format!("{}_candidate.ply", COUNTER.get())),
-                            &vec![candidate_point.borrow().pos],
-                        )
-                        .expect("Failed(debug) to write ball_center file");
-                    }
-                    None => {
-                        eprintln!("debug: trying to display a candidate point which doe not exist");
-                    }
-                }
-                println!("{ss}");
+                save_points(
+                    &PathBuf::from(format!("{}_candidate.ply", COUNTER.get())),
+                    &vec![point_with_smallest_angle.borrow().pos],
+                )
+                .expect("Failed(debug) to write ball_center file");
+                log::debug!("{ss}");
             }
 
             return Some(PivotResult {
-                p: point_with_smallest_angle.unwrap(),
+                p: point_with_smallest_angle,
                 center: center_of_smallest,
+                angle: smallest_angle,
             });
-        } else if DEBUG {
+        }
+        rejections.ball_not_empty += 1;
+        note(
+            &mut candidates,
+            point_with_smallest_angle.borrow().pos,
+            Outcome::Rejected("ball_not_empty"),
+        );
+        #[cfg(feature = "debug-dumps")]
+        {
             writeln!(
                 &mut ss,
                 "        found candidate {smallest_number} but bail int not empty",
@@ -472,29 +1300,30 @@ pub(crate) fn ball_pivot(
         }
     }
 
-    if DEBUG {
-        println!("{ss}");
+    #[cfg(feature = "debug-dumps")]
+    {
+        log::debug!("{ss}");
     }
 
     None
 }
 
-pub(crate) const fn not_used(p: &MeshPoint) -> bool {
+/// True when the point is not yet part of the mesh.
+#[must_use]
+pub const fn not_used(p: &MeshPoint) -> bool {
     !p.used
 }
 
-pub(crate) fn on_front(p: &MeshPoint) -> bool {
+/// True when the point has an active edge.
+#[must_use]
+pub fn on_front(p: &MeshPoint) -> bool {
     p.edges
         .iter()
         .any(|e| e.borrow().status == EdgeStatus::Active)
 }
 
-// Removed edge from consideration
-fn remove(e: &Rc<RefCell<MeshEdge>>) {
-    e.borrow_mut().status = EdgeStatus::Inner;
-}
-
-pub(crate) fn output_triangle(f: &MeshFace, triangles: &mut Vec<Triangle>) {
+/// Appends the face to the output mesh.
+pub fn output_triangle(f: &MeshFace, triangles: &mut Vec<Triangle>) {
     triangles.push(Triangle([
         f.0[0].borrow().pos,
         f.0[1].borrow().pos,
@@ -502,11 +1331,96 @@ pub(crate) fn output_triangle(f: &MeshFace, triangles: &mut Vec<Triangle>) {
     ]));
 }
 
-pub(crate) fn join(
+/// Seeds the front with the three edges of the seed triangle.
+pub fn open_front(seed: &SeedResult, front: &mut Front, edges: &mut Vec<Rc<RefCell<MeshEdge>>>) {
+    let ball_center = seed.ball_center;
+    let vertices = &seed.f.0;
+
+    let e0 = Rc::new(RefCell::new(MeshEdge::new(
+        &vertices[0],
+        &vertices[1],
+        &vertices[2].clone(),
+        ball_center,
+    )));
+    edges.push(e0.clone());
+
+    let e1 = Rc::new(RefCell::new(MeshEdge::new(
+        &vertices[1],
+        &vertices[2],
+        &vertices[0].clone(),
+        ball_center,
+    )));
+    edges.push(e1.clone());
+
+    let e2 = Rc::new(RefCell::new(MeshEdge::new(
+        &vertices[2],
+        &vertices[0],
+        &vertices[1].clone(),
+        ball_center,
+    )));
+    edges.push(e2.clone());
+
+    e0.borrow_mut().prev = Some(e2.clone());
+    e1.borrow_mut().next = Some(e2.clone());
+    e0.borrow_mut().next = Some(e1.clone());
+    e2.borrow_mut().prev = Some(e1.clone());
+    e1.borrow_mut().prev = Some(e0.clone());
+    e2.borrow_mut().next = Some(e0.clone());
+
+    vertices[0].borrow_mut().edges = vec![e0.clone(), e2.clone()];
+    vertices[1].borrow_mut().edges = vec![e0.clone(), e1.clone()];
+    vertices[2].borrow_mut().edges = vec![e1.clone(), e2.clone()];
+
+    front.push(&e0);
+    front.push(&e1);
+    front.push(&e2);
+}
+
+/// Seeds the front with a closed loop of edges through the points in order,
+/// each linked to its neighbours, and returns them.
+///
+/// The edge from `points[i]` takes the point before it as its opposite
+/// vertex, and every edge the ball center `center`, so three points give
+/// the same front as [`open_front()`]. A point may appear more than once,
+/// building the pinched and self-touching loops the glue cases handle.
+#[cfg(feature = "unstable")]
+pub fn open_loop(
+    points: &[Rc<RefCell<MeshPoint>>],
+    center: Vec3,
+    front: &mut Front,
+    edges: &mut Vec<Rc<RefCell<MeshEdge>>>,
+) -> Vec<Rc<RefCell<MeshEdge>>> {
+    let n = points.len();
+    let opened: Vec<_> = (0..n)
+        .map(|i| {
+            Rc::new(RefCell::new(MeshEdge::new(
+                &points[i],
+                &points[(i + 1) % n],
+                &points[(i + n - 1) % n],
+                center,
+            )))
+        })
+        .collect();
+    for (i, e) in opened.iter().enumerate() {
+        e.borrow_mut().prev = Some(opened[(i + n - 1) % n].clone());
+        e.borrow_mut().next = Some(opened[(i + 1) % n].clone());
+        points[i].borrow_mut().edges.push(e.clone());
+        points[(i + 1) % n].borrow_mut().edges.push(e.clone());
+        edges.push(e.clone());
+        front.push(e);
+    }
+    opened
+}
+
+/// Replaces `e_ij` on the front with the edges `e_ik` and `e_kj` of the
+/// triangle formed by pivoting onto `o_k`, and returns them.
+#[allow(clippy::similar_names)]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+pub fn join(
     e_ij: &Rc<RefCell<MeshEdge>>,
     o_k: &Rc<RefCell<MeshPoint>>,
     o_k_ball_center: Vec3,
-    front: &mut Vec<Rc<RefCell<MeshEdge>>>,
+    front: &mut Front,
     edges: &mut Vec<Rc<RefCell<MeshEdge>>>,
 ) -> (Rc<RefCell<MeshEdge>>, Rc<RefCell<MeshEdge>>) {
     let e_ik = Rc::new(RefCell::new(MeshEdge::new(
@@ -527,18 +1441,17 @@ pub(crate) fn join(
     // e_ik
     e_ik.borrow_mut().next = Some(e_kj.clone());
     e_ik.borrow_mut().prev.clone_from(&e_ij.borrow().prev);
-    match &e_ij.borrow().prev {
-        Some(prev) => prev.borrow_mut().next = Some(e_ik.clone()),
-        None => panic!("e_ij.prev Must be defined at this point"),
+    // Only a front rebuilt from an existing mesh can have open ends.
+    if let Some(prev) = &e_ij.borrow().prev {
+        prev.borrow_mut().next = Some(e_ik.clone());
     }
     e_ij.borrow().a.borrow_mut().edges.push(e_ik.clone());
 
     // e_kj
     e_kj.borrow_mut().prev = Some(e_ik.clone());
     e_kj.borrow_mut().next.clone_from(&e_ij.borrow().next);
-    match &mut e_ij.borrow().next.clone() {
-        Some(next) => next.borrow_mut().prev = Some(e_kj.clone()),
-        None => panic!("e_ij.prev is None"),
+    if let Some(next) = &e_ij.borrow().next {
+        next.borrow_mut().prev = Some(e_kj.clone());
     }
     e_ij.borrow().b.borrow_mut().edges.push(e_kj.clone());
 
@@ -547,30 +1460,57 @@ pub(crate) fn join(
     o_k_inner.edges.push(e_ik.clone());
     o_k_inner.edges.push(e_kj.clone());
 
-    front.push(e_ik.clone());
-    front.push(e_kj.clone());
-    remove(e_ij);
+    front.push(&e_ik);
+    front.push(&e_kj);
+    front.remove(e_ij);
 
     (e_ik, e_kj)
 }
 
-pub(crate) fn glue(
+/// Removes a pair of coincident, opposite edges from the front, relinking
+/// their neighbours.
+///
+/// # Panics
+///  (Debug ONLY) File system issues when saving the debug output.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub fn glue(a: &Rc<RefCell<MeshEdge>>, b: &Rc<RefCell<MeshEdge>>, front: &mut Front) {
+    glue_case(a, b, front);
+}
+
+/// The ways two coincident edges are removed from the front.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlueCase {
+    /// The edges form a loop of their own, which is closed.
+    ClosedLoop,
+    /// The edges are neighbours on the front, so their loop closes around
+    /// them.
+    Adjacent,
+    /// The edges are apart, so removing them splits a loop in two, or
+    /// merges two loops into one.
+    Split,
+}
+
+/// [`glue()`], returning which case applied.
+///
+/// # Panics
+///  (Debug ONLY) File system issues when saving the debug output.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+pub fn glue_case(
     a: &Rc<RefCell<MeshEdge>>,
     b: &Rc<RefCell<MeshEdge>>,
-    front: &[Rc<RefCell<MeshEdge>>],
-) {
-    if DEBUG {
+    front: &mut Front,
+) -> GlueCase {
+    #[cfg(feature = "debug-dumps")]
+    {
         let mut front_triangles = vec![];
-        for e in front {
-            if e.borrow().status == EdgeStatus::Active {
-                // This looks buggy the cpp version repeats e.a.pos.
-                // So a line not a triangle.
-                front_triangles.push(Triangle([
-                    e.borrow().a.borrow().pos,
-                    e.borrow().a.borrow().pos,
-                    e.borrow().b.borrow().pos,
-                ]));
-            }
+        for e in front.iter() {
+            // This looks buggy the cpp version repeats e.a.pos.
+            // So a line not a triangle.
+            front_triangles.push(Triangle([
+                e.borrow().a.borrow().pos,
+                e.borrow().a.borrow().pos,
+                e.borrow().b.borrow().pos,
+            ]));
             save_triangles_ascii(&PathBuf::from("glue_front.stl"), &front_triangles)
                 .expect("Err debug failing writing glue_front.stl");
             save_triangles_ascii(
@@ -585,75 +1525,74 @@ pub(crate) fn glue(
         }
     }
     // case 1
-    if a.borrow().next.clone().unwrap().as_ptr() == b.as_ptr()
-        && a.borrow().prev.clone().unwrap().as_ptr() == b.as_ptr()
-        && b.borrow().next.clone().unwrap().as_ptr() == a.as_ptr()
-        && b.borrow().prev.clone().unwrap().as_ptr() == a.as_ptr()
+    if links_to(a.borrow().next.as_ref(), b)
+        && links_to(a.borrow().prev.as_ref(), b)
+        && links_to(b.borrow().next.as_ref(), a)
+        && links_to(b.borrow().prev.as_ref(), a)
     {
-        remove(&a.clone());
-        remove(&b.clone());
-        return;
+        front.remove(a);
+        front.remove(b);
+        return GlueCase::ClosedLoop;
     }
 
     // case 2
-    if a.borrow().next.clone().unwrap().as_ptr() == b.as_ptr()
-        && b.borrow().prev.clone().unwrap().as_ptr() == a.as_ptr()
-    {
-        a.clone()
-            .borrow()
-            .prev
-            .as_ref()
-            .unwrap()
-            .borrow_mut()
-            .next
-            .clone_from(&b.borrow().next);
-        b.clone()
-            .borrow()
-            .next
-            .as_ref()
-            .unwrap()
-            .borrow_mut()
-            .prev
-            .clone_from(&a.borrow().prev);
-        remove(&a.clone());
-        remove(&b.clone());
-        return;
-        // }
-    }
-
-    if a.borrow().prev.clone().unwrap().as_ptr() == b.as_ptr()
-        && b.borrow().next.clone().unwrap().as_ptr() == a.as_ptr()
-    {
-        a.clone().borrow_mut().next.clone_from(&b.borrow().next);
-        b.clone().borrow_mut().prev.clone_from(&a.borrow().prev);
-        remove(&a.clone());
-        remove(&b.clone());
-        return;
+    if links_to(a.borrow().next.as_ref(), b) && links_to(b.borrow().prev.as_ref(), a) {
+        if let Some(a_prev) = &a.borrow().prev {
+            a_prev.borrow_mut().next.clone_from(&b.borrow().next);
+        }
+        if let Some(b_next) = &b.borrow().next {
+            b_next.borrow_mut().prev.clone_from(&a.borrow().prev);
+        }
+        front.remove(a);
+        front.remove(b);
+        return GlueCase::Adjacent;
+    }
+
+    if links_to(a.borrow().prev.as_ref(), b) && links_to(b.borrow().next.as_ref(), a) {
+        if let Some(b_prev) = &b.borrow().prev {
+            b_prev.borrow_mut().next.clone_from(&a.borrow().next);
+        }
+        if let Some(a_next) = &a.borrow().next {
+            a_next.borrow_mut().prev.clone_from(&b.borrow().prev);
+        }
+        front.remove(a);
+        front.remove(b);
+        return GlueCase::Adjacent;
     }
 
     // case 3/4
-    if let Some(a_prev) = &mut a.borrow().prev.clone() {
-        a_prev.borrow_mut().next.clone_from(&b.borrow().next);
+    // The links are read before any is changed, as a stalled front can
+    // leave an edge linked to its own reverse.
+    let (a_prev, a_next) = (a.borrow().prev.clone(), a.borrow().next.clone());
+    let (b_prev, b_next) = (b.borrow().prev.clone(), b.borrow().next.clone());
+    if let Some(a_prev) = &a_prev {
+        a_prev.borrow_mut().next.clone_from(&b_next);
     }
 
-    if let Some(b_next) = &mut b.borrow().next.clone() {
-        b_next.borrow_mut().prev.clone_from(&a.borrow().prev);
+    if let Some(b_next) = &b_next {
+        b_next.borrow_mut().prev.clone_from(&a_prev);
     }
 
-    if let Some(a_next) = &mut a.borrow().next.clone() {
-        a_next.borrow_mut().prev.clone_from(&b.borrow().prev);
+    if let Some(a_next) = &a_next {
+        a_next.borrow_mut().prev.clone_from(&b_prev);
     }
 
-    if let Some(b_prev) = &mut b.borrow().prev.clone() {
-        b_prev.borrow_mut().next.clone_from(&a.borrow().next);
+    if let Some(b_prev) = &b_prev {
+        b_prev.borrow_mut().next.clone_from(&a_next);
     }
-    remove(a);
-    remove(b);
+    front.remove(a);
+    front.remove(b);
+    GlueCase::Split
+}
+
+// True when the (optional) neighbour is `edge`.
+fn links_to(link: Option<&Rc<RefCell<MeshEdge>>>, edge: &Rc<RefCell<MeshEdge>>) -> bool {
+    link.is_some_and(|link| Rc::ptr_eq(link, edge))
 }
 
-pub(crate) fn find_reverse_edge_on_front(
-    edge: &Rc<RefCell<MeshEdge>>,
-) -> Option<Rc<RefCell<MeshEdge>>> {
+/// The edge running in the opposite direction, if any.
+#[must_use]
+pub fn find_reverse_edge_on_front(edge: &Rc<RefCell<MeshEdge>>) -> Option<Rc<RefCell<MeshEdge>>> {
     for e in &edge.borrow().a.borrow().edges {
         if e.borrow().a.as_ptr() == edge.borrow().b.as_ptr() {
             return Some(e.clone());
@@ -1,105 +1,223 @@
-use core::error::Error;
-use std::fs::File;
-use std::io::BufRead;
-use std::io::BufReader;
 use std::io::BufWriter;
-use std::io::Read;
 use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
 
 use glam::Vec3;
-use log::error;
-use log::info;
 
+use crate::ReconstructionConfig;
+use crate::point_cloud::PointCloud;
+use crate::report::Report;
 use crate::{Point, Triangle};
 
-static ATTRIBUTE_COUNT: [u8; 2] = [0; 2];
-
-/// Write triangles to file.
+mod fem;
+#[cfg(feature = "obj")]
+mod obj;
+mod options;
+#[cfg(feature = "ply")]
+mod ply;
+#[cfg(feature = "ply")]
+mod quality;
+mod scalars;
+#[cfg(feature = "stl")]
+mod stl;
+#[cfg(any(feature = "ply", feature = "stl"))]
+mod stream;
+mod xyz;
+
+pub use fem::save_gmsh;
+pub use fem::save_medit;
+#[cfg(feature = "obj")]
+pub use obj::VertexColors;
+#[cfg(feature = "obj")]
+pub use obj::save_obj;
+#[cfg(feature = "obj")]
+pub use obj::save_obj_with_options;
+#[cfg(feature = "obj")]
+pub use obj::save_obj_with_provenance;
+pub use options::Encoding;
+pub use options::SaveOptions;
+#[cfg(feature = "ply")]
+pub use ply::load_ply;
+#[cfg(feature = "ply")]
+pub use ply::load_ply_with_colors;
+#[cfg(all(feature = "ply", any(test, feature = "tokio")))]
+pub(crate) use ply::read_ply;
+#[cfg(feature = "ply")]
+pub use ply::reconstruct_to_ply;
+#[cfg(feature = "ply")]
+pub use ply::save_cloud_preview;
+#[cfg(feature = "ply")]
+pub use ply::save_orphans;
+#[cfg(feature = "ply")]
+pub use ply::save_points_and_normals;
+#[cfg(feature = "ply")]
+pub use ply::save_points_and_normals_with_provenance;
+#[cfg(feature = "ply")]
+pub use quality::FaceMetric;
+#[cfg(feature = "ply")]
+pub use quality::save_quality_ply;
+#[cfg(feature = "ply")]
+pub use scalars::save_ply_with_scalars;
+pub use scalars::save_vtk_with_scalars;
+#[cfg(feature = "stl")]
+pub use stl::load_stl;
+#[cfg(feature = "stl")]
+pub use stl::reconstruct_to_stl;
+#[cfg(feature = "stl")]
+pub use stl::save_triangles;
+#[cfg(feature = "stl")]
+pub use stl::save_triangles_with_provenance;
+#[cfg(all(feature = "stl", feature = "tokio"))]
+pub(crate) use stl::stl_facet;
+pub use xyz::XyzCloud;
+pub use xyz::XyzOptions;
+#[cfg(any(feature = "rayon", feature = "tokio"))]
+pub(crate) use xyz::invalid_line;
+pub use xyz::load_xyz_with_options;
+
+/// Reconstructs a mesh from a point cloud file, and writes it to `output`.
+///
+/// The formats are chosen from the file extensions: the input may be an
+/// `.xyz`, `.pts` or `.ply` file, and the output any format written by
+/// [`save_mesh()`], with its default options. Returns the report on the
+/// reconstruction.
 ///
 /// # Errors
-///   When the file cannot be created or written to.
+///   When a file extension is not recognised, or its format was not compiled
+///   in, or a file cannot be read or written, or the header of a ply input
+///   file cannot be parsed. When no mesh can be generated the error wraps a
+///   [`crate::ReconstructError`], and no file is written.
+pub fn reconstruct_file(
+    input: &Path,
+    output: &Path,
+    config: &ReconstructionConfig,
+) -> std::io::Result<Report> {
+    let points = load_cloud(input)?;
+
+    if !saves_mesh(output) {
+        return Err(unsupported("output", output));
+    }
+    let reconstruction =
+        crate::reconstruct_with_config(&points, config).map_err(std::io::Error::other)?;
+    save_mesh(output, &reconstruction.triangles, &SaveOptions::default())?;
+    Ok(reconstruction.report)
+}
+
+/// Return a point cloud stored in an `.xyz`, `.pts` or `.ply` file, chosen
+/// by the file extension.
 ///
-/// # Panics
-///   When the number of triangles exceeds that allow by the stl format.
-pub fn save_triangles(path: &PathBuf, triangles: &[Triangle]) -> std::io::Result<()> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+/// # Errors
+///   When the file extension is not recognised, the file cannot be read, or
+///   the header of a ply file cannot be parsed.
+pub fn load_cloud(path: &Path) -> std::io::Result<Vec<Point>> {
+    let path = path.to_path_buf();
+    match extension(&path).as_deref() {
+        Some("xyz" | "pts") => load_xyz(&path),
+        #[cfg(feature = "ply")]
+        Some("ply") => load_ply(&path),
+        _ => Err(unsupported("input", &path)),
     }
+}
 
-    let file = std::fs::File::create(path)?;
+/// Write triangles to a mesh file, in the format chosen by the extension:
+/// `.stl`, `.ply`, `.obj`, `.mesh` (MEDIT) or `.msh` (Gmsh).
+///
+/// The encoding, precision, scale, winding and sharing of the vertices are
+/// set by `options`, for every format that can hold them.
+///
+/// # Errors
+///   When the file extension is not recognised, or its format was not
+///   compiled in, when the file cannot be created or written to, or when the
+///   mesh is too large for the format.
+pub fn save_mesh(
+    path: &Path,
+    triangles: &[Triangle],
+    options: &SaveOptions,
+) -> std::io::Result<()> {
+    match extension(path).as_deref() {
+        #[cfg(feature = "stl")]
+        Some("stl") => stl::write_stl(path, triangles, options),
+        #[cfg(feature = "ply")]
+        Some("ply") => ply::write_ply_mesh(path, triangles, options),
+        #[cfg(feature = "obj")]
+        Some("obj") => obj::write_obj(path, triangles, None, options),
+        Some("mesh") => fem::write_medit(path, triangles, options),
+        Some("msh") => fem::write_gmsh(path, triangles, options),
+        _ => Err(unsupported("output", path)),
+    }
+}
 
-    let mut writer = BufWriter::new(file);
+// The lower case file extension.
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+}
 
-    // Header
-    writer.write_all(&[b' '; 80])?;
+// True when `save_mesh()` can write a file with this extension.
+fn saves_mesh(path: &Path) -> bool {
+    let extension = extension(path);
+    let is = |format| extension.as_deref() == Some(format);
+    (cfg!(feature = "stl") && is("stl"))
+        || (cfg!(feature = "ply") && is("ply"))
+        || (cfg!(feature = "obj") && is("obj"))
+        || is("mesh")
+        || is("msh")
+}
 
-    let count = u32::try_from(triangles.len())
-        .expect("stl file format cannot contain more than 4,294,967,295 triangles");
-    writer.write_all(&count.to_le_bytes())?;
+fn unsupported(role: &str, path: &Path) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("unsupported {role} file format: {}", path.display()),
+    )
+}
 
-    for t in triangles {
-        // Normals
-        let normal = (t.0[0] - t.0[1]).cross(t.0[0] - t.0[2]).normalize();
-        let normal_bytes = normal.to_array().map(f32::to_le_bytes).concat();
-        writer.write_all(&normal_bytes)?;
-        // Triangles
-        let triangle_bytes =
-            t.0.map(|v| v.to_array())
-                .iter()
-                .flatten()
-                .map(|f| f.to_le_bytes())
-                .collect::<Vec<_>>()
-                .concat();
-        writer.write_all(&triangle_bytes)?;
-
-        // Attribute count
-        writer.write_all(&ATTRIBUTE_COUNT)?;
+// Creates the file, and the directories leading to it.
+fn create(path: &Path) -> std::io::Result<BufWriter<std::fs::File>> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
     }
-
-    Ok(())
+    Ok(BufWriter::new(std::fs::File::create(path)?))
 }
 
 /// Write triangles as a STL file (in ascii format).
 ///
-/// Use only when debugging.
+/// Several times larger and slower to write than [`save_triangles()`], but
+/// readable by eye and by tools that lack binary STL support.
 ///
 /// # Errors
-///
-/// # Panics
-///
-pub fn save_triangles_ascii(path: &PathBuf, triangles: &[Triangle]) -> std::io::Result<()> {
-    if path.parent().is_some() {
-        std::fs::create_dir_all(path.parent().unwrap())?;
+///   When the file cannot be created or written to.
+pub fn save_triangles_ascii(path: &Path, triangles: &[Triangle]) -> std::io::Result<()> {
+    let options = SaveOptions::default().with_encoding(Encoding::Ascii);
+    write_stl_ascii(path, triangles, &options)
+}
+
+// Writes an ascii STL file. Its facets cannot share vertices.
+fn write_stl_ascii(
+    path: &Path,
+    triangles: &[Triangle],
+    options: &SaveOptions,
+) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
     }
     let file = std::fs::File::create(path)?;
     let mut writer = BufWriter::new(file);
 
-    writeln!(writer, "solid {}", path.to_str().unwrap())?;
+    match (&options.solid_name, &options.provenance) {
+        (Some(name), _) => writeln!(writer, "solid {name}")?,
+        (None, Some(provenance)) => writeln!(writer, "solid {}", provenance.stl_fields())?,
+        (None, None) => writeln!(writer, "solid {}", path.display())?,
+    }
 
     for t in triangles {
+        let t = options.triangle(t);
         let normal = (t.0[0] - t.0[1]).cross(t.0[0] - t.0[2]).normalize();
-        writeln!(
-            writer,
-            "  facet normal {} {} {}",
-            normal.x, normal.y, normal.z
-        )?;
+        writeln!(writer, "  facet normal {}", options.xyz(normal))?;
         writeln!(writer, "    outer loop")?;
-        writeln!(
-            writer,
-            "      vertex {} {} {}",
-            t.0[0].x, t.0[0].y, t.0[0].z
-        )?;
-        writeln!(
-            writer,
-            "      vertex {} {} {}",
-            t.0[1].x, t.0[1].y, t.0[1].z
-        )?;
-        writeln!(
-            writer,
-            "      vertex {} {} {}",
-            t.0[2].x, t.0[2].y, t.0[2].z
-        )?;
+        for v in t.0 {
+            writeln!(writer, "      vertex {}", options.xyz(v))?;
+        }
         writeln!(writer, "    endloop")?;
         writeln!(writer, "  endfacet")?;
     }
@@ -108,56 +226,6 @@ pub fn save_triangles_ascii(path: &PathBuf, triangles: &[Triangle]) -> std::io::
     Ok(())
 }
 
-/// Write Point cloud to file.
-///
-/// outout point and normal.
-///
-/// # Errors
-///   Problems writing to file.
-pub fn save_points_and_normals(
-    path: &PathBuf,
-    points: &Vec<Point>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-
-    let file = std::fs::File::create(path)?;
-    let mut writer = BufWriter::new(file);
-    writeln!(writer, "ply")?;
-    writeln!(writer, "format binary_little_endian 1.0")?;
-    writeln!(writer, "element vertex {}", points.len())?;
-    writeln!(writer, "property float x")?;
-    writeln!(writer, "property float y")?;
-    writeln!(writer, "property float z")?;
-    writeln!(writer, "property float nx")?;
-    writeln!(writer, "property float ny")?;
-    writeln!(writer, "property float nz")?;
-    writeln!(writer, "end_header")?;
-    let mut buffer: Vec<u8> = Vec::new();
-    for point in points {
-        buffer.extend_from_slice(
-            &point
-                .pos
-                .to_array()
-                .iter()
-                .flat_map(|f| f.to_le_bytes())
-                .collect::<Vec<u8>>(),
-        );
-        buffer.extend_from_slice(
-            &point
-                .normal
-                .to_array()
-                .iter()
-                .flat_map(|f| f.to_le_bytes())
-                .collect::<Vec<u8>>(),
-        );
-    }
-    writer.write_all(&buffer)?;
-
-    Ok(())
-}
-
 /// Write Point cloud to file.
 ///
 /// # Errors
@@ -191,370 +259,689 @@ pub fn save_points(path: &PathBuf, points: &Vec<Vec3>) -> Result<(), Box<dyn std
     Ok(())
 }
 
-/// Return a point cloud stored in file.
+/// Return a point cloud stored in a `.xyz` or `.pts` file.
 ///
-/// # Errors
-///   If the file cannot be opened.
+/// Columns after the normal are ignored. See [`load_xyz_with_options()`] to
+/// keep them, and for the accepted syntax.
 ///
-/// # Panics
-///   When there is a unreadable value in the file.
-pub fn load_xyz(path: &PathBuf) -> std::io::Result<Vec<Point>> {
-    let file = std::fs::File::open(path)?;
-    let reader = std::io::BufReader::new(file);
-    let mut points = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() < 3 {
-            continue;
-        }
-        let x: f32 = parts[0].parse().expect("Failed to parse x");
-        let y: f32 = parts[1].parse().expect("Failed to parse y");
-        let z: f32 = parts[2].parse().expect("Failed to parse z");
-        let nx: f32 = parts[3].parse().expect("Failed to parse normal x");
-        let ny: f32 = parts[4].parse().expect("Failed to parse normal y");
-        let nz: f32 = parts[5].parse().expect("Failed to parse normal z");
-        points.push(Point {
-            pos: Vec3::new(x, y, z),
-            normal: Vec3::new(nx, ny, nz),
-        });
-    }
-    Ok(points)
+/// # Errors
+///   If the file cannot be opened or read, or a line has a value that cannot
+///   be read.
+pub fn load_xyz(path: &Path) -> std::io::Result<Vec<Point>> {
+    load_xyz_with_options(path, &XyzOptions::default()).map(|cloud| cloud.points)
 }
 
-/// Return a point cloud stored in file.
+/// Return a point cloud stored in a `.xyz` or `.pts` file, parsing on all
+/// available cores. See [`load_xyz()`].
 ///
-/// # Errors
-///   If the file cannot be opened.
+/// The file is split into chunks on line boundaries, each chunk is parsed
+/// independently and the results are concatenated in file order.
 ///
-/// # Panics
-///   When there is a unreadable value in the file.
-pub fn load_ply(path: &PathBuf) -> std::io::Result<Vec<Point>> {
-    let file = std::fs::File::open(path)?;
-    let mut reader = BufReader::new(file);
-
-    let header = parse_ply_header(&mut reader)
-        .map_err(|_| std::io::Error::other("did not decode header correctly"))?;
-
-    println!("{header:#?}");
-    let vertex_count = header.vertex_count;
-    let col_count = header.ordered_properties.len();
-
-    let mut points = Vec::new();
-
-    for next in reader.lines() {
-        let line = next.map_err(|_| std::io::Error::other("no more lines"))?;
-        let parts: Vec<&str> = line.split_whitespace().collect();
-
-        assert!(
-            parts.len() == col_count,
-            "Did not parse the expected number of cols."
-        );
-
-        let mut nx = 0_f32;
-        let mut ny = 0_f32;
-        let mut nz = 0_f32;
-        let mut x = 0_f32;
-        let mut y = 0_f32;
-        let mut z = 0_f32;
-        for (i, (value, _value_type, _nitems_type)) in header.ordered_properties.iter().enumerate()
-        {
-            if value == "x" {
-                x = parts[i].parse().unwrap();
-            }
-            if *value == "y" {
-                y = parts[i].parse().unwrap();
-            }
-            if *value == "z" {
-                z = parts[i].parse().unwrap();
-            }
-            if *value == "nx" {
-                nx = parts[i].parse().unwrap();
-            }
-            if *value == "ny" {
-                ny = parts[i].parse().unwrap();
+/// # Errors
+///   If the file cannot be read, is not valid UTF-8, or a line has a value
+///   that cannot be read.
+#[cfg(feature = "rayon")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn load_xyz_par(path: &PathBuf) -> std::io::Result<Vec<Point>> {
+    use rayon::prelude::*;
+
+    let options = XyzOptions::default();
+    let bytes = std::fs::read(path)?;
+    let chunks = split_lines(&bytes, rayon::current_num_threads() * 4);
+    // Each chunk is parsed with its line count, or fails with a line number
+    // counted from the start of the chunk.
+    let parsed = chunks
+        .par_iter()
+        .map(|chunk| {
+            let text = core::str::from_utf8(chunk)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            let mut cloud = XyzCloud::new(&options);
+            for (i, line) in text.lines().enumerate() {
+                if let Err(e) = cloud.parse_line(line, &options) {
+                    return Ok(Err((i, e)));
+                }
             }
-            if *value == "nz" {
-                nz = parts[i].parse().unwrap();
+            Ok(Ok((cloud, text.lines().count())))
+        })
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    let mut cloud = XyzCloud::new(&options);
+    let mut first_line = 1;
+    for chunk in parsed {
+        match chunk {
+            Ok((chunk, lines)) => {
+                cloud.extend(chunk);
+                first_line += lines;
             }
-            // drop comment labels such as r,g,b
+            Err((i, e)) => return Err(invalid_line(first_line + i, &e)),
         }
-        let point = Point {
-            pos: Vec3::new(x, y, z),
-            normal: Vec3::new(nx, ny, nz),
-        };
-        // println!("{point:#?}");
-        points.push(Point {
-            pos: Vec3::new(x, y, z),
-            normal: Vec3::new(nx, ny, nz),
-        });
     }
-    info!("load_ply - extracted points");
-    Ok(points)
+    Ok(cloud.finish().points)
 }
 
-// The file type of the PLY file.
-//
-// Stores the version number of the format.
-#[derive(Debug)]
-enum Format {
-    Ascii(f32),
-    BinaryLittleEndian(f32),
-    BinaryBigEndian(f32),
+// Splits the buffer into roughly `n` pieces, each ending on a newline.
+#[cfg(feature = "rayon")]
+fn split_lines(bytes: &[u8], n: usize) -> Vec<&[u8]> {
+    let target = bytes.len().div_ceil(n.max(1)).max(1);
+    let mut chunks = vec![];
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let end = rest
+            .iter()
+            .skip(target)
+            .position(|b| *b == b'\n')
+            .map_or(rest.len(), |i| target + i + 1);
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
 }
 
-/// Possible types of properties in a PLY file.
+/// Return a point cloud stored in an `.xyz`, `.pts` or `.ply` file, chosen
+/// by the file extension, with the attributes the file holds.
 ///
-/// "The type can be specified with one of
-///   char uchar short ushort int uint float double,
-/// or one of
-///   int8 uint8 int16 uint16 int32 uint32 float32 float64"
+/// Normals are read when a ply file has `nx`, `ny` or `nz` properties, and
+/// always from xyz files, as in [`load_cloud()`]. Colours are read as in
+/// [`load_ply_with_colors()`]. Every other single valued property of a ply
+/// vertex becomes a scalar of the same name.
 ///
-/// As described here <https://en.wikipedia.org/wiki/PLY_(file_format)>
-#[derive(Debug)]
-enum Type {
-    INT8,
-    Char,
-    Uint8,
-    Uchar,
-    Int16,
-    Short,
-    Uint16,
-    Int,
-    Int32,
-    Ushort,
-    Uint,
-    Uint32,
-    Float,
-    Float32,
-    Double,
-    Float64,
-}
-
-#[derive(Debug)]
-struct UnknownType;
-
-impl std::fmt::Display for UnknownType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Unknown type")
+/// # Errors
+///   When the file extension is not recognised, the file cannot be read, or
+///   the header of a ply file cannot be parsed.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn load_point_cloud(path: &Path) -> std::io::Result<PointCloud> {
+    match extension(path).as_deref() {
+        Some("xyz" | "pts") => load_xyz(path).map(PointCloud::from),
+        #[cfg(feature = "ply")]
+        Some("ply") => {
+            let file = std::fs::File::open(path)?;
+            ply::read_ply_cloud(std::io::BufReader::new(file))
+        }
+        _ => Err(unsupported("input", path)),
     }
 }
 
-impl Error for UnknownType {}
-
-///   char uchar short ushort int uint float double,
-/// or one of
-///   int8 uint8 int16 uint16 int32 uint32 float32 float64"
-///
-impl TryFrom<&str> for Type {
-    type Error = UnknownType;
-    fn try_from(input: &str) -> Result<Self, Self::Error> {
-        match input {
-            "char" => Ok(Self::Char),
-            "int8" => Ok(Self::INT8),
+#[cfg(test)]
+mod test {
 
-            "uchar" => Ok(Self::Uchar),
-            "uint8" => Ok(Self::Uint8),
+    #[cfg(feature = "ply")]
+    use std::io::BufReader;
+    #[cfg(feature = "ply")]
+    use std::io::Cursor;
 
-            "short" => Ok(Self::Short),
-            "int16" => Ok(Self::Int16),
+    #[cfg(feature = "ply")]
+    use super::ply::parse_ply_header;
+    #[cfg(all(feature = "obj", feature = "ply"))]
+    use super::ply::read_ply_with_colors;
+    #[cfg(feature = "stl")]
+    use super::stl::{STL_FACET_SIZE, stl_facet};
+    use super::*;
+    #[cfg(any(feature = "ply", feature = "stl"))]
+    use crate::ReconstructionConfig;
+    #[cfg(all(feature = "ply", feature = "stl"))]
+    use crate::provenance::Provenance;
+    #[cfg(feature = "ply")]
+    use insta::assert_debug_snapshot;
 
-            "ushort" => Ok(Self::Ushort),
-            "uint16" => Ok(Self::Uint16),
+    // Tests the use of property list
+    #[cfg(feature = "ply")]
+    #[test]
+    fn test_parse_ply_header() {
+        let header = r"ply
+format ascii 1.0
+comment This is a comment!
+element vertex 779966
+property float x
+property float y
+property float z
+property list uchar int vertex_indicies
+end_header";
 
-            "int" => Ok(Self::Int),
-            "int32" => Ok(Self::Int32),
+        let mut cursor = Cursor::new(header);
+        let mut bufreader = BufReader::new(&mut cursor);
 
-            "uint" => Ok(Self::Uint),
-            "uint32" => Ok(Self::Uint32),
+        let result = parse_ply_header(&mut bufreader);
+        assert!(result.is_ok(), "Failed to parse PLY header: {result:?}");
+        let header = result.unwrap();
+        assert_debug_snapshot!(header);
+    }
 
-            "float" => Ok(Self::Float),
-            "float32" => Ok(Self::Float32),
+    #[cfg(feature = "stl")]
+    #[test]
+    fn reconstruct_to_stl_matches_save_triangles() {
+        let cloud = load_xyz(&PathBuf::from("../data/bunny.xyz")).expect("Cannot load bunny");
+        let config = ReconstructionConfig::new(0.002);
+        let dir = std::env::temp_dir().join("bpa_rs_reconstruct_to_stl");
+
+        let streamed = dir.join("streamed.stl");
+        let report = reconstruct_to_stl(&streamed, &cloud, &config).expect("write failed");
+
+        let triangles = crate::reconstruct_with_config(&cloud, &config)
+            .expect("no mesh")
+            .triangles;
+        let saved = dir.join("saved.stl");
+        save_triangles(&saved, &triangles).expect("write failed");
+
+        assert_eq!(report.triangles, triangles.len());
+        assert!(std::fs::read(&streamed).unwrap() == std::fs::read(&saved).unwrap());
+    }
 
-            "double" => Ok(Self::Double),
-            "float64" => Ok(Self::Float64),
+    #[cfg(feature = "ply")]
+    #[test]
+    fn reconstruct_to_ply_streams_triangles() {
+        // A Fibonacci sphere.
+        let cloud: Vec<Point> = (0..400_u16)
+            .map(|i| {
+                let i = f32::from(i);
+                let z = 1.0 - (i + 0.5) / 200.0;
+                let r = (1.0 - z * z).sqrt();
+                let theta = i * 2.399_963;
+                let pos = Vec3::new(r * theta.cos(), r * theta.sin(), z);
+                Point { pos, normal: pos }
+            })
+            .collect();
+        let config = ReconstructionConfig::new(0.3);
+        let path = std::env::temp_dir().join("bpa_rs_reconstruct_to_ply/streamed.ply");
+        let report = reconstruct_to_ply(&path, &cloud, &config).expect("write failed");
+
+        let triangles = crate::reconstruct_with_config(&cloud, &config)
+            .expect("no mesh")
+            .triangles;
+        assert_eq!(report.triangles, triangles.len());
+
+        let mut reader = BufReader::new(std::fs::File::open(&path).expect("no ply"));
+        let header = parse_ply_header(&mut reader).expect("unreadable header");
+        let counts: Vec<u64> = header.elements.iter().map(|e| e.count).collect();
+        assert_eq!(counts, [3 * triangles.len() as u64, triangles.len() as u64]);
+
+        let vertices: Vec<Vec3> = load_ply(&path)
+            .expect("unreadable ply")
+            .iter()
+            .map(|p| p.pos)
+            .collect();
+        let expected: Vec<Vec3> = triangles.iter().flat_map(|t| t.0).collect();
+        assert_eq!(vertices, expected);
+    }
 
-            _ => Err(UnknownType),
-        }
+    #[cfg(feature = "stl")]
+    #[test]
+    fn reconstruct_file_detects_formats() {
+        let input = PathBuf::from("../data/bunny.xyz");
+        let dir = std::env::temp_dir().join("bpa_rs_reconstruct_file");
+
+        let output = dir.join("bunny.STL");
+        let config = ReconstructionConfig::new(0.002);
+        let report = reconstruct_file(&input, &output, &config).expect("reconstruction failed");
+        let stl = std::fs::read(&output).unwrap();
+        assert_eq!(stl.len(), 84 + STL_FACET_SIZE * report.triangles);
+        assert!(report.triangles > 0);
+
+        let error = reconstruct_file(&input, &dir.join("bunny.xyz"), &config).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
     }
-}
-/// The header of a PLY file
-#[derive(Debug)]
-struct Header {
-    /// The format of the PLY file.
-    pub format: Format,
-    /// The number of vertices in the PLY file.
-    pub vertex_count: u64,
-    /// The columns of the data section (label, type, n_items_type)
-    pub ordered_properties: Vec<(String, Type, Option<Type>)>,
-}
 
-#[derive(Debug)]
-enum HeaderError {
-    InvalidFile,
-    Malformed,
-}
+    #[cfg(feature = "ply")]
+    #[test]
+    fn reconstruct_file_to_ply() {
+        let input = PathBuf::from("../data/bunny.xyz");
+        let output = std::env::temp_dir().join("bpa_rs_reconstruct_file_to_ply/bunny.ply");
+        let config = ReconstructionConfig::new(0.002);
+        let report = reconstruct_file(&input, &output, &config).expect("reconstruction failed");
+
+        let mut reader = BufReader::new(std::fs::File::open(&output).expect("no ply"));
+        let header = parse_ply_header(&mut reader).expect("unreadable header");
+        let faces = header.elements.iter().find(|e| e.name == "face");
+        assert_eq!(faces.map(|e| e.count), Some(report.triangles as u64));
+        assert!(report.triangles > 0);
+    }
 
-// Extract data from a PLY header
-//header format
-// ply
-// format ascii 1.0
-// comment This is a comment!
-// element vertex 779966
-// property float x
-// property float y
-// property float z
-// end_header
-//
-// The second line is one of
-// format ascii 1.0
-// format binary_little_endian 1.0
-// format binary_big_endian 1.0
-//
-#[track_caller]
-fn parse_ply_header<T>(buffer: &mut BufReader<T>) -> Result<Header, HeaderError>
-where
-    T: Read,
-{
-    info!("Reading header");
-    // Return error is the first line is not "ply"
-    let mut line = String::new();
-    buffer
-        .read_line(&mut line)
-        .expect("Failed looking for header token");
-
-    assert!(
-        line.starts_with("ply"),
-        "Does not container the FILE descriptor of a ply file."
-    );
-
-    let mut format: Option<Format> = None;
-    let mut ordered_properties = vec![];
-
-    let mut vertex_count: u64 = 0;
-
-    for line in buffer.lines().map(|l| l.unwrap()) {
-        info!("parse_ply_header: loop");
-        let line = line.trim();
-        info!("parse_ply_header: loop {line}");
-        // If the line is "end_header", return the header
-        if line == "end_header" {
-            info!("end_header seen");
-            match format {
-                Some(format) => {
-                    info!("Parsing header complete.");
-                    return Ok(Header {
-                        format,
-                        vertex_count,
-                        ordered_properties,
-                    });
-                }
-                None => {
-                    panic!("At the end of the header the format is unknown or invalid");
-                }
-            }
+    #[cfg(feature = "stl")]
+    #[test]
+    fn load_stl_reads_both_formats() {
+        let triangles = [
+            Triangle([Vec3::ZERO, Vec3::X, Vec3::Y]),
+            Triangle([Vec3::new(0.5, 0.25, -1.0), Vec3::Y, Vec3::Z]),
+        ];
+        let dir = std::env::temp_dir().join("bpa_rs_load_stl");
+
+        let binary = dir.join("binary.stl");
+        save_triangles(&binary, &triangles).expect("write failed");
+        let ascii = dir.join("ascii.stl");
+        save_triangles_ascii(&ascii, &triangles).expect("write failed");
+
+        for path in [binary, ascii] {
+            let loaded = load_stl(&path).expect("cannot load stl");
+            assert_eq!(format!("{loaded:?}"), format!("{triangles:?}"));
         }
+    }
 
-        if line.starts_with("comment") {
-            // Ignore comments
-            continue;
-        }
+    #[cfg(feature = "stl")]
+    #[test]
+    fn stl_facet_layout() {
+        let t = Triangle([
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        ]);
+        let facet = stl_facet(&t);
+
+        let floats: Vec<f32> = facet[..48]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(
+            floats,
+            [0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0]
+        );
+        assert_eq!(facet[48..], [0, 0]);
+    }
 
-        if line.starts_with("element face") {
-            // Ignore faces
-            continue;
-        }
+    #[cfg(all(feature = "ply", feature = "stl"))]
+    #[test]
+    fn provenance_is_embedded() {
+        let config = ReconstructionConfig::new(0.25);
+        let provenance = Provenance::new(&config)
+            .with_input(std::path::Path::new("../data/bunny.xyz"))
+            .expect("Cannot hash bunny");
+        let dir = std::env::temp_dir().join("bpa_rs_provenance");
+
+        let stl = dir.join("mesh.stl");
+        let t = Triangle([Vec3::ZERO, Vec3::X, Vec3::Y]);
+        save_triangles_with_provenance(&stl, std::slice::from_ref(&t), &provenance)
+            .expect("write failed");
+        let header = String::from_utf8(std::fs::read(&stl).unwrap()[..80].to_vec()).unwrap();
+        // A binary STL header must not look like an ascii one.
+        assert!(header.starts_with("bpa_rs "));
+        assert!(header.contains("radius=0.25"));
+        assert!(header.contains(&format!("{:016x}", provenance.input_hash.unwrap())));
+
+        let options = SaveOptions::default()
+            .with_encoding(Encoding::Ascii)
+            .with_provenance(provenance.clone());
+        save_mesh(&stl, &[t], &options).expect("write failed");
+        let text = std::fs::read_to_string(&stl).unwrap();
+        assert!(text.starts_with("solid bpa_rs "));
+        assert!(text.lines().next().unwrap().contains("radius=0.25"));
+
+        let ply = dir.join("points.ply");
+        let points = vec![Point {
+            pos: Vec3::X,
+            normal: Vec3::Z,
+        }];
+        save_points_and_normals_with_provenance(&ply, &points, &provenance).expect("write failed");
+        let text = String::from_utf8_lossy(&std::fs::read(&ply).unwrap()).into_owned();
+        assert!(text.contains("comment radius 0.25\n"));
+        assert!(text.contains("comment input ../data/bunny.xyz\n"));
+        let mut reader = BufReader::new(std::fs::File::open(&ply).unwrap());
+        let header = parse_ply_header(&mut reader).expect("comments broke the header");
+        assert_eq!(header.elements[0].count, 1);
+    }
 
-        if line.starts_with("element vertex") {
-            // Extract the vertex count
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            assert!(parts.len() == 3, "Failed to parse: {line}");
-            vertex_count = parts[2].parse::<u64>().expect("unrecognized count");
-            continue;
-        }
+    #[cfg(feature = "ply")]
+    #[test]
+    fn ply_skips_other_elements() {
+        let ply = "ply
+format ascii 1.0
+element camera 1
+property float view_px
+property float view_py
+element vertex 2
+property float x
+property float y
+property float z
+property list uchar int vertex_indices
+element face 1
+property list uchar int vertex_indices
+element material 2
+property uchar ambient_red
+end_header
+7 8
+1 2 3 0
+4 5 6 2 10 11
+
+3 0 1 2
+0
+255
+";
+        let points = read_ply(BufReader::new(Cursor::new(ply))).expect("unreadable ply");
+        let positions: Vec<Vec3> = points.iter().map(|p| p.pos).collect();
+        assert_eq!(
+            positions,
+            [Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0)]
+        );
 
-        if line.starts_with("format") {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            assert!(parts.len() == 3, "Failed to parse: {line}");
-            format = Some(match parts[1] {
-                "ascii" => {
-                    let version = parts[2]
-                        .parse::<f32>()
-                        .expect("format ascii expecting a version number");
-                    Format::Ascii(version)
-                }
-                "binary_little_endian" => {
-                    let version = parts[2]
-                        .parse::<f32>()
-                        .expect("format binary_little_endian expecting a version number");
-                    Format::BinaryLittleEndian(version)
-                }
-                "binary_big_endian" => {
-                    let version = parts[2]
-                        .parse::<f32>()
-                        .expect("format binary_big_endian expecting a version number");
-                    Format::BinaryBigEndian(version)
-                }
-                _ => {
-                    error!("unrecognised format string");
-                    return Err(HeaderError::InvalidFile);
-                }
-            });
-        }
+        // A missing record is an error rather than a panic.
+        let truncated = &ply[..ply.find("3 0 1 2").unwrap()];
+        let error = read_ply(BufReader::new(Cursor::new(truncated))).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
 
-        if line.starts_with("property") {
-            // Extract the property
-            println!("Property: {line}");
-            let parts: Vec<&str> = line.split_whitespace().map(|s| s.into()).collect();
-            if parts[1] == "list" {
-                assert!(parts.len() == 5, "Failed to parse property list: {line}");
-
-                println!("n_items {}", parts[2]);
-                let n_item_type = Type::try_from(parts[2]).expect("Unknown type");
-                let prop_type = Type::try_from(parts[3]).expect("Unknown type");
-                let label = parts[4].to_owned();
-                ordered_properties.push((label, prop_type, Some(n_item_type)));
-            } else {
-                assert!(parts.len() == 3, "Failed to parse: property {line}");
-                let prop_type = Type::try_from(parts[1]).expect("Unknown type");
-                let label = parts[2].to_owned();
-                // Dummy n_items type (uint32) as the values will be 1.
-                ordered_properties.push((label, prop_type, None));
-                continue;
-            }
+    #[cfg(feature = "ply")]
+    #[test]
+    fn binary_ply() {
+        let points = vec![
+            Point {
+                pos: Vec3::new(1.0, 2.0, 3.0),
+                normal: Vec3::Z,
+            },
+            Point {
+                pos: Vec3::new(-4.5, 0.25, 6.0),
+                normal: Vec3::X,
+            },
+        ];
+        let path = std::env::temp_dir().join("bpa_rs_binary_ply/points.ply");
+        save_points_and_normals(&path, &points).expect("write failed");
+        let loaded = load_ply(&path).expect("unreadable ply");
+        assert_eq!(format!("{loaded:?}"), format!("{points:?}"));
+
+        // Big endian, with a face and a camera to skip.
+        let mut ply = b"ply
+format binary_big_endian 1.0
+element vertex 1
+property double x
+property float y
+property short z
+element face 1
+property list uchar uint vertex_indices
+element camera 1
+property uchar r
+end_header
+"
+        .to_vec();
+        ply.extend(1.5_f64.to_be_bytes());
+        ply.extend(2.5_f32.to_be_bytes());
+        ply.extend((-3_i16).to_be_bytes());
+        ply.push(3);
+        for i in 0_u32..3 {
+            ply.extend(i.to_be_bytes());
         }
+        ply.push(9);
+        let loaded = read_ply(BufReader::new(Cursor::new(ply))).expect("unreadable ply");
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].pos, Vec3::new(1.5, 2.5, -3.0));
     }
 
-    Err(HeaderError::Malformed)
-}
+    #[cfg(feature = "ply")]
+    #[test]
+    fn cloud_preview() {
+        let mut cloud = PointCloud {
+            normals: Some(vec![Vec3::Z, Vec3::NEG_X]),
+            ..PointCloud::new(vec![Vec3::new(1.0, 2.0, 3.0), Vec3::new(-4.5, 0.25, 6.0)])
+        };
+        let path = std::env::temp_dir().join("bpa_rs_cloud_preview/cloud.ply");
+        save_cloud_preview(&path, &cloud).expect("write failed");
+        let text = std::fs::read_to_string(&path).expect("unreadable preview");
+        assert!(text.starts_with("ply\nformat ascii 1.0\n"));
+        let loaded = load_point_cloud(&path).expect("unreadable ply");
+        assert_eq!(loaded.positions, cloud.positions);
+        assert_eq!(loaded.normals, cloud.normals);
+        // Coloured by normal.
+        let colors = loaded.colors.expect("colours are written");
+        assert_eq!(colors[0], Vec3::new(128.0, 128.0, 255.0) / 255.0);
+        assert_eq!(colors[1], Vec3::new(0.0, 128.0, 128.0) / 255.0);
+
+        cloud.colors = Some(vec![Vec3::X, Vec3::ONE]);
+        save_cloud_preview(&path, &cloud).expect("write failed");
+        let loaded = load_point_cloud(&path).expect("unreadable ply");
+        assert_eq!(loaded.colors, cloud.colors);
+    }
 
-#[cfg(test)]
-mod test {
+    #[test]
+    fn fem_surfaces() {
+        let triangles = [
+            Triangle([Vec3::ZERO, Vec3::X, Vec3::Y]),
+            Triangle([Vec3::X, Vec3::new(1.0, 1.0, 0.5), Vec3::Y]),
+        ];
+        let dir = std::env::temp_dir().join("bpa_rs_fem_surfaces");
+
+        let path = dir.join("surface.mesh");
+        save_medit(&path, &triangles).expect("write failed");
+        let text = std::fs::read_to_string(&path).expect("unreadable mesh");
+        assert_eq!(
+            text,
+            "MeshVersionFormatted 2\nDimension 3\n\
+             Vertices\n4\n0 0 0 0\n1 0 0 0\n0 1 0 0\n1 1 0.5 0\n\
+             Triangles\n2\n1 2 3 1\n2 4 3 1\nEnd\n"
+        );
 
-    use std::io::Cursor;
+        let path = dir.join("surface.msh");
+        save_gmsh(&path, &triangles).expect("write failed");
+        let text = std::fs::read_to_string(&path).expect("unreadable msh");
+        assert_eq!(
+            text,
+            "$MeshFormat\n2.2 0 8\n$EndMeshFormat\n\
+             $Nodes\n4\n1 0 0 0\n2 1 0 0\n3 0 1 0\n4 1 1 0.5\n$EndNodes\n\
+             $Elements\n2\n1 2 2 1 1 1 2 3\n2 2 2 1 1 2 4 3\n$EndElements\n"
+        );
+    }
 
-    use super::*;
-    use insta::assert_debug_snapshot;
+    #[cfg(all(feature = "ply", feature = "stl"))]
+    #[test]
+    fn save_mesh_options() {
+        let triangles = [
+            Triangle([Vec3::ZERO, Vec3::X, Vec3::Y]),
+            Triangle([Vec3::X, Vec3::new(1.0, 1.0, 0.5), Vec3::Y]),
+        ];
+        let dir = std::env::temp_dir().join("bpa_rs_save_mesh_options");
+
+        let stl = dir.join("mesh.stl");
+        let options = SaveOptions::default()
+            .with_encoding(Encoding::Ascii)
+            .with_precision(2)
+            .with_solid_name("part")
+            .with_scale(2.0)
+            .with_flipped_winding(true);
+        save_mesh(&stl, &triangles[..1], &options).expect("write failed");
+        let text = std::fs::read_to_string(&stl).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "solid part",
+                "  facet normal -0.00 -0.00 -1.00",
+                "    outer loop",
+                "      vertex 0.00 0.00 0.00",
+                "      vertex 0.00 2.00 0.00",
+                "      vertex 2.00 0.00 0.00",
+                "    endloop",
+                "  endfacet",
+                "endsolid",
+            ]
+        );
 
-    // Tests the use of property list
+        let ply = dir.join("mesh.ply");
+        let shared = SaveOptions::default().with_scale(2.0);
+        save_mesh(&ply, &triangles, &shared).expect("write failed");
+        let vertices = load_ply(&ply).expect("unreadable ply");
+        assert_eq!(vertices.len(), 4);
+        assert_eq!(vertices[3].pos, Vec3::new(2.0, 2.0, 1.0));
+
+        let apart = SaveOptions::default()
+            .with_encoding(Encoding::Ascii)
+            .with_dedup(false);
+        save_mesh(&ply, &triangles, &apart).expect("write failed");
+        let text = std::fs::read_to_string(&ply).unwrap();
+        assert!(text.contains("element vertex 6\n"));
+        assert!(text.ends_with("3 0 1 2\n3 3 4 5\n"));
+        assert_eq!(load_ply(&ply).expect("unreadable ply").len(), 6);
+
+        let jittered = [
+            triangles[0].clone(),
+            Triangle(triangles[1].0.map(|v| v + 1e-6)),
+        ];
+        let welded = SaveOptions::default().with_weld(1e-4);
+        save_mesh(&ply, &jittered, &welded).expect("write failed");
+        assert_eq!(load_ply(&ply).expect("unreadable ply").len(), 4);
+
+        let error = save_mesh(&dir.join("mesh.xyz"), &triangles, &shared).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[cfg(all(feature = "obj", feature = "ply"))]
     #[test]
-    fn test_parse_ply_header() {
-        let header = r"ply
+    fn colored_obj() {
+        let ply = "ply
 format ascii 1.0
-comment This is a comment!
-element vertex 779966
+element vertex 3
 property float x
 property float y
 property float z
-property list uchar int vertex_indicies
-end_header";
+property uchar red
+property uchar green
+property uchar blue
+end_header
+0 0 0 255 0 0
+1 0 0 0 255 0
+0 1 0 0 0 255
+";
+        let (points, colors) =
+            read_ply_with_colors(BufReader::new(Cursor::new(ply))).expect("unreadable ply");
+        let colors = colors.expect("the colours were dropped");
+        assert_eq!(colors, [Vec3::X, Vec3::Y, Vec3::Z]);
+
+        let dir = std::env::temp_dir().join("bpa_rs_obj");
+        let obj = dir.join("mesh.obj");
+        let triangles = [
+            Triangle([Vec3::ZERO, Vec3::X, Vec3::Y]),
+            Triangle([Vec3::Y, Vec3::X, Vec3::ONE]),
+        ];
+        let colors = VertexColors::new(&points, &colors);
+        save_obj(&obj, &triangles, Some(&colors)).expect("write failed");
+
+        let text = std::fs::read_to_string(&obj).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "mtllib mesh.mtl",
+                "v 0 0 0 1 0 0",
+                "v 1 0 0 0 1 0",
+                "v 0 1 0 0 0 1",
+                "v 1 1 1 0.5 0.5 0.5",
+                "usemtl vertex_colors",
+                "f 1 2 3",
+                "f 3 2 4",
+            ]
+        );
+        let mtl = std::fs::read_to_string(dir.join("mesh.mtl")).unwrap();
+        assert!(mtl.starts_with("newmtl vertex_colors\nKd "));
 
-        let mut cursor = Cursor::new(header);
-        let mut bufreader = BufReader::new(&mut cursor);
+        save_obj(&obj, &triangles, None).expect("write failed");
+        let text = std::fs::read_to_string(&obj).unwrap();
+        assert!(text.starts_with("v 0 0 0\n"));
+    }
 
-        let result = parse_ply_header(&mut bufreader);
-        assert!(result.is_ok(), "Failed to parse PLY header: {result:?}");
-        let header = result.unwrap();
-        assert_debug_snapshot!(header);
+    #[test]
+    fn xyz_extra_columns() {
+        let dir =
+            std::env::temp_dir().join(format!("bpa_rs_xyz_extra_columns_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("cannot create directory");
+        let path = dir.join("scan.xyz");
+        std::fs::write(
+            &path,
+            "# x y z nx ny nz r g b intensity\r\n\
+             3\r\n\
+             1 2 3 0 0 1 255 0 51 0.5\r\n\
+             \r\n\
+             -1.5e-1 2E2 3 0 1 0 0 255 0 7\r\n",
+        )
+        .expect("cannot write xyz");
+
+        let points = load_xyz(&path).expect("cannot load xyz");
+        let positions: Vec<Vec3> = points.iter().map(|p| p.pos).collect();
+        assert_eq!(
+            positions,
+            [Vec3::new(1.0, 2.0, 3.0), Vec3::new(-0.15, 200.0, 3.0)]
+        );
+        assert_eq!(points[1].normal, Vec3::Y);
+
+        let options = XyzOptions {
+            color: Some(6),
+            intensity: Some(9),
+            ..XyzOptions::default()
+        };
+        let cloud = load_xyz_with_options(&path, &options).expect("cannot load xyz");
+        assert_eq!(cloud.colors, Some(vec![Vec3::new(1.0, 0.0, 0.2), Vec3::Y]));
+        assert_eq!(cloud.intensities, Some(vec![0.5, 7.0]));
+
+        std::fs::write(&path, "1 2 3 0 0 1\n1 2 three 0 0 1\n").expect("cannot write xyz");
+        let error = load_xyz(&path).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(
+            error
+                .to_string()
+                .starts_with("line 2: cannot read column 3")
+        );
+    }
+
+    #[test]
+    fn pts_without_normals() {
+        let dir =
+            std::env::temp_dir().join(format!("bpa_rs_pts_without_normals_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("cannot create directory");
+        let path = dir.join("scan.pts");
+        std::fs::write(
+            &path,
+            "2\n\
+             1.5 2 3 -1204 255 128 0\n\
+             4 5 6 -87 0 0 255\n",
+        )
+        .expect("cannot write pts");
+
+        let options = XyzOptions {
+            normal: None,
+            intensity: Some(3),
+            color: Some(4),
+        };
+        let cloud = load_xyz_with_options(&path, &options).expect("cannot load pts");
+        let points: Vec<(Vec3, Vec3)> = cloud.points.iter().map(|p| (p.pos, p.normal)).collect();
+        assert_eq!(
+            points,
+            [
+                (Vec3::new(1.5, 2.0, 3.0), Vec3::ZERO),
+                (Vec3::new(4.0, 5.0, 6.0), Vec3::ZERO)
+            ]
+        );
+        assert_eq!(cloud.intensities, Some(vec![-1204.0, -87.0]));
+        assert_eq!(
+            cloud.colors,
+            Some(vec![Vec3::new(1.0, 128.0 / 255.0, 0.0), Vec3::Z])
+        );
+
+        std::fs::write(&path, "1 2 3\n").expect("cannot write xyz");
+        let options = XyzOptions {
+            normal: None,
+            ..XyzOptions::default()
+        };
+        let cloud = load_xyz_with_options(&path, &options).expect("cannot load xyz");
+        assert_eq!(cloud.points[0].pos, Vec3::new(1.0, 2.0, 3.0));
+        assert!(load_xyz(&path).is_err());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn load_xyz_par_preserves_order() {
+        let path = PathBuf::from("../data/bunny.xyz");
+        let serial = load_xyz(&path).expect("Cannot load bunny");
+        let parallel = load_xyz_par(&path).expect("Cannot load bunny");
+        assert_eq!(format!("{parallel:?}"), format!("{serial:?}"));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn split_lines_on_newlines() {
+        let text = b"1 2 3\n4 5 6\n7 8 9";
+        let chunks = split_lines(text, 3);
+        assert_eq!(chunks.concat(), text);
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert_eq!(chunk.last(), Some(&b'\n'));
+        }
     }
 }
@@ -0,0 +1,97 @@
+use std::io::Write;
+use std::path::Path;
+
+use super::SaveOptions;
+use super::create;
+use crate::Triangle;
+use crate::topology::IndexedMesh;
+
+/// Write triangles as a MEDIT `.mesh` surface, sharing vertices between
+/// triangles, to seed a volume mesher such as `TetGen` or `MMG`.
+///
+/// Vertices are given reference 0 and triangles reference 1.
+///
+/// # Errors
+///   When the file cannot be created or written to.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn save_medit(path: &Path, triangles: &[Triangle]) -> std::io::Result<()> {
+    write_medit(path, triangles, &SaveOptions::default())
+}
+
+pub(super) fn write_medit(
+    path: &Path,
+    triangles: &[Triangle],
+    options: &SaveOptions,
+) -> std::io::Result<()> {
+    let IndexedMesh { vertices, faces } = options.mesh(triangles);
+    let mut writer = create(path)?;
+    writeln!(writer, "MeshVersionFormatted 2")?;
+    writeln!(writer, "Dimension 3")?;
+    writeln!(writer, "Vertices")?;
+    writeln!(writer, "{}", vertices.len())?;
+    for v in &vertices {
+        writeln!(writer, "{} 0", options.xyz(*v))?;
+    }
+    writeln!(writer, "Triangles")?;
+    writeln!(writer, "{}", faces.len())?;
+    // Vertices are counted from one.
+    for [a, b, c] in faces {
+        writeln!(writer, "{} {} {} 1", a + 1, b + 1, c + 1)?;
+    }
+    writeln!(writer, "End")?;
+    writer.flush()
+}
+
+/// Write triangles as an ascii Gmsh `.msh` surface, in the 2.2 format read
+/// by Gmsh and most finite element tools, sharing vertices between
+/// triangles.
+///
+/// Every triangle is in physical group 1 and elementary entity 1.
+///
+/// # Errors
+///   When the file cannot be created or written to.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn save_gmsh(path: &Path, triangles: &[Triangle]) -> std::io::Result<()> {
+    write_gmsh(path, triangles, &SaveOptions::default())
+}
+
+pub(super) fn write_gmsh(
+    path: &Path,
+    triangles: &[Triangle],
+    options: &SaveOptions,
+) -> std::io::Result<()> {
+    // The element type of a three node triangle.
+    const TRIANGLE: u8 = 2;
+
+    let IndexedMesh { vertices, faces } = options.mesh(triangles);
+    let mut writer = create(path)?;
+    writeln!(writer, "$MeshFormat")?;
+    writeln!(writer, "2.2 0 8")?;
+    writeln!(writer, "$EndMeshFormat")?;
+    writeln!(writer, "$Nodes")?;
+    writeln!(writer, "{}", vertices.len())?;
+    for (i, v) in vertices.iter().enumerate() {
+        writeln!(writer, "{} {}", i + 1, options.xyz(*v))?;
+    }
+    writeln!(writer, "$EndNodes")?;
+    writeln!(writer, "$Elements")?;
+    writeln!(writer, "{}", faces.len())?;
+    for (i, [a, b, c]) in faces.into_iter().enumerate() {
+        writeln!(
+            writer,
+            "{} {TRIANGLE} 2 1 1 {} {} {}",
+            i + 1,
+            a + 1,
+            b + 1,
+            c + 1
+        )?;
+    }
+    writeln!(writer, "$EndElements")?;
+    writer.flush()
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use glam::Vec3;
+
+use super::SaveOptions;
+use crate::provenance::Provenance;
+use crate::topology::IndexedMesh;
+use crate::{Point, Triangle};
+
+/// The colours of the input points, found by position.
+///
+/// The vertices of a reconstructed mesh are input points, so their colours
+/// can be looked up exactly.
+#[derive(Clone, Debug, Default)]
+pub struct VertexColors {
+    by_position: HashMap<[u32; 3], Vec3>,
+}
+
+impl VertexColors {
+    /// Pairs each point with the colour at the same index, as returned by
+    /// [`super::load_ply_with_colors()`]. Extra points or colours are ignored.
+    #[must_use]
+    pub fn new(points: &[Point], colors: &[Vec3]) -> Self {
+        Self {
+            by_position: points
+                .iter()
+                .zip(colors)
+                .map(|(p, c)| (key(p.pos), *c))
+                .collect(),
+        }
+    }
+
+    /// The colour of the point at `pos`, if it is known.
+    #[must_use]
+    pub fn get(&self, pos: Vec3) -> Option<Vec3> {
+        self.by_position.get(&key(pos)).copied()
+    }
+}
+
+fn key(v: Vec3) -> [u32; 3] {
+    v.to_array().map(f32::to_bits)
+}
+
+/// Write triangles as a Wavefront OBJ file, sharing vertices between
+/// triangles.
+///
+/// With colours, each vertex is written as `v x y z r g b`, the extension
+/// read by Blender and `MeshLab`, and a material library is written beside the
+/// file, with the same name and an `.mtl` extension. Its single material has
+/// the mean colour, for viewers that ignore vertex colours. Vertices without
+/// a known colour are grey.
+///
+/// # Errors
+///   When a file cannot be created or written to.
+pub fn save_obj(
+    path: &Path,
+    triangles: &[Triangle],
+    colors: Option<&VertexColors>,
+) -> std::io::Result<()> {
+    write_obj(path, triangles, colors, &SaveOptions::default())
+}
+
+/// Write triangles as a Wavefront OBJ file, recording how it was produced as
+/// comments. See [`save_obj()`].
+///
+/// # Errors
+///   When a file cannot be created or written to.
+pub fn save_obj_with_provenance(
+    path: &Path,
+    triangles: &[Triangle],
+    colors: Option<&VertexColors>,
+    provenance: &Provenance,
+) -> std::io::Result<()> {
+    let options = SaveOptions::default().with_provenance(provenance.clone());
+    write_obj(path, triangles, colors, &options)
+}
+
+/// Write triangles as a Wavefront OBJ file, with the scale, winding,
+/// sharing and provenance set by `options`. See [`save_obj()`].
+///
+/// # Errors
+///   When a file cannot be created or written to.
+pub fn save_obj_with_options(
+    path: &Path,
+    triangles: &[Triangle],
+    colors: Option<&VertexColors>,
+    options: &SaveOptions,
+) -> std::io::Result<()> {
+    write_obj(path, triangles, colors, options)
+}
+
+const MATERIAL: &str = "vertex_colors";
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub(super) fn write_obj(
+    path: &Path,
+    triangles: &[Triangle],
+    colors: Option<&VertexColors>,
+    options: &SaveOptions,
+) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // The colours are found before the vertices are scaled.
+    let IndexedMesh {
+        mut vertices,
+        faces,
+    } = options.indexed(triangles);
+    let mut writer = BufWriter::new(std::fs::File::create(path)?);
+    if let Some(provenance) = &options.provenance {
+        for comment in provenance.lines() {
+            writeln!(writer, "# {comment}")?;
+        }
+    }
+    if let Some(colors) = colors {
+        let vertex_colors: Vec<Vec3> = vertices
+            .iter()
+            .map(|v| colors.get(*v).unwrap_or(Vec3::splat(0.5)))
+            .collect();
+        options.scale(&mut vertices);
+        let mtl = path.with_extension("mtl");
+        write_mtl(&mtl, mean(&vertex_colors))?;
+        if let Some(name) = mtl.file_name() {
+            writeln!(writer, "mtllib {}", name.to_string_lossy())?;
+        }
+        for (v, c) in vertices.iter().zip(&vertex_colors) {
+            writeln!(writer, "v {} {} {} {}", options.xyz(*v), c.x, c.y, c.z)?;
+        }
+        writeln!(writer, "usemtl {MATERIAL}")?;
+    } else {
+        options.scale(&mut vertices);
+        for v in &vertices {
+            writeln!(writer, "v {}", options.xyz(*v))?;
+        }
+    }
+    // OBJ counts vertices from one.
+    for [a, b, c] in faces {
+        writeln!(writer, "f {} {} {}", a + 1, b + 1, c + 1)?;
+    }
+    writer.flush()
+}
+
+fn write_mtl(path: &Path, color: Vec3) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(std::fs::File::create(path)?);
+    writeln!(writer, "newmtl {MATERIAL}")?;
+    writeln!(writer, "Kd {} {} {}", color.x, color.y, color.z)?;
+    writer.flush()
+}
+
+fn mean(colors: &[Vec3]) -> Vec3 {
+    if colors.is_empty() {
+        return Vec3::splat(0.5);
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let n = colors.len() as f32;
+    colors.iter().sum::<Vec3>() / n
+}
@@ -0,0 +1,215 @@
+use core::fmt;
+
+use glam::Vec3;
+
+use crate::Triangle;
+use crate::provenance::Provenance;
+use crate::topology::IndexedMesh;
+use crate::units::Unit;
+
+/// Whether a mesh file is written as text or as binary.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// Little endian binary, smaller and faster to read and write.
+    #[default]
+    Binary,
+    /// Text, readable by eye and by tools lacking binary support.
+    Ascii,
+}
+
+/// How a mesh is written by [`super::save_mesh()`].
+///
+/// The defaults write the triangles as they are, in binary where the format
+/// allows it, with the vertices shared between triangles. Options that a
+/// format cannot hold are ignored: OBJ, MEDIT and Gmsh files are always
+/// text, and an STL file never shares its vertices.
+#[derive(Clone, Debug)]
+pub struct SaveOptions {
+    /// Text or binary, for the STL and PLY formats.
+    pub encoding: Encoding,
+    /// The number of digits after the decimal point of each coordinate in a
+    /// text file. By default the shortest number that reads back exactly.
+    pub precision: Option<usize>,
+    /// The name after `solid` in a text STL file. By default the fields of
+    /// the provenance, when it is recorded, or else the path.
+    pub solid_name: Option<String>,
+    /// The factor every coordinate is multiplied by, as when converting
+    /// between units.
+    pub scale: f32,
+    /// Reverses the winding of every triangle, turning its normal around.
+    pub flip_winding: bool,
+    /// Writes each vertex once, shared by the triangles around it. When
+    /// off, each triangle writes its own three vertices.
+    pub dedup: bool,
+    /// When sharing vertices, also joins those within this distance of each
+    /// other, closing the cracks left by rounding. See
+    /// [`IndexedMesh::welded()`].
+    pub weld: Option<f32>,
+    /// Records how the mesh was produced, in the STL header or solid name, or
+    /// as comments.
+    pub provenance: Option<Provenance>,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            encoding: Encoding::default(),
+            precision: None,
+            solid_name: None,
+            scale: 1.0,
+            flip_winding: false,
+            dedup: true,
+            weld: None,
+            provenance: None,
+        }
+    }
+}
+
+impl SaveOptions {
+    /// Writes text or binary.
+    #[must_use]
+    pub fn with_encoding(self, encoding: Encoding) -> Self {
+        Self { encoding, ..self }
+    }
+
+    /// Writes `digits` digits after the decimal point in text files.
+    #[must_use]
+    pub fn with_precision(self, digits: usize) -> Self {
+        Self {
+            precision: Some(digits),
+            ..self
+        }
+    }
+
+    /// Names the solid of a text STL file.
+    #[must_use]
+    pub fn with_solid_name(self, name: impl Into<String>) -> Self {
+        Self {
+            solid_name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Multiplies every coordinate by `scale`.
+    #[must_use]
+    pub fn with_scale(self, scale: f32) -> Self {
+        Self { scale, ..self }
+    }
+
+    /// Converts the coordinates from one unit to another. See
+    /// [`Provenance::with_unit()`] to record the unit in the file.
+    #[must_use]
+    pub fn with_units(self, from: Unit, to: Unit) -> Self {
+        self.with_scale(from.scale_to(to))
+    }
+
+    /// Reverses the winding of the triangles, or not.
+    #[must_use]
+    pub fn with_flipped_winding(self, flip_winding: bool) -> Self {
+        Self {
+            flip_winding,
+            ..self
+        }
+    }
+
+    /// Shares the vertices between triangles, or not.
+    #[must_use]
+    pub fn with_dedup(self, dedup: bool) -> Self {
+        Self { dedup, ..self }
+    }
+
+    /// Joins the vertices within `tolerance` of each other.
+    #[must_use]
+    pub fn with_weld(self, tolerance: f32) -> Self {
+        Self {
+            weld: Some(tolerance),
+            ..self
+        }
+    }
+
+    /// Records how the mesh was produced.
+    #[must_use]
+    pub fn with_provenance(self, provenance: Provenance) -> Self {
+        Self {
+            provenance: Some(provenance),
+            ..self
+        }
+    }
+
+    // The triangle as written: scaled, and with its winding reversed when
+    // asked.
+    pub(super) fn triangle(&self, t: &Triangle) -> Triangle {
+        let [a, b, c] = t.0.map(|v| v * self.scale);
+        if self.flip_winding {
+            Triangle([a, c, b])
+        } else {
+            Triangle([a, b, c])
+        }
+    }
+
+    // The triangles as indexed faces, sharing or welding their vertices when
+    // asked, and with their winding reversed when asked. The coordinates are
+    // not scaled, so that they can still be looked up.
+    pub(super) fn indexed(&self, triangles: &[Triangle]) -> IndexedMesh {
+        let mut mesh = if self.dedup {
+            self.weld.map_or_else(
+                || IndexedMesh::new(triangles),
+                |tolerance| IndexedMesh::welded(triangles, tolerance),
+            )
+        } else {
+            IndexedMesh {
+                vertices: triangles.iter().flat_map(|t| t.0).collect(),
+                faces: (0..triangles.len())
+                    .map(|i| [3 * i, 3 * i + 1, 3 * i + 2])
+                    .collect(),
+            }
+        };
+        if self.flip_winding {
+            for face in &mut mesh.faces {
+                face.swap(1, 2);
+            }
+        }
+        mesh
+    }
+
+    // The triangles as written, as indexed faces.
+    pub(super) fn mesh(&self, triangles: &[Triangle]) -> IndexedMesh {
+        let mut mesh = self.indexed(triangles);
+        self.scale(&mut mesh.vertices);
+        mesh
+    }
+
+    // Multiplies the coordinates by the scale.
+    pub(super) fn scale(&self, vertices: &mut [Vec3]) {
+        #[allow(clippy::float_cmp)]
+        if self.scale != 1.0 {
+            for v in vertices {
+                *v *= self.scale;
+            }
+        }
+    }
+
+    // The coordinates of `v` as text, separated by spaces.
+    pub(super) const fn xyz(&self, v: Vec3) -> Xyz {
+        Xyz {
+            v,
+            precision: self.precision,
+        }
+    }
+}
+
+// Coordinates written to the precision of the options.
+pub(super) struct Xyz {
+    v: Vec3,
+    precision: Option<usize>,
+}
+
+impl fmt::Display for Xyz {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Vec3 { x, y, z } = self.v;
+        match self.precision {
+            Some(p) => write!(f, "{x:.p$} {y:.p$} {z:.p$}"),
+            None => write!(f, "{x} {y} {z}"),
+        }
+    }
+}
@@ -0,0 +1,806 @@
+use core::borrow::Borrow;
+use core::error::Error;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
+
+use glam::Vec3;
+use log::debug;
+use log::error;
+use log::info;
+
+use super::Encoding;
+use super::SaveOptions;
+use super::create;
+use super::scalars::index;
+use super::stream::reconstruct_streaming;
+use crate::ReconstructionConfig;
+use crate::point_cloud::PointCloud;
+use crate::provenance::Provenance;
+use crate::report::Report;
+use crate::topology::IndexedMesh;
+use crate::{Point, Triangle};
+
+/// Reconstructs a mesh, writing it as a binary PLY file as it is generated.
+///
+/// Like [`super::reconstruct_to_stl()`], no more than a batch of triangles is held
+/// in memory. Vertices are not shared: each triangle writes its own three,
+/// and the faces, which follow the vertices in a PLY file, are numbered from
+/// the vertex count alone. The counts in the header are filled in once the
+/// reconstruction is complete.
+///
+/// Returns a report on the reconstruction. When a budget is exceeded the
+/// partial mesh is written.
+///
+/// # Errors
+///   When the file cannot be created or written to, or the mesh has more
+///   vertices than 32 bit indices can number. When no mesh can be generated
+///   the error wraps a [`crate::ReconstructError`], and an empty PLY file is
+///   still written.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn reconstruct_to_ply<P: Borrow<Point>>(
+    path: &Path,
+    points: &[P],
+    config: &ReconstructionConfig,
+) -> std::io::Result<Report> {
+    reconstruct_streaming(path, points, config, write_ply_batches)
+}
+
+// Writes each batch of vertices as it arrives, then the faces, then patches
+// the counts. The counts are reserved as ten zero padded digits, enough for
+// any 32 bit count.
+fn write_ply_batches(
+    file: std::fs::File,
+    batches: &Receiver<Vec<Triangle>>,
+) -> std::io::Result<()> {
+    const COUNT_WIDTH: usize = 10;
+    let too_many =
+        || std::io::Error::other("ply file cannot index more than 4,294,967,295 vertices");
+
+    let mut writer = BufWriter::new(file);
+    let vertex_line = "ply\nformat binary_little_endian 1.0\nelement vertex ";
+    let face_line = "\nproperty float x\nproperty float y\nproperty float z\nelement face ";
+    let vertex_offset = vertex_line.len();
+    let face_offset = vertex_offset + COUNT_WIDTH + face_line.len();
+    write!(
+        writer,
+        "{vertex_line}{:0COUNT_WIDTH$}{face_line}{:0COUNT_WIDTH$}",
+        0, 0
+    )?;
+    writeln!(writer)?;
+    writeln!(writer, "property list uchar uint vertex_indices")?;
+    writeln!(writer, "end_header")?;
+
+    let mut vertices = 0_u32;
+    for batch in batches {
+        vertices = u32::try_from(batch.len() * 3)
+            .ok()
+            .and_then(|len| vertices.checked_add(len))
+            .ok_or_else(too_many)?;
+        for t in &batch {
+            for v in t.0 {
+                for f in v.to_array() {
+                    writer.write_all(&f.to_le_bytes())?;
+                }
+            }
+        }
+    }
+    let faces = vertices / 3;
+    for face in 0..faces {
+        writer.write_all(&[3])?;
+        for i in 0..3 {
+            writer.write_all(&(face * 3 + i).to_le_bytes())?;
+        }
+    }
+
+    let mut file = writer
+        .into_inner()
+        .map_err(std::io::IntoInnerError::into_error)?;
+    file.seek(SeekFrom::Start(vertex_offset as u64))?;
+    write!(file, "{vertices:0COUNT_WIDTH$}")?;
+    file.seek(SeekFrom::Start(face_offset as u64))?;
+    write!(file, "{faces:0COUNT_WIDTH$}")
+}
+
+/// Write Point cloud to file.
+///
+/// outout point and normal.
+///
+/// # Errors
+///   Problems writing to file.
+pub fn save_points_and_normals(
+    path: &PathBuf,
+    points: &Vec<Point>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    write_points_and_normals(path, points, &[])
+}
+
+/// Write Point cloud to file, recording how it was produced as comments.
+///
+/// # Errors
+///   Problems writing to file.
+pub fn save_points_and_normals_with_provenance(
+    path: &PathBuf,
+    points: &Vec<Point>,
+    provenance: &Provenance,
+) -> Result<(), Box<dyn std::error::Error>> {
+    write_points_and_normals(path, points, &provenance.lines())
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+fn write_points_and_normals(
+    path: &PathBuf,
+    points: &Vec<Point>,
+    comments: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let file = std::fs::File::create(path)?;
+    let mut writer = BufWriter::new(file);
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format binary_little_endian 1.0")?;
+    for comment in comments {
+        writeln!(writer, "comment {comment}")?;
+    }
+    writeln!(writer, "element vertex {}", points.len())?;
+    writeln!(writer, "property float x")?;
+    writeln!(writer, "property float y")?;
+    writeln!(writer, "property float z")?;
+    writeln!(writer, "property float nx")?;
+    writeln!(writer, "property float ny")?;
+    writeln!(writer, "property float nz")?;
+    writeln!(writer, "end_header")?;
+    let mut buffer: Vec<u8> = Vec::new();
+    for point in points {
+        buffer.extend_from_slice(
+            &point
+                .pos
+                .to_array()
+                .iter()
+                .flat_map(|f| f.to_le_bytes())
+                .collect::<Vec<u8>>(),
+        );
+        buffer.extend_from_slice(
+            &point
+                .normal
+                .to_array()
+                .iter()
+                .flat_map(|f| f.to_le_bytes())
+                .collect::<Vec<u8>>(),
+        );
+    }
+    writer.write_all(&buffer)?;
+
+    Ok(())
+}
+
+/// Write the input points that are not vertices of the mesh to a PLY file.
+///
+/// # Errors
+///   Problems writing to file, or when the report does not describe a
+///   reconstruction of `points`.
+pub fn save_orphans<P: Borrow<Point>>(
+    path: &PathBuf,
+    points: &[P],
+    report: &Report,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let orphans = report
+        .orphans
+        .iter()
+        .map(|&i| points.get(i).map(|p| p.borrow().clone()))
+        .collect::<Option<_>>()
+        .ok_or("the report does not describe these points")?;
+    save_points_and_normals(path, &orphans)
+}
+
+/// Write a point cloud as an ASCII PLY file, with normals and colours, for
+/// a quick look at an intermediate stage such as a downsampled cloud or
+/// one with estimated normals.
+///
+/// Normals are written as zero when they are not known. Points are given
+/// their own colours when the cloud has them, and are otherwise coloured by
+/// normal, each component mapped from -1..1 onto 0..255, so a flipped
+/// normal stands out. Scalars are not written.
+///
+/// # Errors
+///   Problems writing to file.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn save_cloud_preview(path: &Path, cloud: &PointCloud) -> std::io::Result<()> {
+    let mut writer = create(path)?;
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format ascii 1.0")?;
+    writeln!(writer, "element vertex {}", cloud.len())?;
+    for property in ["x", "y", "z", "nx", "ny", "nz"] {
+        writeln!(writer, "property float {property}")?;
+    }
+    for property in ["red", "green", "blue"] {
+        writeln!(writer, "property uchar {property}")?;
+    }
+    writeln!(writer, "end_header")?;
+    for (i, pos) in cloud.positions.iter().enumerate() {
+        let normal = cloud.normal(i);
+        let color = cloud
+            .colors
+            .as_ref()
+            .map_or(normal * 0.5 + 0.5, |colors| colors[i]);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let [r, g, b] = color
+            .to_array()
+            .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        writeln!(
+            writer,
+            "{} {} {} {} {} {} {r} {g} {b}",
+            pos.x, pos.y, pos.z, normal.x, normal.y, normal.z
+        )?;
+    }
+    writer.flush()
+}
+
+// Writes a triangle mesh, with its faces indexing its vertices.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub(super) fn write_ply_mesh(
+    path: &Path,
+    triangles: &[Triangle],
+    options: &SaveOptions,
+) -> std::io::Result<()> {
+    let IndexedMesh { vertices, faces } = options.mesh(triangles);
+    let mut writer = create(path)?;
+    writeln!(writer, "ply")?;
+    match options.encoding {
+        Encoding::Binary => writeln!(writer, "format binary_little_endian 1.0")?,
+        Encoding::Ascii => writeln!(writer, "format ascii 1.0")?,
+    }
+    if let Some(provenance) = &options.provenance {
+        for comment in provenance.lines() {
+            writeln!(writer, "comment {comment}")?;
+        }
+    }
+    writeln!(writer, "element vertex {}", vertices.len())?;
+    writeln!(writer, "property float x")?;
+    writeln!(writer, "property float y")?;
+    writeln!(writer, "property float z")?;
+    writeln!(writer, "element face {}", faces.len())?;
+    writeln!(writer, "property list uchar int vertex_indices")?;
+    writeln!(writer, "end_header")?;
+    match options.encoding {
+        Encoding::Binary => {
+            for v in &vertices {
+                for f in v.to_array() {
+                    writer.write_all(&f.to_le_bytes())?;
+                }
+            }
+            for face in &faces {
+                writer.write_all(&[3])?;
+                for v in face {
+                    writer.write_all(&index(*v)?.to_le_bytes())?;
+                }
+            }
+        }
+        Encoding::Ascii => {
+            for v in &vertices {
+                writeln!(writer, "{}", options.xyz(*v))?;
+            }
+            for [a, b, c] in &faces {
+                writeln!(writer, "3 {a} {b} {c}")?;
+            }
+        }
+    }
+    writer.flush()
+}
+
+/// Return a point cloud stored in an ascii or binary PLY file.
+///
+/// Only the `vertex` element is read; faces and any other elements are
+/// skipped.
+///
+/// # Errors
+///   If the file cannot be opened or read, the header cannot be parsed, or a
+///   record is truncated or unreadable.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn load_ply(path: &PathBuf) -> std::io::Result<Vec<Point>> {
+    let file = std::fs::File::open(path)?;
+    read_ply(BufReader::new(file))
+}
+
+// Reads a PLY point cloud.
+pub fn read_ply<T: Read>(reader: BufReader<T>) -> std::io::Result<Vec<Point>> {
+    read_ply_with_colors(reader).map(|(points, _)| points)
+}
+
+/// Return a point cloud stored in a PLY file, with the colour of each point
+/// when the file has `red`, `green` and `blue` properties. See
+/// [`load_ply()`].
+///
+/// Colours are scaled to lie between 0 and 1, whether they are stored as
+/// bytes or floats.
+///
+/// # Errors
+///   If the file cannot be opened or read, the header cannot be parsed, or a
+///   record is truncated or unreadable.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn load_ply_with_colors(path: &PathBuf) -> std::io::Result<(Vec<Point>, Option<Vec<Vec3>>)> {
+    let file = std::fs::File::open(path)?;
+    read_ply_with_colors(BufReader::new(file))
+}
+
+pub(super) fn read_ply_with_colors<T: Read>(
+    reader: BufReader<T>,
+) -> std::io::Result<(Vec<Point>, Option<Vec<Vec3>>)> {
+    read_ply_cloud(reader).map(|cloud| (cloud.to_points(), cloud.colors))
+}
+
+pub(super) fn read_ply_cloud<T: Read>(mut reader: BufReader<T>) -> std::io::Result<PointCloud> {
+    let header = parse_ply_header(&mut reader)?;
+    debug!("{header:#?}");
+
+    let mut records = Records {
+        reader,
+        format: &header.format,
+        line: String::new(),
+    };
+    let mut cloud = PointCloud::default();
+    // Elements other than the vertices, such as faces, cameras or materials,
+    // are read past.
+    for element in &header.elements {
+        if element.name != "vertex" {
+            for _ in 0..element.count {
+                records.read(&element.properties)?;
+            }
+            continue;
+        }
+
+        let column = |names: &[&str]| {
+            element
+                .properties
+                .iter()
+                .position(|(label, _, _)| names.contains(&label.as_str()))
+        };
+        let [x, y, z, nx, ny, nz] =
+            [["x"], ["y"], ["z"], ["nx"], ["ny"], ["nz"]].map(|n| column(&n));
+        // The column and scale of each colour channel.
+        let channel =
+            |names: [&str; 2]| column(&names).map(|i| (i, element.properties[i].1.color_scale()));
+        let channels = if let [Some(r), Some(g), Some(b)] = [
+            channel(["red", "diffuse_red"]),
+            channel(["green", "diffuse_green"]),
+            channel(["blue", "diffuse_blue"]),
+        ] {
+            Some([r, g, b])
+        } else {
+            None
+        };
+        let has_normals = nx.is_some() || ny.is_some() || nz.is_some();
+        // The other single valued properties.
+        let mut used = vec![x, y, z, nx, ny, nz];
+        used.extend(channels.iter().flatten().map(|&(i, _)| Some(i)));
+        let scalars: Vec<usize> = (0..element.properties.len())
+            .filter(|i| !used.contains(&Some(*i)) && element.properties[*i].2.is_none())
+            .collect();
+
+        // The count is read from the file, so is not trusted to size the
+        // buffers beyond a million points.
+        let count = usize::try_from(element.count).map_or(0, |count| count.min(1 << 20));
+        let mut positions = Vec::with_capacity(count);
+        let mut normals = has_normals.then(|| Vec::with_capacity(count));
+        let mut colors = channels.map(|_| Vec::with_capacity(count));
+        let mut values_of: Vec<Vec<f32>> =
+            scalars.iter().map(|_| Vec::with_capacity(count)).collect();
+        for _ in 0..element.count {
+            let values = records.read(&element.properties)?;
+            #[allow(clippy::cast_possible_truncation)]
+            let value = |column: Option<usize>| column.map_or(0.0, |i| values[i] as f32);
+            positions.push(Vec3::new(value(x), value(y), value(z)));
+            if let Some(normals) = &mut normals {
+                normals.push(Vec3::new(value(nx), value(ny), value(nz)));
+            }
+            if let (Some(colors), Some(channels)) = (&mut colors, channels) {
+                #[allow(clippy::cast_possible_truncation)]
+                let [r, g, b] = channels.map(|(i, scale)| values[i] as f32 / scale);
+                colors.push(Vec3::new(r, g, b));
+            }
+            for (column, scalar) in scalars.iter().zip(&mut values_of) {
+                scalar.push(value(Some(*column)));
+            }
+        }
+        cloud = PointCloud {
+            positions,
+            normals,
+            colors,
+            scalars: scalars
+                .iter()
+                .map(|&i| element.properties[i].0.clone())
+                .zip(values_of)
+                .collect(),
+        };
+    }
+    info!("load_ply - extracted points");
+    Ok(cloud)
+}
+
+// Reads the records of a PLY file's elements, one at a time.
+struct Records<'a, T> {
+    reader: BufReader<T>,
+    format: &'a Format,
+    line: String,
+}
+
+impl<T: Read> Records<'_, T> {
+    // The value of each property of the next record. Lists are read past and
+    // given the value zero.
+    fn read(&mut self, properties: &[(String, Type, Option<Type>)]) -> std::io::Result<Vec<f64>> {
+        let truncated =
+            || std::io::Error::new(std::io::ErrorKind::InvalidData, "truncated PLY record");
+        match self.format {
+            Format::Ascii(_) => {
+                self.line.clear();
+                while self.line.trim().is_empty() {
+                    self.line.clear();
+                    if self.reader.read_line(&mut self.line)? == 0 {
+                        return Err(truncated());
+                    }
+                }
+                let mut tokens = self.line.split_whitespace();
+                let mut next = || -> std::io::Result<f64> {
+                    tokens.next().ok_or_else(truncated)?.parse().map_err(|_| {
+                        std::io::Error::new(std::io::ErrorKind::InvalidData, "unreadable PLY value")
+                    })
+                };
+                let mut values = Vec::with_capacity(properties.len());
+                for (_, _, list) in properties {
+                    if list.is_some() {
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        let n = next()? as usize;
+                        for _ in 0..n {
+                            next()?;
+                        }
+                        values.push(0.0);
+                    } else {
+                        values.push(next()?);
+                    }
+                }
+                Ok(values)
+            }
+            Format::BinaryLittleEndian(_) | Format::BinaryBigEndian(_) => {
+                let big_endian = matches!(self.format, Format::BinaryBigEndian(_));
+                let mut values = Vec::with_capacity(properties.len());
+                for (_, value_type, list) in properties {
+                    if let Some(count_type) = list {
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        let n = count_type.read(&mut self.reader, big_endian)? as usize;
+                        for _ in 0..n {
+                            value_type.read(&mut self.reader, big_endian)?;
+                        }
+                        values.push(0.0);
+                    } else {
+                        values.push(value_type.read(&mut self.reader, big_endian)?);
+                    }
+                }
+                Ok(values)
+            }
+        }
+    }
+}
+
+// The file type of the PLY file.
+//
+// Stores the version number of the format.
+#[derive(Debug)]
+#[allow(dead_code)]
+pub(super) enum Format {
+    Ascii(f32),
+    BinaryLittleEndian(f32),
+    BinaryBigEndian(f32),
+}
+
+/// Possible types of properties in a PLY file.
+///
+/// "The type can be specified with one of
+///   char uchar short ushort int uint float double,
+/// or one of
+///   int8 uint8 int16 uint16 int32 uint32 float32 float64"
+///
+/// As described here <https://en.wikipedia.org/wiki/PLY_(file_format)>
+#[derive(Debug)]
+pub(super) enum Type {
+    INT8,
+    Char,
+    Uint8,
+    Uchar,
+    Int16,
+    Short,
+    Uint16,
+    Int,
+    Int32,
+    Ushort,
+    Uint,
+    Uint32,
+    Float,
+    Float32,
+    Double,
+    Float64,
+}
+
+impl Type {
+    // Reads a binary value.
+    fn read<R: Read>(&self, reader: &mut R, big_endian: bool) -> std::io::Result<f64> {
+        let mut buffer = [0_u8; 8];
+        let bytes = &mut buffer[..self.size()];
+        reader.read_exact(bytes)?;
+        if big_endian {
+            bytes.reverse();
+        }
+        let [b0, b1, b2, b3, b4, b5, b6, b7] = buffer;
+        Ok(match self {
+            Self::INT8 | Self::Char => f64::from(i8::from_le_bytes([b0])),
+            Self::Uint8 | Self::Uchar => f64::from(b0),
+            Self::Int16 | Self::Short => f64::from(i16::from_le_bytes([b0, b1])),
+            Self::Uint16 | Self::Ushort => f64::from(u16::from_le_bytes([b0, b1])),
+            Self::Int | Self::Int32 => f64::from(i32::from_le_bytes([b0, b1, b2, b3])),
+            Self::Uint | Self::Uint32 => f64::from(u32::from_le_bytes([b0, b1, b2, b3])),
+            Self::Float | Self::Float32 => f64::from(f32::from_le_bytes([b0, b1, b2, b3])),
+            Self::Double | Self::Float64 => f64::from_le_bytes([b0, b1, b2, b3, b4, b5, b6, b7]),
+        })
+    }
+
+    // The size of a binary value in bytes.
+    const fn size(&self) -> usize {
+        match self {
+            Self::INT8 | Self::Char | Self::Uint8 | Self::Uchar => 1,
+            Self::Int16 | Self::Short | Self::Uint16 | Self::Ushort => 2,
+            Self::Int | Self::Int32 | Self::Uint | Self::Uint32 | Self::Float | Self::Float32 => 4,
+            Self::Double | Self::Float64 => 8,
+        }
+    }
+
+    // The value of a colour channel at full intensity.
+    const fn color_scale(&self) -> f32 {
+        match self {
+            Self::Float | Self::Float32 | Self::Double | Self::Float64 => 1.0,
+            Self::Int16 | Self::Short => 32767.0,
+            Self::Uint16 | Self::Ushort => 65535.0,
+            _ => 255.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct UnknownType;
+
+impl std::fmt::Display for UnknownType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown type")
+    }
+}
+
+impl Error for UnknownType {}
+
+///   char uchar short ushort int uint float double,
+/// or one of
+///   int8 uint8 int16 uint16 int32 uint32 float32 float64"
+///
+impl TryFrom<&str> for Type {
+    type Error = UnknownType;
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        match input {
+            "char" => Ok(Self::Char),
+            "int8" => Ok(Self::INT8),
+
+            "uchar" => Ok(Self::Uchar),
+            "uint8" => Ok(Self::Uint8),
+
+            "short" => Ok(Self::Short),
+            "int16" => Ok(Self::Int16),
+
+            "ushort" => Ok(Self::Ushort),
+            "uint16" => Ok(Self::Uint16),
+
+            "int" => Ok(Self::Int),
+            "int32" => Ok(Self::Int32),
+
+            "uint" => Ok(Self::Uint),
+            "uint32" => Ok(Self::Uint32),
+
+            "float" => Ok(Self::Float),
+            "float32" => Ok(Self::Float32),
+
+            "double" => Ok(Self::Double),
+            "float64" => Ok(Self::Float64),
+
+            _ => Err(UnknownType),
+        }
+    }
+}
+/// The header of a PLY file
+#[derive(Debug)]
+#[allow(dead_code)]
+pub(super) struct Header {
+    /// The format of the PLY file.
+    pub format: Format,
+    /// The elements, in the order their records appear in the data section.
+    pub elements: Vec<Element>,
+}
+
+/// A kind of record in a PLY file, such as `vertex` or `face`.
+#[derive(Debug)]
+pub(super) struct Element {
+    /// The name of the element.
+    pub name: String,
+    /// The number of records.
+    pub count: u64,
+    /// The columns of each record (label, type, `n_items_type`)
+    pub properties: Vec<(String, Type, Option<Type>)>,
+}
+
+#[derive(Debug)]
+pub(super) enum HeaderError {
+    InvalidFile,
+    Malformed,
+    Io(std::io::Error),
+}
+
+impl From<HeaderError> for std::io::Error {
+    fn from(error: HeaderError) -> Self {
+        match error {
+            HeaderError::InvalidFile => Self::new(
+                std::io::ErrorKind::InvalidData,
+                "not a ply file, or an unknown format",
+            ),
+            HeaderError::Malformed => Self::new(
+                std::io::ErrorKind::InvalidData,
+                "did not decode header correctly",
+            ),
+            HeaderError::Io(e) => e,
+        }
+    }
+}
+
+// Extract data from a PLY header
+//header format
+// ply
+// format ascii 1.0
+// comment This is a comment!
+// element vertex 779966
+// property float x
+// property float y
+// property float z
+// end_header
+//
+// The second line is one of
+// format ascii 1.0
+// format binary_little_endian 1.0
+// format binary_big_endian 1.0
+//
+pub(super) fn parse_ply_header<T>(buffer: &mut BufReader<T>) -> Result<Header, HeaderError>
+where
+    T: Read,
+{
+    info!("Reading header");
+    // Return error is the first line is not "ply"
+    let mut line = String::new();
+    buffer.read_line(&mut line).map_err(HeaderError::Io)?;
+
+    if !line.starts_with("ply") {
+        error!("Does not container the FILE descriptor of a ply file.");
+        return Err(HeaderError::InvalidFile);
+    }
+
+    let mut format: Option<Format> = None;
+    let mut elements: Vec<Element> = vec![];
+
+    for line in buffer.lines() {
+        let line = line.map_err(HeaderError::Io)?;
+        info!("parse_ply_header: loop");
+        let line = line.trim();
+        info!("parse_ply_header: loop {line}");
+        // If the line is "end_header", return the header
+        if line == "end_header" {
+            info!("end_header seen");
+            let Some(format) = format else {
+                error!("At the end of the header the format is unknown or invalid");
+                return Err(HeaderError::InvalidFile);
+            };
+            info!("Parsing header complete.");
+            return Ok(Header { format, elements });
+        }
+
+        if line.starts_with("comment") {
+            // Ignore comments
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let malformed = || {
+            error!("Failed to parse: {line}");
+            HeaderError::Malformed
+        };
+
+        if line.starts_with("element") {
+            // Extract the element name and record count
+            let [_, name, count] = parts[..] else {
+                return Err(malformed());
+            };
+            elements.push(Element {
+                name: name.to_owned(),
+                count: count.parse::<u64>().map_err(|_| malformed())?,
+                properties: vec![],
+            });
+            continue;
+        }
+
+        if line.starts_with("format") {
+            let [_, kind, version] = parts[..] else {
+                return Err(malformed());
+            };
+            let version = version.parse::<f32>().map_err(|_| malformed())?;
+            format = Some(match kind {
+                "ascii" => Format::Ascii(version),
+                "binary_little_endian" => Format::BinaryLittleEndian(version),
+                "binary_big_endian" => Format::BinaryBigEndian(version),
+                _ => {
+                    error!("unrecognised format string");
+                    return Err(HeaderError::InvalidFile);
+                }
+            });
+        }
+
+        if line.starts_with("property") {
+            // Extract the property
+            debug!("Property: {line}");
+            let Some(element) = elements.last_mut() else {
+                error!("property declared before any element");
+                return Err(HeaderError::Malformed);
+            };
+            let parse_type = |name: &str| {
+                Type::try_from(name).map_err(|e| {
+                    error!("{e}");
+                    HeaderError::Malformed
+                })
+            };
+            match parts[..] {
+                [_, "list", n_items, prop_type, label] => {
+                    debug!("n_items {n_items}");
+                    let n_item_type = parse_type(n_items)?;
+                    let prop_type = parse_type(prop_type)?;
+                    element
+                        .properties
+                        .push((label.to_owned(), prop_type, Some(n_item_type)));
+                }
+                [_, prop_type, label] if prop_type != "list" => {
+                    let prop_type = parse_type(prop_type)?;
+                    // Dummy n_items type (uint32) as the values will be 1.
+                    element.properties.push((label.to_owned(), prop_type, None));
+                }
+                _ => return Err(malformed()),
+            }
+        }
+    }
+
+    Err(HeaderError::Malformed)
+}
@@ -0,0 +1,142 @@
+use std::io::Write;
+use std::path::Path;
+
+use glam::Vec3;
+
+use super::create;
+use super::scalars::index;
+use crate::bvh::Bvh;
+use crate::topology::IndexedMesh;
+use crate::{Point, Triangle};
+
+/// A measure of each triangle of a mesh, to colour it by in
+/// [`save_quality_ply()`].
+#[derive(Clone, Copy, Debug)]
+pub enum FaceMetric<'a> {
+    /// The longest edge times the perimeter, over 4√3 times the area: one
+    /// for an equilateral triangle, growing as it thins into a sliver, and
+    /// infinite when it has no area.
+    AspectRatio,
+    /// The area of the triangle.
+    Area,
+    /// The furthest any of these points lies from the triangle, among the
+    /// points closer to it than to any other triangle. Zero where no point
+    /// is nearest, as across a filled hole.
+    Distance(&'a [Point]),
+}
+
+impl FaceMetric<'_> {
+    /// The metric of each triangle, in order.
+    #[must_use]
+    pub fn evaluate(&self, triangles: &[Triangle]) -> Vec<f32> {
+        match self {
+            Self::AspectRatio => triangles.iter().map(aspect_ratio).collect(),
+            Self::Area => triangles
+                .iter()
+                .map(|Triangle([a, b, c])| (*b - *a).cross(*c - *a).length() / 2.0)
+                .collect(),
+            Self::Distance(points) => {
+                let bvh = Bvh::new(triangles);
+                let mut furthest = vec![0.0_f32; triangles.len()];
+                for point in *points {
+                    if let Some(closest) = bvh.closest_point(point.pos) {
+                        let d = &mut furthest[closest.face];
+                        *d = d.max(closest.distance);
+                    }
+                }
+                furthest
+            }
+        }
+    }
+}
+
+fn aspect_ratio(Triangle([a, b, c]): &Triangle) -> f32 {
+    let edges = [b.distance(*a), c.distance(*b), a.distance(*c)];
+    let longest = edges.iter().copied().fold(0.0, f32::max);
+    let perimeter: f32 = edges.iter().sum();
+    let area = (*b - *a).cross(*c - *a).length() / 2.0;
+    if area > 0.0 {
+        longest * perimeter / (4.0 * 3.0_f32.sqrt() * area)
+    } else {
+        f32::INFINITY
+    }
+}
+
+/// Write triangles as a binary PLY file, sharing vertices between
+/// triangles, with each face coloured by `metric` for visual inspection.
+///
+/// Colours run from blue at the lowest finite value, through green, to red
+/// at the highest. Infinite and missing values, such as the aspect ratio of
+/// a triangle with no area, are red too. The values are also written as
+/// the `quality` property of each face, which `MeshLab` reads.
+///
+/// # Errors
+///   When the mesh has too many vertices for a PLY file, or the file
+///   cannot be created or written to.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn save_quality_ply(
+    path: &Path,
+    triangles: &[Triangle],
+    metric: FaceMetric<'_>,
+) -> std::io::Result<()> {
+    let values = metric.evaluate(triangles);
+    let (low, high) = values
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(low, high), v| {
+            (low.min(v), high.max(v))
+        });
+
+    let mesh = IndexedMesh::new(triangles);
+    let mut writer = create(path)?;
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format binary_little_endian 1.0")?;
+    writeln!(writer, "element vertex {}", mesh.vertices.len())?;
+    writeln!(writer, "property float x")?;
+    writeln!(writer, "property float y")?;
+    writeln!(writer, "property float z")?;
+    writeln!(writer, "element face {}", mesh.faces.len())?;
+    writeln!(writer, "property list uchar int vertex_indices")?;
+    writeln!(writer, "property uchar red")?;
+    writeln!(writer, "property uchar green")?;
+    writeln!(writer, "property uchar blue")?;
+    writeln!(writer, "property float quality")?;
+    writeln!(writer, "end_header")?;
+    for v in &mesh.vertices {
+        for f in v.to_array() {
+            writer.write_all(&f.to_le_bytes())?;
+        }
+    }
+    for (face, value) in mesh.faces.iter().zip(values) {
+        writer.write_all(&[3])?;
+        for v in face {
+            writer.write_all(&index(*v)?.to_le_bytes())?;
+        }
+        let t = if value.is_finite() && high > low {
+            (value - low) / (high - low)
+        } else if value.is_finite() {
+            0.0
+        } else {
+            1.0
+        };
+        writer.write_all(&color(t))?;
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+// Blue at zero, green at a half and red at one.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn color(t: f32) -> [u8; 3] {
+    let t = t.clamp(0.0, 1.0);
+    let rgb = if t < 0.5 {
+        Vec3::new(0.0, t * 2.0, 1.0 - t * 2.0)
+    } else {
+        Vec3::new(t * 2.0 - 1.0, 2.0 - t * 2.0, 0.0)
+    };
+    (rgb * 255.0).round().to_array().map(|c| c as u8)
+}
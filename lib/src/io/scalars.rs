@@ -0,0 +1,140 @@
+use std::io::Write;
+use std::path::Path;
+
+use super::create;
+use crate::topology::IndexedMesh;
+
+/// Write an indexed mesh as a binary PLY file, with a float property on
+/// each vertex for every named scalar.
+///
+/// The scalars, such as the mean and Gaussian curvature from
+/// [`IndexedMesh::curvature()`], let tools like `MeshLab` or
+/// `CloudCompare` colour the surface.
+///
+/// # Errors
+///   When a scalar does not have one value per vertex, or its name is not a
+///   single word, and when the file cannot be created or written to.
+#[cfg(feature = "ply")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn save_ply_with_scalars(
+    path: &Path,
+    mesh: &IndexedMesh,
+    scalars: &[(&str, &[f32])],
+) -> std::io::Result<()> {
+    check(mesh, scalars)?;
+    let mut writer = create(path)?;
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format binary_little_endian 1.0")?;
+    writeln!(writer, "element vertex {}", mesh.vertices.len())?;
+    writeln!(writer, "property float x")?;
+    writeln!(writer, "property float y")?;
+    writeln!(writer, "property float z")?;
+    for (name, _) in scalars {
+        writeln!(writer, "property float {name}")?;
+    }
+    writeln!(writer, "element face {}", mesh.faces.len())?;
+    writeln!(writer, "property list uchar int vertex_indices")?;
+    writeln!(writer, "end_header")?;
+    for (i, v) in mesh.vertices.iter().enumerate() {
+        for f in v.to_array() {
+            writer.write_all(&f.to_le_bytes())?;
+        }
+        for (_, values) in scalars {
+            writer.write_all(&values[i].to_le_bytes())?;
+        }
+    }
+    for face in &mesh.faces {
+        writer.write_all(&[3])?;
+        for v in face {
+            writer.write_all(&index(*v)?.to_le_bytes())?;
+        }
+    }
+    writer.flush()
+}
+
+/// Write an indexed mesh as a legacy ascii VTK polydata file, with a
+/// point data array for every named scalar, for `ParaView` and other VTK
+/// based tools.
+///
+/// # Errors
+///   When a scalar does not have one value per vertex, or its name is not a
+///   single word, and when the file cannot be created or written to.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn save_vtk_with_scalars(
+    path: &Path,
+    mesh: &IndexedMesh,
+    scalars: &[(&str, &[f32])],
+) -> std::io::Result<()> {
+    check(mesh, scalars)?;
+    let mut writer = create(path)?;
+    writeln!(writer, "# vtk DataFile Version 3.0")?;
+    writeln!(writer, "bpa_rs")?;
+    writeln!(writer, "ASCII")?;
+    writeln!(writer, "DATASET POLYDATA")?;
+    writeln!(writer, "POINTS {} float", mesh.vertices.len())?;
+    for v in &mesh.vertices {
+        writeln!(writer, "{} {} {}", v.x, v.y, v.z)?;
+    }
+    // Each polygon is preceded by its number of vertices.
+    writeln!(
+        writer,
+        "POLYGONS {} {}",
+        mesh.faces.len(),
+        mesh.faces.len() * 4
+    )?;
+    for [a, b, c] in &mesh.faces {
+        writeln!(writer, "3 {a} {b} {c}")?;
+    }
+    if !scalars.is_empty() {
+        writeln!(writer, "POINT_DATA {}", mesh.vertices.len())?;
+    }
+    for (name, values) in scalars {
+        writeln!(writer, "SCALARS {name} float 1")?;
+        writeln!(writer, "LOOKUP_TABLE default")?;
+        for value in *values {
+            writeln!(writer, "{value}")?;
+        }
+    }
+    writer.flush()
+}
+
+// Checks that each scalar has a value per vertex, and a name that can be
+// written in a header.
+fn check(mesh: &IndexedMesh, scalars: &[(&str, &[f32])]) -> std::io::Result<()> {
+    for (name, values) in scalars {
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("the scalar name {name:?} is not a single word"),
+            ));
+        }
+        if values.len() != mesh.vertices.len() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "the scalar {name} has {} values for {} vertices",
+                    values.len(),
+                    mesh.vertices.len()
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// PLY stores the vertex indices as signed 32 bit integers.
+#[cfg(feature = "ply")]
+pub(super) fn index(v: usize) -> std::io::Result<i32> {
+    i32::try_from(v).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "too many vertices for a PLY file",
+        )
+    })
+}
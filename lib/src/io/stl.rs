@@ -0,0 +1,221 @@
+use core::borrow::Borrow;
+use std::io::BufWriter;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
+
+use glam::Vec3;
+
+use super::Encoding;
+use super::SaveOptions;
+use super::stream::reconstruct_streaming;
+use super::write_stl_ascii;
+use crate::ReconstructionConfig;
+use crate::provenance::Provenance;
+use crate::report::Report;
+use crate::{Point, Triangle};
+
+// Normal, three vertices and a two byte attribute count.
+pub const STL_FACET_SIZE: usize = 50;
+
+/// Write triangles to file.
+///
+/// # Errors
+///   When the file cannot be created or written to, or the number of
+///   triangles exceeds that allowed by the stl format.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn save_triangles(path: &Path, triangles: &[Triangle]) -> std::io::Result<()> {
+    write_stl(path, triangles, &SaveOptions::default())
+}
+
+/// Write triangles to file, recording how they were produced in the header.
+///
+/// # Errors
+///   When the file cannot be created or written to, or the number of
+///   triangles exceeds that allowed by the stl format.
+pub fn save_triangles_with_provenance(
+    path: &Path,
+    triangles: &[Triangle],
+    provenance: &Provenance,
+) -> std::io::Result<()> {
+    let options = SaveOptions::default().with_provenance(provenance.clone());
+    write_stl(path, triangles, &options)
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub(super) fn write_stl(
+    path: &Path,
+    triangles: &[Triangle],
+    options: &SaveOptions,
+) -> std::io::Result<()> {
+    if options.encoding == Encoding::Ascii {
+        return write_stl_ascii(path, triangles, options);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let file = std::fs::File::create(path)?;
+
+    let mut writer = BufWriter::new(file);
+
+    let header = options
+        .provenance
+        .as_ref()
+        .map_or([b' '; 80], Provenance::stl_header);
+    writer.write_all(&header)?;
+
+    let count = u32::try_from(triangles.len()).map_err(|_| {
+        std::io::Error::other("stl file format cannot contain more than 4,294,967,295 triangles")
+    })?;
+    writer.write_all(&count.to_le_bytes())?;
+
+    for t in triangles {
+        writer.write_all(&stl_facet(&options.triangle(t)))?;
+    }
+
+    Ok(())
+}
+
+/// Reconstructs a mesh, writing it as a binary STL file as it is generated.
+///
+/// Triangles are passed in batches to a writer thread, so the file is
+/// written while the reconstruction is still running. The triangle count in
+/// the header is filled in once the reconstruction is complete.
+///
+/// Returns a report on the reconstruction. When a budget is exceeded the
+/// partial mesh is written.
+///
+/// # Errors
+///   When the file cannot be created or written to, or the mesh exceeds the
+///   number of triangles allowed by the stl format. When no mesh can be
+///   generated the error wraps a [`crate::ReconstructError`], and an empty STL file
+///   is still written.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn reconstruct_to_stl<P: Borrow<Point>>(
+    path: &Path,
+    points: &[P],
+    config: &ReconstructionConfig,
+) -> std::io::Result<Report> {
+    reconstruct_streaming(path, points, config, write_stl_batches)
+}
+
+// Writes each batch as it arrives, then patches the triangle count.
+fn write_stl_batches(
+    file: std::fs::File,
+    batches: &Receiver<Vec<Triangle>>,
+) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(file);
+    writer.write_all(&[b' '; 80])?;
+    writer.write_all(&0_u32.to_le_bytes())?;
+
+    let mut count = 0_u32;
+    for batch in batches {
+        count = u32::try_from(batch.len())
+            .ok()
+            .and_then(|len| count.checked_add(len))
+            .ok_or_else(|| {
+                std::io::Error::other(
+                    "stl file format cannot contain more than 4,294,967,295 triangles",
+                )
+            })?;
+        for t in &batch {
+            writer.write_all(&stl_facet(t))?;
+        }
+    }
+
+    let mut file = writer
+        .into_inner()
+        .map_err(std::io::IntoInnerError::into_error)?;
+    file.seek(SeekFrom::Start(80))?;
+    file.write_all(&count.to_le_bytes())
+}
+
+// Encodes a binary STL facet without touching the heap.
+//
+// The attribute count (the last two bytes) is always zero.
+pub fn stl_facet(t: &Triangle) -> [u8; STL_FACET_SIZE] {
+    let normal = (t.0[0] - t.0[1]).cross(t.0[0] - t.0[2]).normalize();
+    let mut facet = [0_u8; STL_FACET_SIZE];
+    let floats = normal
+        .to_array()
+        .into_iter()
+        .chain(t.0.iter().flat_map(Vec3::to_array));
+    for (bytes, f) in facet.chunks_exact_mut(4).zip(floats) {
+        bytes.copy_from_slice(&f.to_le_bytes());
+    }
+    facet
+}
+
+/// Return the triangles stored in a STL file, in binary or ascii format.
+///
+/// # Errors
+///   If the file cannot be read, or is not a valid STL file.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn load_stl(path: &PathBuf) -> std::io::Result<Vec<Triangle>> {
+    let bytes = std::fs::read(path)?;
+    let invalid = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+
+    // Binary files may also start with "solid", so trust the size first.
+    let binary_count = bytes
+        .get(80..84)
+        .map(|count| u32::from_le_bytes(count.try_into().unwrap_or_default()) as usize);
+    if let Some(count) = binary_count
+        && count
+            .checked_mul(STL_FACET_SIZE)
+            .and_then(|n| n.checked_add(84))
+            == Some(bytes.len())
+    {
+        return Ok(bytes[84..]
+            .chunks_exact(STL_FACET_SIZE)
+            .map(|facet| {
+                let f = |i: usize| {
+                    let at = 12 + 4 * i;
+                    f32::from_le_bytes([facet[at], facet[at + 1], facet[at + 2], facet[at + 3]])
+                };
+                Triangle([0, 3, 6].map(|i| Vec3::new(f(i), f(i + 1), f(i + 2))))
+            })
+            .collect());
+    }
+
+    let text = core::str::from_utf8(&bytes).map_err(|_| invalid("not a valid STL file"))?;
+    if !text.trim_start().starts_with("solid") {
+        return Err(invalid("not a valid STL file"));
+    }
+    let mut triangles = vec![];
+    let mut vertices = vec![];
+    for line in text.lines() {
+        let mut parts = line.split_whitespace();
+        if parts.next() != Some("vertex") {
+            continue;
+        }
+        let coords: Vec<f32> = parts
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid("unreadable STL vertex"))?;
+        let [x, y, z] = coords[..] else {
+            return Err(invalid("unreadable STL vertex"));
+        };
+        vertices.push(Vec3::new(x, y, z));
+        if let [a, b, c] = vertices[..] {
+            triangles.push(Triangle([a, b, c]));
+            vertices.clear();
+        }
+    }
+    Ok(triangles)
+}
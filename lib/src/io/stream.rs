@@ -0,0 +1,41 @@
+use core::borrow::Borrow;
+use std::path::Path;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::sync_channel;
+
+use crate::ReconstructionConfig;
+use crate::reconstruct_batches;
+use crate::report::Report;
+use crate::{Point, Triangle};
+
+// Number of triangles handed to the writer thread at a time.
+const STREAM_BATCH_SIZE: usize = 4096;
+
+// Reconstructs the mesh while `write` saves each batch of triangles to the
+// file at `path` on another thread.
+pub fn reconstruct_streaming<P: Borrow<Point>>(
+    path: &Path,
+    points: &[P],
+    config: &ReconstructionConfig,
+    write: fn(std::fs::File, &Receiver<Vec<Triangle>>) -> std::io::Result<()>,
+) -> std::io::Result<Report> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::File::create(path)?;
+
+    // One batch in flight while the next is being filled.
+    let (sender, receiver) = sync_channel(1);
+    let writer = std::thread::spawn(move || write(file, &receiver));
+
+    let report = reconstruct_batches(points, config, STREAM_BATCH_SIZE, &mut |batch| {
+        // A failed send means the writer has stopped; its error is reported below.
+        let _ = sender.send(batch);
+    });
+    drop(sender);
+
+    writer
+        .join()
+        .map_err(|_| std::io::Error::other("mesh writer thread panicked"))??;
+    report.map_err(std::io::Error::other)
+}
@@ -0,0 +1,155 @@
+use std::io::BufRead;
+use std::path::Path;
+
+use glam::Vec3;
+
+use crate::Point;
+
+/// Which columns of a `.xyz` or `.pts` file hold the normal and the extra
+/// attributes.
+///
+/// Every line holds a position, `x y z`, in the first three columns. By
+/// default the normal follows in the next three, `x y z nx ny nz`. Other
+/// columns are ignored unless they are named here, counting from zero, so
+/// `x y z nx ny nz r g b` has its colour at column 6, and a `.pts` file,
+/// `x y z intensity r g b`, has no normal, its intensity at column 3 and
+/// its colour at column 4.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct XyzOptions {
+    /// The first of three columns holding the normal, or `None` for a cloud
+    /// without normals, whose normals are left zero and so unknown.
+    pub normal: Option<usize>,
+    /// The first of three columns holding a red, green and blue colour.
+    pub color: Option<usize>,
+    /// The column holding the scanner's intensity.
+    pub intensity: Option<usize>,
+}
+
+impl Default for XyzOptions {
+    fn default() -> Self {
+        Self {
+            normal: Some(3),
+            color: None,
+            intensity: None,
+        }
+    }
+}
+
+/// A point cloud read from a `.xyz` or `.pts` file, with the extra columns
+/// chosen in [`XyzOptions`].
+#[derive(Clone, Debug, Default)]
+pub struct XyzCloud {
+    /// The points, in file order.
+    pub points: Vec<Point>,
+    /// The colour of each point, between zero and one, when
+    /// [`XyzOptions::color`] is set. Colours written as 0 to 255 are scaled
+    /// down, which is assumed when any channel in the file is above one.
+    pub colors: Option<Vec<Vec3>>,
+    /// The intensity of each point, as written, when
+    /// [`XyzOptions::intensity`] is set.
+    pub intensities: Option<Vec<f32>>,
+}
+
+impl XyzCloud {
+    pub(crate) fn new(options: &XyzOptions) -> Self {
+        Self {
+            points: vec![],
+            colors: options.color.map(|_| vec![]),
+            intensities: options.intensity.map(|_| vec![]),
+        }
+    }
+
+    // Parses "x y z", the normal and any extra columns.
+    //
+    // Blank lines, lines starting with `#` and lines with fewer than three
+    // columns, such as the point count heading a `.pts` file, are skipped.
+    pub(crate) fn parse_line(&mut self, line: &str, options: &XyzOptions) -> Result<(), String> {
+        if line.trim_start().starts_with('#') {
+            return Ok(());
+        }
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        if columns.len() < 3 {
+            return Ok(());
+        }
+        let value = |i: usize| -> Result<f32, String> {
+            let text = columns
+                .get(i)
+                .ok_or_else(|| format!("expected at least {} columns", i + 1))?;
+            text.parse()
+                .map_err(|e| format!("cannot read column {}, {text:?}: {e}", i + 1))
+        };
+        let vec3 = |i: usize| -> Result<Vec3, String> {
+            Ok(Vec3::new(value(i)?, value(i + 1)?, value(i + 2)?))
+        };
+
+        self.points.push(Point {
+            pos: vec3(0)?,
+            normal: options.normal.map_or(Ok(Vec3::ZERO), vec3)?,
+        });
+        if let (Some(colors), Some(i)) = (&mut self.colors, options.color) {
+            colors.push(vec3(i)?);
+        }
+        if let (Some(intensities), Some(i)) = (&mut self.intensities, options.intensity) {
+            intensities.push(value(i)?);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    pub(crate) fn extend(&mut self, other: Self) {
+        self.points.extend(other.points);
+        if let (Some(colors), Some(other)) = (&mut self.colors, other.colors) {
+            colors.extend(other);
+        }
+        if let (Some(intensities), Some(other)) = (&mut self.intensities, other.intensities) {
+            intensities.extend(other);
+        }
+    }
+
+    // Scales 0 to 255 colours down to between zero and one.
+    pub(crate) fn finish(mut self) -> Self {
+        if let Some(colors) = &mut self.colors
+            && colors.iter().any(|c| c.max_element() > 1.0)
+        {
+            for c in colors.iter_mut() {
+                *c /= 255.0;
+            }
+        }
+        self
+    }
+}
+
+/// Return a point cloud stored in a `.xyz` or `.pts` file, keeping the extra
+/// columns chosen in `options`.
+///
+/// Lines may end in CRLF, values may use scientific notation, and lines
+/// starting with `#` are comments.
+///
+/// # Errors
+///   If the file cannot be opened or read, or a line has a value that cannot
+///   be read.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(path = %path.display()))
+)]
+pub fn load_xyz_with_options(path: &Path, options: &XyzOptions) -> std::io::Result<XyzCloud> {
+    let file = std::fs::File::open(path)?;
+    read_xyz(std::io::BufReader::new(file), options)
+}
+
+fn read_xyz<R: BufRead>(reader: R, options: &XyzOptions) -> std::io::Result<XyzCloud> {
+    let mut cloud = XyzCloud::new(options);
+    for (i, line) in reader.lines().enumerate() {
+        cloud
+            .parse_line(&line?, options)
+            .map_err(|e| invalid_line(i + 1, &e))?;
+    }
+    Ok(cloud.finish())
+}
+
+pub fn invalid_line(number: usize, message: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("line {number}: {message}"),
+    )
+}
@@ -7,44 +7,206 @@
 #![warn(missing_debug_implementations)]
 #![warn(missing_docs)]
 #![allow(clippy::many_single_char_names)]
+#![cfg_attr(not(feature = "std"), no_std)]
 //! Surface reconstruction using the Ball Pivoting Algorithm.
 //!
 //! Converts a point cloud into a STL mesh.
+//!
+//! Bad input, such as an unusable radius, a cloud of NaNs or a corrupt file,
+//! is reported as an error rather than a panic, so a long running service
+//! can carry on. The exceptions are the files written with the
+//! `debug-dumps` feature, and indices out of range that were not returned by
+//! this library.
+//!
+//! The `std` feature is on by default. Without it the reconstruction itself
+//! builds with `no_std` and `alloc`, for targets with a custom runtime; the
+//! `libm` feature then supplies the float maths. File IO, tracing, time
+//! budgets, and the modules that measure or compare clouds and meshes need
+//! `std`.
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("bpa_rs needs the `std` or `libm` feature for its float maths");
 
+/// Geometric properties of point clouds, such as curvature and density, and
+/// spheres and planes fitted to calibration scans.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub mod analysis;
+/// Async loaders and savers, for use with tokio.
+///
+/// Requires the `tokio` feature.
+#[cfg(feature = "tokio")]
+pub mod async_io;
+/// Closest point, distance and ray queries on a mesh.
+pub mod bvh;
+mod cloud;
+/// Compact storage for clouds too large to hold at full precision.
+pub mod compact;
+/// Parameters controlling a reconstruction.
+pub mod config;
+/// Comparing meshes, triangle by triangle or by the distance between their
+/// surfaces.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub mod diff;
+mod dump;
+/// Errors returned by a reconstruction.
+pub mod error;
+mod front;
 /// Stores the point cloud, helper functions and the main algorithm.
-pub mod grid;
+pub(crate) mod grid;
 /// Load and Save points and meshes.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
 pub mod io;
+mod math;
 /// Internal structures for Points, Edges and Faces.
-pub mod mesh;
-#[cfg(test)]
+pub(crate) mod mesh;
+/// Orienting the normals of a point cloud toward the scanner.
+pub mod normals;
+/// Multi-threaded reconstruction.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub mod parallel;
+/// The building blocks of the algorithm, for implementing variants of it.
+///
+/// A reconstruction buckets the points into a [`pipeline::Grid`], finds a
+/// seed triangle with [`pipeline::find_seed_triangle()`] and seeds a
+/// [`pipeline::Front`] with [`pipeline::open_front()`]. Then, while the front
+/// has an active edge, [`pipeline::ball_pivot()`] finds the next point: a new
+/// triangle is added with [`pipeline::join()`], and any coincident edges are
+/// removed with [`pipeline::glue()`]. Edges the ball cannot pivot around are
+/// marked as boundaries.
+///
+/// To test a step on its own, [`pipeline::open_loop()`] builds a front of
+/// any shape, and [`pipeline::Front::loops()`] shows how its edges are
+/// linked afterwards.
+///
+/// Requires the `unstable` feature. These items may change in any release.
+#[cfg(feature = "unstable")]
+pub mod pipeline;
+/// A point cloud with optional normals, colours and scalars, and removing
+/// duplicate points.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub mod point_cloud;
+/// Repairs applied to a finished mesh.
+pub mod postprocess;
+/// Quick drafts of a mesh from a downsampled cloud.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub mod preview;
+/// Metadata recording how an output file was produced.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub mod provenance;
+/// Offscreen rendering of meshes into PNG images, for previews.
+///
+/// Requires the `render` feature.
+#[cfg(feature = "render")]
+pub mod render;
+/// Statistics describing a reconstruction.
+pub mod report;
+/// Small point clouds built into the library, for examples, fuzzers and
+/// benchmarks that should not depend on files.
+///
+/// Requires the `sample-data` feature.
+#[cfg(feature = "sample-data")]
+pub mod samples;
+/// Scattering points over the surface of a mesh, for synthetic clouds and
+/// for comparing a cloud with a mesh.
+pub mod sampling;
+/// Choosing a ball radius from the spacing of the points.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub mod spacing;
+#[cfg(all(test, feature = "std"))]
 mod test;
+/// Shared vertices and the adjacency of the faces of a mesh.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub mod topology;
+mod trace;
+/// Units of length, for scaling meshes on export.
+pub mod units;
 
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
 use core::cell::RefCell;
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "debug-dumps")]
 use std::path::PathBuf;
-use std::rc::Rc;
-use std::vec;
+#[cfg(feature = "std")]
+use std::time::Instant;
 
+use cloud::Cloud;
+use cloud::Soa;
+use cloud::WithContext;
+use config::CompatMode;
+use config::NormalDisagreement;
+pub use config::ReconstructionConfig;
+use config::Winding;
+use dump::Dumps;
+pub use error::ReconstructError;
+use front::Front;
 use glam::Vec3;
 use grid::Grid;
 use grid::SeedResult;
-use grid::ball_pivot;
+use grid::bounds;
+use grid::compute_ball_center;
 use grid::find_reverse_edge_on_front;
-use grid::find_seed_triangle;
-use grid::get_active_edge;
-use grid::glue;
+use grid::find_seed_triangle_until;
+use grid::glue_case;
+use grid::grid_dims;
 use grid::join;
+use grid::min_radius_for;
 use grid::not_used;
 use grid::on_front;
+use grid::open_front;
 use grid::output_triangle;
+use grid::traced_ball_pivot;
+#[cfg(feature = "std")]
+pub use io::reconstruct_file;
+#[cfg(feature = "debug-dumps")]
 use io::save_points;
+#[cfg(feature = "debug-dumps")]
 use io::save_triangles_ascii;
+use log::warn;
 use mesh::EdgeStatus;
 use mesh::MeshEdge;
 use mesh::MeshFace;
 use mesh::MeshPoint;
-
-const DEBUG: bool = false;
+use mesh::sanitize_normal;
+use postprocess::Orientation;
+use postprocess::keep_largest_component;
+use postprocess::orient;
+use postprocess::remove_slivers;
+use postprocess::remove_small_components;
+use report::Budget;
+use report::Diagnostics;
+use report::PivotRecord;
+use report::Progress;
+use report::Report;
+use report::SeedRejections;
+use trace::Candidate;
+use trace::Trace;
 
 // Why  Rc<RefCell<MeshPoint>>?
 //
@@ -62,7 +224,7 @@ const DEBUG: bool = false;
 type Cell = Vec<Rc<RefCell<MeshPoint>>>;
 
 /// A series of Points
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Triangle(pub [Vec3; 3]);
 
 impl Triangle {
@@ -70,10 +232,41 @@ impl Triangle {
         let cross = (self.0[0] - self.0[1]).cross(self.0[0] - self.0[2]);
         cross.normalize()
     }
+
+    /// Reverses the winding, and so the facet normal.
+    pub const fn flip(&mut self) {
+        self.0.swap(1, 2);
+    }
+
+    /// The vertices, in winding order.
+    pub fn iter(&self) -> core::slice::Iter<'_, Vec3> {
+        self.0.iter()
+    }
+}
+
+impl From<Triangle> for [[f32; 3]; 3] {
+    fn from(t: Triangle) -> Self {
+        t.0.map(Into::into)
+    }
+}
+
+impl From<[[f32; 3]; 3]> for Triangle {
+    fn from(vertices: [[f32; 3]; 3]) -> Self {
+        Self(vertices.map(Vec3::from_array))
+    }
+}
+
+impl<'a> IntoIterator for &'a Triangle {
+    type Item = &'a Vec3;
+    type IntoIter = core::slice::Iter<'a, Vec3>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 /// Base primitive for triangles and meshes.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Point {
     /// Position of the point
     pub pos: Vec3,
@@ -81,145 +274,1075 @@ pub struct Point {
     pub normal: Vec3,
 }
 
+/// A mesh and a report on how it was built.
+#[derive(Debug)]
+pub struct Reconstruction {
+    /// The output mesh.
+    pub triangles: Vec<Triangle>,
+    /// Statistics describing the reconstruction.
+    pub report: Report,
+}
+
+impl Reconstruction {
+    /// Puts the triangles in a deterministic order. See
+    /// [`postprocess::canonicalize()`]. Any pivot records follow their
+    /// triangles.
+    pub fn canonicalize(&mut self) {
+        let pivots = pivots_by_face(&self.triangles, &self.report.pivots);
+        postprocess::canonicalize(&mut self.triangles);
+        realign_pivots(&self.triangles, pivots.as_ref(), &mut self.report.pivots);
+    }
+
+    /// Joins the triangles at their shared vertices, for the adjacency of the
+    /// faces. See [`topology::IndexedMesh`].
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn to_indexed(&self) -> topology::IndexedMesh {
+        topology::IndexedMesh::new(&self.triangles)
+    }
+
+    /// Builds a tree over the triangles for spatial queries, such as the
+    /// distance from the input points to the mesh.
+    #[must_use]
+    pub fn build_bvh(&self) -> bvh::Bvh {
+        bvh::Bvh::new(&self.triangles)
+    }
+
+    /// Draws the mesh as seen by the camera into a PNG image, `size` pixels
+    /// wide and high. See [`render::render_png()`].
+    ///
+    /// Requires the `render` feature.
+    #[cfg(feature = "render")]
+    #[must_use]
+    pub fn render_png(&self, camera: &render::Camera, size: (u32, u32)) -> Vec<u8> {
+        render::render_png(&self.triangles, camera, size)
+    }
+}
+
 /// Returns a mesh from a point cloud.
 ///
 /// Main entry point for this library.
 ///
+/// The points can be owned or borrowed, such as `&[Point]`, `&[&Point]` or
+/// `&[Rc<Point>]`. The bounds of the cloud are needed before the points are
+/// bucketed, so a slice is taken rather than an iterator; collect points from
+/// an iterator into a `Vec<&Point>` to avoid copying them.
+///
 /// # Panics
 ///  (Debug ONLY) File system issues when `saving_points()`'s or `saving_triangle()`'s
 #[must_use]
-pub fn reconstruct(points: &[Point], radius: f32) -> Option<Vec<Triangle>> {
-    let mut grid = Grid::new(points, radius);
-
-    match find_seed_triangle(&grid, radius) {
-        None => {
-            eprintln!("No seed triangle found");
-            None
-        }
-        Some(SeedResult { f, ball_center }) => {
-            let mut triangles: Vec<Triangle> = Vec::new();
-            let mut edges: Vec<Rc<RefCell<MeshEdge>>> = Vec::new();
-            output_triangle(&f, &mut triangles);
-
-            let seed = f.0;
-
-            let e0 = Rc::new(RefCell::new(MeshEdge::new(
-                &seed[0],
-                &seed[1],
-                &seed[2].clone(),
-                ball_center,
-            )));
-            edges.push(e0.clone());
-
-            let e1 = Rc::new(RefCell::new(MeshEdge::new(
-                &seed[1],
-                &seed[2],
-                &seed[0].clone(),
-                ball_center,
-            )));
-            edges.push(e1.clone());
-
-            let e2 = Rc::new(RefCell::new(MeshEdge::new(
-                &seed[2],
-                &seed[0],
-                &seed[1].clone(),
-                ball_center,
-            )));
-            edges.push(e2.clone());
-
-            e0.borrow_mut().prev = Some(e2.clone());
-            e1.borrow_mut().next = Some(e2.clone());
-            e0.borrow_mut().next = Some(e1.clone());
-            e2.borrow_mut().prev = Some(e1.clone());
-            e1.borrow_mut().prev = Some(e0.clone());
-            e2.borrow_mut().next = Some(e0.clone());
-
-            seed[0].borrow_mut().edges = vec![e0.clone(), e2.clone()];
-            seed[1].borrow_mut().edges = vec![e0.clone(), e1.clone()];
-            seed[2].borrow_mut().edges = vec![e1.clone(), e2.clone()];
-
-            let mut front = vec![e0, e1, e2];
-            if DEBUG {
-                save_triangles_ascii(&PathBuf::from("seed.stl"), &triangles)
-                    .expect("Failed(debug) to write seed to file");
-            }
-
-            while let Some(e_ij) = get_active_edge(&mut front) {
-                if DEBUG {
-                    save_triangles_ascii(
-                        &PathBuf::from("current_active_edge.stl"),
-                        &[Triangle([
-                            e_ij.clone().borrow().a.borrow().pos,
-                            e_ij.clone().borrow().a.borrow().pos,
-                            e_ij.clone().borrow().b.borrow().pos,
-                        ])],
-                    )
-                    .expect("Failed(debug) to write front to file");
+pub fn reconstruct<P: core::borrow::Borrow<Point>>(
+    points: &[P],
+    radius: f32,
+) -> Option<Vec<Triangle>> {
+    reconstruct_with_config(points, &ReconstructionConfig::new(radius))
+        .ok()
+        .map(|reconstruction| reconstruction.triangles)
+}
+
+/// Returns a mesh from a point cloud, using the supplied parameters.
+///
+/// When a budget is exceeded the partial mesh is returned, and the report
+/// records which budget stopped the reconstruction. The report's diagnostics
+/// help to choose a better radius when the mesh is poor.
+///
+/// # Errors
+///   When the radius is not positive and finite, there are fewer than three
+///   points, the grid would be too large, or no seed triangle is found. The
+///   error for a failed seed search carries diagnostics describing it.
+///
+/// # Panics
+///  (Debug ONLY) File system issues when `saving_points()`'s or `saving_triangle()`'s
+pub fn reconstruct_with_config<P: core::borrow::Borrow<Point>>(
+    points: &[P],
+    config: &ReconstructionConfig,
+) -> Result<Reconstruction, ReconstructError> {
+    reconstruct_cloud(points, config)
+}
+
+/// Returns a mesh for each class of a segmented cloud, such as ground,
+/// buildings and vegetation, keyed by label.
+///
+/// `labels[i]` is the label of `points[i]`. Only the labels accepted by
+/// `filter` are reconstructed, each as a cloud of its own, so a class too
+/// sparse for the radius fails without affecting the others. The point
+/// indices in each report refer to `points`.
+///
+/// # Errors
+///   When `labels` and `points` differ in length. The reconstruction of each
+///   label fails for the reasons given by [`reconstruct_with_config()`].
+pub fn reconstruct_by_label<P: core::borrow::Borrow<Point>, L: Copy + Ord>(
+    points: &[P],
+    labels: &[L],
+    filter: impl Fn(L) -> bool,
+    config: &ReconstructionConfig,
+) -> Result<BTreeMap<L, Result<Reconstruction, ReconstructError>>, ReconstructError> {
+    if points.len() != labels.len() {
+        return Err(ReconstructError::MismatchedLabels {
+            points: points.len(),
+            labels: labels.len(),
+        });
+    }
+    let mut classes: BTreeMap<L, Vec<usize>> = BTreeMap::new();
+    for (i, &label) in labels.iter().enumerate() {
+        if filter(label) {
+            classes.entry(label).or_default().push(i);
+        }
+    }
+    Ok(classes
+        .into_iter()
+        .map(|(label, indices)| {
+            let class: Vec<&Point> = indices.iter().map(|&i| points[i].borrow()).collect();
+            let reconstruction = reconstruct_with_config(&class, config).map(|mut r| {
+                for i in r
+                    .report
+                    .orphans
+                    .iter_mut()
+                    .chain(&mut r.report.flagged_points)
+                {
+                    *i = indices[*i];
                 }
+                r
+            });
+            (label, reconstruction)
+        })
+        .collect())
+}
+
+/// Returns a mesh from a point cloud in which some points are context only,
+/// such as a turntable or fixture captured alongside the object.
+///
+/// `context[i]` marks `points[i]`. Context points are never vertices of the
+/// mesh, but the ball still cannot pass through them: a triangle whose ball
+/// would hold one is not made. They are not counted as orphans.
+///
+/// # Errors
+///   When `context` and `points` differ in length, and for the reasons given
+///   by [`reconstruct_with_config()`].
+pub fn reconstruct_with_context<P: core::borrow::Borrow<Point>>(
+    points: &[P],
+    context: &[bool],
+    config: &ReconstructionConfig,
+) -> Result<Reconstruction, ReconstructError> {
+    if points.len() != context.len() {
+        return Err(ReconstructError::MismatchedMask {
+            points: points.len(),
+            mask: context.len(),
+        });
+    }
+    reconstruct_cloud(
+        &WithContext {
+            cloud: points,
+            context,
+        },
+        config,
+    )
+}
+
+/// Continues a reconstruction from an existing mesh, such as the mesh of a
+/// region scanned earlier.
+///
+/// The mesh is frozen: the ball pivots from its open edges, including those
+/// around its holes, into the points not yet meshed, and its triangles are
+/// not returned again. Only the new triangles are returned, wound to match
+/// the frozen ones. The vertices of `frozen` must be points of `points`, as
+/// when the cloud holds the earlier scan together with the new one;
+/// triangles with any other vertex are ignored. Points that cannot be
+/// reached from the open edges are left as orphans. The clean-ups and
+/// orientation of `config` are not applied, as they need the whole mesh.
+///
+/// Requires the `std` feature.
+///
+/// # Errors
+///   For the reasons given by [`reconstruct_with_config()`], other than a
+///   failed seed search.
+#[cfg(feature = "std")]
+pub fn extend_reconstruction<P: core::borrow::Borrow<Point>>(
+    points: &[P],
+    frozen: &[Triangle],
+    config: &ReconstructionConfig,
+) -> Result<Reconstruction, ReconstructError> {
+    validate(points, config)?;
+    let Reconstruction {
+        mut triangles,
+        mut report,
+    } = extend_mesh(points, frozen, frozen, config, &|_, _| true);
+    let pivots = pivots_by_face(&triangles, &report.pivots);
+    // The front can re-emit a frozen face where it closes up.
+    let frozen: BTreeSet<_> = frozen.iter().map(face_key).collect();
+    triangles.retain(|t| !frozen.contains(&face_key(t)));
+    report.triangles = triangles.len();
+    realign_pivots(&triangles, pivots.as_ref(), &mut report.pivots);
+    Ok(Reconstruction { triangles, report })
+}
+
+/// Returns a mesh from positions and normals held in separate slices.
+///
+/// Avoids interleaving the arrays into [`Point`]s when the data already
+/// lives apart, as in numpy arrays or GPU readbacks.
+///
+/// # Panics
+///  (Debug ONLY) File system issues when `saving_points()`'s or `saving_triangle()`'s
+#[must_use]
+pub fn reconstruct_soa(positions: &[Vec3], normals: &[Vec3], radius: f32) -> Option<Vec<Triangle>> {
+    reconstruct_soa_with_config(positions, normals, &ReconstructionConfig::new(radius))
+        .ok()
+        .map(|reconstruction| reconstruction.triangles)
+}
+
+/// Returns a mesh from positions and normals held in separate slices, using
+/// the supplied parameters.
+///
+/// # Errors
+///   When the slices differ in length, or for the reasons given by
+///   [`reconstruct_with_config()`].
+///
+/// # Panics
+///  (Debug ONLY) File system issues when `saving_points()`'s or `saving_triangle()`'s
+pub fn reconstruct_soa_with_config(
+    positions: &[Vec3],
+    normals: &[Vec3],
+    config: &ReconstructionConfig,
+) -> Result<Reconstruction, ReconstructError> {
+    if positions.len() != normals.len() {
+        return Err(ReconstructError::MismatchedLengths {
+            positions: positions.len(),
+            normals: normals.len(),
+        });
+    }
+    reconstruct_cloud(&Soa { positions, normals }, config)
+}
+
+/// Returns a mesh from a [`point_cloud::PointCloud`], using the supplied
+/// parameters.
+///
+/// Points without normals are treated as having unknown normals, so no
+/// triangle is rejected for facing away from them. Colours and scalars are
+/// not used.
+///
+/// Requires the `std` feature.
+///
+/// # Errors
+///   When the normals differ in number from the positions, or for the
+///   reasons given by [`reconstruct_with_config()`].
+///
+/// # Panics
+///  (Debug ONLY) File system issues when `saving_points()`'s or `saving_triangle()`'s
+#[cfg(feature = "std")]
+pub fn reconstruct_point_cloud(
+    cloud: &point_cloud::PointCloud,
+    config: &ReconstructionConfig,
+) -> Result<Reconstruction, ReconstructError> {
+    if let Some(normals) = &cloud.normals
+        && normals.len() != cloud.len()
+    {
+        return Err(ReconstructError::MismatchedLengths {
+            positions: cloud.len(),
+            normals: normals.len(),
+        });
+    }
+    reconstruct_cloud(cloud, config)
+}
+
+/// Returns a mesh from a quantized point cloud, using the supplied
+/// parameters. The points are dequantized as they are read.
+///
+/// Quantize with the radius of the reconstruction, so that the cubes of the
+/// [`compact::CompactCloud`] match the cells of the grid.
+///
+/// # Errors
+///   For the reasons given by [`reconstruct_with_config()`].
+///
+/// # Panics
+///  (Debug ONLY) File system issues when `saving_points()`'s or `saving_triangle()`'s
+pub fn reconstruct_compact_with_config(
+    points: &compact::CompactCloud,
+    config: &ReconstructionConfig,
+) -> Result<Reconstruction, ReconstructError> {
+    reconstruct_cloud(points, config)
+}
+
+fn reconstruct_cloud<C: Cloud + ?Sized>(
+    points: &C,
+    config: &ReconstructionConfig,
+) -> Result<Reconstruction, ReconstructError> {
+    let mut triangles = vec![];
+    let mut report = reconstruct_batches(points, config, usize::MAX, &mut |mut batch| {
+        triangles.append(&mut batch);
+    })?;
+    let pivots = pivots_by_face(&triangles, &report.pivots);
+    if clean_up(&mut triangles, config, &mut report) {
+        report.orphans = orphans_after_clean_up(points, &triangles, &report.orphans);
+    }
+    realign_pivots(&triangles, pivots.as_ref(), &mut report.pivots);
+    if config.orient_outward {
+        report.orientation = Some(orient_with_winding(&mut triangles, config.winding));
+    }
+    Ok(Reconstruction { triangles, report })
+}
+
+// The vertices bit patterns in sorted order, ignoring winding.
+pub(crate) fn face_key(t: &Triangle) -> [[u32; 3]; 3] {
+    let mut key = t.0.map(|v| v.to_array().map(f32::to_bits));
+    key.sort_unstable();
+    key
+}
+
+// The pivot records by face, when there are any, so that they can follow
+// their triangles as the mesh is changed.
+type PivotsByFace = BTreeMap<[[u32; 3]; 3], Option<PivotRecord>>;
+
+fn pivots_by_face(triangles: &[Triangle], pivots: &[Option<PivotRecord>]) -> Option<PivotsByFace> {
+    (!pivots.is_empty()).then(|| {
+        triangles
+            .iter()
+            .map(face_key)
+            .zip(pivots.iter().copied())
+            .collect()
+    })
+}
 
-                let o_k = ball_pivot(&e_ij.clone(), &mut grid, radius);
-                if DEBUG {
-                    save_triangles_ascii(&PathBuf::from("current_mesh.stl"), &triangles)
-                        .expect("Failed(debug) writing current mesh to file");
+// Puts the pivot records back in the order of the triangles. Triangles the
+// records do not know have none.
+fn realign_pivots(
+    triangles: &[Triangle],
+    by_face: Option<&PivotsByFace>,
+    pivots: &mut Vec<Option<PivotRecord>>,
+) {
+    if let Some(by_face) = by_face {
+        *pivots = triangles
+            .iter()
+            .map(|t| by_face.get(&face_key(t)).copied().flatten())
+            .collect();
+    }
+}
+
+// Applies the clean-ups requested in `config` to the finished mesh, noting
+// them in `report`. Returns true when triangles were removed.
+pub(crate) fn clean_up(
+    triangles: &mut Vec<Triangle>,
+    config: &ReconstructionConfig,
+    report: &mut Report,
+) -> bool {
+    let before = triangles.len();
+    if let Some(options) = config.remove_slivers {
+        report.slivers = Some(remove_slivers(triangles, options));
+    }
+    if let Some(min_faces) = config.min_component_faces {
+        report.removed_components += remove_small_components(triangles, min_faces);
+    }
+    if config.keep_largest_component {
+        report.removed_components += keep_largest_component(triangles);
+    }
+    report.triangles = triangles.len();
+    triangles.len() < before
+}
+
+// The orphans, with the points the clean-ups left out of the mesh.
+fn orphans_after_clean_up<C: Cloud + ?Sized>(
+    points: &C,
+    triangles: &[Triangle],
+    orphans: &[usize],
+) -> Vec<usize> {
+    let bits = |v: Vec3| v.to_array().map(f32::to_bits);
+    let vertices: BTreeSet<[u32; 3]> = triangles.iter().flat_map(|t| t.0.map(bits)).collect();
+    let mut orphans = orphans.iter().copied().peekable();
+    (0..points.len())
+        .filter(|&i| {
+            if orphans.next_if_eq(&i).is_some() {
+                return true;
+            }
+            !points.is_context(i) && !vertices.contains(&bits(points.pos(i)))
+        })
+        .collect()
+}
+
+// Orients the mesh outward, then applies the requested winding.
+pub(crate) fn orient_with_winding(triangles: &mut [Triangle], winding: Winding) -> Orientation {
+    let orientation = orient(triangles);
+    if winding == Winding::Clockwise {
+        triangles.iter_mut().for_each(Triangle::flip);
+    }
+    orientation
+}
+
+// The main algorithm.
+//
+// Triangles are handed to `emit` in batches of (at least) `batch_size`, with
+// any remainder emitted at the end.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = points.len(), radius = config.radius))
+)]
+pub(crate) fn reconstruct_batches<C: Cloud + ?Sized>(
+    points: &C,
+    config: &ReconstructionConfig,
+    batch_size: usize,
+    emit: &mut dyn FnMut(Vec<Triangle>),
+) -> Result<Report, ReconstructError> {
+    validate(points, config)?;
+
+    let mut mesher = Mesher::new(points, config, batch_size, emit)?;
+
+    let seed = loop {
+        // Only the cells scanned add up over the attempts.
+        let diagnostics = &mut mesher.report.diagnostics;
+        diagnostics.isolated_points = 0;
+        diagnostics.seed_rejections = SeedRejections::default();
+        let out_of_time = mesher.out_of_time();
+        let seed = find_seed_triangle_until(
+            &mesher.grid,
+            mesher.radius,
+            config.seed_strategy,
+            &mut mesher.report.diagnostics,
+            &out_of_time,
+        );
+        if let Some(seed) = seed {
+            break seed;
+        }
+        if out_of_time() {
+            mesher.report.budget_exceeded = Some(Budget::Time);
+            return Ok(mesher.finish());
+        }
+        let next = config
+            .radius_escalation
+            .and_then(|escalation| escalation.next(mesher.radius));
+        let Some(radius) = next else {
+            let mut diagnostics = mesher.report.diagnostics;
+            diagnostics.densest_cell_spacing = mesher.grid.densest_cell_spacing();
+            return Err(ReconstructError::NoSeed(Box::new(diagnostics)));
+        };
+        warn!(
+            "No seed triangle found with radius {}, retrying with {radius}",
+            mesher.radius
+        );
+        mesher.radius = radius;
+        mesher.grid = build_grid(points, config, radius)?;
+    };
+
+    mesher.seed(&seed);
+    mesher.expand();
+    // The front stalled straight after the seed triangle.
+    if mesher.report.triangles == 1 && mesher.report.budget_exceeded.is_none() {
+        mesher.report.diagnostics.densest_cell_spacing = mesher.grid.densest_cell_spacing();
+    }
+    if let Some(factor) = config.boundary_pass {
+        mesher.bridge_holes(factor);
+    }
+    Ok(mesher.finish())
+}
+
+// Continues a reconstruction from the open boundary of a frozen mesh.
+//
+// Only edges accepted by `is_open` are pivoted. Points that are vertices of
+// `used` are treated as already meshed. Returns the new triangles only.
+#[cfg(feature = "std")]
+pub(crate) fn extend_mesh<C: Cloud + ?Sized>(
+    points: &C,
+    frozen: &[Triangle],
+    used: &[Triangle],
+    config: &ReconstructionConfig,
+    is_open: &dyn Fn(Vec3, Vec3) -> bool,
+) -> Reconstruction {
+    let mut triangles = vec![];
+    let mut emit = |mut batch: Vec<Triangle>| triangles.append(&mut batch);
+    // Fails only when there are no points, as the whole cloud was validated.
+    let Ok(mut mesher) = Mesher::new(points, config, usize::MAX, &mut emit) else {
+        return Reconstruction {
+            triangles: vec![],
+            report: Report::default(),
+        };
+    };
+    for pos in used.iter().flat_map(|t| t.0) {
+        if let Some(p) = mesher.grid.point_at(&pos) {
+            p.borrow_mut().used = true;
+        }
+    }
+    mesher.freeze(frozen, is_open);
+    mesher.expand();
+    let report = mesher.finish();
+    Reconstruction { triangles, report }
+}
+
+// Rejects inputs the algorithm cannot work with.
+pub(crate) fn validate<C: Cloud + ?Sized>(
+    points: &C,
+    config: &ReconstructionConfig,
+) -> Result<(), ReconstructError> {
+    if !(config.radius.is_finite() && config.radius > 0.0) {
+        return Err(ReconstructError::InvalidRadius(config.radius));
+    }
+    if !(0.0..1.0).contains(&config.empty_ball_tolerance) {
+        return Err(ReconstructError::InvalidEmptyBallTolerance(
+            config.empty_ball_tolerance,
+        ));
+    }
+    if points.len() < 3 {
+        return Err(ReconstructError::InsufficientPoints {
+            got: points.len(),
+            need: 3,
+        });
+    }
+    if let Some((lower, upper)) = bounds(points) {
+        let cells = grid_dims(upper - lower, config.radius).map(|(_, cells)| cells);
+        if cells.is_none_or(|cells| cells > config.max_grid_cells) {
+            return Err(ReconstructError::GridTooLarge {
+                cells,
+                max_cells: config.max_grid_cells,
+                min_radius: min_radius_for(upper - lower, config.max_grid_cells),
+            });
+        }
+    }
+    Ok(())
+}
+
+// Counts the normals that are sanitized on ingest.
+fn count_bad_normals<C: Cloud + ?Sized>(points: &C) -> Diagnostics {
+    let mut diagnostics = Diagnostics::default();
+    let normals = (0..points.len()).map(|i| points.normal(i));
+    for normal in normals.filter(|n| !n.is_normalized()) {
+        if sanitize_normal(normal) == Vec3::ZERO {
+            diagnostics.unknown_normals += 1;
+        } else {
+            diagnostics.rescaled_normals += 1;
+        }
+    }
+    diagnostics
+}
+
+// The grid for a reconstruction with a ball of this radius.
+fn build_grid<C: Cloud + ?Sized>(
+    points: &C,
+    config: &ReconstructionConfig,
+    radius: f32,
+) -> Result<Grid, ReconstructError> {
+    Ok(Grid::from_cloud(points, radius)?
+        .with_predicates(config.predicates)
+        .with_max_neighbors(config.max_neighbors)
+        .with_max_dihedral_angle(config.max_dihedral_angle)
+        .with_normal_disagreement(config.normal_disagreement)
+        .with_max_edge_length(config.max_edge_length.map(|max| max.resolve(radius)))
+        .with_empty_ball_tolerance(config.empty_ball_tolerance)
+        .with_compat(config.compat))
+}
+
+// The pivots between calls to the progress callback.
+const PROGRESS_INTERVAL: usize = 4096;
+
+// The most times the ball is pivoted around a pair of points. Each edge is
+// normally pivoted around once from either side.
+const MAX_PIVOTS: u8 = 8;
+
+// The key of an edge's points in `Mesher::pivoted`.
+fn pivot_key(e: &Rc<RefCell<MeshEdge>>) -> (usize, usize) {
+    let (a, b) = (e.borrow().a.borrow().index, e.borrow().b.borrow().index);
+    (a.min(b), a.max(b))
+}
+
+// State of a reconstruction in progress.
+struct Mesher<'a> {
+    grid: Grid,
+    front: Front,
+    edges: Vec<Rc<RefCell<MeshEdge>>>,
+    triangles: Vec<Triangle>,
+    config: &'a ReconstructionConfig,
+    radius: f32,
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+    batch_size: usize,
+    emit: &'a mut dyn FnMut(Vec<Triangle>),
+    report: Report,
+    trace: Option<Trace>,
+    dumps: Option<Dumps>,
+    // Candidates of the pivot in progress, collected only when they are
+    // traced or dumped.
+    candidates: Option<Vec<Candidate>>,
+    // Times the ball was pivoted around each pair of points, by their input
+    // indices, to catch a front cycling over the same edges.
+    pivoted: BTreeMap<(usize, usize), u8>,
+    // The faces output so far, as the sorted input indices of their
+    // vertices.
+    emitted: BTreeSet<[usize; 3]>,
+}
+
+impl<'a> Mesher<'a> {
+    fn new<C: Cloud + ?Sized>(
+        points: &C,
+        config: &'a ReconstructionConfig,
+        batch_size: usize,
+        emit: &'a mut dyn FnMut(Vec<Triangle>),
+    ) -> Result<Self, ReconstructError> {
+        // The clock starts before the grid is built.
+        #[cfg(feature = "std")]
+        let deadline = config.time_budget.map(|budget| Instant::now() + budget);
+        #[cfg(feature = "std")]
+        let trace = config.trace.as_ref().and_then(|path| {
+            Trace::create(path)
+                .map_err(|e| warn!("Cannot trace to {}: {e}", path.display()))
+                .ok()
+        });
+        #[cfg(not(feature = "std"))]
+        let trace = None;
+        #[cfg(feature = "std")]
+        let dumps = config.debug.as_ref().and_then(|output| {
+            Dumps::create(output)
+                .map_err(|e| warn!("Cannot write debug files to {}: {e}", output.dir.display()))
+                .ok()
+        });
+        #[cfg(not(feature = "std"))]
+        let dumps = None;
+        let candidates =
+            (trace.is_some() || dumps.as_ref().is_some_and(Dumps::wants_candidates)).then(Vec::new);
+        Ok(Self {
+            grid: build_grid(points, config, config.radius)?,
+            front: Front::new(config.traversal)
+                .with_merge_duplicates(config.compat == CompatMode::Improved),
+            edges: Vec::new(),
+            triangles: Vec::new(),
+            config,
+            radius: config.radius,
+            #[cfg(feature = "std")]
+            deadline,
+            batch_size,
+            emit,
+            report: Report {
+                points: points.len(),
+                diagnostics: count_bad_normals(points),
+                ..Report::default()
+            },
+            trace,
+            dumps,
+            candidates,
+            pivoted: BTreeMap::new(),
+            emitted: BTreeSet::new(),
+        })
+    }
+
+    // Writes a step to the trace, abandoning the trace when writing fails.
+    fn trace<E: Display>(&mut self, step: impl FnOnce(&mut Trace) -> Result<(), E>) {
+        if let Some(trace) = &mut self.trace
+            && let Err(e) = step(trace)
+        {
+            warn!("Abandoning the trace after failing to write to it: {e}");
+            self.trace = None;
+        }
+    }
+
+    // Outputs the face, unless it has been output already and the C++
+    // implementation is not being followed, with the ball that found it.
+    fn output(&mut self, f: &MeshFace, pivot: PivotRecord) {
+        let mut key = f.0.each_ref().map(|p| p.borrow().index);
+        key.sort_unstable();
+        if !self.emitted.insert(key) {
+            self.report.duplicate_triangles += 1;
+            if self.config.compat != CompatMode::Cpp {
+                return;
+            }
+        }
+        output_triangle(f, &mut self.triangles);
+        if self.config.winding == Winding::Clockwise
+            && let Some(t) = self.triangles.last_mut()
+        {
+            t.flip();
+        }
+        self.report.triangles += 1;
+        if self.config.record_pivots {
+            self.report.pivots.push(Some(pivot));
+        }
+    }
+
+    // The budget, if any, that has run out.
+    fn exceeded_budget(&self) -> Option<Budget> {
+        if self
+            .config
+            .max_triangles
+            .is_some_and(|max| self.report.triangles >= max)
+        {
+            return Some(Budget::Triangles);
+        }
+        #[cfg(feature = "std")]
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Some(Budget::Time);
+        }
+        None
+    }
+
+    // A check of the time budget that does not borrow the mesher, for the
+    // seed search.
+    #[cfg(feature = "std")]
+    fn out_of_time(&self) -> impl Fn() -> bool + use<> {
+        let deadline = self.deadline;
+        move || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[allow(clippy::unused_self)]
+    fn out_of_time(&self) -> impl Fn() -> bool + use<> {
+        || false
+    }
+
+    // Outputs the seed triangle and places its edges on the front.
+    fn seed(&mut self, seed: &SeedResult) {
+        self.output(
+            &seed.f,
+            PivotRecord {
+                center: seed.ball_center,
+                angle: None,
+            },
+        );
+        open_front(seed, &mut self.front, &mut self.edges);
+        self.trace(|trace| trace.seed(seed));
+        if let Some(triangle) = self.triangles.last().cloned() {
+            dump::write(&mut self.dumps, |dumps| dumps.seed(triangle));
+        }
+        #[cfg(feature = "debug-dumps")]
+        {
+            save_triangles_ascii(&PathBuf::from("seed.stl"), &self.triangles)
+                .expect("Failed(debug) to write seed to file");
+        }
+    }
+
+    // Rebuilds the front along the open boundary of an existing mesh.
+    //
+    // The mesh is frozen: its triangles are not output again, and its points
+    // are marked as used. Edges used by a single triangle become active when
+    // `is_open` accepts them, every other edge is inner. Triangles with a
+    // vertex that is not a point of the grid are ignored.
+    #[cfg(feature = "std")]
+    fn freeze(&mut self, frozen: &[Triangle], is_open: &dyn Fn(Vec3, Vec3) -> bool) {
+        let faces: Vec<MeshFace> = frozen
+            .iter()
+            .filter_map(|t| {
+                let [a, b, c] = t.0.map(|pos| self.grid.point_at(&pos));
+                Some(MeshFace([a?, b?, c?]))
+            })
+            .collect();
+
+        // Undirected edge usage, keyed by point identity.
+        let key = |a: &Rc<RefCell<MeshPoint>>, b: &Rc<RefCell<MeshPoint>>| {
+            let (a, b) = (Rc::as_ptr(a) as usize, Rc::as_ptr(b) as usize);
+            (a.min(b), a.max(b))
+        };
+        let mut usage: HashMap<(usize, usize), usize> = HashMap::new();
+        for f in &faces {
+            for i in 0..3 {
+                *usage.entry(key(&f.0[i], &f.0[(i + 1) % 3])).or_default() += 1;
+            }
+        }
+
+        let mut starting_at = HashMap::new();
+        let mut ending_at = HashMap::new();
+        let mut open = vec![];
+        for f in &faces {
+            let center = compute_ball_center(f, self.radius);
+            for i in 0..3 {
+                let (a, b, opposite) = (&f.0[i], &f.0[(i + 1) % 3], &f.0[(i + 2) % 3]);
+                let e = Rc::new(RefCell::new(MeshEdge::new(
+                    a,
+                    b,
+                    opposite,
+                    center.unwrap_or(Vec3::ZERO),
+                )));
+                a.borrow_mut().edges.push(e.clone());
+                b.borrow_mut().edges.push(e.clone());
+                a.borrow_mut().used = true;
+                self.edges.push(e.clone());
+
+                if center.is_some()
+                    && usage[&key(a, b)] == 1
+                    && is_open(a.borrow().pos, b.borrow().pos)
+                {
+                    starting_at.insert(Rc::as_ptr(a), e.clone());
+                    ending_at.insert(Rc::as_ptr(b), e.clone());
+                    open.push(e);
+                } else {
+                    e.borrow_mut().status = EdgeStatus::Inner;
                 }
+            }
+        }
+
+        for e in &open {
+            let (a, b) = (Rc::as_ptr(&e.borrow().a), Rc::as_ptr(&e.borrow().b));
+            e.borrow_mut().next = starting_at.get(&b).cloned();
+            e.borrow_mut().prev = ending_at.get(&a).cloned();
+            self.front.push(e);
+        }
+    }
+
+    // Counts a pivot around the edge, and returns true when its points have
+    // been pivoted around too often for the front to be making progress, as
+    // when numeric jitter closes and reopens the same edges.
+    fn stalled(&mut self, e: &Rc<RefCell<MeshEdge>>) -> bool {
+        let pivots = self.pivoted.entry(pivot_key(e)).or_default();
+        *pivots = pivots.saturating_add(1);
+        *pivots > MAX_PIVOTS
+    }
+
+    // Counts a pivot, reporting progress every few thousand.
+    fn count_pivot(&mut self) {
+        self.report.diagnostics.pivots += 1;
+        if self
+            .report
+            .diagnostics
+            .pivots
+            .is_multiple_of(PROGRESS_INTERVAL)
+        {
+            self.progress();
+        }
+    }
+
+    // Passes the size of the state, and the memory it holds, to the progress
+    // callback.
+    fn progress(&self) {
+        let Some(callback) = &self.config.progress else {
+            return;
+        };
+        // An edge is held by the list of edges, by its points and by its
+        // neighbours on the front.
+        let edge = 2 * size_of::<usize>()
+            + size_of::<RefCell<MeshEdge>>()
+            + 5 * size_of::<Rc<RefCell<MeshEdge>>>();
+        let bytes = self.grid.memory_bytes()
+            + self.edges.len() * edge
+            + self.front.memory_bytes()
+            + self.triangles.capacity() * size_of::<Triangle>()
+            + self.emitted.len() * size_of::<[usize; 3]>()
+            + self.pivoted.len() * size_of::<((usize, usize), u8)>();
+        (callback.0)(&Progress {
+            points: self.report.points,
+            edges: self.edges.len(),
+            front: self.front.len(),
+            triangles: self.report.triangles,
+            bytes,
+        });
+    }
+
+    // Flags or flips the normal of the point the ball was pivoted onto from
+    // the edge, when it faces away from their triangle.
+    fn settle_normal(&mut self, e: &Rc<RefCell<MeshEdge>>, p: &Rc<RefCell<MeshPoint>>) {
+        let policy = self.config.normal_disagreement;
+        if !self.config.heuristics.normal_half_space || policy == NormalDisagreement::Reject {
+            return;
+        }
+        let face_normal = {
+            let e = e.borrow();
+            Triangle([e.b.borrow().pos, e.a.borrow().pos, p.borrow().pos]).normal()
+        };
+        if face_normal.dot(p.borrow().normal) >= 0.0 {
+            return;
+        }
+        match policy {
+            NormalDisagreement::Reject => {}
+            NormalDisagreement::Flag => {
+                self.report.diagnostics.flagged_normals += 1;
+                self.report.flagged_points.push(p.borrow().index);
+            }
+            NormalDisagreement::Flip => {
+                self.report.diagnostics.flipped_normals += 1;
+                self.grid.flip_normal(p);
+            }
+        }
+    }
+
+    // Pivots again from the boundary edges with a ball `factor` times as
+    // large, bridging the holes the ball fell through. Edges that stalled
+    // stay open.
+    fn bridge_holes(&mut self, factor: f32) {
+        let radius = self.radius * factor;
+        if factor <= 1.0 || self.report.budget_exceeded.is_some() {
+            return;
+        }
+        match self.grid.rebucketed(radius) {
+            Ok(grid) => self.grid = grid,
+            Err(e) => {
+                warn!("Skipping the boundary pass with radius {radius}: {e}");
+                return;
+            }
+        }
+
+        for e in &self.edges {
+            if e.borrow().status != EdgeStatus::Boundary
+                || self.pivoted.get(&pivot_key(e)).copied().unwrap_or(0) > MAX_PIVOTS
+            {
+                continue;
+            }
+            let f = {
+                let e = e.borrow();
+                MeshFace([e.a.clone(), e.b.clone(), e.opposite.clone()])
+            };
+            // The larger ball resting on the edge's triangle, on the same
+            // side as the one that left it.
+            let Some(center) = compute_ball_center(&f, radius) else {
+                continue;
+            };
+            e.borrow_mut().center = center;
+            e.borrow_mut().status = EdgeStatus::Active;
+            self.front.push(e);
+        }
+
+        let (main, before) = (self.radius, self.report.triangles);
+        self.radius = radius;
+        self.expand();
+        self.radius = main;
+        self.report.bridged_triangles = self.report.triangles - before;
+    }
+
+    // Pivots the ball around active edges until the front is exhausted.
+    fn expand(&mut self) {
+        while let Some(e_ij) = self.front.active_edge() {
+            if let Some(budget) = self.exceeded_budget() {
+                self.report.budget_exceeded = Some(budget);
+                break;
+            }
+
+            #[cfg(feature = "debug-dumps")]
+            {
+                log::debug!("{} active edges on the front", self.front.len());
+                save_triangles_ascii(
+                    &PathBuf::from("current_active_edge.stl"),
+                    &[Triangle([
+                        e_ij.clone().borrow().a.borrow().pos,
+                        e_ij.clone().borrow().a.borrow().pos,
+                        e_ij.clone().borrow().b.borrow().pos,
+                    ])],
+                )
+                .expect("Failed(debug) to write front to file");
+            }
+
+            dump::write(&mut self.dumps, |dumps| dumps.front(&self.front));
+
+            if self.stalled(&e_ij) {
+                let (a, b) = (e_ij.borrow().a.borrow().pos, e_ij.borrow().b.borrow().pos);
+                self.front.mark_boundary(&e_ij);
+                self.report.stalled_edges += 1;
+                self.trace(|trace| trace.stalled(a, b));
+                continue;
+            }
+
+            self.count_pivot();
+            let o_k = traced_ball_pivot(
+                &e_ij.clone(),
+                &self.grid,
+                self.radius,
+                self.config.heuristics,
+                &mut self.report.diagnostics.pivot_rejections,
+                self.candidates.as_mut(),
+            );
+            let (a, b) = (e_ij.borrow().a.borrow().pos, e_ij.borrow().b.borrow().pos);
+            let accepted = o_k.as_ref().map(|o_k| o_k.p.borrow().pos);
+            if let Some(mut candidates) = self.candidates.take() {
+                self.trace(|trace| trace.pivot(a, b, &candidates, accepted));
+                dump::write(&mut self.dumps, |dumps| dumps.candidates(&candidates));
+                candidates.clear();
+                self.candidates = Some(candidates);
+            }
+            #[cfg(feature = "debug-dumps")]
+            {
+                save_triangles_ascii(&PathBuf::from("current_mesh.stl"), &self.triangles)
+                    .expect("Failed(debug) writing current mesh to file");
+            }
+
+            let mut boundary_test = false;
+            if let Some(o_k) = &o_k {
+                let nu = not_used(&o_k.p.borrow());
+                let of = on_front(&o_k.p.borrow());
+                if nu || of {
+                    boundary_test = true;
+                    self.settle_normal(&e_ij, &o_k.p);
+
+                    self.output(
+                        &MeshFace([
+                            e_ij.clone().borrow().a.clone(),
+                            o_k.p.clone(),
+                            e_ij.clone().borrow().b.clone(),
+                        ]),
+                        PivotRecord {
+                            center: o_k.center,
+                            angle: Some(o_k.angle),
+                        },
+                    );
 
-                let mut boundary_test = false;
-                if let Some(o_k) = &o_k {
-                    let nu = not_used(&o_k.p.borrow());
-                    let of = on_front(&o_k.p.borrow());
-                    if nu || of {
-                        boundary_test = true;
-
-                        output_triangle(
-                            &MeshFace([
-                                e_ij.clone().borrow().a.clone(),
-                                o_k.p.clone(),
-                                e_ij.clone().borrow().b.clone(),
-                            ]),
-                            &mut triangles,
-                        );
-
-                        let (e_ik, e_kj) = join(&e_ij, &o_k.p, o_k.center, &mut front, &mut edges);
-                        if let Some(e_ki) = find_reverse_edge_on_front(&e_ik.clone()) {
-                            glue(&e_ik, &e_ki, &front);
-                        }
-
-                        if let Some(e_jk) = find_reverse_edge_on_front(&e_kj.clone()) {
-                            glue(&e_kj.clone(), &e_jk.clone(), &front);
-                        }
+                    let (e_ik, e_kj) =
+                        join(&e_ij, &o_k.p, o_k.center, &mut self.front, &mut self.edges);
+                    if let Some(e_ki) = find_reverse_edge_on_front(&e_ik.clone()) {
+                        let case = glue_case(&e_ik, &e_ki, &mut self.front);
+                        self.trace(|trace| trace.glue(case));
                     }
-                }
-                if !boundary_test {
-                    if DEBUG && let Some(o_k_value) = o_k {
-                        save_points(
-                            &PathBuf::from("current_boundary.ply"),
-                            &vec![o_k_value.p.borrow().pos],
-                        )
-                        .expect("could not save current boundary");
+
+                    if let Some(e_jk) = find_reverse_edge_on_front(&e_kj.clone()) {
+                        let case = glue_case(&e_kj, &e_jk, &mut self.front);
+                        self.trace(|trace| trace.glue(case));
                     }
 
-                    // Tarpaulin: This is uncovered.
-                    e_ij.borrow_mut().status = EdgeStatus::Boundary;
+                    if self.triangles.len() >= self.batch_size {
+                        (self.emit)(core::mem::take(&mut self.triangles));
+                    }
                 }
             }
+            if !boundary_test {
+                #[cfg(feature = "debug-dumps")]
+                if let Some(o_k_value) = o_k {
+                    save_points(
+                        &PathBuf::from("current_boundary.ply"),
+                        &vec![o_k_value.p.borrow().pos],
+                    )
+                    .expect("could not save current boundary");
+                }
 
-            if DEBUG {
-                let mut boundary_edges = vec![];
+                // Tarpaulin: This is uncovered.
+                self.front.mark_boundary(&e_ij);
+                self.report.diagnostics.boundary_edges += 1;
+                self.trace(|trace| trace.boundary(a, b));
+            }
+        }
+    }
 
-                for e in front {
-                    if e.borrow().status == EdgeStatus::Boundary {
-                        boundary_edges.push(Triangle([
-                            e.borrow().a.borrow().pos,
-                            e.borrow().a.borrow().pos,
-                            e.borrow().b.borrow().pos,
-                        ]));
-                    }
+    // Emits the remaining triangles.
+    fn finish(mut self) -> Report {
+        self.progress();
+        #[cfg(feature = "debug-dumps")]
+        {
+            let mut boundary_edges = vec![];
+
+            for e in &self.edges {
+                if e.borrow().status == EdgeStatus::Boundary {
+                    boundary_edges.push(Triangle([
+                        e.borrow().a.borrow().pos,
+                        e.borrow().a.borrow().pos,
+                        e.borrow().b.borrow().pos,
+                    ]));
                 }
-                save_triangles_ascii(&PathBuf::from("boundary_edges.stl"), &boundary_edges)
-                    .expect("Failed writing boundary_edges to file");
             }
+            save_triangles_ascii(&PathBuf::from("boundary_edges.stl"), &boundary_edges)
+                .expect("Failed writing boundary_edges to file");
+        }
 
-            Some(triangles)
+        self.trace(Trace::flush);
+        if self.dumps.is_some() {
+            let boundary_edges: Vec<Triangle> = self
+                .edges
+                .iter()
+                .filter(|e| e.borrow().status == EdgeStatus::Boundary)
+                .map(|e| {
+                    let (a, b) = (e.borrow().a.borrow().pos, e.borrow().b.borrow().pos);
+                    Triangle([a, a, b])
+                })
+                .collect();
+            dump::write(&mut self.dumps, |dumps| dumps.boundary(&boundary_edges));
         }
+        (self.emit)(self.triangles);
+        self.report.radius = self.radius;
+        self.report.merged_edges = self.front.merged();
+        self.report.flagged_points.sort_unstable();
+        self.report.flagged_points.dedup();
+        self.report.orphans = self.grid.unused_points();
+        #[cfg(feature = "metrics")]
+        self.report.record_metrics();
+        self.report
     }
 }
@@ -0,0 +1,109 @@
+// The float functions used by the reconstruction.
+//
+// With `std` they are the inherent methods, which call the platform's maths
+// library and may fuse `mul_add` into a single instruction. Without `std`,
+// or with the `deterministic` feature, they come from `libm`, which gives the
+// same bits on every platform. `deterministic` also splits `mul_add` into a
+// multiply and an add, so that no result depends on an FMA unit.
+
+#[cfg(all(feature = "std", not(feature = "deterministic")))]
+pub fn acos(x: f32) -> f32 {
+    x.acos()
+}
+
+#[cfg(any(not(feature = "std"), feature = "deterministic"))]
+pub fn acos(x: f32) -> f32 {
+    libm::acosf(x)
+}
+
+#[cfg(all(feature = "std", not(feature = "deterministic")))]
+pub const fn mul_add(x: f32, a: f32, b: f32) -> f32 {
+    x.mul_add(a, b)
+}
+
+#[cfg(all(not(feature = "std"), not(feature = "deterministic")))]
+pub fn mul_add(x: f32, a: f32, b: f32) -> f32 {
+    libm::fmaf(x, a, b)
+}
+
+#[cfg(feature = "deterministic")]
+#[allow(clippy::suboptimal_flops)]
+pub const fn mul_add(x: f32, a: f32, b: f32) -> f32 {
+    x * a + b
+}
+
+#[cfg(all(feature = "std", not(feature = "deterministic")))]
+pub fn powi(x: f32, n: i32) -> f32 {
+    x.powi(n)
+}
+
+#[cfg(any(not(feature = "std"), feature = "deterministic"))]
+#[allow(clippy::cast_precision_loss)]
+pub fn powi(x: f32, n: i32) -> f32 {
+    libm::powf(x, n as f32)
+}
+
+#[cfg(all(feature = "std", not(feature = "deterministic")))]
+pub fn sqrt(x: f32) -> f32 {
+    x.sqrt()
+}
+
+#[cfg(any(not(feature = "std"), feature = "deterministic"))]
+pub fn sqrt(x: f32) -> f32 {
+    libm::sqrtf(x)
+}
+
+#[cfg(all(feature = "std", not(feature = "deterministic")))]
+pub fn atan2_f64(y: f64, x: f64) -> f64 {
+    y.atan2(x)
+}
+
+#[cfg(any(not(feature = "std"), feature = "deterministic"))]
+pub fn atan2_f64(y: f64, x: f64) -> f64 {
+    libm::atan2(y, x)
+}
+
+#[cfg(all(feature = "std", not(feature = "deterministic")))]
+pub fn sqrt_f64(x: f64) -> f64 {
+    x.sqrt()
+}
+
+#[cfg(any(not(feature = "std"), feature = "deterministic"))]
+pub fn sqrt_f64(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+#[cfg(all(feature = "std", not(feature = "deterministic")))]
+pub const fn mul_add_f64(x: f64, a: f64, b: f64) -> f64 {
+    x.mul_add(a, b)
+}
+
+#[cfg(all(not(feature = "std"), not(feature = "deterministic")))]
+pub fn mul_add_f64(x: f64, a: f64, b: f64) -> f64 {
+    libm::fma(x, a, b)
+}
+
+#[cfg(feature = "deterministic")]
+#[allow(clippy::suboptimal_flops)]
+pub const fn mul_add_f64(x: f64, a: f64, b: f64) -> f64 {
+    x * a + b
+}
+
+// SplitMix64, a small seeded generator for repeatable shuffles and samples.
+pub struct SplitMix64(pub u64);
+
+impl SplitMix64 {
+    pub const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1).
+    #[allow(clippy::cast_precision_loss)]
+    pub const fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
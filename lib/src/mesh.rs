@@ -1,5 +1,7 @@
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
 use core::cell::RefCell;
-use std::rc::Rc;
 
 use glam::Vec3;
 
@@ -11,11 +13,16 @@ pub struct MeshPoint {
     pub(crate) pos: Vec3,
     pub(crate) normal: Vec3,
     pub(crate) used: bool,
+    // Bounds the ball, but is never a vertex of the mesh.
+    pub(crate) context: bool,
     pub(crate) edges: Vec<Rc<RefCell<MeshEdge>>>,
+    // Position in the input slice.
+    pub(crate) index: usize,
 }
 
 // Defining is MeshPoint without a normal
 // is useful for testing ONLY.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
 impl MeshPoint {
     /// Constructor
     #[must_use]
@@ -24,43 +31,100 @@ impl MeshPoint {
             pos,
             normal: glam::vec3(0.0, 0.0, 0.0),
             used: false,
+            context: false,
             edges: vec![],
+            index: 0,
         }
     }
+
+    /// Position of the point.
+    #[must_use]
+    pub const fn pos(&self) -> Vec3 {
+        self.pos
+    }
+
+    /// Normal of the point.
+    #[must_use]
+    pub const fn normal(&self) -> Vec3 {
+        self.normal
+    }
+
+    /// True once the point is a vertex of the mesh.
+    #[must_use]
+    pub const fn used(&self) -> bool {
+        self.used
+    }
+
+    /// True when the point is context only: it keeps the ball from passing
+    /// through it, but is never a vertex of the mesh.
+    #[must_use]
+    pub const fn is_context(&self) -> bool {
+        self.context
+    }
+
+    /// Edges of the mesh ending at this point.
+    #[must_use]
+    pub fn edges(&self) -> &[Rc<RefCell<MeshEdge>>] {
+        &self.edges
+    }
 }
 
 impl From<&Point> for MeshPoint {
     fn from(point: &Point) -> Self {
         Self {
             pos: point.pos,
-            normal: point.normal,
+            normal: sanitize_normal(point.normal),
             used: false,
+            context: false,
             edges: vec![],
+            index: 0,
         }
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq)]
-pub(crate) enum EdgeStatus {
+// Normalizes the normal. A zero or non-finite normal is unknown, and is
+// stored as zero so it never rejects a triangle. Unit normals are kept as
+// they are.
+pub fn sanitize_normal(normal: Vec3) -> Vec3 {
+    if normal.is_normalized() {
+        normal
+    } else {
+        normal.try_normalize().unwrap_or(Vec3::ZERO)
+    }
+}
+
+/// The state of an edge of the mesh.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum EdgeStatus {
+    /// On the front, waiting to be pivoted.
     #[default]
     Active,
+    /// Shared by two triangles.
     Inner,
+    /// The ball could not pivot around the edge.
     Boundary,
 }
 
+/// A directed edge of a triangle, from `a` to `b`.
 #[derive(Clone, Debug)]
-pub(crate) struct MeshEdge {
+pub struct MeshEdge {
     pub(crate) a: Rc<RefCell<MeshPoint>>,
     pub(crate) b: Rc<RefCell<MeshPoint>>,
     pub(crate) opposite: Rc<RefCell<MeshPoint>>,
     pub(crate) center: Vec3,
-    pub(crate) prev: Option<Rc<RefCell<MeshEdge>>>,
-    pub(crate) next: Option<Rc<RefCell<MeshEdge>>>,
+    pub(crate) prev: Option<Rc<RefCell<Self>>>,
+    pub(crate) next: Option<Rc<RefCell<Self>>>,
     pub(crate) status: EdgeStatus,
+    // Position in the front, while the edge is active.
+    pub(crate) front_slot: Option<usize>,
 }
 
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
 impl MeshEdge {
-    pub(crate) fn new(
+    /// An active edge, with the vertex `opposite` completing its triangle
+    /// and the ball touching all three centered at `center`.
+    #[must_use]
+    pub fn new(
         a: &Rc<RefCell<MeshPoint>>,
         b: &Rc<RefCell<MeshPoint>>,
         opposite: &Rc<RefCell<MeshPoint>>,
@@ -74,8 +138,51 @@ impl MeshEdge {
             prev: None,
             next: None,
             status: EdgeStatus::Active,
+            front_slot: None,
         }
     }
+
+    /// The start of the edge.
+    #[must_use]
+    pub const fn a(&self) -> &Rc<RefCell<MeshPoint>> {
+        &self.a
+    }
+
+    /// The end of the edge.
+    #[must_use]
+    pub const fn b(&self) -> &Rc<RefCell<MeshPoint>> {
+        &self.b
+    }
+
+    /// The third vertex of the edge's triangle.
+    #[must_use]
+    pub const fn opposite(&self) -> &Rc<RefCell<MeshPoint>> {
+        &self.opposite
+    }
+
+    /// Center of the ball resting on the edge's triangle.
+    #[must_use]
+    pub const fn center(&self) -> Vec3 {
+        self.center
+    }
+
+    /// The state of the edge.
+    #[must_use]
+    pub const fn status(&self) -> &EdgeStatus {
+        &self.status
+    }
+
+    /// The edge before this one on the front.
+    #[must_use]
+    pub const fn prev(&self) -> Option<&Rc<RefCell<Self>>> {
+        self.prev.as_ref()
+    }
+
+    /// The edge after this one on the front.
+    #[must_use]
+    pub const fn next(&self) -> Option<&Rc<RefCell<Self>>> {
+        self.next.as_ref()
+    }
 }
 
 /// A triangle in 3D space defined by three points
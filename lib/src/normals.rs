@@ -0,0 +1,49 @@
+use glam::Vec3;
+
+use crate::Point;
+
+/// Flips the normals pointing away from `sensor_origin`, so that each faces
+/// the scanner that saw its point. Returns the number flipped.
+///
+/// When the scanner's position is known this is cheaper and more reliable
+/// than propagating an orientation between neighbouring points. Normals
+/// perpendicular to the line of sight, and zero normals, are left as they
+/// are.
+pub fn orient_normals_toward(points: &mut [Point], sensor_origin: Vec3) -> usize {
+    points
+        .iter_mut()
+        .map(|p| flip_toward(p, sensor_origin))
+        .filter(|&flipped| flipped)
+        .count()
+}
+
+/// Flips the normals pointing away from the scanner that saw each point.
+///
+/// This is [`orient_normals_toward()`] for clouds merged from scans taken at
+/// several positions. `origins` holds the scanner's position for each point.
+///
+/// Returns the number flipped, or `None`, leaving the normals untouched,
+/// when there is not one origin per point.
+pub fn orient_normals_toward_each(points: &mut [Point], origins: &[Vec3]) -> Option<usize> {
+    if points.len() != origins.len() {
+        return None;
+    }
+    Some(
+        points
+            .iter_mut()
+            .zip(origins)
+            .map(|(p, &origin)| flip_toward(p, origin))
+            .filter(|&flipped| flipped)
+            .count(),
+    )
+}
+
+// Flips the normal when it points away from `origin`, returning true when it
+// did.
+fn flip_toward(p: &mut Point, origin: Vec3) -> bool {
+    let away = p.normal.dot(origin - p.pos) < 0.0;
+    if away {
+        p.normal = -p.normal;
+    }
+    away
+}
@@ -0,0 +1,246 @@
+use core::borrow::Borrow;
+use std::collections::HashSet;
+use std::thread;
+use std::time::Instant;
+
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructError;
+use crate::Reconstruction;
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::clean_up;
+use crate::config::Winding;
+use crate::extend_mesh;
+use crate::face_key;
+use crate::grid::bounds;
+use crate::orient_with_winding;
+use crate::reconstruct_with_config;
+use crate::report::Budget;
+use crate::report::Report;
+use crate::validate;
+
+/// Returns a mesh from a point cloud, reconstructed on several threads.
+///
+/// The cloud is cut into `slabs` slabs along its longest axis. Each slab is
+/// reconstructed on its own thread, together with a margin of neighbouring
+/// points, and keeps the triangles whose centroid lies inside it. A final
+/// pass pivots from the open edges near each cut, stitching the slabs
+/// together.
+///
+/// Every slab grows from its own seed, so the mesh is not identical to the
+/// one returned by [`reconstruct_with_config()`], and can contain overlapping
+/// triangles along a cut. The number of slabs is reduced when the slabs
+/// would be too thin compared to the ball radius.
+///
+/// The time budget covers the whole call. The triangle budget is applied to
+/// each slab and to the final mesh, which is truncated when it is exceeded.
+/// Diagnostics are not collected from the slabs, and the radius is only
+/// escalated when falling back to a serial reconstruction.
+///
+/// # Errors
+///   When the radius is not positive and finite, there are fewer than three
+///   points, or the grid would be too large. When no slab finds a seed
+///   triangle, the cloud is reconstructed serially and its error returned.
+///
+/// # Panics
+///  When a slab's reconstruction panics.
+pub fn reconstruct_parallel<P: Borrow<Point> + Sync>(
+    points: &[P],
+    config: &ReconstructionConfig,
+    slabs: usize,
+) -> Result<Reconstruction, ReconstructError> {
+    let started = Instant::now();
+    validate(points, config)?;
+    let Some((lower, upper)) = bounds(points) else {
+        return reconstruct_with_config(points, config);
+    };
+    let axis = (upper - lower).max_position();
+    let margin = 4.0 * config.radius;
+
+    let extent = upper[axis] - lower[axis];
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let slabs = slabs.min((extent / (2.0 * margin)) as usize);
+    if slabs <= 1 {
+        return reconstruct_with_config(points, config);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let cuts: Vec<f32> = (1..slabs)
+        .map(|i| (extent * i as f32).mul_add(1.0 / slabs as f32, lower[axis]))
+        .collect();
+    let slab_of = |x: f32| cuts.partition_point(|cut| *cut <= x);
+
+    // The stitching pass relies on the slabs' natural winding; the requested
+    // winding is applied at the end. A slab holds only part of the mesh, so
+    // the clean-ups are applied at the end too.
+    let slab_config = ReconstructionConfig {
+        radius_escalation: None,
+        winding: Winding::default(),
+        keep_largest_component: false,
+        min_component_faces: None,
+        remove_slivers: None,
+        trace: None,
+        debug: None,
+        progress: None,
+        ..config.clone()
+    };
+    let mut budget_exceeded = None;
+    let mut triangles: Vec<Triangle> = thread::scope(|scope| {
+        // Spawn every slab before joining any of them.
+        #[allow(clippy::needless_collect)]
+        let handles: Vec<_> = (0..slabs)
+            .map(|i| {
+                let lo = if i == 0 { f32::MIN } else { cuts[i - 1] };
+                let hi = cuts.get(i).copied().unwrap_or(f32::MAX);
+                let slab_of = &slab_of;
+                let slab_config = &slab_config;
+                scope.spawn(move || {
+                    let local: Vec<&Point> = points
+                        .iter()
+                        .map(Borrow::borrow)
+                        .filter(|p| p.pos[axis] >= lo - margin && p.pos[axis] < hi + margin)
+                        .collect();
+                    if local.len() < 3 {
+                        return None;
+                    }
+                    let mut slab = reconstruct_with_config(&local, slab_config).ok()?;
+                    slab.triangles.retain(|t| slab_of(centroid(t)[axis]) == i);
+                    Some(slab)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .flat_map(|slab| {
+                budget_exceeded = budget_exceeded.or(slab.report.budget_exceeded);
+                slab.triangles
+            })
+            .collect()
+    });
+    if triangles.is_empty() {
+        return reconstruct_with_config(points, config);
+    }
+
+    let stitched = stitch(points, &mut triangles, &cuts, axis, &slab_config, started);
+    budget_exceeded = budget_exceeded.or(stitched);
+
+    if let Some(max) = config.max_triangles
+        && triangles.len() > max
+    {
+        triangles.truncate(max);
+        budget_exceeded = Some(Budget::Triangles);
+    }
+
+    let mut cleaned = Report::default();
+    clean_up(&mut triangles, config, &mut cleaned);
+    let orientation = if config.orient_outward {
+        Some(orient_with_winding(&mut triangles, config.winding))
+    } else {
+        if config.winding == Winding::Clockwise {
+            triangles.iter_mut().for_each(Triangle::flip);
+        }
+        None
+    };
+
+    let report = Report {
+        points: points.len(),
+        triangles: triangles.len(),
+        orphans: orphans(points, &triangles),
+        radius: config.radius,
+        budget_exceeded,
+        slivers: cleaned.slivers,
+        removed_components: cleaned.removed_components,
+        orientation,
+        ..Report::default()
+    };
+    Ok(Reconstruction { triangles, report })
+}
+
+// Pivots from the open edges near each cut, adding the new triangles.
+// Returns the budget, if any, that cut the stitching short.
+fn stitch<P: Borrow<Point>>(
+    points: &[P],
+    triangles: &mut Vec<Triangle>,
+    cuts: &[f32],
+    axis: usize,
+    config: &ReconstructionConfig,
+    started: Instant,
+) -> Option<Budget> {
+    // Stitch along each cut. The band is the margin plus a ball diameter, so
+    // it holds every triangle with a vertex near the cut.
+    let margin = 4.0 * config.radius;
+    let band = 6.0 * config.radius;
+    let mut budget_exceeded = None;
+    let mut seen: HashSet<_> = triangles.iter().map(face_key).collect();
+    for &cut in cuts {
+        let Some(stitch_config) = remaining(config, started) else {
+            budget_exceeded = Some(Budget::Time);
+            break;
+        };
+        let near = |x: f32, width: f32| (x - cut).abs() < width;
+        let zone: Vec<&Point> = points
+            .iter()
+            .map(Borrow::borrow)
+            .filter(|p| near(p.pos[axis], band))
+            .collect();
+        let frozen: Vec<Triangle> = triangles
+            .iter()
+            .filter(|t| t.0.iter().all(|v| near(v[axis], band)))
+            .cloned()
+            .collect();
+        let is_open = |a: Vec3, b: Vec3| near(a[axis], margin) || near(b[axis], margin);
+        let stitches = extend_mesh(
+            zone.as_slice(),
+            &frozen,
+            triangles,
+            &stitch_config,
+            &is_open,
+        );
+        budget_exceeded = budget_exceeded.or(stitches.report.budget_exceeded);
+        // The front rebuilt along a cut can have open ends, which
+        // occasionally re-emits a face.
+        triangles.extend(
+            stitches
+                .triangles
+                .into_iter()
+                .filter(|t| seen.insert(face_key(t))),
+        );
+    }
+
+    budget_exceeded
+}
+
+// The config for a pass starting now, or `None` when the time budget has
+// already run out.
+fn remaining(config: &ReconstructionConfig, started: Instant) -> Option<ReconstructionConfig> {
+    let time_budget = match config.time_budget {
+        Some(budget) => Some(budget.checked_sub(started.elapsed())?),
+        None => None,
+    };
+    Some(ReconstructionConfig {
+        time_budget,
+        ..config.clone()
+    })
+}
+
+// Indices of the points that are not vertices of the mesh.
+fn orphans<P: Borrow<Point>>(points: &[P], triangles: &[Triangle]) -> Vec<usize> {
+    let vertices: HashSet<_> = triangles
+        .iter()
+        .flat_map(|t| t.0.map(|v| v.to_array().map(f32::to_bits)))
+        .collect();
+    points
+        .iter()
+        .map(Borrow::borrow)
+        .enumerate()
+        .filter(|(_, p): &(usize, &Point)| !vertices.contains(&p.pos.to_array().map(f32::to_bits)))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn centroid(t: &Triangle) -> Vec3 {
+    (t.0[0] + t.0[1] + t.0[2]) / 3.0
+}
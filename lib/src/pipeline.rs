@@ -0,0 +1,29 @@
+pub use crate::config::Heuristics;
+pub use crate::config::Predicates;
+pub use crate::config::SeedStrategy;
+pub use crate::config::Traversal;
+pub use crate::front::Front;
+pub use crate::grid::GlueCase;
+pub use crate::grid::Grid;
+pub use crate::grid::PivotResult;
+pub use crate::grid::SeedResult;
+pub use crate::grid::ball_pivot;
+pub use crate::grid::compute_ball_center;
+pub use crate::grid::compute_ball_center_robust;
+pub use crate::grid::find_reverse_edge_on_front;
+pub use crate::grid::find_seed_triangle;
+pub use crate::grid::find_seed_triangle_with;
+pub use crate::grid::glue;
+pub use crate::grid::glue_case;
+pub use crate::grid::join;
+pub use crate::grid::not_used;
+pub use crate::grid::on_front;
+pub use crate::grid::open_front;
+pub use crate::grid::open_loop;
+pub use crate::grid::output_triangle;
+pub use crate::mesh::EdgeStatus;
+pub use crate::mesh::MeshEdge;
+pub use crate::mesh::MeshFace;
+pub use crate::mesh::MeshPoint;
+pub use crate::report::Diagnostics;
+pub use crate::report::PivotRejections;
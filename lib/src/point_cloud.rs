@@ -0,0 +1,202 @@
+use core::borrow::Borrow;
+use std::collections::HashMap;
+
+use glam::Vec3;
+
+use crate::Point;
+use crate::cloud::Cloud;
+use crate::diff::VertexIndex;
+use crate::io::XyzCloud;
+
+/// A point cloud with its attributes held in parallel arrays, one entry a
+/// point, as loaded by [`crate::io::load_point_cloud()`].
+///
+/// Only the positions are required. Normals, colours and named scalars,
+/// such as a scanner's intensity or confidence, are kept alongside when the
+/// source has them, so they can be passed through to the output or used to
+/// filter the points. Every array present should be as long as the
+/// positions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PointCloud {
+    /// The position of each point.
+    pub positions: Vec<Vec3>,
+    /// The normal of each point, when known.
+    pub normals: Option<Vec<Vec3>>,
+    /// The colour of each point, between zero and one, when known.
+    pub colors: Option<Vec<Vec3>>,
+    /// Other values, one per point, by name.
+    pub scalars: HashMap<String, Vec<f32>>,
+}
+
+impl PointCloud {
+    /// A cloud of positions with no attributes.
+    #[must_use]
+    pub fn new(positions: Vec<Vec3>) -> Self {
+        Self {
+            positions,
+            ..Self::default()
+        }
+    }
+
+    /// The number of points.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// True when there are no points.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// The normal of point `i`, or zero when normals are not known.
+    #[must_use]
+    pub fn normal(&self, i: usize) -> Vec3 {
+        self.normals
+            .as_ref()
+            .map_or(Vec3::ZERO, |normals| normals[i])
+    }
+
+    /// A copy without the points within `tolerance` of an earlier point, as
+    /// [`dedup_points()`], with the attributes of the points kept.
+    ///
+    /// # Panics
+    ///   When an attribute array is shorter than the positions.
+    #[must_use]
+    pub fn deduplicated(&self, tolerance: f32) -> (Self, Dedup) {
+        let dedup = Dedup::new(self.positions.iter().copied(), tolerance);
+        let cloud = Self {
+            positions: dedup.select(&self.positions),
+            normals: self.normals.as_deref().map(|n| dedup.select(n)),
+            colors: self.colors.as_deref().map(|c| dedup.select(c)),
+            scalars: self
+                .scalars
+                .iter()
+                .map(|(name, values)| (name.clone(), dedup.select(values)))
+                .collect(),
+        };
+        (cloud, dedup)
+    }
+
+    /// The positions and normals as [`Point`]s, with zero normals when they
+    /// are not known.
+    #[must_use]
+    pub fn to_points(&self) -> Vec<Point> {
+        (0..self.len())
+            .map(|i| Point {
+                pos: self.positions[i],
+                normal: self.normal(i),
+            })
+            .collect()
+    }
+}
+
+/// Which points were kept when a cloud was deduplicated, by
+/// [`PointCloud::deduplicated()`] or [`dedup_points()`].
+///
+/// Use it to keep other arrays, one entry a point, in step with the cloud
+/// that was reconstructed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Dedup {
+    /// For each original point, the index of the point it became in the
+    /// deduplicated cloud.
+    pub mapping: Vec<usize>,
+    /// For each point of the deduplicated cloud, the index of the original
+    /// point kept for it: the first of those merged.
+    pub kept: Vec<usize>,
+}
+
+impl Dedup {
+    /// The number of points merged into an earlier one.
+    #[must_use]
+    pub const fn removed(&self) -> usize {
+        self.mapping.len() - self.kept.len()
+    }
+
+    /// The entries of `values` for the kept points, in the order of the
+    /// deduplicated cloud.
+    ///
+    /// # Panics
+    ///   When `values` is shorter than the original cloud.
+    #[must_use]
+    pub fn select<T: Clone>(&self, values: &[T]) -> Vec<T> {
+        self.kept.iter().map(|&i| values[i].clone()).collect()
+    }
+
+    // Merges each position into the first within `tolerance` of it.
+    fn new(positions: impl Iterator<Item = Vec3>, tolerance: f32) -> Self {
+        let mut index = VertexIndex::new(tolerance);
+        let mut dedup = Self::default();
+        for (i, pos) in positions.enumerate() {
+            let j = index.insert(pos);
+            if j == dedup.kept.len() {
+                dedup.kept.push(i);
+            }
+            dedup.mapping.push(j);
+        }
+        dedup
+    }
+}
+
+/// Removes the points within `tolerance` of an earlier point, as when scans
+/// overlap or a file repeats its points. A tolerance of zero removes only
+/// exact copies.
+///
+/// Returns the points kept, in their original order, and which they were.
+#[must_use]
+pub fn dedup_points<P: Borrow<Point>>(points: &[P], tolerance: f32) -> (Vec<Point>, Dedup) {
+    let dedup = Dedup::new(points.iter().map(|p| p.borrow().pos), tolerance);
+    let kept = dedup.kept.iter().map(|&i| points[i].borrow().clone());
+    (kept.collect(), dedup)
+}
+
+impl From<&[Point]> for PointCloud {
+    fn from(points: &[Point]) -> Self {
+        Self {
+            positions: points.iter().map(|p| p.pos).collect(),
+            normals: Some(points.iter().map(|p| p.normal).collect()),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<Vec<Point>> for PointCloud {
+    fn from(points: Vec<Point>) -> Self {
+        Self::from(points.as_slice())
+    }
+}
+
+impl From<XyzCloud> for PointCloud {
+    /// The intensities, when read, become the scalar `intensity`.
+    fn from(cloud: XyzCloud) -> Self {
+        let mut scalars = HashMap::new();
+        if let Some(intensities) = cloud.intensities {
+            scalars.insert(String::from("intensity"), intensities);
+        }
+        Self {
+            colors: cloud.colors,
+            scalars,
+            ..Self::from(cloud.points)
+        }
+    }
+}
+
+impl Cloud for PointCloud {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn pos(&self, i: usize) -> Vec3 {
+        self.positions[i]
+    }
+
+    fn normal(&self, i: usize) -> Vec3 {
+        self.normal(i)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn positions(&self) -> Option<&[Vec3]> {
+        Some(&self.positions)
+    }
+}
@@ -0,0 +1,588 @@
+use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use glam::Vec3;
+
+use crate::Triangle;
+use crate::math::acos;
+
+mod remesh;
+mod subdivide;
+
+pub use remesh::remesh;
+pub use subdivide::subdivide;
+
+/// The outcome of [`orient()`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Orientation {
+    /// Number of edge-connected pieces of the mesh.
+    pub components: usize,
+    /// Pieces with no boundary, which were turned to face outward.
+    pub closed_components: usize,
+    /// Number of triangles whose winding was reversed.
+    pub flipped: usize,
+    /// Edges whose two triangles could not be made consistent, as on a
+    /// Möbius strip. Their pieces are left with an arbitrary orientation.
+    pub conflicting_edges: usize,
+}
+
+/// Orients the triangles consistently, and turns closed pieces outward.
+///
+/// Neighbouring triangles are made to traverse their shared edge in opposite
+/// directions. When a piece of the mesh is closed, it is then flipped if
+/// needed so that its signed volume is positive: its facet normals point out
+/// of the enclosed space. Edges shared by more than two triangles are
+/// ignored.
+pub fn orient(triangles: &mut [Triangle]) -> Orientation {
+    let faces = vertex_indices(triangles);
+
+    // The triangles on each undirected edge.
+    let mut edge_faces: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+    for (f, face) in faces.iter().enumerate() {
+        for (u, v) in directed_edges(*face) {
+            edge_faces.entry((u.min(v), u.max(v))).or_default().push(f);
+        }
+    }
+
+    let mut orientation = Orientation::default();
+    let mut flips: Vec<Option<bool>> = vec![None; faces.len()];
+    for start in 0..faces.len() {
+        if flips[start].is_some() {
+            continue;
+        }
+        orientation.components += 1;
+        flips[start] = Some(false);
+
+        let mut component = vec![start];
+        let mut stack = vec![start];
+        let mut closed = true;
+        let mut conflicts = 0;
+        while let Some(f) = stack.pop() {
+            let face = oriented(faces[f], flips[f] == Some(true));
+            for (u, v) in directed_edges(face) {
+                let shared = &edge_faces[&(u.min(v), u.max(v))];
+                let [a, b] = shared.as_slice() else {
+                    closed = false;
+                    continue;
+                };
+                let g = if *a == f { *b } else { *a };
+                // The neighbour must run v -> u; as given, it runs u -> v.
+                let flip = directed_edges(faces[g]).contains(&(u, v));
+                match flips[g] {
+                    None => {
+                        flips[g] = Some(flip);
+                        component.push(g);
+                        stack.push(g);
+                    }
+                    Some(flipped) if flipped != flip && f < g => conflicts += 1,
+                    Some(_) => {}
+                }
+            }
+        }
+        orientation.conflicting_edges += conflicts;
+
+        if closed && conflicts == 0 {
+            orientation.closed_components += 1;
+            let volume: f64 = component
+                .iter()
+                .map(|&f| signed_volume(&triangles[f], flips[f] == Some(true)))
+                .sum();
+            if volume < 0.0 {
+                for &f in &component {
+                    flips[f] = flips[f].map(|flipped| !flipped);
+                }
+            }
+        }
+    }
+
+    for (t, flip) in triangles.iter_mut().zip(flips) {
+        if flip == Some(true) {
+            t.flip();
+            orientation.flipped += 1;
+        }
+    }
+    orientation
+}
+
+/// Puts the triangles in a deterministic order, independent of the order in
+/// which they were generated.
+///
+/// Each triangle is rotated, keeping its winding, so that its smallest vertex
+/// comes first. The triangles are then sorted. Vertices are compared
+/// lexicographically by position, using [`f32::total_cmp()`]. Meshes with
+/// the same triangles compare equal after canonicalization, which keeps
+/// snapshots and cross-machine comparisons stable.
+pub fn canonicalize(triangles: &mut [Triangle]) {
+    for t in triangles.iter_mut() {
+        let first = (0..3)
+            .min_by(|&i, &j| compare_vertices(t.0[i], t.0[j]))
+            .unwrap_or(0);
+        t.0.rotate_left(first);
+    }
+    triangles.sort_by(|s, t| {
+        (0..3)
+            .map(|i| compare_vertices(s.0[i], t.0[i]))
+            .find(|order| order.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+/// Keeps only the edge-connected piece of the mesh with the most
+/// triangles, returning the number of pieces removed.
+///
+/// Of pieces of equal size, the one holding the earliest triangle is kept.
+pub fn keep_largest_component(triangles: &mut Vec<Triangle>) -> usize {
+    let (ids, sizes) = components(&vertex_indices(triangles));
+    let Some(largest) = (0..sizes.len()).reduce(|a, b| if sizes[b] > sizes[a] { b } else { a })
+    else {
+        return 0;
+    };
+    retain_components(triangles, &ids, |c| c == largest);
+    sizes.len() - 1
+}
+
+/// Removes the edge-connected pieces of the mesh with fewer than
+/// `min_faces` triangles, such as shells around stray blobs of noise.
+/// Returns the number of pieces removed.
+pub fn remove_small_components(triangles: &mut Vec<Triangle>, min_faces: usize) -> usize {
+    let (ids, sizes) = components(&vertex_indices(triangles));
+    retain_components(triangles, &ids, |c| sizes[c] >= min_faces);
+    sizes.iter().filter(|&&size| size < min_faces).count()
+}
+
+// The edge-connected piece of each face, numbered in order of their first
+// face, and the number of faces in each piece.
+fn components(faces: &[[usize; 3]]) -> (Vec<usize>, Vec<usize>) {
+    let mut edge_faces: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+    for (f, face) in faces.iter().enumerate() {
+        for (u, v) in directed_edges(*face) {
+            edge_faces.entry((u.min(v), u.max(v))).or_default().push(f);
+        }
+    }
+
+    let mut ids = vec![usize::MAX; faces.len()];
+    let mut sizes = vec![];
+    for start in 0..faces.len() {
+        if ids[start] != usize::MAX {
+            continue;
+        }
+        let id = sizes.len();
+        ids[start] = id;
+        let mut size = 0;
+        let mut stack = vec![start];
+        while let Some(f) = stack.pop() {
+            size += 1;
+            for (u, v) in directed_edges(faces[f]) {
+                for &g in &edge_faces[&(u.min(v), u.max(v))] {
+                    if ids[g] == usize::MAX {
+                        ids[g] = id;
+                        stack.push(g);
+                    }
+                }
+            }
+        }
+        sizes.push(size);
+    }
+    (ids, sizes)
+}
+
+// Keeps the triangles whose piece is accepted by `keep`.
+fn retain_components(triangles: &mut Vec<Triangle>, ids: &[usize], keep: impl Fn(usize) -> bool) {
+    let mut ids = ids.iter();
+    triangles.retain(|_| ids.next().is_some_and(|&c| keep(c)));
+}
+
+/// Limits for [`remove_slivers()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SliverOptions {
+    /// Triangles of lower quality are slivers. The quality is one for an
+    /// equilateral triangle and zero for a degenerate one: four root three
+    /// times the area, over the sum of the squared edge lengths.
+    pub min_quality: f32,
+    /// Triangles of smaller area are slivers too.
+    pub min_area: f32,
+    /// The largest angle, in radians, through which a collapse or a flip may
+    /// turn a triangle's normal. Keeps the cleanup from folding the surface.
+    pub max_normal_deviation: f32,
+}
+
+impl Default for SliverOptions {
+    fn default() -> Self {
+        Self {
+            min_quality: 0.1,
+            min_area: 0.0,
+            max_normal_deviation: core::f32::consts::FRAC_PI_6,
+        }
+    }
+}
+
+/// The outcome of [`remove_slivers()`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SliverRemoval {
+    /// Edges collapsed, each removing a vertex and the needles on the edge.
+    pub collapsed: usize,
+    /// Edges flipped, replacing two triangles with a better shaped pair.
+    pub flipped: usize,
+    /// Slivers left, as every collapse or flip would break a guard.
+    pub remaining: usize,
+}
+
+// Passes over the mesh before giving up on the slivers left.
+const SLIVER_PASSES: usize = 32;
+
+/// Removes needle and cap triangles, which break downstream tools such as
+/// finite element meshers.
+///
+/// A needle, with one edge much shorter than the others, has that edge
+/// collapsed onto one of its vertices; a cap, with one angle near a straight
+/// line, has its longest edge flipped. Whichever is tried first, the other
+/// is tried when it fails. A collapse is refused when it would change the
+/// shape of the boundary, pinch the surface or turn a triangle's normal by
+/// more than `max_normal_deviation`. A flip is refused when it would do the
+/// same, or not improve the worse of the two triangles. Vertices are only
+/// removed, never moved, so the mesh still interpolates the points.
+pub fn remove_slivers(triangles: &mut Vec<Triangle>, options: SliverOptions) -> SliverRemoval {
+    let (positions, faces) = indexed(triangles);
+    let mut mesh = SliverMesh {
+        positions,
+        faces: faces.into_iter().map(Some).collect(),
+        options,
+    };
+
+    let mut removal = SliverRemoval::default();
+    for _ in 0..SLIVER_PASSES {
+        if !mesh.pass(&mut removal) {
+            break;
+        }
+    }
+    removal.remaining = mesh
+        .faces
+        .iter()
+        .flatten()
+        .filter(|&&f| mesh.is_sliver(f))
+        .count();
+
+    *triangles = mesh
+        .faces
+        .iter()
+        .flatten()
+        .map(|f| Triangle(f.map(|i| mesh.positions[i])))
+        .collect();
+    removal
+}
+
+// An indexed mesh being cleaned of slivers. Removed faces are `None`.
+struct SliverMesh {
+    positions: Vec<Vec3>,
+    faces: Vec<Option<[usize; 3]>>,
+    options: SliverOptions,
+}
+
+// The faces around each undirected edge and each vertex, as they were at the
+// start of a pass.
+struct Adjacency {
+    edges: BTreeMap<(usize, usize), Vec<usize>>,
+    vertices: Vec<Vec<usize>>,
+}
+
+impl Adjacency {
+    fn new(faces: &[Option<[usize; 3]>], vertices: usize) -> Self {
+        let mut adjacency = Self {
+            edges: BTreeMap::new(),
+            vertices: vec![vec![]; vertices],
+        };
+        for (f, face) in faces.iter().enumerate() {
+            let Some(face) = face else {
+                continue;
+            };
+            for (u, v) in directed_edges(*face) {
+                adjacency
+                    .edges
+                    .entry((u.min(v), u.max(v)))
+                    .or_default()
+                    .push(f);
+                adjacency.vertices[u].push(f);
+            }
+        }
+        adjacency
+    }
+
+    fn edge(&self, u: usize, v: usize) -> &[usize] {
+        self.edges
+            .get(&(u.min(v), u.max(v)))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    // True when an edge of vertex `x` borders a single face.
+    fn is_boundary(&self, faces: &[Option<[usize; 3]>], x: usize) -> bool {
+        self.vertices[x].iter().any(|&f| {
+            faces[f].is_some_and(|face| {
+                directed_edges(face)
+                    .iter()
+                    .any(|&(p, q)| (p == x || q == x) && self.edge(p, q).len() == 1)
+            })
+        })
+    }
+
+    // The vertices joined to vertex `x` by an edge.
+    fn ring(&self, faces: &[Option<[usize; 3]>], x: usize) -> BTreeSet<usize> {
+        self.vertices[x]
+            .iter()
+            .filter_map(|&f| faces[f])
+            .flatten()
+            .filter(|&y| y != x)
+            .collect()
+    }
+}
+
+impl SliverMesh {
+    // Collapses or flips an edge of each sliver whose neighbourhood is
+    // untouched so far this pass. Returns true when the mesh changed.
+    fn pass(&mut self, removal: &mut SliverRemoval) -> bool {
+        let adjacency = Adjacency::new(&self.faces, self.positions.len());
+        // Vertices of faces changed this pass, whose adjacency is stale.
+        let mut touched = vec![false; self.positions.len()];
+        let mut changed = false;
+        for f in 0..self.faces.len() {
+            let Some(face) = self.faces[f] else {
+                continue;
+            };
+            if face.iter().any(|&v| touched[v]) || !self.is_sliver(face) {
+                continue;
+            }
+            let edges = directed_edges(face);
+            let length = |&(u, v): &(usize, usize)| self.positions[u].distance(self.positions[v]);
+            let by_length =
+                |a: &(usize, usize), b: &(usize, usize)| length(a).total_cmp(&length(b));
+            let shortest = edges.iter().min_by(|a, b| by_length(a, b)).copied();
+            let longest = edges.iter().max_by(|a, b| by_length(a, b)).copied();
+            let (Some(shortest), Some(longest)) = (shortest, longest) else {
+                continue;
+            };
+            let is_needle = length(&shortest) < 0.5 * length(&longest);
+
+            let collapse = |mesh: &mut Self, touched: &mut [bool]| {
+                let (u, v) = shortest;
+                mesh.collapse(u, v, &adjacency, touched) || mesh.collapse(v, u, &adjacency, touched)
+            };
+            let flip =
+                |mesh: &mut Self, touched: &mut [bool]| mesh.flip(f, longest, &adjacency, touched);
+            let (collapsed, flipped) = if is_needle {
+                let collapsed = collapse(self, &mut touched);
+                (collapsed, !collapsed && flip(self, &mut touched))
+            } else {
+                let flipped = flip(self, &mut touched);
+                (!flipped && collapse(self, &mut touched), flipped)
+            };
+            removal.collapsed += usize::from(collapsed);
+            removal.flipped += usize::from(flipped);
+            changed |= collapsed || flipped;
+        }
+        changed
+    }
+
+    fn is_sliver(&self, face: [usize; 3]) -> bool {
+        let [a, b, c] = face.map(|i| self.positions[i]);
+        let area = (b - a).cross(c - a).length() / 2.0;
+        area < self.options.min_area || quality(a, b, c) < self.options.min_quality
+    }
+
+    // The unit normal of the face, with `from` moved to `to`.
+    fn normal(&self, face: [usize; 3], from: usize, to: usize) -> Option<Vec3> {
+        let [a, b, c] = face.map(|i| self.positions[if i == from { to } else { i }]);
+        (b - a).cross(c - a).try_normalize()
+    }
+
+    fn within_deviation(&self, a: Vec3, b: Vec3) -> bool {
+        acos(a.dot(b).clamp(-1.0, 1.0)) <= self.options.max_normal_deviation
+    }
+
+    // Collapses the edge from `u` onto `v`, removing `u`, unless a guard
+    // fails.
+    fn collapse(
+        &mut self,
+        u: usize,
+        v: usize,
+        adjacency: &Adjacency,
+        touched: &mut [bool],
+    ) -> bool {
+        let shared = adjacency.edge(u, v);
+        if shared.is_empty() || shared.len() > 2 {
+            return false;
+        }
+        // Moving a boundary vertex inward changes the shape of the boundary.
+        if shared.len() == 2 && adjacency.is_boundary(&self.faces, u) {
+            return false;
+        }
+        // The vertices joined to both ends must be those opposite the edge,
+        // or the collapse pinches the surface.
+        let common = adjacency
+            .ring(&self.faces, u)
+            .intersection(&adjacency.ring(&self.faces, v))
+            .count();
+        if common != shared.len() {
+            return false;
+        }
+        // Nor may it fold two faces onto one another, as on a tetrahedron.
+        let mut kept: BTreeSet<[usize; 3]> = adjacency.vertices[v]
+            .iter()
+            .filter(|f| !shared.contains(f))
+            .filter_map(|&f| self.faces[f].map(sorted))
+            .collect();
+        for &f in &adjacency.vertices[u] {
+            let Some(face) = self.faces[f] else {
+                continue;
+            };
+            if shared.contains(&f) {
+                continue;
+            }
+            if !kept.insert(sorted(face.map(|x| if x == u { v } else { x }))) {
+                return false;
+            }
+            let Some(new) = self.normal(face, u, v) else {
+                return false;
+            };
+            if let Some(old) = self.normal(face, u, u)
+                && !self.within_deviation(old, new)
+            {
+                return false;
+            }
+        }
+
+        for &f in &adjacency.vertices[u] {
+            if let Some(face) = self.faces[f] {
+                for x in face {
+                    touched[x] = true;
+                }
+                self.faces[f] = if shared.contains(&f) {
+                    None
+                } else {
+                    Some(face.map(|x| if x == u { v } else { x }))
+                };
+            }
+        }
+        true
+    }
+
+    // Flips the edge of face `f`, replacing it and its neighbour across the
+    // edge with the pair on the other diagonal, unless a guard fails.
+    fn flip(
+        &mut self,
+        f: usize,
+        edge: (usize, usize),
+        adjacency: &Adjacency,
+        touched: &mut [bool],
+    ) -> bool {
+        let shared = adjacency.edge(edge.0, edge.1);
+        let [f1, f2] = shared else {
+            return false;
+        };
+        let g = if *f1 == f { *f2 } else { *f1 };
+        let (Some(face), Some(other)) = (self.faces[f], self.faces[g]) else {
+            return false;
+        };
+        // The face runs a -> b -> c, and its neighbour b -> a -> d.
+        let (a, b) = edge;
+        let Some(c) = face.into_iter().find(|&x| x != a && x != b) else {
+            return false;
+        };
+        let Some(d) = other.into_iter().find(|&x| x != a && x != b) else {
+            return false;
+        };
+        if c == d || !directed_edges(other).contains(&(b, a)) || !adjacency.edge(c, d).is_empty() {
+            return false;
+        }
+
+        let [pa, pb, pc, pd] = [a, b, c, d].map(|i| self.positions[i]);
+        let Some(old) = ((pb - pa).cross(pc - pa) + (pa - pb).cross(pd - pb)).try_normalize()
+        else {
+            return false;
+        };
+        let flipped = [[a, d, c], [d, b, c]];
+        let turned = flipped.iter().any(|&face| {
+            self.normal(face, a, a)
+                .is_none_or(|n| !self.within_deviation(old, n))
+        });
+        let better = quality(pa, pd, pc).min(quality(pd, pb, pc))
+            > quality(pa, pb, pc).min(quality(pb, pa, pd));
+        if turned || !better {
+            return false;
+        }
+
+        for x in [a, b, c, d] {
+            touched[x] = true;
+        }
+        self.faces[f] = Some(flipped[0]);
+        self.faces[g] = Some(flipped[1]);
+        true
+    }
+}
+
+// The face's vertices in ascending order, the same for every winding.
+fn sorted(mut face: [usize; 3]) -> [usize; 3] {
+    face.sort_unstable();
+    face
+}
+
+// One for an equilateral triangle, falling to zero as it degenerates.
+fn quality(a: Vec3, b: Vec3, c: Vec3) -> f32 {
+    const FOUR_ROOT_THREE: f32 = 6.928_203;
+    let area = (b - a).cross(c - a).length() / 2.0;
+    let squares = (b - a).length_squared() + (c - b).length_squared() + (a - c).length_squared();
+    if squares > 0.0 {
+        FOUR_ROOT_THREE * area / squares
+    } else {
+        0.0
+    }
+}
+
+fn compare_vertices(a: Vec3, b: Vec3) -> Ordering {
+    a.x.total_cmp(&b.x)
+        .then(a.y.total_cmp(&b.y))
+        .then(a.z.total_cmp(&b.z))
+}
+
+// Each triangle as indices into its distinct vertices.
+fn vertex_indices(triangles: &[Triangle]) -> Vec<[usize; 3]> {
+    let mut ids: BTreeMap<[u32; 3], usize> = BTreeMap::new();
+    triangles
+        .iter()
+        .map(|t| {
+            t.0.map(|v| {
+                let next = ids.len();
+                *ids.entry(v.to_array().map(f32::to_bits)).or_insert(next)
+            })
+        })
+        .collect()
+}
+
+// The distinct vertices of the triangles, and each triangle as indices into
+// them.
+fn indexed(triangles: &[Triangle]) -> (Vec<Vec3>, Vec<[usize; 3]>) {
+    let faces = vertex_indices(triangles);
+    let mut positions = vec![Vec3::ZERO; faces.iter().flatten().max().map_or(0, |&i| i + 1)];
+    for (t, face) in triangles.iter().zip(&faces) {
+        for (v, &i) in t.0.iter().zip(face) {
+            positions[i] = *v;
+        }
+    }
+    (positions, faces)
+}
+
+const fn oriented([a, b, c]: [usize; 3], flipped: bool) -> [usize; 3] {
+    if flipped { [a, c, b] } else { [a, b, c] }
+}
+
+const fn directed_edges([a, b, c]: [usize; 3]) -> [(usize, usize); 3] {
+    [(a, b), (b, c), (c, a)]
+}
+
+// Six times the signed volume of the tetrahedron formed with the origin.
+fn signed_volume(t: &Triangle, flipped: bool) -> f64 {
+    let [a, b, c] = t.0.map(Vec3::as_dvec3);
+    let volume = a.dot(b.cross(c));
+    if flipped { -volume } else { volume }
+}
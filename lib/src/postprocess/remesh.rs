@@ -0,0 +1,378 @@
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use glam::Vec3;
+
+use super::Adjacency;
+use super::directed_edges;
+use super::indexed;
+use super::sorted;
+use crate::Triangle;
+use crate::bvh::Bvh;
+use crate::math::acos;
+
+// The largest angle through which a collapse, a flip or a smoothing pass may
+// turn the normal of a triangle.
+const MAX_TURN: f32 = core::f32::consts::FRAC_PI_4;
+
+/// Remeshes the triangles so that their edges are close to
+/// `target_edge_length`, whatever the sampling of the scan.
+///
+/// Each iteration splits the edges longer than four thirds of the target,
+/// collapses those shorter than four fifths of it, flips edges to bring the
+/// vertices towards six neighbours (four on the boundary), then slides each
+/// vertex towards the centroid of its neighbours and back onto the original
+/// surface. Boundary vertices are never moved, no collapse or flip may pinch
+/// the surface, no step may turn a triangle by more than 45°, and smoothing
+/// never turns a triangle to face away from the original surface.
+///
+/// The number of triangles grows with the area of the mesh over the square
+/// of the target length. Nothing is done when the target length is not
+/// positive and finite.
+pub fn remesh(triangles: &mut Vec<Triangle>, target_edge_length: f32, iterations: usize) {
+    if !(target_edge_length > 0.0 && target_edge_length.is_finite()) {
+        return;
+    }
+    let original = Bvh::new(triangles);
+    let mut mesh = Remesher::new(triangles);
+    let (low, high) = (target_edge_length * 0.8, target_edge_length * 4.0 / 3.0);
+    for _ in 0..iterations {
+        while mesh.split(high) {}
+        while mesh.collapse(low, high) {}
+        while mesh.flip() {}
+        mesh.smooth(&original);
+    }
+    *triangles = mesh
+        .faces
+        .iter()
+        .flatten()
+        .map(|f| Triangle(f.map(|i| mesh.positions[i])))
+        .collect();
+}
+
+// An indexed mesh being remeshed. Removed faces are `None`; removed vertices
+// are left in place, unreferenced.
+struct Remesher {
+    positions: Vec<Vec3>,
+    faces: Vec<Option<[usize; 3]>>,
+}
+
+impl Remesher {
+    fn new(triangles: &[Triangle]) -> Self {
+        let (positions, faces) = indexed(triangles);
+        Self {
+            positions,
+            faces: faces.into_iter().map(Some).collect(),
+        }
+    }
+
+    fn adjacency(&self) -> Adjacency {
+        Adjacency::new(&self.faces, self.positions.len())
+    }
+
+    fn length(&self, u: usize, v: usize) -> f32 {
+        self.positions[u].distance(self.positions[v])
+    }
+
+    // The unit normal of the face, with the vertices at `position`.
+    fn normal(face: [usize; 3], position: impl Fn(usize) -> Vec3) -> Option<Vec3> {
+        let [a, b, c] = face.map(position);
+        (b - a).cross(c - a).try_normalize()
+    }
+
+    // Splits each edge longer than `high` at its midpoint, where the faces
+    // around it are untouched so far. Returns true when the mesh changed.
+    fn split(&mut self, high: f32) -> bool {
+        let adjacency = self.adjacency();
+        let mut touched = vec![false; self.positions.len()];
+        let mut changed = false;
+        for (&(u, v), shared) in &adjacency.edges {
+            let length = self.length(u, v);
+            if !(length > high && length.is_finite()) || shared.len() > 2 {
+                continue;
+            }
+            let faces: Vec<[usize; 3]> = shared.iter().filter_map(|&f| self.faces[f]).collect();
+            if faces.iter().flatten().any(|&x| touched[x]) {
+                continue;
+            }
+            let m = self.positions.len();
+            self.positions
+                .push((self.positions[u] + self.positions[v]) / 2.0);
+            for (&f, face) in shared.iter().zip(faces) {
+                for x in face {
+                    touched[x] = true;
+                }
+                // Rotated to run a -> b -> c, along the edge.
+                let Some(i) = (0..3).find(|&i| {
+                    let (a, b) = (face[i], face[(i + 1) % 3]);
+                    (a, b) == (u, v) || (a, b) == (v, u)
+                }) else {
+                    continue;
+                };
+                let [a, b, c] = [face[i], face[(i + 1) % 3], face[(i + 2) % 3]];
+                self.faces[f] = Some([a, m, c]);
+                self.faces.push(Some([m, b, c]));
+            }
+            touched.push(true);
+            changed = true;
+        }
+        changed
+    }
+
+    // Collapses each edge shorter than `low`, unless it would make an edge
+    // longer than `high` or break a guard. Returns true when the mesh
+    // changed.
+    fn collapse(&mut self, low: f32, high: f32) -> bool {
+        let adjacency = self.adjacency();
+        let mut touched = vec![false; self.positions.len()];
+        let mut changed = false;
+        for (&(u, v), shared) in &adjacency.edges {
+            let length = self.length(u, v);
+            if touched[u] || touched[v] || length >= low || length.is_nan() {
+                continue;
+            }
+            let rings = [u, v].map(|x| adjacency.ring(&self.faces, x));
+            if rings.iter().flatten().any(|&x| touched[x]) {
+                continue;
+            }
+            if self.try_collapse(u, v, shared, &adjacency, &rings, high) {
+                for x in rings.iter().flatten() {
+                    touched[*x] = true;
+                }
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    // Collapses the edge from `u` to `v` onto a single vertex, at the
+    // midpoint or on the boundary, unless a guard fails.
+    fn try_collapse(
+        &mut self,
+        u: usize,
+        v: usize,
+        shared: &[usize],
+        adjacency: &Adjacency,
+        rings: &[BTreeSet<usize>; 2],
+        high: f32,
+    ) -> bool {
+        if shared.is_empty() || shared.len() > 2 {
+            return false;
+        }
+        // Boundary vertices stay where they are.
+        let boundary = [u, v].map(|x| adjacency.is_boundary(&self.faces, x));
+        let (keep, remove) = match boundary {
+            [true, true] if shared.len() == 2 => return false,
+            [false, true] => (v, u),
+            _ => (u, v),
+        };
+        let at = if boundary.contains(&true) {
+            self.positions[keep]
+        } else {
+            (self.positions[u] + self.positions[v]) / 2.0
+        };
+        // The vertices joined to both ends must be those opposite the edge,
+        // or the collapse pinches the surface.
+        if rings[0].intersection(&rings[1]).count() != shared.len() {
+            return false;
+        }
+        if rings
+            .iter()
+            .flatten()
+            .any(|&x| x != u && x != v && self.positions[x].distance(at) > high)
+        {
+            return false;
+        }
+
+        let moved = |i: usize| {
+            if i == u || i == v {
+                at
+            } else {
+                self.positions[i]
+            }
+        };
+        let around: BTreeSet<usize> = [u, v]
+            .iter()
+            .flat_map(|&x| adjacency.vertices[x].iter().copied())
+            .filter(|f| !shared.contains(f))
+            .collect();
+        let mut kept = BTreeSet::new();
+        for &f in &around {
+            let Some(face) = self.faces[f] else {
+                continue;
+            };
+            let collapsed = face.map(|x| if x == remove { keep } else { x });
+            if !kept.insert(sorted(collapsed)) {
+                return false;
+            }
+            let Some(new) = Self::normal(face, moved) else {
+                return false;
+            };
+            if let Some(old) = Self::normal(face, |i| self.positions[i])
+                && acos(old.dot(new).clamp(-1.0, 1.0)) > MAX_TURN
+            {
+                return false;
+            }
+        }
+
+        self.positions[keep] = at;
+        for &f in shared {
+            self.faces[f] = None;
+        }
+        for &f in &around {
+            self.faces[f] =
+                self.faces[f].map(|face| face.map(|x| if x == remove { keep } else { x }));
+        }
+        true
+    }
+
+    // Flips each edge whose flip brings its four vertices closer to their
+    // ideal number of neighbours. Returns true when the mesh changed.
+    fn flip(&mut self) -> bool {
+        let adjacency = self.adjacency();
+        let valence: Vec<(usize, usize)> = (0..self.positions.len())
+            .map(|x| {
+                let ideal = if adjacency.is_boundary(&self.faces, x) {
+                    4
+                } else {
+                    6
+                };
+                (adjacency.ring(&self.faces, x).len(), ideal)
+            })
+            .collect();
+        let mut touched = vec![false; self.positions.len()];
+        let mut changed = false;
+        for (&(u, v), shared) in &adjacency.edges {
+            let &[f, g] = shared.as_slice() else {
+                continue;
+            };
+            let (Some(face), Some(other)) = (self.faces[f], self.faces[g]) else {
+                continue;
+            };
+            // The face runs a -> b -> c, and its neighbour b -> a -> d.
+            let (a, b) = if directed_edges(face).contains(&(u, v)) {
+                (u, v)
+            } else {
+                (v, u)
+            };
+            let third = |face: [usize; 3]| face.into_iter().find(|&x| x != a && x != b);
+            let (Some(c), Some(d)) = (third(face), third(other)) else {
+                continue;
+            };
+            if c == d
+                || [a, b, c, d].iter().any(|&x| touched[x])
+                || !directed_edges(other).contains(&(b, a))
+                || !adjacency.edge(c, d).is_empty()
+            {
+                continue;
+            }
+
+            let deviation = |x: usize, change: isize| {
+                let (valence, ideal) = valence[x];
+                valence.saturating_add_signed(change).abs_diff(ideal)
+            };
+            let before: usize = [a, b, c, d].iter().map(|&x| deviation(x, 0)).sum();
+            let after = deviation(a, -1) + deviation(b, -1) + deviation(c, 1) + deviation(d, 1);
+            if after >= before {
+                continue;
+            }
+            let position = |i: usize| self.positions[i];
+            let flipped = [[a, d, c], [d, b, c]];
+            let turned = [face, other].iter().any(|&old| {
+                let old = Self::normal(old, position);
+                flipped
+                    .iter()
+                    .any(|&new| match (old, Self::normal(new, position)) {
+                        (Some(old), Some(new)) => acos(old.dot(new).clamp(-1.0, 1.0)) > MAX_TURN,
+                        (_, None) => true,
+                        (None, Some(_)) => false,
+                    })
+            });
+            if turned {
+                continue;
+            }
+
+            for x in [a, b, c, d] {
+                touched[x] = true;
+            }
+            self.faces[f] = Some(flipped[0]);
+            self.faces[g] = Some(flipped[1]);
+            changed = true;
+        }
+        changed
+    }
+
+    // Slides each vertex off the boundary halfway towards the centroid of
+    // its neighbours, within the tangent plane, then back onto the original
+    // surface. A move that would turn one of its triangles by more than
+    // `MAX_TURN` from where the pass began, or to face away from the original
+    // surface, is shortened, and dropped if it still does.
+    fn smooth(&mut self, original: &Bvh) {
+        let adjacency = self.adjacency();
+        let offsets: Vec<Option<Vec3>> = (0..self.positions.len())
+            .map(|x| self.tangential_offset(&adjacency, x))
+            .collect();
+        let before: Vec<Option<Vec3>> = self
+            .faces
+            .iter()
+            .map(|face| face.and_then(|face| Self::normal(face, |i| self.positions[i])))
+            .collect();
+        for (x, &offset) in offsets.iter().enumerate() {
+            let Some(offset) = offset else {
+                continue;
+            };
+            let p = self.positions[x];
+            for step in [0.5, 0.25, 0.125] {
+                let q = p + offset * step;
+                let q = original.closest_point(q).map_or(q, |closest| closest.point);
+                let turns = adjacency.vertices[x].iter().any(|&f| {
+                    let Some(face) = self.faces[f] else {
+                        return false;
+                    };
+                    let moved = |i: usize| if i == x { q } else { self.positions[i] };
+                    let Some(new) = Self::normal(face, moved) else {
+                        return true;
+                    };
+                    before[f].is_some_and(|old| acos(old.dot(new).clamp(-1.0, 1.0)) > MAX_TURN)
+                        || Self::faces_away(original, face.map(moved), new)
+                });
+                if !turns {
+                    self.positions[x] = q;
+                    break;
+                }
+            }
+        }
+    }
+
+    // True when a triangle faces away from the original surface beneath it.
+    fn faces_away(original: &Bvh, vertices: [Vec3; 3], normal: Vec3) -> bool {
+        let centroid = vertices.iter().sum::<Vec3>() / 3.0;
+        original
+            .closest_point(centroid)
+            .is_some_and(|closest| original.triangles()[closest.face].normal().dot(normal) < 0.0)
+    }
+
+    // The offset from a vertex off the boundary to the centroid of its
+    // neighbours, within the plane of its triangles.
+    fn tangential_offset(&self, adjacency: &Adjacency, x: usize) -> Option<Vec3> {
+        let ring = adjacency.ring(&self.faces, x);
+        if ring.is_empty() || adjacency.is_boundary(&self.faces, x) {
+            return None;
+        }
+        let normal = adjacency.vertices[x]
+            .iter()
+            .filter_map(|&f| self.faces[f])
+            .map(|face| {
+                let [a, b, c] = face.map(|i| self.positions[i]);
+                (b - a).cross(c - a)
+            })
+            .sum::<Vec3>()
+            .try_normalize()?;
+        let p = self.positions[x];
+        #[allow(clippy::cast_precision_loss)]
+        let centroid = ring.iter().map(|&y| self.positions[y]).sum::<Vec3>() / ring.len() as f32;
+        let offset = centroid - p;
+        Some(offset - normal * normal.dot(offset))
+    }
+}
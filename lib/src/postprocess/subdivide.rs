@@ -0,0 +1,92 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use glam::Vec3;
+
+use super::Adjacency;
+use super::directed_edges;
+use super::indexed;
+use crate::Triangle;
+use crate::math::mul_add;
+
+/// Smooths and refines the mesh by Loop subdivision, `levels` times.
+///
+/// Each level splits every triangle into four at its edge midpoints, then
+/// moves the vertices towards a smooth surface, approximating the original
+/// one. Boundary edges, and edges shared by more than two triangles, are
+/// treated as creases: they are smoothed as curves along themselves, and
+/// vertices on more than two of them stay where they are. The winding of
+/// the triangles is kept.
+///
+/// Each level multiplies the number of triangles by four.
+pub fn subdivide(triangles: &mut Vec<Triangle>, levels: usize) {
+    for _ in 0..levels {
+        *triangles = subdivide_once(triangles);
+    }
+}
+
+fn subdivide_once(triangles: &[Triangle]) -> Vec<Triangle> {
+    let (positions, faces) = indexed(triangles);
+    let indexed: Vec<Option<[usize; 3]>> = faces.iter().copied().map(Some).collect();
+    let adjacency = Adjacency::new(&indexed, positions.len());
+    let is_crease = |u: usize, v: usize| adjacency.edge(u, v).len() != 2;
+
+    // A new vertex on each edge, weighted towards the opposite vertices
+    // across an inner edge.
+    let edge_points: BTreeMap<(usize, usize), Vec3> = adjacency
+        .edges
+        .iter()
+        .map(|(&(u, v), shared)| {
+            let ends = positions[u] + positions[v];
+            let point = if let [f, g] = shared.as_slice() {
+                let opposite = |f: usize| faces[f].into_iter().find(|&x| x != u && x != v);
+                match (opposite(*f), opposite(*g)) {
+                    (Some(c), Some(d)) => ends * 0.375 + (positions[c] + positions[d]) * 0.125,
+                    _ => ends / 2.0,
+                }
+            } else {
+                ends / 2.0
+            };
+            ((u, v), point)
+        })
+        .collect();
+
+    // Each old vertex moved towards its neighbours.
+    let vertex_points: Vec<Vec3> = (0..positions.len())
+        .map(|x| {
+            let p = positions[x];
+            let ring = adjacency.ring(&indexed, x);
+            let creases: Vec<usize> = ring.iter().copied().filter(|&y| is_crease(x, y)).collect();
+            match creases.as_slice() {
+                [] if !ring.is_empty() => {
+                    #[allow(clippy::cast_precision_loss)]
+                    let n = ring.len() as f32;
+                    let beta = if ring.len() == 3 {
+                        3.0 / 16.0
+                    } else {
+                        3.0 / (8.0 * n)
+                    };
+                    let neighbours: Vec3 = ring.iter().map(|&y| positions[y]).sum();
+                    p * mul_add(-n, beta, 1.0) + neighbours * beta
+                }
+                [a, b] => p * 0.75 + (positions[*a] + positions[*b]) * 0.125,
+                _ => p,
+            }
+        })
+        .collect();
+
+    let edge_point = |u: usize, v: usize| edge_points[&(u.min(v), u.max(v))];
+    faces
+        .iter()
+        .flat_map(|&face| {
+            let [a, b, c] = face.map(|x| vertex_points[x]);
+            let [ab, bc, ca] = directed_edges(face).map(|(u, v)| edge_point(u, v));
+            [
+                Triangle([a, ab, ca]),
+                Triangle([ab, b, bc]),
+                Triangle([ca, bc, c]),
+                Triangle([ab, bc, ca]),
+            ]
+        })
+        .collect()
+}
@@ -0,0 +1,108 @@
+use core::borrow::Borrow;
+use std::collections::HashMap;
+
+use glam::IVec3;
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructError;
+use crate::Reconstruction;
+use crate::ReconstructionConfig;
+use crate::reconstruct_with_config;
+
+/// A number of points a preview reconstructs in well under a second on a
+/// desktop, for [`reconstruct_preview()`].
+pub const DEFAULT_PREVIEW_POINTS: usize = 20_000;
+
+/// A draft mesh, from [`reconstruct_preview()`].
+#[derive(Debug)]
+pub struct Preview {
+    /// The draft mesh. Its report counts the points of the downsampled
+    /// cloud, and its orphans are indices into it.
+    pub reconstruction: Reconstruction,
+    /// The downsampled cloud that was reconstructed.
+    pub points: Vec<Point>,
+    /// The width of the cubes the cloud was downsampled into, or `None`
+    /// when it was small enough already.
+    pub voxel_size: Option<f32>,
+}
+
+/// Returns a quick draft of the mesh, for showing while the full
+/// reconstruction runs or before committing to it.
+///
+/// The cloud is downsampled into cubes, each replaced by the mean of its
+/// points, growing the cubes until no more than `max_points` remain. The
+/// draft is then reconstructed with the ball radius grown to the width of
+/// the cubes, so that it rests on the sparser points. The other settings of
+/// `config` are kept; set its `time_budget` to bound the wait.
+///
+/// # Errors
+///   For the reasons given by [`reconstruct_with_config()`], for the
+///   downsampled cloud.
+pub fn reconstruct_preview<P: Borrow<Point>>(
+    points: &[P],
+    config: &ReconstructionConfig,
+    max_points: usize,
+) -> Result<Preview, ReconstructError> {
+    const MAX_PASSES: usize = 16;
+
+    let mut points: Vec<Point> = points.iter().map(|p| p.borrow().clone()).collect();
+    let mut voxel_size = None;
+    let mut size = config.radius;
+    for _ in 0..MAX_PASSES {
+        if points.len() <= max_points || !size.is_finite() || size <= 0.0 {
+            break;
+        }
+        let downsampled = voxel_downsample(&points, size);
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = downsampled.len() as f32 / max_points.max(1) as f32;
+        if downsampled.len() < points.len() {
+            points = downsampled;
+            voxel_size = Some(size);
+        }
+        // A surface holds points in inverse proportion to the square of
+        // their spacing.
+        size *= ratio.sqrt().max(1.25);
+    }
+
+    let config = ReconstructionConfig {
+        radius: voxel_size.map_or(config.radius, |size| size.max(config.radius)),
+        ..config.clone()
+    };
+    let reconstruction = reconstruct_with_config(&points, &config)?;
+    Ok(Preview {
+        reconstruction,
+        points,
+        voxel_size,
+    })
+}
+
+/// Replaces the points in each cube `voxel_size` wide by their mean.
+///
+/// The normal of each new point is the normalized sum of the normals it
+/// replaces, or zero when they cancel. The new points are ordered by the
+/// first point of each cube.
+#[must_use]
+pub fn voxel_downsample<P: Borrow<Point>>(points: &[P], voxel_size: f32) -> Vec<Point> {
+    let mut voxels: HashMap<IVec3, usize> = HashMap::new();
+    let mut sums: Vec<(Vec3, Vec3, u32)> = vec![];
+    for p in points {
+        let p = p.borrow();
+        let key = (p.pos / voxel_size).floor().as_ivec3();
+        let index = *voxels.entry(key).or_insert_with(|| {
+            sums.push((Vec3::ZERO, Vec3::ZERO, 0));
+            sums.len() - 1
+        });
+        let (pos, normal, count) = &mut sums[index];
+        *pos += p.pos;
+        *normal += p.normal;
+        *count += 1;
+    }
+    sums.into_iter()
+        .map(|(pos, normal, count)| Point {
+            #[allow(clippy::cast_precision_loss)]
+            pos: pos / count as f32,
+            normal: normal.normalize_or_zero(),
+        })
+        .collect()
+}
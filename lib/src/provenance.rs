@@ -0,0 +1,139 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::ReconstructionConfig;
+use crate::units::Unit;
+
+/// Metadata recording how an output file was produced.
+///
+/// Written as comments in PLY files and packed into the header of binary
+/// STL files, so a mesh can be traced back to the run that produced it.
+#[derive(Clone, Debug)]
+pub struct Provenance {
+    /// Version of this crate.
+    pub version: &'static str,
+    /// Parameters of the reconstruction.
+    pub config: Option<ReconstructionConfig>,
+    /// The input file.
+    pub input: Option<PathBuf>,
+    /// FNV-1a hash of the input file's contents.
+    pub input_hash: Option<u64>,
+    /// The unit of the coordinates in the file.
+    pub unit: Option<Unit>,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl Provenance {
+    /// Metadata for a reconstruction with `config`, run now.
+    #[must_use]
+    pub fn new(config: &ReconstructionConfig) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            config: Some(config.clone()),
+            input: None,
+            input_hash: None,
+            unit: None,
+            timestamp,
+        }
+    }
+
+    /// Records the input file, and a hash of its contents.
+    ///
+    /// # Errors
+    ///   When the file cannot be read.
+    pub fn with_input(self, path: &Path) -> std::io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut buffer = [0_u8; 8192];
+        loop {
+            let n = reader.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            hash = fnv1a(hash, &buffer[..n]);
+        }
+        Ok(Self {
+            input: Some(path.to_path_buf()),
+            input_hash: Some(hash),
+            ..self
+        })
+    }
+
+    /// Records the unit of the coordinates.
+    #[must_use]
+    pub fn with_unit(self, unit: Unit) -> Self {
+        Self {
+            unit: Some(unit),
+            ..self
+        }
+    }
+
+    /// The metadata as `key value` lines, suitable for file comments.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("bpa_rs {}", self.version)];
+        if let Some(config) = &self.config {
+            lines.push(format!("radius {}", config.radius));
+            lines.push(format!("config {config:?}"));
+        }
+        if let Some(unit) = self.unit {
+            lines.push(format!("unit {unit}"));
+        }
+        if let Some(input) = &self.input {
+            lines.push(format!("input {}", input.display()));
+        }
+        if let Some(hash) = self.input_hash {
+            lines.push(format!("input_fnv1a {hash:016x}"));
+        }
+        lines.push(format!("timestamp {}", self.timestamp));
+        lines
+    }
+
+    /// The metadata packed into the 80 byte header of a binary STL file.
+    ///
+    /// Only the version, radius, unit, input hash and timestamp fit; the rest
+    /// is dropped. Unused bytes are spaces.
+    #[must_use]
+    pub fn stl_header(&self) -> [u8; 80] {
+        let text = self.stl_fields();
+        let mut header = [b' '; 80];
+        let len = text.len().min(header.len());
+        header[..len].copy_from_slice(&text.as_bytes()[..len]);
+        header
+    }
+
+    // The fields of `stl_header()`, on one line.
+    pub(crate) fn stl_fields(&self) -> String {
+        let mut fields = vec![format!("bpa_rs {}", self.version)];
+        if let Some(config) = &self.config {
+            fields.push(format!("radius={}", config.radius));
+        }
+        if let Some(unit) = self.unit {
+            fields.push(format!("unit={unit}"));
+        }
+        if let Some(hash) = self.input_hash {
+            fields.push(format!("input={hash:016x}"));
+        }
+        fields.push(format!("t={}", self.timestamp));
+        fields.join(" ")
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &b in bytes {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
@@ -0,0 +1,213 @@
+use glam::Vec3;
+
+use crate::Triangle;
+use crate::math::mul_add;
+
+// The colour behind the mesh.
+const BACKGROUND: [u8; 3] = [255, 255, 255];
+// The colour of faces seen from the side their normal points to.
+const FRONT: Vec3 = Vec3::new(0.69, 0.75, 0.84);
+// The colour of faces seen from behind, so that holes and flipped triangles
+// stand out.
+const BACK: Vec3 = Vec3::new(0.91, 0.55, 0.24);
+// The share of the colour kept by faces seen edge on.
+const AMBIENT: f32 = 0.2;
+
+/// Where a mesh is viewed from, for [`render_png()`].
+///
+/// The projection is perspective, with the image as wide as the vertical
+/// field of view allows at its aspect ratio.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    /// The position of the camera.
+    pub eye: Vec3,
+    /// The point at the center of the image.
+    pub target: Vec3,
+    /// The direction that is up in the image.
+    pub up: Vec3,
+    /// The vertical field of view, in radians.
+    pub fov_y: f32,
+}
+
+impl Camera {
+    /// A camera looking along `direction` at the whole mesh, with its
+    /// bounding sphere filling the height of the image. Up is +z, unless
+    /// the camera looks along it, when it is +y.
+    ///
+    /// Returns `None` when there are no triangles, a vertex is not finite,
+    /// or `direction` is zero.
+    #[must_use]
+    pub fn framing(triangles: &[Triangle], direction: Vec3) -> Option<Self> {
+        // The field of view, a little narrower than a normal lens.
+        const FOV_Y: f32 = core::f32::consts::FRAC_PI_4;
+
+        let direction = direction.try_normalize()?;
+        let mut vertices = triangles.iter().flat_map(|t| t.0);
+        let first = vertices.next()?;
+        let (min, max) = vertices.fold((first, first), |(min, max), v| (min.min(v), max.max(v)));
+        if !(min.is_finite() && max.is_finite()) {
+            return None;
+        }
+        let target = (min + max) / 2.0;
+        // A flat or single point mesh still needs to be seen from outside.
+        let radius = (max - min).length().max(f32::EPSILON) / 2.0;
+        let distance = radius / (FOV_Y / 2.0).sin();
+        let up = if direction.cross(Vec3::Z).length_squared() > 1e-6 {
+            Vec3::Z
+        } else {
+            Vec3::Y
+        };
+        Some(Self {
+            eye: target - direction * distance,
+            target,
+            up,
+            fov_y: FOV_Y,
+        })
+    }
+}
+
+/// Draws the triangles as seen by the camera, and encodes the picture as a
+/// PNG file, `size` pixels wide and high.
+///
+/// Faces are shaded by a light at the camera, blue-grey from the front and
+/// orange from behind, on a white background. Triangles reaching behind the
+/// camera are not drawn. Each side of the image is at least one pixel.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+#[must_use]
+pub fn render_png(triangles: &[Triangle], camera: &Camera, size: (u32, u32)) -> Vec<u8> {
+    let (width, height) = (size.0.max(1), size.1.max(1));
+    let pixels = render(triangles, camera, width, height);
+    encode_png(&pixels, width, height)
+}
+
+// Rasterizes the triangles into rows of RGB pixels, top row first, keeping
+// the nearest face at each pixel.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn render(triangles: &[Triangle], camera: &Camera, width: u32, height: u32) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let mut pixels: Vec<u8> = BACKGROUND.repeat(w * h);
+    // The reciprocal of the depth at each pixel, zero where nothing is drawn.
+    let mut nearest = vec![0.0_f32; w * h];
+
+    let forward = (camera.target - camera.eye).normalize_or_zero();
+    let right = forward.cross(camera.up).normalize_or_zero();
+    let up = right.cross(forward);
+    let scale = 1.0 / (camera.fov_y / 2.0).tan();
+    let aspect = width as f32 / height as f32;
+    // Keeps triangles passing through the eye from dividing by zero.
+    let near = 1e-6;
+
+    for triangle in triangles {
+        let view = triangle.0.map(|p| {
+            let d = p - camera.eye;
+            Vec3::new(d.dot(right), d.dot(up), d.dot(forward))
+        });
+        if view.iter().any(|v| !(v.z > near && v.is_finite())) {
+            continue;
+        }
+        // Pixel coordinates, and the reciprocal of the depth.
+        let screen = view.map(|v| {
+            let x = f32::midpoint(v.x / v.z * scale / aspect, 1.0) * width as f32;
+            let y = mul_add(v.y / v.z, -scale, 1.0) / 2.0 * height as f32;
+            Vec3::new(x, y, 1.0 / v.z)
+        });
+        let [a, b, c] = screen;
+        let area = edge(a, b, c);
+        if area == 0.0 || !area.is_finite() {
+            continue;
+        }
+
+        let [p, q, r] = triangle.0;
+        let normal = (q - p).cross(r - p).normalize_or_zero();
+        let toward_eye = (camera.eye - (p + q + r) / 3.0).normalize_or_zero();
+        let facing = normal.dot(toward_eye);
+        let base = if facing >= 0.0 { FRONT } else { BACK };
+        let shade = base * mul_add(1.0 - AMBIENT, facing.abs(), AMBIENT);
+        let color = (shade * 255.0).round().to_array().map(|c| c as u8);
+
+        let min = a.min(b).min(c).max(Vec3::ZERO);
+        let max = a.max(b).max(c);
+        let (x0, y0) = (min.x.floor() as usize, min.y.floor() as usize);
+        let x1 = (max.x.ceil().max(0.0) as usize).min(w);
+        let y1 = (max.y.ceil().max(0.0) as usize).min(h);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let at = Vec3::new(x as f32 + 0.5, y as f32 + 0.5, 0.0);
+                let weights = [edge(b, c, at), edge(c, a, at), edge(a, b, at)].map(|e| e / area);
+                if weights.iter().any(|&e| e < 0.0) {
+                    continue;
+                }
+                let depth = Vec3::from(weights).dot(Vec3::new(a.z, b.z, c.z));
+                let i = y * w + x;
+                if depth > nearest[i] {
+                    nearest[i] = depth;
+                    pixels[i * 3..i * 3 + 3].copy_from_slice(&color);
+                }
+            }
+        }
+    }
+    pixels
+}
+
+// Twice the signed area of the triangle, in the image plane.
+fn edge(a: Vec3, b: Vec3, c: Vec3) -> f32 {
+    mul_add(b.x - a.x, c.y - a.y, -(b.y - a.y) * (c.x - a.x))
+}
+
+// Encodes 8 bit RGB pixels as a PNG file, with no filtering.
+fn encode_png(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    // A bit depth of 8, RGB colour, and the only compression, filter and
+    // interlace methods.
+    const HEADER: [u8; 5] = [8, 2, 0, 0, 0];
+
+    let row = width as usize * 3;
+    let mut raw = Vec::with_capacity(pixels.len() + height as usize);
+    for line in pixels.chunks(row) {
+        raw.push(0);
+        raw.extend_from_slice(line);
+    }
+
+    let mut png = SIGNATURE.to_vec();
+    let mut header = [width.to_be_bytes(), height.to_be_bytes()].concat();
+    header.extend_from_slice(&HEADER);
+    chunk(&mut png, *b"IHDR", &header);
+    chunk(
+        &mut png,
+        *b"IDAT",
+        &miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6),
+    );
+    chunk(&mut png, *b"IEND", &[]);
+    png
+}
+
+// Appends a PNG chunk: its length, type, data and checksum.
+fn chunk(png: &mut Vec<u8>, kind: [u8; 4], data: &[u8]) {
+    let length = u32::try_from(data.len()).unwrap_or(u32::MAX);
+    png.extend_from_slice(&length.to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(&kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// The CRC-32 used by PNG and zlib.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
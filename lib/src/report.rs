@@ -0,0 +1,226 @@
+use alloc::vec::Vec;
+
+use glam::Vec3;
+
+use crate::postprocess::Orientation;
+use crate::postprocess::SliverRemoval;
+
+/// The budget that ended a reconstruction early.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Budget {
+    /// `max_triangles` triangles were output.
+    Triangles,
+    /// The `time_budget` ran out.
+    Time,
+}
+
+/// Statistics describing a reconstruction.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// Number of input points.
+    pub points: usize,
+    /// Number of triangles output.
+    pub triangles: usize,
+    /// Indices of the input points that are not vertices of the mesh, in
+    /// ascending order.
+    pub orphans: Vec<usize>,
+    /// The ball radius used, after any escalation.
+    pub radius: f32,
+    /// Set when a budget stopped the reconstruction before the front was
+    /// exhausted. The mesh is then partial.
+    pub budget_exceeded: Option<Budget>,
+    /// Front edges left open because the ball had already been pivoted
+    /// around their points too many times, as when numeric jitter makes the
+    /// front cycle over the same edges. Like boundary edges, they border a
+    /// hole in the mesh.
+    pub stalled_edges: usize,
+    /// Faces found a second time, as when gluing reverse edges closes a
+    /// triangle already output. Only the first is kept.
+    pub duplicate_triangles: usize,
+    /// Front edges found alongside an active edge with the same start and
+    /// end, and merged into it rather than pivoted into an overlapping face.
+    /// Always zero with [`crate::config::CompatMode::Cpp`].
+    pub merged_edges: usize,
+    /// Indices of the points accepted with a normal facing away from their
+    /// triangle, in ascending order, when
+    /// [`crate::ReconstructionConfig::normal_disagreement`] is
+    /// [`crate::config::NormalDisagreement::Flag`].
+    pub flagged_points: Vec<usize>,
+    /// Triangles added by the boundary pass, see
+    /// [`crate::ReconstructionConfig::boundary_pass`].
+    pub bridged_triangles: usize,
+    /// How each triangle was found, in the order of the triangles, when
+    /// [`crate::ReconstructionConfig::record_pivots`] is set; otherwise
+    /// empty. Triangles reshaped by sliver removal have no record.
+    pub pivots: Vec<Option<PivotRecord>>,
+    /// Counts explaining the result.
+    pub diagnostics: Diagnostics,
+    /// The outcome of the sliver removal, when it was requested. The
+    /// vertices it collapsed away are counted as orphans.
+    pub slivers: Option<SliverRemoval>,
+    /// Pieces of the mesh removed by `keep_largest_component` and
+    /// `min_component_faces`. The points they held are counted as orphans.
+    pub removed_components: usize,
+    /// The outcome of the orientation pass, when it was requested.
+    pub orientation: Option<Orientation>,
+}
+
+impl Report {
+    /// The fraction of input points that are not vertices of the mesh.
+    ///
+    /// A high ratio is the clearest sign that the radius is too small.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn orphan_ratio(&self) -> f32 {
+        if self.points == 0 {
+            return 0.0;
+        }
+        self.orphans.len() as f32 / self.points as f32
+    }
+
+    // Adds the counts to the `metrics` recorder, if one is installed.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn record_metrics(&self) {
+        let count = |n: usize| u64::try_from(n).unwrap_or(u64::MAX);
+        let diagnostics = &self.diagnostics;
+        metrics::counter!("bpa_rs_triangles").increment(count(self.triangles));
+        metrics::counter!("bpa_rs_orphans").increment(count(self.orphans.len()));
+        metrics::counter!("bpa_rs_pivots").increment(count(diagnostics.pivots));
+        metrics::counter!("bpa_rs_boundary_edges").increment(count(diagnostics.boundary_edges));
+        metrics::counter!("bpa_rs_flagged_normals").increment(count(diagnostics.flagged_normals));
+        metrics::counter!("bpa_rs_flipped_normals").increment(count(diagnostics.flipped_normals));
+        metrics::counter!("bpa_rs_stalled_edges").increment(count(self.stalled_edges));
+        metrics::counter!("bpa_rs_duplicate_triangles").increment(count(self.duplicate_triangles));
+        metrics::counter!("bpa_rs_merged_edges").increment(count(self.merged_edges));
+        metrics::counter!("bpa_rs_bridged_triangles").increment(count(self.bridged_triangles));
+
+        let seed = diagnostics.seed_rejections;
+        for (reason, n) in [
+            ("normal", seed.normal),
+            ("edge_length", seed.edge_length),
+            ("no_ball_center", seed.no_ball_center),
+            ("ball_not_empty", seed.ball_not_empty),
+        ] {
+            metrics::counter!("bpa_rs_seed_rejections", "reason" => reason).increment(count(n));
+        }
+
+        let pivot = diagnostics.pivot_rejections;
+        for (reason, n) in [
+            ("normal_half_space", pivot.normal_half_space),
+            ("ball_above_triangle", pivot.ball_above_triangle),
+            ("existing_inner_edge", pivot.existing_inner_edge),
+            ("dihedral_angle", pivot.dihedral_angle),
+            ("edge_length", pivot.edge_length),
+            ("no_ball_center", pivot.no_ball_center),
+            ("ball_not_empty", pivot.ball_not_empty),
+        ] {
+            metrics::counter!("bpa_rs_pivot_rejections", "reason" => reason).increment(count(n));
+        }
+    }
+}
+
+/// The state of a reconstruction in progress, passed to
+/// [`crate::ReconstructionConfig::progress`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// Points in the cloud.
+    pub points: usize,
+    /// Edges allocated, whether on the front, inside the mesh or on its
+    /// boundary.
+    pub edges: usize,
+    /// Active edges on the front.
+    pub front: usize,
+    /// Triangles output so far.
+    pub triangles: usize,
+    /// An estimate of the bytes held by the grid, the points, the edges and
+    /// the front, and by the triangles not yet emitted.
+    pub bytes: usize,
+}
+
+/// The ball that found a triangle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PivotRecord {
+    /// The center of the ball resting on the triangle's vertices.
+    pub center: Vec3,
+    /// The angle the ball turned through about the front edge to reach the
+    /// triangle, in radians, or `None` for a seed triangle.
+    pub angle: Option<f32>,
+}
+
+/// Counts explaining how a reconstruction went, or why it failed.
+#[derive(Clone, Debug, Default)]
+pub struct Diagnostics {
+    /// Input points whose normal is zero or not finite. Their normals are
+    /// treated as unknown, and never reject a triangle.
+    pub unknown_normals: usize,
+    /// Input points whose normal was not unit length, and was normalized.
+    pub rescaled_normals: usize,
+    /// Grid cells searched for a seed triangle, over every attempt.
+    pub cells_scanned: usize,
+    /// Points with fewer than two neighbours within a ball diameter, for the
+    /// last radius tried. Such points can never be part of a triangle.
+    pub isolated_points: usize,
+    /// Candidate seed triangles, by the reason they were rejected, for the
+    /// last radius tried.
+    pub seed_rejections: SeedRejections,
+    /// Candidate points while pivoting, by the reason they were rejected.
+    pub pivot_rejections: PivotRejections,
+    /// Times the ball was pivoted around a front edge.
+    pub pivots: usize,
+    /// Front edges the ball could not pivot around.
+    pub boundary_edges: usize,
+    /// Triangles made with a point whose normal faced away from them,
+    /// accepted and flagged as set by
+    /// [`crate::ReconstructionConfig::normal_disagreement`]. Points rejected
+    /// instead are counted in [`PivotRejections::normal_half_space`].
+    pub flagged_normals: usize,
+    /// Triangles made with a point whose normal faced away from them, whose
+    /// normal was flipped.
+    pub flipped_normals: usize,
+    /// Mean distance between the points in the most populated grid cell and
+    /// their nearest neighbours.
+    ///
+    /// Only measured when no seed triangle is found, or when the front stalls
+    /// after the seed triangle. The radius should be comfortably larger than
+    /// this spacing.
+    pub densest_cell_spacing: Option<f32>,
+}
+
+/// Candidate seed triangles, by the reason they were rejected.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SeedRejections {
+    /// The triangle faces away from the average normal of its cell.
+    pub normal: usize,
+    /// The triangle has an edge longer than
+    /// [`crate::ReconstructionConfig::max_edge_length`].
+    pub edge_length: usize,
+    /// The triangle's circumcircle is larger than the ball.
+    pub no_ball_center: usize,
+    /// Other points lie inside the ball.
+    pub ball_not_empty: usize,
+}
+
+/// Candidate points while pivoting, by the reason they were rejected.
+///
+/// The first three counts are for the optional checks in
+/// [`crate::config::Heuristics`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PivotRejections {
+    /// The point's normal faces away from the new triangle.
+    pub normal_half_space: usize,
+    /// The ball center lies beneath the new triangle.
+    pub ball_above_triangle: usize,
+    /// The point is already joined to the edge by an inner edge.
+    pub existing_inner_edge: usize,
+    /// The new triangle folds further than
+    /// [`crate::ReconstructionConfig::max_dihedral_angle`] from the
+    /// triangle across the edge.
+    pub dihedral_angle: usize,
+    /// The new triangle has an edge longer than
+    /// [`crate::ReconstructionConfig::max_edge_length`].
+    pub edge_length: usize,
+    /// The new triangle's circumcircle is larger than the ball.
+    pub no_ball_center: usize,
+    /// The ball at the smallest pivot angle contains other points.
+    pub ball_not_empty: usize,
+}
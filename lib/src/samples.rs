@@ -0,0 +1,53 @@
+use glam::Vec3;
+
+use crate::Point;
+
+// Every tenth line of `data/bunny.xyz`, at the root of the repository.
+const BUNNY: &str = include_str!("../samples/bunny.xyz");
+
+/// A ball radius that meshes [`bunny()`] with few holes.
+pub const BUNNY_RADIUS: f32 = 0.005;
+
+/// A subset of the Stanford bunny: 3595 points with normals, every tenth of
+/// the scan in `data/bunny.xyz`, about 0.15 units across. Mesh it with
+/// [`BUNNY_RADIUS`].
+#[must_use]
+pub fn bunny() -> Vec<Point> {
+    BUNNY
+        .lines()
+        .filter_map(|line| {
+            let mut values = line.split_whitespace().map(str::parse::<f32>);
+            let mut next = || values.next()?.ok();
+            let pos = Vec3::new(next()?, next()?, next()?);
+            let normal = Vec3::new(next()?, next()?, next()?);
+            Some(Point { pos, normal })
+        })
+        .collect()
+}
+
+/// Points on the unit sphere around the origin, with outward normals: one
+/// at each pole, and `stacks - 1` rings of `slices` points between them,
+/// evenly spaced in longitude and latitude.
+///
+/// A radius of 0.3 meshes the 36 by 18 sphere. Smaller spacings need a
+/// proportionally smaller radius.
+#[must_use]
+pub fn sphere(slices: u32, stacks: u32) -> Vec<Point> {
+    let pole = |z: f32| Point {
+        pos: Vec3::new(0.0, 0.0, z),
+        normal: Vec3::new(0.0, 0.0, z),
+    };
+    let mut points = vec![pole(-1.0)];
+    for slice in 0..slices {
+        for stack in 1..stacks {
+            let yaw = f64::from(slice) / f64::from(slices) * core::f64::consts::TAU;
+            let z = ((f64::from(stack) / f64::from(stacks) - 0.5) * core::f64::consts::PI).sin();
+            let r = (1.0 - z * z).sqrt();
+            #[allow(clippy::cast_possible_truncation)]
+            let pos = Vec3::new((r * yaw.sin()) as f32, (r * yaw.cos()) as f32, z as f32);
+            points.push(Point { pos, normal: pos });
+        }
+    }
+    points.push(pole(1.0));
+    points
+}
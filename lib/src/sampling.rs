@@ -0,0 +1,50 @@
+use alloc::vec::Vec;
+
+use crate::Point;
+use crate::Triangle;
+use crate::math::SplitMix64;
+use crate::math::sqrt;
+
+/// Returns `count` points scattered uniformly over the surface of a mesh,
+/// each with the normal of its triangle.
+///
+/// Triangles are chosen in proportion to their area, so the density of the
+/// points is even across the mesh. The same `seed` gives the same points.
+/// Returns no points when the mesh has no area.
+#[must_use]
+pub fn sample_surface(triangles: &[Triangle], count: usize, seed: u64) -> Vec<Point> {
+    // The running total of the areas, doubled.
+    let mut total = 0.0_f64;
+    let cumulative: Vec<f64> = triangles
+        .iter()
+        .map(|t| {
+            let cross = (t.0[1] - t.0[0]).cross(t.0[2] - t.0[0]);
+            let area = f64::from(cross.length());
+            if area.is_finite() {
+                total += area;
+            }
+            total
+        })
+        .collect();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut rng = SplitMix64(seed);
+    (0..count)
+        .map(|_| {
+            let target = f64::from(rng.next_f32()) * total;
+            let i = cumulative
+                .partition_point(|&c| c <= target)
+                .min(triangles.len() - 1);
+            let [a, b, c] = triangles[i].0;
+            // Uniform barycentric coordinates.
+            let r = sqrt(rng.next_f32());
+            let s = rng.next_f32();
+            Point {
+                pos: a * (1.0 - r) + b * (r * (1.0 - s)) + c * (r * s),
+                normal: (b - a).cross(c - a).normalize_or_zero(),
+            }
+        })
+        .collect()
+}
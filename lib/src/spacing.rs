@@ -0,0 +1,185 @@
+use core::borrow::Borrow;
+use std::collections::HashMap;
+
+use glam::IVec3;
+use glam::Vec3;
+
+use crate::Point;
+
+/// Ball radii suggested by the spacing of a point cloud, from
+/// [`estimate_radius()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RadiusEstimate {
+    /// Bridges the gaps in the densest tenth of the cloud. Smaller radii
+    /// leave holes almost everywhere.
+    pub min: f32,
+    /// Bridges the gaps around the median point. A good first radius to try.
+    pub typical: f32,
+    /// Bridges the gaps in all but the sparsest tenth of the cloud. Larger
+    /// radii lose detail and slow the reconstruction.
+    pub max: f32,
+    /// The number of points whose neighbours were measured.
+    pub sampled: usize,
+}
+
+/// Suggests ball radii from the distances between the points and their `k`
+/// nearest neighbours, without running a reconstruction.
+///
+/// At most `sample` points, spread evenly through the input, are measured.
+/// Returns `None` when there are fewer than `k + 1` points, or `k` or
+/// `sample` is zero.
+#[must_use]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = points.len()))
+)]
+pub fn estimate_radius<P: Borrow<Point>>(
+    points: &[P],
+    k: usize,
+    sample: usize,
+) -> Option<RadiusEstimate> {
+    if k == 0 || sample == 0 || points.len() <= k {
+        return None;
+    }
+    let positions: Vec<Vec3> = points.iter().map(|p| p.borrow().pos).collect();
+    let index = NeighbourIndex::new(&positions);
+
+    // The mean distance from each sampled point to its neighbours.
+    let stride = positions.len().div_ceil(sample);
+    let mut spacings: Vec<f32> = (0..positions.len())
+        .step_by(stride)
+        .filter_map(|i| index.spacing(i, k))
+        .collect();
+    if spacings.is_empty() {
+        return None;
+    }
+    spacings.sort_by(f32::total_cmp);
+
+    // The mean distance to the neighbours is a little over the gap between
+    // adjacent points, so a ball of that radius rests on them without
+    // falling through.
+    let radius = |percentile: usize| spacings[(spacings.len() - 1) * percentile / 100];
+    Some(RadiusEstimate {
+        min: radius(10),
+        typical: radius(50),
+        max: radius(90),
+        sampled: spacings.len(),
+    })
+}
+
+// The points bucketed into cubes around the expected spacing, for
+// nearest neighbour queries.
+pub(crate) struct NeighbourIndex<'a> {
+    positions: &'a [Vec3],
+    lower: Vec3,
+    cell_size: f32,
+    cells: HashMap<IVec3, Vec<usize>>,
+    // The largest ring of cells worth searching.
+    max_ring: i32,
+}
+
+impl<'a> NeighbourIndex<'a> {
+    pub(crate) fn new(positions: &'a [Vec3]) -> Self {
+        let lower = positions.iter().copied().fold(Vec3::INFINITY, Vec3::min);
+        let upper = positions
+            .iter()
+            .copied()
+            .fold(Vec3::NEG_INFINITY, Vec3::max);
+        let extent = upper - lower;
+
+        // Scanned surfaces spread their points over an area, so the spacing
+        // is roughly the size of the cloud over the square root of the count.
+        #[allow(clippy::cast_precision_loss)]
+        let spacing = extent.max_element() / (positions.len() as f32).sqrt();
+        let cell_size = if spacing > 0.0 { spacing } else { 1.0 };
+
+        let cell = |p: Vec3| ((p - lower) / cell_size).floor().as_ivec3();
+        let mut cells: HashMap<IVec3, Vec<usize>> = HashMap::new();
+        for (i, p) in positions.iter().enumerate() {
+            cells.entry(cell(*p)).or_default().push(i);
+        }
+        let max_ring = cell(upper).max_element().saturating_add(1);
+
+        Self {
+            positions,
+            lower,
+            cell_size,
+            cells,
+            max_ring,
+        }
+    }
+
+    // The mean distance from point `i` to its `k` nearest neighbours, or
+    // `None` when it has none.
+    pub(crate) fn spacing(&self, i: usize, k: usize) -> Option<f32> {
+        let nearest = self.nearest(i, k);
+        #[allow(clippy::cast_precision_loss)]
+        (!nearest.is_empty())
+            .then(|| nearest.iter().map(|(d, _)| d).sum::<f32>() / nearest.len() as f32)
+    }
+
+    // The distances from point `i` to its `k` nearest neighbours, and their
+    // indices, nearest first.
+    pub(crate) fn nearest(&self, i: usize, k: usize) -> Vec<(f32, usize)> {
+        let p = self.positions[i];
+        let center = ((p - self.lower) / self.cell_size).floor().as_ivec3();
+        let mut nearest: Vec<(f32, usize)> = Vec::with_capacity(k + 1);
+
+        for ring in 0..=self.max_ring {
+            // Points in this ring and beyond are at least this far away.
+            #[allow(clippy::cast_precision_loss)]
+            let reach = (ring - 1) as f32 * self.cell_size;
+            if nearest.len() == k && nearest[k - 1].0 <= reach {
+                break;
+            }
+            // Once a ring spans more cells than are occupied, as around an
+            // outlier, the occupied cells from here on are visited instead.
+            let side = 2 * i64::from(ring) + 1;
+            #[allow(clippy::cast_possible_wrap)]
+            if side.saturating_mul(side).saturating_mul(side) > self.cells.len() as i64 {
+                for (cell, ids) in &self.cells {
+                    let distance = (cell.as_i64vec3() - center.as_i64vec3()).abs();
+                    if distance.max_element() >= i64::from(ring) {
+                        self.offer(i, k, ids, &mut nearest);
+                    }
+                }
+                break;
+            }
+            for offset in ring_offsets(ring) {
+                if let Some(ids) = self.cells.get(&center.wrapping_add(offset)) {
+                    self.offer(i, k, ids, &mut nearest);
+                }
+            }
+        }
+        nearest
+    }
+
+    // Adds the points `ids` to the `k` nearest to point `i` found so far,
+    // when they are nearer.
+    fn offer(&self, i: usize, k: usize, ids: &[usize], nearest: &mut Vec<(f32, usize)>) {
+        let p = self.positions[i];
+        for &j in ids {
+            if j == i {
+                continue;
+            }
+            let d = p.distance(self.positions[j]);
+            if nearest.len() < k || d < nearest[k - 1].0 {
+                let at = nearest.partition_point(|&(n, _)| n <= d);
+                nearest.insert(at, (d, j));
+                nearest.truncate(k);
+            }
+        }
+    }
+}
+
+// Offsets of the cells on the surface of the cube `ring` cells from the
+// center.
+fn ring_offsets(ring: i32) -> impl Iterator<Item = IVec3> {
+    (-ring..=ring).flat_map(move |x| {
+        (-ring..=ring).flat_map(move |y| {
+            (-ring..=ring)
+                .map(move |z| IVec3::new(x, y, z))
+                .filter(move |o| o.abs().max_element() == ring)
+        })
+    })
+}
@@ -0,0 +1,164 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::analysis::CellDensity;
+use crate::analysis::density_stats;
+use crate::analysis::estimate_curvature;
+use crate::analysis::fit_plane;
+use crate::analysis::fit_sphere;
+
+// A Fibonacci sphere, with outward normals.
+fn sphere(radius: f32, n: u16) -> Vec<Point> {
+    (0..n)
+        .map(|i| {
+            let i = f32::from(i);
+            let z = 1.0 - 2.0 * (i + 0.5) / f32::from(n);
+            let r = (1.0 - z * z).sqrt();
+            let theta = i * 2.399_963;
+            let normal = Vec3::new(r * theta.cos(), r * theta.sin(), z);
+            Point {
+                pos: normal * radius,
+                normal,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn sphere_curvature() {
+    let curvatures = estimate_curvature(&sphere(2.0, 2000), 12).expect("enough points");
+    assert_eq!(curvatures.len(), 2000);
+    for c in curvatures {
+        assert!((c.k1 - 0.5).abs() < 0.05, "{c:?}");
+        assert!((c.k2 - 0.5).abs() < 0.05, "{c:?}");
+        assert!((c.mean() - 0.5).abs() < 0.05);
+        assert!((c.gaussian() - 0.25).abs() < 0.05);
+        assert!(c.variation < 0.01);
+    }
+}
+
+#[test]
+fn cylinder_and_plane() {
+    let mut points = vec![];
+    for i in 0..60_u8 {
+        for j in 0..20_u8 {
+            let angle = f32::from(i) * core::f32::consts::TAU / 60.0;
+            let normal = Vec3::new(angle.cos(), angle.sin(), 0.0);
+            points.push(Point {
+                pos: normal * 3.0 + Vec3::Z * f32::from(j) * 0.3,
+                normal,
+            });
+        }
+    }
+    // Without normals the sign of the curvature is arbitrary.
+    for i in 0..20_u8 {
+        for j in 0..20_u8 {
+            points.push(Point {
+                pos: Vec3::new(f32::from(i), f32::from(j), 0.0) * 0.3 + Vec3::X * 10.0,
+                normal: Vec3::ZERO,
+            });
+        }
+    }
+    let curvatures = estimate_curvature(&points, 8).expect("enough points");
+
+    // Away from the ends of the cylinder.
+    for c in curvatures[..1200].iter().skip(3).step_by(20).take(60) {
+        assert!((c.k1 - 1.0 / 3.0).abs() < 0.03, "{c:?}");
+        assert!(c.k2.abs() < 0.03, "{c:?}");
+    }
+    for c in &curvatures[1200..] {
+        assert!(c.k1.abs() < 1e-3 && c.k2.abs() < 1e-3, "{c:?}");
+        assert!(c.variation < 1e-6);
+    }
+}
+
+#[test]
+fn too_few_points() {
+    let points = sphere(1.0, 5);
+    assert!(estimate_curvature(&points, 5).is_none());
+    assert!(estimate_curvature(&points, 2).is_none());
+    assert!(estimate_curvature(&points, 4).is_some());
+}
+
+#[test]
+fn density() {
+    // A dense square beside a sparse one.
+    let mut points = vec![];
+    for (origin, step) in [(Vec3::ZERO, 0.1), (Vec3::X * 16.0, 0.4)] {
+        for i in 0..20_u8 {
+            for j in 0..20_u8 {
+                points.push(Point {
+                    pos: origin + Vec3::new(f32::from(i), f32::from(j), 0.0) * step,
+                    normal: Vec3::Z,
+                });
+            }
+        }
+    }
+    let stats = density_stats(&points).expect("enough points");
+
+    assert_eq!(stats.global.points, 800);
+    assert_eq!(stats.histogram.iter().sum::<usize>(), 800);
+    assert!(stats.global.min > 0.1 && stats.global.max < 0.4 * 2.0);
+    assert!(stats.global.min <= stats.global.median && stats.global.median <= stats.global.p95);
+    // The cloud is 23.6 long.
+    assert!((stats.cell_size - 23.6 / 8.0).abs() < 1e-4);
+
+    let (dense, sparse): (Vec<&CellDensity>, Vec<&CellDensity>) =
+        stats.cells.iter().partition(|c| c.lower.x < 8.0);
+    assert_eq!(dense.len(), 1);
+    assert_eq!(sparse.iter().map(|c| c.spacing.points).sum::<usize>(), 400);
+    assert!(
+        sparse
+            .iter()
+            .all(|c| c.spacing.median > 3.0 * dense[0].spacing.median)
+    );
+
+    assert!(density_stats(&points[..8]).is_none());
+}
+
+#[test]
+fn sphere_fit() {
+    let center = Vec3::new(1.0, -2.0, 30.0);
+    // A cap, as a scanner sees a calibration sphere, with a little noise.
+    let points: Vec<Vec3> = sphere(12.5, 2000)
+        .iter()
+        .filter(|p| p.normal.z > 0.2)
+        .enumerate()
+        .map(|(i, p)| center + p.pos + p.normal * if i % 2 == 0 { 0.01 } else { -0.01 })
+        .collect();
+    let fit = fit_sphere(&points).expect("a sphere");
+
+    assert!(fit.center.distance(center) < 1e-3, "{:?}", fit.center);
+    assert!((fit.radius - 12.5).abs() < 1e-3, "{}", fit.radius);
+    assert_eq!(fit.residuals.len(), points.len());
+    assert!((fit.rms() - 0.01).abs() < 1e-3, "{}", fit.rms());
+    assert!(fit.max_residual() < 0.012);
+
+    assert!(fit_sphere(&points[..3]).is_none());
+    let flat: Vec<Vec3> = points.iter().map(|p| p.with_z(0.0)).collect();
+    assert!(fit_sphere(&flat).is_none());
+}
+
+#[test]
+fn plane_fit() {
+    let normal = Vec3::new(1.0, 2.0, 2.0) / 3.0;
+    let (u, v) = normal.any_orthonormal_pair();
+    let mut points: Vec<Vec3> = (0..100_u8)
+        .map(|i| {
+            let (x, y) = (f32::from(i % 10), f32::from(i / 10));
+            Vec3::new(5.0, 0.0, -1.0) + u * x + v * y
+        })
+        .collect();
+    // One point off the plane, near its middle.
+    points[55] += normal * 0.5;
+    let fit = fit_plane(&points).expect("a plane");
+
+    assert!(fit.normal.dot(normal).abs() > 0.999, "{:?}", fit.normal);
+    let off = fit.residuals[55] * fit.normal.dot(normal).signum();
+    assert!((off - 0.495).abs() < 0.01, "{off}");
+    assert!(fit.max_residual() >= off);
+
+    let line: Vec<Vec3> = (0..10_u8).map(|i| Vec3::X * f32::from(i)).collect();
+    assert!(fit_plane(&line).is_none());
+    assert!(fit_plane(&points[..2]).is_none());
+}
@@ -0,0 +1,53 @@
+use std::path::Path;
+
+#[cfg(any(feature = "ply", feature = "stl"))]
+use glam::Vec3;
+
+#[cfg(feature = "stl")]
+use crate::Triangle;
+use crate::async_io;
+use crate::io;
+
+#[tokio::test]
+async fn load_xyz_matches_sync() {
+    let path = Path::new("../data/bunny.xyz");
+    let sync = io::load_xyz(path).expect("Cannot load bunny");
+    let loaded = async_io::load_xyz(path).await.expect("Cannot load bunny");
+    assert_eq!(format!("{loaded:?}"), format!("{sync:?}"));
+}
+
+#[cfg(feature = "ply")]
+#[tokio::test]
+async fn read_ply_from_stream() {
+    let ply = b"ply
+format ascii 1.0
+comment uploaded
+element vertex 2
+property float x
+property float y
+property float z
+end_header
+1 2 3
+4 5 6
+";
+    let points = async_io::read_ply(&ply[..]).await.expect("Cannot read ply");
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[1].pos, Vec3::new(4.0, 5.0, 6.0));
+}
+
+#[cfg(feature = "stl")]
+#[tokio::test]
+async fn write_stl_matches_save_triangles() {
+    let triangles = [
+        Triangle([Vec3::ZERO, Vec3::X, Vec3::Y]),
+        Triangle([Vec3::ZERO, Vec3::Y, Vec3::Z]),
+    ];
+    let mut written = vec![];
+    async_io::write_stl(&mut written, &triangles)
+        .await
+        .expect("write failed");
+
+    let path = std::env::temp_dir().join("bpa_rs_async_io/saved.stl");
+    io::save_triangles(&path, &triangles).expect("write failed");
+    assert!(written == std::fs::read(&path).unwrap());
+}
@@ -0,0 +1,113 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glam::Vec3;
+
+use crate::Point;
+use crate::Triangle;
+use crate::config::DEFAULT_EMPTY_BALL_TOLERANCE;
+use crate::grid::PackedPositions;
+use crate::grid::ball_is_empty;
+use crate::mesh::MeshPoint;
+use crate::reconstruct;
+use crate::test::reconstruct::create_spherical_cloud;
+
+fn points(positions: &[Vec3]) -> Vec<Rc<RefCell<MeshPoint>>> {
+    positions
+        .iter()
+        .map(|p| Rc::new(RefCell::new(MeshPoint::new(*p))))
+        .collect()
+}
+
+#[test]
+fn empty_neighborhood() {
+    let packed = PackedPositions::new(&[]);
+    assert!(ball_is_empty(
+        &Vec3::ZERO,
+        &packed,
+        1.0,
+        DEFAULT_EMPTY_BALL_TOLERANCE
+    ));
+}
+
+// The point inside the ball must be found in every lane and in the padded tail.
+#[test]
+fn detects_point_in_any_lane() {
+    for len in 1..=9 {
+        for inside in 0..len {
+            let mut positions = vec![Vec3::new(10.0, 0.0, 0.0); len];
+            positions[inside] = Vec3::new(0.5, 0.0, 0.0);
+            let packed = PackedPositions::new(&points(&positions));
+            assert!(
+                !ball_is_empty(&Vec3::ZERO, &packed, 1.0, DEFAULT_EMPTY_BALL_TOLERANCE),
+                "missed point {inside} of {len}"
+            );
+        }
+    }
+}
+
+#[test]
+fn points_on_the_surface_are_outside() {
+    let positions = [
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, -1.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec3::new(0.0, 0.0, -1.0),
+        Vec3::new(-1.0, 0.0, 0.0),
+    ];
+    let packed = PackedPositions::new(&points(&positions));
+    assert!(ball_is_empty(
+        &Vec3::ZERO,
+        &packed,
+        1.0,
+        DEFAULT_EMPTY_BALL_TOLERANCE
+    ));
+}
+
+// The same points, in any unit, are on the surface or inside alike.
+#[test]
+fn tolerance_scales_with_radius() {
+    for scale in [1e-3, 1.0, 1e3] {
+        let on_surface = points(&[Vec3::new(0.999_99 * scale, 0.0, 0.0)]);
+        let inside = points(&[Vec3::new(0.999 * scale, 0.0, 0.0)]);
+        let empty = |points| {
+            ball_is_empty(
+                &Vec3::ZERO,
+                &PackedPositions::new(points),
+                scale,
+                DEFAULT_EMPTY_BALL_TOLERANCE,
+            )
+        };
+        assert!(empty(&on_surface), "{scale}");
+        assert!(!empty(&inside), "{scale}");
+    }
+}
+
+// Scaling by a power of two is exact, so only tolerances or a grid in
+// absolute units could tell the clouds apart.
+#[test]
+fn reconstruction_is_unit_independent() {
+    let cloud = create_spherical_cloud(36, 18);
+    let triangles = |scale: f32| {
+        let scaled: Vec<Point> = cloud
+            .iter()
+            .map(|p| Point {
+                pos: p.pos * scale,
+                normal: p.normal,
+            })
+            .collect();
+        reconstruct(&scaled, 0.3 * scale).expect("must generate a mesh")
+    };
+    let expected = triangles(1.0);
+    for scale in [1.0 / 1024.0, 0.125, 8.0, 1024.0] {
+        let scaled: Vec<Triangle> = expected
+            .iter()
+            .map(|t| Triangle(t.0.map(|v| v * scale)))
+            .collect();
+        assert_eq!(
+            format!("{:?}", triangles(scale)),
+            format!("{scaled:?}"),
+            "{scale}"
+        );
+    }
+}
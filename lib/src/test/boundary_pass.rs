@@ -0,0 +1,70 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::reconstruct_with_config;
+use crate::topology::IndexedMesh;
+
+// A square sheet of points 0.1 apart, with the point in the middle missing.
+// Its neighbours leave a hole that balls smaller than 0.1 fall through.
+fn pierced_sheet() -> Vec<Point> {
+    let mut points = vec![];
+    for i in 0..20 {
+        for j in 0..20 {
+            if (i, j) != (9, 9) {
+                points.push(Point {
+                    pos: Vec3::new(i as f32 * 0.1, j as f32 * 0.1, 0.0),
+                    normal: Vec3::Z,
+                });
+            }
+        }
+    }
+    points
+}
+
+// The number of holes and outer borders of the mesh.
+fn boundary_loops(triangles: &[crate::Triangle]) -> usize {
+    IndexedMesh::welded(triangles, 1e-6)
+        .half_edges()
+        .boundary_loops()
+        .len()
+}
+
+#[test]
+fn closes_pinholes() {
+    let cloud = pierced_sheet();
+    let config = ReconstructionConfig::new(0.08);
+
+    let open = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    assert_eq!(boundary_loops(&open.triangles), 2);
+    assert_eq!(open.report.bridged_triangles, 0);
+
+    let config = ReconstructionConfig {
+        boundary_pass: Some(1.5),
+        ..config
+    };
+    let closed = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    assert_eq!(boundary_loops(&closed.triangles), 1);
+    assert_eq!(closed.report.bridged_triangles, 2);
+    assert_eq!(
+        closed.report.triangles,
+        open.report.triangles + closed.report.bridged_triangles
+    );
+    // The main pass keeps its radius.
+    assert!((closed.report.radius - 0.08).abs() < f32::EPSILON);
+}
+
+#[test]
+fn factor_bounds_the_holes() {
+    let cloud = pierced_sheet();
+    for factor in [0.5, 1.0, 1.2] {
+        let config = ReconstructionConfig {
+            boundary_pass: Some(factor),
+            ..ReconstructionConfig::new(0.08)
+        };
+        let reconstruction =
+            reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+        assert_eq!(boundary_loops(&reconstruction.triangles), 2, "{factor}");
+        assert_eq!(reconstruction.report.bridged_triangles, 0, "{factor}");
+    }
+}
@@ -0,0 +1,76 @@
+use core::time::Duration;
+
+use crate::ReconstructionConfig;
+use crate::config::RadiusEscalation;
+use crate::reconstruct;
+use crate::reconstruct_with_config;
+use crate::report::Budget;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn unlimited() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    let expected = reconstruct(&cloud, 0.3).expect("must generate a mesh");
+    assert_eq!(reconstruction.report.budget_exceeded, None);
+    assert_eq!(reconstruction.report.triangles, expected.len());
+}
+
+#[test]
+fn max_triangles() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        max_triangles: Some(100),
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    let full = reconstruct(&cloud, 0.3).expect("must generate a mesh");
+    assert_eq!(
+        reconstruction.report.budget_exceeded,
+        Some(Budget::Triangles)
+    );
+    assert_eq!(reconstruction.triangles.len(), 100);
+    assert_eq!(reconstruction.report.triangles, 100);
+    // The partial mesh is the start of the full one.
+    assert_eq!(
+        format!("{:?}", reconstruction.triangles),
+        format!("{:?}", &full[..100])
+    );
+}
+
+#[test]
+fn exhausted_time_budget_stops_the_seed_search() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        time_budget: Some(Duration::ZERO),
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("a partial mesh");
+    assert_eq!(reconstruction.report.budget_exceeded, Some(Budget::Time));
+    assert!(reconstruction.triangles.is_empty());
+    assert_eq!(reconstruction.report.diagnostics.cells_scanned, 0);
+}
+
+#[test]
+fn exhausted_time_budget_stops_radius_escalation() {
+    // No radius tried finds a seed, so without a budget the search would
+    // fail with NoSeed.
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        time_budget: Some(Duration::ZERO),
+        radius_escalation: Some(RadiusEscalation {
+            factor: 1.1,
+            max_radius: 0.06,
+        }),
+        ..ReconstructionConfig::new(0.01)
+    };
+
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("a partial mesh");
+    assert_eq!(reconstruction.report.budget_exceeded, Some(Budget::Time));
+    // Stopped before growing the ball.
+    assert!(reconstruction.report.radius < 0.011);
+}
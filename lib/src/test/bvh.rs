@@ -0,0 +1,112 @@
+use glam::Vec3;
+
+use crate::Reconstruction;
+use crate::ReconstructionConfig;
+use crate::bvh::Bvh;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+fn sphere() -> Reconstruction {
+    let cloud = create_spherical_cloud(36, 18);
+    reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3)).expect("must generate a mesh")
+}
+
+// Points around and inside the unit sphere.
+fn probes() -> Vec<Vec3> {
+    (0..200_u16)
+        .map(|i| {
+            let i = f32::from(i);
+            Vec3::new((i * 0.37).sin(), (i * 0.53).cos(), (i * 0.11).sin()) * (0.2 + i / 100.0)
+        })
+        .collect()
+}
+
+#[test]
+fn closest_point_matches_brute_force() {
+    let triangles = sphere().triangles;
+    let bvh = Bvh::new(&triangles);
+    for p in probes() {
+        let closest = bvh.closest_point(p).expect("the mesh is not empty");
+        let brute = triangles
+            .iter()
+            .map(|t| {
+                let single = Bvh::new(std::slice::from_ref(t));
+                single.distance(p).expect("one triangle")
+            })
+            .fold(f32::INFINITY, f32::min);
+        assert!((closest.distance - brute).abs() < 1e-5);
+        assert!((closest.point.distance(p) - closest.distance).abs() < 1e-5);
+        let face = Bvh::new(std::slice::from_ref(&triangles[closest.face]));
+        assert!(face.distance(closest.point).expect("one triangle") < 1e-5);
+    }
+}
+
+#[test]
+fn distance_to_sphere() {
+    let bvh = sphere().build_bvh();
+    // The mesh is inscribed in the unit sphere, so lies just inside it.
+    let distance = bvh.distance(Vec3::new(0.0, 0.0, 3.0)).expect("not empty");
+    assert!((distance - 2.0).abs() < 1e-4);
+    let distance = bvh.distance(Vec3::ZERO).expect("not empty");
+    assert!(distance > 0.95 && distance <= 1.0);
+}
+
+#[test]
+fn raycast() {
+    let bvh = sphere().build_bvh();
+    for direction in [Vec3::X, Vec3::NEG_Y, Vec3::new(1.0, 1.0, 1.0).normalize()] {
+        let hit = bvh
+            .raycast(Vec3::ZERO, direction)
+            .expect("inside the sphere");
+        assert!(hit.distance > 0.95 && hit.distance <= 1.0);
+
+        // From outside, the near side is hit first.
+        let outside = bvh
+            .raycast(direction * -3.0, direction)
+            .expect("aimed at the sphere");
+        assert!((outside.distance - (3.0 - hit.distance)).abs() < 0.05);
+    }
+    assert!(bvh.raycast(Vec3::new(0.0, 0.0, 3.0), Vec3::Z).is_none());
+    assert!(bvh.raycast(Vec3::new(3.0, 0.0, 0.0), Vec3::Y).is_none());
+}
+
+#[test]
+fn empty_mesh() {
+    let bvh = Bvh::new(&[]);
+    assert!(bvh.closest_point(Vec3::ZERO).is_none());
+    assert!(bvh.raycast(Vec3::ZERO, Vec3::X).is_none());
+}
+
+#[test]
+fn barycentrics() {
+    let bvh = sphere().build_bvh();
+    let direction = Vec3::new(0.3, -0.5, 0.8).normalize();
+    let hit = bvh
+        .raycast(Vec3::ZERO, direction)
+        .expect("inside the sphere");
+    let [a, b, c] = bvh.triangles()[hit.face].0;
+    let w = hit.barycentric;
+    assert!(w.min_element() >= 0.0);
+    assert!((w.element_sum() - 1.0).abs() < 1e-5);
+    let point = a * w.x + b * w.y + c * w.z;
+    assert!(point.distance(hit.point(Vec3::ZERO, direction)) < 1e-5);
+}
+
+#[test]
+fn visibility() {
+    let bvh = sphere().build_bvh();
+    let scanner = Vec3::new(0.0, 0.0, 3.0);
+    let near = bvh
+        .raycast(scanner, Vec3::NEG_Z)
+        .expect("aimed at the sphere");
+    let far = bvh
+        .raycast(Vec3::new(0.0, 0.0, -3.0), Vec3::Z)
+        .expect("aimed at the sphere");
+    let near = near.point(scanner, Vec3::NEG_Z);
+    let far = far.point(Vec3::new(0.0, 0.0, -3.0), Vec3::Z);
+
+    assert!(bvh.is_visible(scanner, near, 1e-4));
+    assert!(!bvh.is_visible(scanner, far, 1e-4));
+    assert!(!bvh.is_visible(Vec3::ZERO, scanner, 1e-4));
+    assert!(bvh.is_visible(scanner, Vec3::new(3.0, 0.0, 3.0), 1e-4));
+}
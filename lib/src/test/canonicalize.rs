@@ -0,0 +1,43 @@
+use std::collections::HashSet;
+
+use crate::Triangle;
+use crate::postprocess::canonicalize;
+use crate::reconstruct;
+use crate::test::reconstruct::create_spherical_cloud;
+
+fn bits(t: &Triangle) -> [[u32; 3]; 3] {
+    t.0.map(|v| v.to_array().map(f32::to_bits))
+}
+
+#[test]
+fn order_does_not_matter() {
+    let cloud = create_spherical_cloud(36, 18);
+    let mut triangles = reconstruct(&cloud, 0.3).expect("must generate a mesh");
+
+    // Every rotation keeps the winding of the original triangle.
+    let mut rotations = HashSet::new();
+    for t in &triangles {
+        for i in 0..3 {
+            let mut rotated = t.clone();
+            rotated.0.rotate_left(i);
+            rotations.insert(bits(&rotated));
+        }
+    }
+
+    // The same mesh, generated in another order.
+    let mut shuffled = triangles.clone();
+    shuffled.reverse();
+    for (i, t) in shuffled.iter_mut().enumerate() {
+        t.0.rotate_left(i % 3);
+    }
+
+    canonicalize(&mut triangles);
+    canonicalize(&mut shuffled);
+    assert_eq!(format!("{triangles:?}"), format!("{shuffled:?}"));
+
+    for t in &triangles {
+        assert!(rotations.contains(&bits(t)));
+        // The smallest vertex comes first.
+        assert!(t.0[1..].iter().all(|v| t.0[0].to_array() <= v.to_array()));
+    }
+}
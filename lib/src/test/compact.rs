@@ -0,0 +1,86 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::compact::CompactCloud;
+use crate::reconstruct_compact_with_config;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn round_trip() {
+    let cloud = create_spherical_cloud(36, 18);
+    let compact = CompactCloud::new(&cloud, 0.3).unwrap();
+    assert_eq!(compact.len(), cloud.len());
+
+    // Every original point is kept, to within the precision.
+    for original in &cloud {
+        let closest = compact
+            .iter()
+            .min_by(|a, b| {
+                a.pos
+                    .distance(original.pos)
+                    .total_cmp(&b.pos.distance(original.pos))
+            })
+            .unwrap();
+        assert!(closest.pos.distance(original.pos) <= compact.precision());
+        // Most of the cloud's normals are NaN, and unknown once stored.
+        match original.normal.try_normalize() {
+            Some(normal) => {
+                assert!(closest.normal.angle_between(normal) < 1e-3);
+                assert!(closest.normal.is_normalized());
+            }
+            None => assert_eq!(closest.normal, Vec3::ZERO),
+        }
+    }
+}
+
+#[test]
+fn halves_memory() {
+    let cloud = create_spherical_cloud(36, 18);
+    // Few enough cubes that the points dominate.
+    let compact = CompactCloud::new(&cloud, 1.0).unwrap();
+    assert!(compact.resident_bytes() < cloud.len() * size_of::<Point>() / 2);
+}
+
+#[test]
+fn normals() {
+    let mut normals = vec![Vec3::ZERO, Vec3::new(f32::NAN, 0.0, 0.0), -Vec3::Z, Vec3::X];
+    // Directions spread over the sphere, in every octant.
+    normals.extend((0..200).map(|i| {
+        let z = 1.0 - (i as f32 + 0.5) / 100.0;
+        let angle = i as f32 * 2.4;
+        Vec3::new(angle.cos(), angle.sin(), 0.0) * (1.0 - z * z).sqrt() + Vec3::Z * z
+    }));
+    let points: Vec<Point> = normals
+        .iter()
+        .map(|&normal| Point {
+            pos: Vec3::ONE,
+            normal: normal * 3.0,
+        })
+        .collect();
+    // The points share a position, so they keep their order.
+    let compact = CompactCloud::new(&points, 1.0).unwrap();
+    for (stored, normal) in compact.iter().zip(&normals) {
+        match normal.try_normalize() {
+            Some(normal) => assert!(stored.normal.abs_diff_eq(normal, 1e-4)),
+            None => assert_eq!(stored.normal, Vec3::ZERO),
+        }
+    }
+}
+
+#[test]
+fn reconstructs_the_dequantized_points() {
+    let cloud = create_spherical_cloud(36, 18);
+    let compact = CompactCloud::new(&cloud, 0.3).unwrap();
+    let config = ReconstructionConfig::new(0.3);
+
+    let quantized = reconstruct_compact_with_config(&compact, &config).unwrap();
+    let dequantized: Vec<Point> = compact.iter().collect();
+    let expected = reconstruct_with_config(&dequantized, &config).unwrap();
+    assert_eq!(
+        format!("{:?}", quantized.triangles),
+        format!("{:?}", expected.triangles)
+    );
+    assert!(!quantized.triangles.is_empty());
+}
@@ -0,0 +1,58 @@
+use crate::Mesher;
+use crate::ReconstructionConfig;
+use crate::config::CompatMode;
+use crate::grid::find_seed_triangle_with;
+use crate::mesh::MeshFace;
+use crate::reconstruct_with_config;
+use crate::report::PivotRecord;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn cpp_preset() {
+    let config = ReconstructionConfig::cpp(0.3);
+    assert_eq!(config.compat, CompatMode::Cpp);
+    assert_eq!(ReconstructionConfig::new(0.3).compat, CompatMode::Improved);
+}
+
+// The C++ implementation tests for an empty ball with an absolute tolerance,
+// which on this cloud lets more balls through. The counts themselves depend
+// on the platform maths.
+#[test]
+fn absolute_empty_ball_tolerance() {
+    let cloud = create_spherical_cloud(100, 50);
+    let triangles = |config: &ReconstructionConfig| {
+        reconstruct_with_config(&cloud, config)
+            .expect("must generate a mesh")
+            .triangles
+            .len()
+    };
+    assert!(
+        triangles(&ReconstructionConfig::cpp(0.1)) > triangles(&ReconstructionConfig::new(0.1))
+    );
+}
+
+#[test]
+fn duplicate_triangles_are_kept() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::cpp(0.3);
+    let mut emit = |_| {};
+    let mut mesher =
+        Mesher::new(cloud.as_slice(), &config, usize::MAX, &mut emit).expect("usable cloud");
+    let seed = find_seed_triangle_with(
+        &mesher.grid,
+        mesher.radius,
+        config.seed_strategy,
+        &mut mesher.report.diagnostics,
+    )
+    .expect("no seed");
+    let pivot = PivotRecord {
+        center: seed.ball_center,
+        angle: None,
+    };
+    mesher.output(&seed.f, pivot);
+    let [a, b, c] = seed.f.0.clone();
+    mesher.output(&MeshFace([c, b, a]), pivot);
+    assert_eq!(mesher.triangles.len(), 2);
+    assert_eq!(mesher.report.triangles, 2);
+    assert_eq!(mesher.report.duplicate_triangles, 1);
+}
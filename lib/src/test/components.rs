@@ -0,0 +1,122 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::parallel::reconstruct_parallel;
+use crate::postprocess::keep_largest_component;
+use crate::postprocess::remove_small_components;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+// A tetrahedron, a quad and a lone triangle, apart from one another. The
+// lone triangle comes first.
+fn pieces() -> Vec<Triangle> {
+    let [a, b, c, d] = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z];
+    let quad = [a, b, c, b + c].map(|v| v + Vec3::X * 5.0);
+    let lone = [a, b, c].map(|v| v - Vec3::X * 5.0);
+    vec![
+        Triangle(lone),
+        Triangle([a, c, b]),
+        Triangle([quad[0], quad[1], quad[2]]),
+        Triangle([a, b, d]),
+        Triangle([b, c, d]),
+        Triangle([quad[2], quad[1], quad[3]]),
+        Triangle([c, a, d]),
+    ]
+}
+
+#[test]
+fn keep_largest() {
+    let mut triangles = pieces();
+    assert_eq!(keep_largest_component(&mut triangles), 2);
+    assert_eq!(triangles.len(), 4);
+    assert!(
+        triangles
+            .iter()
+            .all(|t| t.0.iter().all(|v| v.x.abs() <= 1.0))
+    );
+}
+
+#[test]
+fn largest_of_equal_pieces_comes_first() {
+    let mut triangles = pieces();
+    triangles.truncate(3);
+    assert_eq!(keep_largest_component(&mut triangles), 2);
+    assert_eq!(triangles.len(), 1);
+    assert!(triangles[0].0[0].x < -4.0);
+}
+
+#[test]
+fn remove_small() {
+    let mut triangles = pieces();
+    assert_eq!(remove_small_components(&mut triangles, 2), 1);
+    assert_eq!(triangles.len(), 6);
+    assert!(triangles.iter().all(|t| t.0.iter().all(|v| v.x > -4.0)));
+
+    assert_eq!(remove_small_components(&mut triangles, 5), 2);
+    assert!(triangles.is_empty());
+}
+
+#[test]
+fn empty() {
+    let mut triangles = vec![];
+    assert_eq!(keep_largest_component(&mut triangles), 0);
+    assert_eq!(remove_small_components(&mut triangles, 10), 0);
+}
+
+// A sphere, with a smaller one beside it, far enough apart for the slabs of
+// a parallel reconstruction to mesh both.
+#[test]
+fn config() {
+    let mut points = create_spherical_cloud(36, 18);
+    let sphere = points.len();
+    points.extend(create_spherical_cloud(12, 6).into_iter().map(|p| Point {
+        pos: p.pos + Vec3::X * 5.0,
+        normal: p.normal,
+    }));
+    let both = reconstruct_parallel(&points, &ReconstructionConfig::new(0.3), 4).unwrap();
+    assert!(both.triangles.iter().any(|t| t.0[0].x > 3.0));
+
+    for config in [
+        ReconstructionConfig {
+            keep_largest_component: true,
+            ..ReconstructionConfig::new(0.3)
+        },
+        ReconstructionConfig {
+            min_component_faces: Some(100),
+            ..ReconstructionConfig::new(0.3)
+        },
+    ] {
+        let reconstruction = reconstruct_parallel(&points, &config, 4).unwrap();
+        assert!(reconstruction.report.removed_components > 0);
+        assert_eq!(
+            reconstruction.report.triangles,
+            reconstruction.triangles.len()
+        );
+        assert!(
+            reconstruction
+                .triangles
+                .iter()
+                .all(|t| t.0.iter().all(|v| v.x < 3.0))
+        );
+        assert!(reconstruction.report.orphans.iter().any(|&i| i >= sphere));
+    }
+}
+
+#[test]
+fn removed_vertices_are_orphans() {
+    let points = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        min_component_faces: Some(usize::MAX),
+        ..ReconstructionConfig::new(0.3)
+    };
+    let reconstruction = reconstruct_with_config(&points, &config).unwrap();
+    assert!(reconstruction.triangles.is_empty());
+    assert_eq!(reconstruction.report.triangles, 0);
+    assert_eq!(reconstruction.report.removed_components, 1);
+    assert_eq!(
+        reconstruction.report.orphans,
+        (0..points.len()).collect::<Vec<_>>()
+    );
+}
@@ -1,4 +1,5 @@
 use crate::grid::compute_ball_center;
+use crate::grid::compute_ball_center_robust;
 use crate::mesh::{MeshFace, MeshPoint};
 use glam::Vec3;
 use std::cell::RefCell;
@@ -55,3 +56,48 @@ fn radius_too_small() {
     let center = compute_ball_center(&f, 1.0);
     assert_eq!(center, None);
 }
+
+#[test]
+fn robust_near_tangent() {
+    // The ball is a fraction of a micron larger than the circumcircle, which
+    // rounding in `f32` can lose, depending on whether `mul_add` is fused.
+    let [a, b, c] = [
+        Vec3::new(1009.91534, 1003.58014, 1008.24475),
+        Vec3::new(1010.2925, 1004.41846, 1008.33417),
+        Vec3::new(1010.66565, 1003.7327, 1008.73773),
+    ];
+    let radius = 0.52245665;
+    let f = MeshFace([a, b, c].map(|p| Rc::new(RefCell::new(MeshPoint::new(p)))));
+
+    let center = compute_ball_center_robust(&f, radius).unwrap();
+    for p in [a, b, c] {
+        assert!((center.distance(p) - radius).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn robust_agrees_when_certain() {
+    let f = MeshFace(
+        [
+            Vec3::ZERO,
+            Vec3::new(10.0, 0.0, 0.0),
+            Vec3::new(0.0, 10.0, 0.0),
+        ]
+        .map(|p| Rc::new(RefCell::new(MeshPoint::new(p)))),
+    );
+    for radius in [1.0, 10.0, 100.0] {
+        assert_eq!(
+            compute_ball_center_robust(&f, radius),
+            compute_ball_center(&f, radius)
+        );
+    }
+}
+
+#[test]
+fn robust_collinear() {
+    let f = MeshFace(
+        [Vec3::ZERO, Vec3::X, Vec3::new(2.0, 1e-7, 0.0)]
+            .map(|p| Rc::new(RefCell::new(MeshPoint::new(p)))),
+    );
+    assert_eq!(compute_ball_center_robust(&f, 1.0), None);
+}
@@ -0,0 +1,92 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructError;
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::reconstruct_with_config;
+use crate::reconstruct_with_context;
+use crate::test::reconstruct::create_spherical_cloud;
+
+// A sphere resting just above a flat turntable. The turntable points come
+// last.
+fn on_turntable() -> (Vec<Point>, usize) {
+    let mut points = create_spherical_cloud(36, 18);
+    let sphere = points.len();
+    for i in -10..=10 {
+        for j in -10..=10 {
+            points.push(Point {
+                pos: Vec3::new(i as f32 * 0.15, j as f32 * 0.15, -1.2),
+                normal: Vec3::Z,
+            });
+        }
+    }
+    (points, sphere)
+}
+
+#[test]
+fn context_points_are_never_meshed() {
+    let (points, sphere) = on_turntable();
+    let context: Vec<bool> = (0..points.len()).map(|i| i >= sphere).collect();
+    let reconstruction =
+        reconstruct_with_context(&points, &context, &ReconstructionConfig::new(0.3)).unwrap();
+
+    assert!(!reconstruction.triangles.is_empty());
+    assert!(
+        reconstruction
+            .triangles
+            .iter()
+            .all(|t| t.0.iter().all(|v| v.z > -1.1))
+    );
+    assert!(reconstruction.report.orphans.iter().all(|&i| i < sphere));
+}
+
+#[test]
+fn context_points_block_the_ball() {
+    let mut points = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+    let whole = reconstruct_with_config(&points, &config).unwrap();
+
+    // Just above the north pole, inside the balls resting on it.
+    points.push(Point {
+        pos: Vec3::Z * 1.1,
+        normal: Vec3::Z,
+    });
+    let mut context = vec![false; points.len()];
+    context[points.len() - 1] = true;
+    let blocked = reconstruct_with_context(&points, &context, &config).unwrap();
+
+    assert!(blocked.triangles.len() < whole.triangles.len());
+    assert!(
+        blocked
+            .triangles
+            .iter()
+            .all(|t| t.0.iter().all(|v| v.z < 1.05))
+    );
+}
+
+#[test]
+fn no_context() {
+    let points = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+    let context = vec![false; points.len()];
+    let vertices =
+        |triangles: Vec<Triangle>| triangles.into_iter().map(|t| t.0).collect::<Vec<_>>();
+    assert_eq!(
+        vertices(
+            reconstruct_with_context(&points, &context, &config)
+                .unwrap()
+                .triangles
+        ),
+        vertices(reconstruct_with_config(&points, &config).unwrap().triangles)
+    );
+}
+
+#[test]
+fn mismatched_mask() {
+    let points = create_spherical_cloud(36, 18);
+    assert!(matches!(
+        reconstruct_with_context(&points, &[true], &ReconstructionConfig::new(0.3)),
+        Err(ReconstructError::MismatchedMask { .. })
+    ));
+}
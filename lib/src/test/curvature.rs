@@ -0,0 +1,146 @@
+use glam::Vec3;
+
+use crate::Triangle;
+#[cfg(feature = "ply")]
+use crate::io::save_ply_with_scalars;
+#[cfg(feature = "ply")]
+use crate::io::save_vtk_with_scalars;
+use crate::postprocess::subdivide;
+use crate::topology::IndexedMesh;
+use crate::topology::VertexCurvature;
+
+// A sphere of the given radius, from an octahedron subdivided three times
+// and pushed out onto the sphere.
+fn sphere(radius: f32) -> IndexedMesh {
+    let mut triangles = vec![];
+    for sx in [-1.0, 1.0] {
+        for sy in [-1.0, 1.0] {
+            for sz in [-1.0, 1.0] {
+                let mut t = Triangle([
+                    Vec3::new(sx, 0.0, 0.0),
+                    Vec3::new(0.0, sy, 0.0),
+                    Vec3::new(0.0, 0.0, sz),
+                ]);
+                if sx * sy * sz < 0.0 {
+                    t.flip();
+                }
+                triangles.push(t);
+            }
+        }
+    }
+    subdivide(&mut triangles, 3);
+    let mut mesh = IndexedMesh::new(&triangles);
+    for v in &mut mesh.vertices {
+        *v = v.normalize() * radius;
+    }
+    mesh
+}
+
+// A flat square of 4 by 4 cells, split into triangles.
+fn grid() -> Vec<Triangle> {
+    let at = |x: usize, y: usize| Vec3::new(x as f32, y as f32, 0.0);
+    (0..4)
+        .flat_map(|x| (0..4).map(move |y| (x, y)))
+        .flat_map(|(x, y)| {
+            [
+                Triangle([at(x, y), at(x + 1, y), at(x + 1, y + 1)]),
+                Triangle([at(x, y), at(x + 1, y + 1), at(x, y + 1)]),
+            ]
+        })
+        .collect()
+}
+
+#[test]
+fn sphere_curvature() {
+    let mesh = sphere(2.0);
+    let curvature = mesh.curvature();
+    assert_eq!(curvature.len(), mesh.vertices.len());
+    for c in &curvature {
+        assert!((c.mean - 0.5).abs() < 0.05, "{c:?}");
+        assert!((c.gaussian - 0.25).abs() < 0.05, "{c:?}");
+    }
+
+    // Turned inside out, the sphere bends the other way.
+    let mut inverted = mesh;
+    for face in &mut inverted.faces {
+        face.swap(1, 2);
+    }
+    for (c, i) in curvature.iter().zip(inverted.curvature()) {
+        assert!((c.mean + i.mean).abs() < 1e-5);
+        assert!((c.gaussian - i.gaussian).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn flat() {
+    let mesh = IndexedMesh::new(&grid());
+    for c in mesh.curvature() {
+        assert!(c.mean.abs() < 1e-6, "{c:?}");
+        assert!(c.gaussian.abs() < 1e-6, "{c:?}");
+    }
+
+    // A bump in the middle is a dome, ringed by saddles.
+    let mut bumped = mesh;
+    let middle = bumped
+        .vertices
+        .iter()
+        .position(|&v| v == Vec3::new(2.0, 2.0, 0.0))
+        .expect("the grid has a middle");
+    bumped.vertices[middle].z = 0.5;
+    let curvature = bumped.curvature();
+    assert!(curvature[middle].mean > 0.0);
+    assert!(curvature[middle].gaussian > 0.0);
+    let edges = bumped.half_edges();
+    for v in edges.one_ring(middle) {
+        assert!(curvature[v].gaussian < 0.0, "{:?}", curvature[v]);
+    }
+    // The boundary has no curvature.
+    for v in edges.boundary_vertices() {
+        assert_eq!(curvature[v], VertexCurvature::default());
+    }
+}
+
+#[cfg(feature = "ply")]
+#[test]
+fn export() {
+    let mesh = IndexedMesh::new(&grid());
+    let (mean, gaussian): (Vec<f32>, Vec<f32>) = mesh
+        .curvature()
+        .iter()
+        .map(|c| (c.mean, c.gaussian))
+        .unzip();
+    let scalars: [(&str, &[f32]); 2] = [("mean", &mean), ("gaussian", &gaussian)];
+    let dir = std::env::temp_dir().join("bpa_rs_curvature");
+
+    let ply = dir.join("grid.ply");
+    save_ply_with_scalars(&ply, &mesh, &scalars).expect("write failed");
+    let bytes = std::fs::read(&ply).expect("read failed");
+    let end = b"end_header\n";
+    let body = bytes
+        .windows(end.len())
+        .position(|w| w == end)
+        .expect("a header")
+        + end.len();
+    let header = std::str::from_utf8(&bytes[..body]).expect("an ascii header");
+    assert!(header.contains("element vertex 25\n"));
+    assert!(header.contains("property float mean\nproperty float gaussian\n"));
+    assert!(header.contains("element face 32\n"));
+    // Five floats a vertex, and a count and three indices a face.
+    assert_eq!(bytes.len() - body, 25 * 5 * 4 + 32 * 13);
+
+    let vtk = dir.join("grid.vtk");
+    save_vtk_with_scalars(&vtk, &mesh, &scalars).expect("write failed");
+    let text = std::fs::read_to_string(&vtk).expect("read failed");
+    assert!(text.starts_with("# vtk DataFile Version 3.0\n"));
+    assert!(text.contains("POINTS 25 float\n"));
+    assert!(text.contains("POLYGONS 32 128\n"));
+    assert!(text.contains("POINT_DATA 25\nSCALARS mean float 1\n"));
+    assert!(text.contains("SCALARS gaussian float 1\n"));
+
+    let short: [(&str, &[f32]); 1] = [("mean", &mean[1..])];
+    let error = save_vtk_with_scalars(&vtk, &mesh, &short).expect_err("too few values");
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    let spaced: [(&str, &[f32]); 1] = [("mean curvature", &mean)];
+    let error = save_ply_with_scalars(&ply, &mesh, &spaced).expect_err("two words");
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
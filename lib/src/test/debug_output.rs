@@ -0,0 +1,90 @@
+use crate::ReconstructionConfig;
+use crate::config::DebugArtifacts;
+use crate::config::DebugOutput;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn artifacts_are_written() {
+    let cloud = create_spherical_cloud(36, 18);
+    let dir = std::env::temp_dir().join("bpa_rs_debug_output");
+    let _ = std::fs::remove_dir_all(&dir);
+    let config = ReconstructionConfig {
+        debug: Some(DebugOutput {
+            dir: dir.clone(),
+            artifacts: DebugArtifacts {
+                seed: true,
+                front: true,
+                candidates: true,
+            },
+        }),
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    let report = reconstruct_with_config(&cloud, &config)
+        .expect("must generate a mesh")
+        .report;
+
+    let count = |suffix: &str| {
+        std::fs::read_dir(&dir)
+            .expect("no debug directory")
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .is_ok_and(|e| e.file_name().to_string_lossy().ends_with(suffix))
+            })
+            .count()
+    };
+    assert_eq!(count("_front.stl"), report.diagnostics.pivots);
+    assert_eq!(count("_candidates.ply"), report.diagnostics.pivots);
+    assert!(dir.join("seed_1.stl").exists());
+    assert!(dir.join("boundary_edges.stl").exists());
+}
+
+#[test]
+fn only_chosen_artifacts_are_written() {
+    let cloud = create_spherical_cloud(36, 18);
+    let dir = std::env::temp_dir().join("bpa_rs_debug_output_seed");
+    let _ = std::fs::remove_dir_all(&dir);
+    let config = ReconstructionConfig {
+        debug: Some(DebugOutput {
+            dir: dir.clone(),
+            artifacts: DebugArtifacts {
+                seed: true,
+                ..DebugArtifacts::default()
+            },
+        }),
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    let files: Vec<_> = std::fs::read_dir(&dir)
+        .expect("no debug directory")
+        .map(|e| e.expect("unreadable entry").file_name())
+        .collect();
+    assert_eq!(files, ["seed_1.stl"]);
+}
+
+#[test]
+fn unwritable_debug_dir_is_skipped() {
+    let cloud = create_spherical_cloud(36, 18);
+    // The directory is a file, so it cannot be created.
+    let dir = std::env::temp_dir().join("bpa_rs_debug_dir_is_a_file");
+    std::fs::write(&dir, "").unwrap();
+    let config = ReconstructionConfig {
+        debug: Some(DebugOutput {
+            dir,
+            artifacts: DebugArtifacts {
+                seed: true,
+                front: true,
+                candidates: true,
+            },
+        }),
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    let debugged = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    let plain = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+        .expect("must generate a mesh");
+    assert_eq!(debugged.triangles.len(), plain.triangles.len());
+}
@@ -0,0 +1,55 @@
+// Meshes built with the `deterministic` feature, recorded as hashes of the
+// bits of every coordinate. They must match on every platform, so a change
+// here changes the output everywhere.
+use glam::Vec3;
+
+use crate::Point;
+use crate::Triangle;
+use crate::reconstruct;
+
+// A lattice on each face of a cube, pushed out onto the unit sphere. Unlike
+// `create_spherical_cloud()` it needs no trigonometry, whose results vary
+// between platforms.
+fn cube_sphere(n: i32) -> Vec<Point> {
+    let mut points = vec![];
+    for axis in [
+        Vec3::X,
+        Vec3::Y,
+        Vec3::Z,
+        Vec3::NEG_X,
+        Vec3::NEG_Y,
+        Vec3::NEG_Z,
+    ] {
+        let u = axis.any_orthonormal_vector();
+        let v = axis.cross(u);
+        for i in 0..=n {
+            for j in 0..=n {
+                let s = 2.0 * i as f32 / n as f32 - 1.0;
+                let t = 2.0 * j as f32 / n as f32 - 1.0;
+                let pos = (axis + u * s + v * t).normalize();
+                points.push(Point { pos, normal: pos });
+            }
+        }
+    }
+    points
+}
+
+// FNV-1a over the bits of the coordinates, in order.
+fn hash(triangles: &[Triangle]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for t in triangles {
+        for c in t.0.iter().flat_map(Vec3::to_array) {
+            for byte in c.to_bits().to_le_bytes() {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+    }
+    hash
+}
+
+#[test]
+fn cube_sphere_is_reproducible() {
+    let triangles = reconstruct(&cube_sphere(16), 0.15).unwrap();
+    assert!(triangles.len() > 1000, "{}", triangles.len());
+    assert_eq!(hash(&triangles), 0x2e5c_faa0_4d04_90c5);
+}
@@ -0,0 +1,256 @@
+use glam::Vec3;
+
+use crate::Mesher;
+use crate::Point;
+use crate::ReconstructError;
+use crate::ReconstructionConfig;
+use crate::grid::find_seed_triangle_with;
+use crate::mesh::EdgeStatus;
+use crate::mesh::MeshFace;
+use crate::reconstruct_with_config;
+use crate::report::PivotRecord;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn radius_too_small() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.05);
+
+    let Err(ReconstructError::NoSeed(diagnostics)) = reconstruct_with_config(&cloud, &config)
+    else {
+        panic!("a tiny radius must not find a seed");
+    };
+    assert!(diagnostics.cells_scanned > 0);
+    assert!(diagnostics.isolated_points > 0);
+    let spacing = diagnostics
+        .densest_cell_spacing
+        .expect("spacing is measured on failure");
+    assert!(spacing > 0.0);
+
+    let message = ReconstructError::NoSeed(diagnostics).to_string();
+    assert!(message.starts_with("no seed triangle found"), "{message}");
+}
+
+#[test]
+fn spacing_of_a_sparse_cloud() {
+    // Points a unit apart, each many cells from the next.
+    let cloud: Vec<Point> = (0..25)
+        .map(|i| Point {
+            pos: Vec3::new((i % 5) as f32, (i / 5) as f32, 0.0),
+            normal: Vec3::Z,
+        })
+        .collect();
+    let config = ReconstructionConfig::new(0.05);
+
+    let Err(ReconstructError::NoSeed(diagnostics)) = reconstruct_with_config(&cloud, &config)
+    else {
+        panic!("a tiny radius must not find a seed");
+    };
+    let spacing = diagnostics
+        .densest_cell_spacing
+        .expect("spacing is measured on failure");
+    assert!((spacing - 1.0).abs() < 1e-5, "{spacing}");
+}
+
+#[test]
+fn success_skips_spacing() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    let diagnostics = reconstruction.report.diagnostics;
+    assert_eq!(diagnostics.cells_scanned, 1);
+    assert_eq!(diagnostics.densest_cell_spacing, None);
+}
+
+#[test]
+fn every_pivot_is_counted() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+
+    let report = reconstruct_with_config(&cloud, &config)
+        .expect("must generate a mesh")
+        .report;
+    // Each pivot past the seed adds a triangle or a boundary edge.
+    assert_eq!(
+        report.diagnostics.pivots,
+        report.triangles - 1 + report.diagnostics.boundary_edges
+    );
+    assert_eq!(report.stalled_edges, 0);
+    assert_eq!(report.duplicate_triangles, 0);
+}
+
+#[test]
+fn stalled_edges_are_left_open() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+    let mut emit = |_| {};
+    let mut mesher =
+        Mesher::new(cloud.as_slice(), &config, usize::MAX, &mut emit).expect("usable cloud");
+    let seed = find_seed_triangle_with(
+        &mesher.grid,
+        mesher.radius,
+        config.seed_strategy,
+        &mut mesher.report.diagnostics,
+    )
+    .expect("no seed");
+    mesher.seed(&seed);
+
+    // As if the front had come back to the first edge again and again.
+    let edge = mesher.front.active_edge().expect("empty front");
+    for _ in 0..8 {
+        assert!(!mesher.stalled(&edge));
+    }
+    mesher.expand();
+    assert_eq!(edge.borrow().status, EdgeStatus::Boundary);
+    let report = mesher.finish();
+    assert_eq!(report.stalled_edges, 1);
+    assert!(report.triangles > 1);
+}
+
+#[test]
+fn duplicate_triangles_are_skipped() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+    let mut emit = |_| {};
+    let mut mesher =
+        Mesher::new(cloud.as_slice(), &config, usize::MAX, &mut emit).expect("usable cloud");
+    let seed = find_seed_triangle_with(
+        &mesher.grid,
+        mesher.radius,
+        config.seed_strategy,
+        &mut mesher.report.diagnostics,
+    )
+    .expect("no seed");
+    let pivot = PivotRecord {
+        center: seed.ball_center,
+        angle: None,
+    };
+    mesher.output(&seed.f, pivot);
+    // The same face, wound the other way.
+    let [a, b, c] = seed.f.0.clone();
+    mesher.output(&MeshFace([c, b, a]), pivot);
+    assert_eq!(mesher.triangles.len(), 1);
+    assert_eq!(mesher.report.triangles, 1);
+    assert_eq!(mesher.report.duplicate_triangles, 1);
+}
+
+#[test]
+fn mismatched_lengths() {
+    let positions = [Vec3::ZERO, Vec3::X, Vec3::Y];
+    let normals = [Vec3::Z, Vec3::Z];
+    let config = ReconstructionConfig::new(0.3);
+
+    let result = crate::reconstruct_soa_with_config(&positions, &normals, &config);
+    assert!(matches!(
+        result,
+        Err(ReconstructError::MismatchedLengths {
+            positions: 3,
+            normals: 2
+        })
+    ));
+}
+
+#[test]
+fn insufficient_points() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+
+    for n in 0..3 {
+        let Err(ReconstructError::InsufficientPoints { got, need }) =
+            reconstruct_with_config(&cloud[..n], &config)
+        else {
+            panic!("{n} points cannot form a mesh");
+        };
+        assert_eq!((got, need), (n, 3));
+    }
+    assert!(crate::reconstruct::<Point>(&[], 0.3).is_none());
+    assert!(matches!(
+        crate::parallel::reconstruct_parallel::<Point>(&[], &config, 4),
+        Err(ReconstructError::InsufficientPoints { got: 0, need: 3 })
+    ));
+}
+
+#[test]
+fn invalid_radius() {
+    let cloud = create_spherical_cloud(36, 18);
+
+    for radius in [0.0, -0.3, f32::NAN, f32::INFINITY] {
+        let config = ReconstructionConfig::new(radius);
+        let result = reconstruct_with_config(&cloud, &config);
+        assert!(
+            matches!(result, Err(ReconstructError::InvalidRadius(r)) if r.to_bits() == radius.to_bits()),
+            "{radius}"
+        );
+        assert!(matches!(
+            crate::parallel::reconstruct_parallel(&cloud, &config, 4),
+            Err(ReconstructError::InvalidRadius(_))
+        ));
+    }
+}
+
+#[test]
+fn invalid_empty_ball_tolerance() {
+    let cloud = create_spherical_cloud(36, 18);
+
+    for tolerance in [-1e-4, 1.0, f32::NAN, f32::INFINITY] {
+        let config = ReconstructionConfig {
+            empty_ball_tolerance: tolerance,
+            ..ReconstructionConfig::new(0.3)
+        };
+        assert!(
+            matches!(
+                reconstruct_with_config(&cloud, &config),
+                Err(ReconstructError::InvalidEmptyBallTolerance(t)) if t.to_bits() == tolerance.to_bits()
+            ),
+            "{tolerance}"
+        );
+    }
+}
+
+#[test]
+fn grid_too_large() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        max_grid_cells: 8,
+        ..ReconstructionConfig::new(0.1)
+    };
+
+    let Err(ReconstructError::GridTooLarge {
+        cells,
+        max_cells,
+        min_radius,
+    }) = reconstruct_with_config(&cloud, &config)
+    else {
+        panic!("the grid must be too large");
+    };
+    assert_eq!(cells, Some(1000));
+    assert_eq!(max_cells, 8);
+
+    // The suggested radius is accepted.
+    let config = ReconstructionConfig {
+        radius: min_radius,
+        ..config
+    };
+    assert!(!matches!(
+        reconstruct_with_config(&cloud, &config),
+        Err(ReconstructError::GridTooLarge { .. })
+    ));
+}
+
+#[test]
+fn grid_overflow() {
+    let cloud: Vec<Point> = [0.0, 1.0, 1e9]
+        .into_iter()
+        .map(|x| Point {
+            pos: Vec3::new(x, x, x),
+            normal: Vec3::Z,
+        })
+        .collect();
+
+    let result = reconstruct_with_config(&cloud, &ReconstructionConfig::new(1e-3));
+    assert!(matches!(
+        result,
+        Err(ReconstructError::GridTooLarge { cells: None, .. })
+    ));
+}
@@ -0,0 +1,90 @@
+use glam::Vec3;
+
+use crate::Triangle;
+use crate::diff::diff;
+use crate::diff::distance;
+use crate::reconstruct;
+use crate::test::reconstruct::create_spherical_cloud;
+
+fn sphere() -> Vec<Triangle> {
+    reconstruct(&create_spherical_cloud(36, 18), 0.3).expect("must generate a mesh")
+}
+
+#[test]
+fn reordered_mesh_is_unchanged() {
+    let a = sphere();
+    let mut b = a.clone();
+    b.reverse();
+    for (i, t) in b.iter_mut().enumerate() {
+        t.0.rotate_left(i % 3);
+    }
+
+    let result = diff(&a, &b, 0.0);
+    assert!(result.is_unchanged());
+    assert_eq!(result.unchanged, a.len());
+    assert!(result.max_deviation.abs() < f32::EPSILON);
+}
+
+#[test]
+fn moved_vertex() {
+    let a = sphere();
+    let moved = a[0].0[0];
+    let offset = Vec3::new(0.01, 0.0, 0.0);
+    let b: Vec<Triangle> = a
+        .iter()
+        .map(|t| Triangle(t.0.map(|v| if v == moved { v + offset } else { v })))
+        .collect();
+    let around = a.iter().filter(|t| t.0.contains(&moved)).count();
+
+    let strict = diff(&a, &b, 1e-5);
+    assert_eq!(strict.moved.len(), around);
+    assert!(strict.moved.iter().all(|(i, j)| i == j));
+    assert!(strict.removed.is_empty() && strict.added.is_empty());
+    assert!((strict.max_deviation - 0.01).abs() < 1e-4);
+
+    let loose = diff(&a, &b, 0.015);
+    assert!(loose.is_unchanged());
+    assert!((loose.max_deviation - 0.01).abs() < 1e-4);
+}
+
+#[test]
+fn added_and_removed() {
+    let a = sphere();
+    let mut b = a[1..].to_vec();
+    let extra = Triangle([Vec3::new(5.0, 5.0, 5.0), Vec3::new(6.0, 5.0, 5.0), Vec3::Y]);
+    b.push(extra);
+
+    let result = diff(&a, &b, 1e-5);
+    assert_eq!(result.removed, [0]);
+    assert_eq!(result.added, [b.len() - 1]);
+    assert_eq!(result.unchanged, a.len() - 1);
+}
+
+#[test]
+fn distance_to_itself() {
+    let a = sphere();
+    let measured = distance(&a, &a).expect("the meshes are not empty");
+    assert!(measured.hausdorff.abs() < 1e-6);
+    assert!(measured.chamfer.abs() < 1e-6);
+}
+
+#[test]
+fn distance_to_offset_copy() {
+    let a = sphere();
+    let offset = Vec3::new(0.0, 0.0, 0.01);
+    let b: Vec<Triangle> = a
+        .iter()
+        .map(|t| Triangle(t.0.map(|v| v + offset)))
+        .collect();
+
+    let measured = distance(&a, &b).expect("the meshes are not empty");
+    // No sample is further than the offset, and those on the sides of the
+    // sphere are nearer.
+    assert!(measured.hausdorff <= 0.01 + 1e-6, "{measured:?}");
+    assert!(measured.hausdorff > 0.009, "{measured:?}");
+    assert!(measured.chamfer < 2.0 * measured.hausdorff, "{measured:?}");
+    assert!(measured.chamfer > 0.0, "{measured:?}");
+
+    assert_eq!(distance(&a, &[]), None);
+    assert_eq!(distance(&[], &a), None);
+}
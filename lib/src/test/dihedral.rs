@@ -0,0 +1,61 @@
+use crate::ReconstructionConfig;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn unlimited_by_default() {
+    let config = ReconstructionConfig::new(0.3);
+    assert_eq!(config.max_dihedral_angle, None);
+
+    let cloud = create_spherical_cloud(36, 18);
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    assert_eq!(
+        reconstruction
+            .report
+            .diagnostics
+            .pivot_rejections
+            .dihedral_angle,
+        0
+    );
+}
+
+// Neighbouring triangles on the sphere bend by a few degrees at most, so only
+// candidates the ball would never reach are rejected.
+#[test]
+fn a_generous_limit_changes_nothing() {
+    let cloud = create_spherical_cloud(36, 18);
+    let limited = ReconstructionConfig {
+        max_dihedral_angle: Some(1.0),
+        ..ReconstructionConfig::new(0.3)
+    };
+    let reconstruction = reconstruct_with_config(&cloud, &limited).expect("must generate a mesh");
+    assert_eq!(
+        format!("{:?}", reconstruction.triangles),
+        format!(
+            "{:?}",
+            reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+                .unwrap()
+                .triangles
+        )
+    );
+}
+
+#[test]
+fn a_tight_limit_rejects_folds() {
+    let cloud = create_spherical_cloud(36, 18);
+    let limited = ReconstructionConfig {
+        max_dihedral_angle: Some(0.01),
+        ..ReconstructionConfig::new(0.3)
+    };
+    let reconstruction = reconstruct_with_config(&cloud, &limited).expect("must generate a mesh");
+    let unlimited = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3)).unwrap();
+    assert!(
+        reconstruction
+            .report
+            .diagnostics
+            .pivot_rejections
+            .dihedral_angle
+            > 0
+    );
+    assert!(reconstruction.triangles.len() < unlimited.triangles.len());
+}
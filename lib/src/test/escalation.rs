@@ -0,0 +1,98 @@
+use crate::ReconstructError;
+use crate::ReconstructionConfig;
+use crate::config::RadiusEscalation;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn disabled_by_default() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.05);
+
+    assert!(matches!(
+        reconstruct_with_config(&cloud, &config),
+        Err(ReconstructError::NoSeed(_))
+    ));
+}
+
+#[test]
+fn grows_until_seeded() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        radius_escalation: Some(RadiusEscalation {
+            factor: 2.0,
+            max_radius: 1.0,
+        }),
+        ..ReconstructionConfig::new(0.05)
+    };
+
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    assert!(reconstruction.report.radius > 0.05);
+    assert!(reconstruction.report.radius <= 1.0);
+}
+
+#[test]
+fn stops_at_the_cap() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        radius_escalation: Some(RadiusEscalation {
+            factor: 1.5,
+            max_radius: 0.06,
+        }),
+        ..ReconstructionConfig::new(0.05)
+    };
+
+    assert!(matches!(
+        reconstruct_with_config(&cloud, &config),
+        Err(ReconstructError::NoSeed(_))
+    ));
+}
+
+#[test]
+fn next_radius() {
+    let escalation = RadiusEscalation {
+        factor: 2.0,
+        max_radius: 3.0,
+    };
+    assert_eq!(escalation.next(1.0), Some(2.0));
+    assert_eq!(escalation.next(2.0), Some(3.0));
+    assert_eq!(escalation.next(3.0), None);
+
+    let shrinking = RadiusEscalation {
+        factor: 0.5,
+        max_radius: 3.0,
+    };
+    assert_eq!(shrinking.next(1.0), None);
+}
+
+#[test]
+fn diagnostics_describe_the_last_attempt() {
+    let cloud = create_spherical_cloud(36, 18);
+    let diagnostics = |config: &ReconstructionConfig| {
+        let Err(ReconstructError::NoSeed(diagnostics)) = reconstruct_with_config(&cloud, config)
+        else {
+            panic!("no radius tried can find a seed");
+        };
+        diagnostics
+    };
+    let last = diagnostics(&ReconstructionConfig::new(0.06));
+    let escalated = diagnostics(&ReconstructionConfig {
+        radius_escalation: Some(RadiusEscalation {
+            factor: 1.2,
+            max_radius: 0.06,
+        }),
+        ..ReconstructionConfig::new(0.05)
+    });
+
+    assert_eq!(escalated.isolated_points, last.isolated_points);
+    assert_eq!(
+        escalated.seed_rejections.no_ball_center,
+        last.seed_rejections.no_ball_center
+    );
+    assert_eq!(
+        escalated.seed_rejections.ball_not_empty,
+        last.seed_rejections.ball_not_empty
+    );
+    // The cells scanned are counted over both attempts.
+    assert!(escalated.cells_scanned > last.cells_scanned);
+}
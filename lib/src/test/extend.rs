@@ -0,0 +1,99 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::extend_reconstruction;
+use crate::face_key;
+use crate::reconstruct_with_config;
+use crate::topology::IndexedMesh;
+
+// A square sheet of points 0.1 apart.
+fn sheet() -> Vec<Point> {
+    let mut points = vec![];
+    for i in 0..20 {
+        for j in 0..20 {
+            points.push(Point {
+                pos: Vec3::new(i as f32 * 0.1, j as f32 * 0.1, 0.0),
+                normal: Vec3::Z,
+            });
+        }
+    }
+    points
+}
+
+fn centroid_x(t: &Triangle) -> f32 {
+    t.0.iter().map(|v| v.x).sum::<f32>() / 3.0
+}
+
+#[test]
+fn grows_from_the_frozen_boundary() {
+    let cloud = sheet();
+    let config = ReconstructionConfig::new(0.08);
+    let whole = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+
+    // As if only the left half had been scanned and meshed before.
+    let frozen: Vec<Triangle> = whole
+        .triangles
+        .iter()
+        .filter(|t| centroid_x(t) < 0.9)
+        .cloned()
+        .collect();
+    let extension = extend_reconstruction(&cloud, &frozen, &config).expect("must extend the mesh");
+
+    assert_eq!(extension.report.triangles, extension.triangles.len());
+    assert!(extension.triangles.iter().all(|t| centroid_x(t) > 0.85));
+    assert!(
+        extension
+            .triangles
+            .iter()
+            .all(|t| !frozen.iter().any(|f| face_key(f) == face_key(t)))
+    );
+    assert_eq!(
+        frozen.len() + extension.triangles.len(),
+        whole.triangles.len()
+    );
+    assert!(extension.report.orphans.is_empty());
+
+    // The halves are joined into a single sheet.
+    let mut triangles = frozen;
+    triangles.extend(extension.triangles);
+    let mesh = IndexedMesh::welded(&triangles, 1e-6);
+    assert_eq!(mesh.half_edges().boundary_loops().len(), 1);
+}
+
+#[test]
+fn complete_mesh_is_not_extended() {
+    let cloud = sheet();
+    let config = ReconstructionConfig::new(0.08);
+    let whole = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+
+    let extension =
+        extend_reconstruction(&cloud, &whole.triangles, &config).expect("must extend the mesh");
+    assert!(extension.triangles.is_empty());
+    assert!(extension.report.orphans.is_empty());
+}
+
+#[test]
+fn pivots_follow_the_new_triangles() {
+    let cloud = sheet();
+    let config = ReconstructionConfig {
+        record_pivots: true,
+        ..ReconstructionConfig::new(0.08)
+    };
+    let whole = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    let frozen: Vec<Triangle> = whole
+        .triangles
+        .iter()
+        .filter(|t| centroid_x(t) < 0.9)
+        .cloned()
+        .collect();
+
+    let extension = extend_reconstruction(&cloud, &frozen, &config).expect("must extend the mesh");
+    let pivots = &extension.report.pivots;
+    assert_eq!(pivots.len(), extension.triangles.len());
+    for (t, pivot) in extension.triangles.iter().zip(pivots) {
+        let center = pivot.expect("every new triangle was pivoted").center;
+        assert!(t.0.iter().all(|v| (v.distance(center) - 0.08).abs() < 1e-4));
+    }
+}
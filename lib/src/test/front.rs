@@ -0,0 +1,83 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::config::Traversal;
+use crate::front::Front;
+use crate::mesh::MeshEdge;
+use crate::mesh::MeshPoint;
+use crate::reconstruct_with_config;
+
+fn tetrahedron() -> Vec<Point> {
+    vec![
+        Point {
+            pos: Vec3::new(0.0, 0.0, 0.0),
+            normal: Vec3::new(-1.0, -1.0, -1.0).normalize(),
+        },
+        Point {
+            pos: Vec3::new(0.0, 1.0, 0.0),
+            normal: Vec3::new(0.0, 1.0, 0.0),
+        },
+        Point {
+            pos: Vec3::new(1.0, 0.0, 0.0),
+            normal: Vec3::new(1.0, 0.0, 0.0),
+        },
+        Point {
+            pos: Vec3::new(0.0, 0.0, 1.0),
+            normal: Vec3::new(0.0, 0.0, 1.0),
+        },
+    ]
+}
+
+#[test]
+fn lifo_is_default() {
+    let config = ReconstructionConfig::new(2.0);
+    assert_eq!(config.traversal, Traversal::Lifo);
+
+    let p: Vec<_> = (0..4)
+        .map(|i| Rc::new(RefCell::new(MeshPoint::new(Vec3::new(i as f32, 0.0, 0.0)))))
+        .collect();
+    let edges: Vec<_> = (0..3)
+        .map(|i| {
+            Rc::new(RefCell::new(MeshEdge::new(
+                &p[i],
+                &p[i + 1],
+                &p[3],
+                Vec3::ZERO,
+            )))
+        })
+        .collect();
+    let mut front = Front::new(config.traversal);
+    for e in &edges {
+        front.push(e);
+    }
+
+    // The newest edge is pivoted first, then the one pushed before it.
+    let active = front.active_edge().expect("must have an active edge");
+    assert!(Rc::ptr_eq(&active, &edges[2]));
+    front.remove(&edges[2]);
+    let active = front.active_edge().expect("must have an active edge");
+    assert!(Rc::ptr_eq(&active, &edges[1]));
+    front.mark_boundary(&edges[1]);
+    let active = front.active_edge().expect("must have an active edge");
+    assert!(Rc::ptr_eq(&active, &edges[0]));
+    front.remove(&edges[0]);
+    assert!(front.active_edge().is_none());
+}
+
+#[test]
+fn fifo_closes_tetrahedron() {
+    let cloud = tetrahedron();
+    let config = ReconstructionConfig {
+        traversal: Traversal::Fifo,
+        ..ReconstructionConfig::new(2.0)
+    };
+
+    let triangles = reconstruct_with_config(&cloud, &config)
+        .expect("must generate a mesh")
+        .triangles;
+    assert_eq!(triangles.len(), 4);
+}
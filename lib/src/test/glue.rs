@@ -0,0 +1,226 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glam::Vec3;
+
+use crate::pipeline::EdgeStatus;
+use crate::pipeline::Front;
+use crate::pipeline::GlueCase;
+use crate::pipeline::MeshEdge;
+use crate::pipeline::MeshPoint;
+use crate::pipeline::Traversal;
+use crate::pipeline::find_reverse_edge_on_front;
+use crate::pipeline::glue_case;
+use crate::pipeline::join;
+use crate::pipeline::open_loop;
+
+// Points along the x axis, numbered by their x coordinate.
+fn points(n: usize) -> Vec<Rc<RefCell<MeshPoint>>> {
+    (0..n)
+        .map(|i| Rc::new(RefCell::new(MeshPoint::new(Vec3::new(i as f32, 0.0, 0.0)))))
+        .collect()
+}
+
+// The number of the point an edge starts at.
+#[allow(clippy::cast_sign_loss)]
+fn start(e: &Rc<RefCell<MeshEdge>>) -> usize {
+    e.borrow().a().borrow().pos().x as usize
+}
+
+// The loops of the front, as the numbers of the points they pass through.
+fn loops(front: &Front) -> Vec<Vec<usize>> {
+    front
+        .loops()
+        .iter()
+        .map(|edges| edges.iter().map(start).collect())
+        .collect()
+}
+
+// Every active edge is the `prev` of its `next`, and the `next` of its
+// `prev`, and both are on the front.
+fn assert_linked(front: &Front) {
+    for e in front.iter() {
+        let next = e.borrow().next().expect("must have a next").clone();
+        let prev = e.borrow().prev().expect("must have a prev").clone();
+        assert!(Rc::ptr_eq(next.borrow().prev().unwrap(), e));
+        assert!(Rc::ptr_eq(prev.borrow().next().unwrap(), e));
+        assert_eq!(next.borrow().status(), &EdgeStatus::Active);
+        assert_eq!(prev.borrow().status(), &EdgeStatus::Active);
+    }
+}
+
+#[test]
+fn a_loop_is_linked_in_order() {
+    let p = points(4);
+    let mut front = Front::new(Traversal::Lifo);
+    let edges = open_loop(&p, Vec3::ZERO, &mut front, &mut vec![]);
+
+    assert_eq!(loops(&front), [[0, 1, 2, 3]]);
+    assert_linked(&front);
+    assert!(Rc::ptr_eq(edges[0].borrow().opposite(), &p[3]));
+    assert!(Rc::ptr_eq(edges[0].borrow().b(), &p[1]));
+    assert_eq!(p[0].borrow().edges().len(), 2);
+}
+
+#[test]
+fn closed_loop() {
+    let p = points(2);
+    let mut front = Front::new(Traversal::Lifo);
+    let e = open_loop(&p, Vec3::ZERO, &mut front, &mut vec![]);
+
+    assert_eq!(glue_case(&e[0], &e[1], &mut front), GlueCase::ClosedLoop);
+    assert!(front.is_empty());
+    assert_eq!(e[0].borrow().status(), &EdgeStatus::Inner);
+    assert_eq!(e[1].borrow().status(), &EdgeStatus::Inner);
+}
+
+// The loop 0 1 0 2 doubles back on itself at 1.
+#[test]
+fn adjacent_next() {
+    let p = points(3);
+    let mut front = Front::new(Traversal::Lifo);
+    let e = open_loop(
+        &[&p[0], &p[1], &p[0], &p[2]].map(Rc::clone),
+        Vec3::ZERO,
+        &mut front,
+        &mut vec![],
+    );
+
+    assert_eq!(glue_case(&e[0], &e[1], &mut front), GlueCase::Adjacent);
+    assert_eq!(loops(&front), [[0, 2]]);
+    assert_linked(&front);
+}
+
+#[test]
+fn adjacent_prev() {
+    let p = points(3);
+    let mut front = Front::new(Traversal::Lifo);
+    let e = open_loop(
+        &[&p[0], &p[1], &p[0], &p[2]].map(Rc::clone),
+        Vec3::ZERO,
+        &mut front,
+        &mut vec![],
+    );
+
+    assert_eq!(glue_case(&e[1], &e[0], &mut front), GlueCase::Adjacent);
+    assert_eq!(loops(&front), [[0, 2]]);
+    assert_linked(&front);
+}
+
+// The loop 0 1 2 1 0 3 touches itself along the edge between 0 and 1.
+#[test]
+fn split() {
+    let p = points(4);
+    let mut front = Front::new(Traversal::Lifo);
+    let e = open_loop(
+        &[&p[0], &p[1], &p[2], &p[1], &p[0], &p[3]].map(Rc::clone),
+        Vec3::ZERO,
+        &mut front,
+        &mut vec![],
+    );
+
+    assert_eq!(glue_case(&e[0], &e[3], &mut front), GlueCase::Split);
+    assert_eq!(loops(&front), [[1, 2], [0, 3]]);
+    assert_linked(&front);
+}
+
+#[test]
+fn merge() {
+    let p = points(4);
+    let mut front = Front::new(Traversal::Lifo);
+    let first = open_loop(
+        &[&p[0], &p[1], &p[2]].map(Rc::clone),
+        Vec3::ZERO,
+        &mut front,
+        &mut vec![],
+    );
+    let second = open_loop(
+        &[&p[1], &p[0], &p[3]].map(Rc::clone),
+        Vec3::ZERO,
+        &mut front,
+        &mut vec![],
+    );
+
+    assert_eq!(
+        glue_case(&first[0], &second[0], &mut front),
+        GlueCase::Split
+    );
+    assert_eq!(loops(&front), [[1, 2, 0, 3]]);
+    assert_linked(&front);
+}
+
+#[test]
+#[allow(clippy::similar_names)]
+fn join_then_glue() {
+    let p = points(4);
+    let mut front = Front::new(Traversal::Lifo);
+    let mut edges = vec![];
+    let e = open_loop(&p, Vec3::ZERO, &mut front, &mut edges);
+
+    let (e_ik, e_kj) = join(&e[0], &p[2], Vec3::ZERO, &mut front, &mut edges);
+    assert_eq!(loops(&front), [[1, 2, 3, 0, 2]]);
+    assert!(find_reverse_edge_on_front(&e_ik).is_none());
+    let e_jk = find_reverse_edge_on_front(&e_kj).expect("must find the reverse edge");
+    assert!(Rc::ptr_eq(&e_jk, &e[1]));
+
+    assert_eq!(glue_case(&e_kj, &e_jk, &mut front), GlueCase::Adjacent);
+    assert_eq!(loops(&front), [[2, 3, 0]]);
+    assert_linked(&front);
+}
+
+// Both loops run from 0 to 1.
+#[test]
+fn duplicate_edges_are_merged() {
+    let p = points(4);
+    let mut front = Front::new(Traversal::Lifo);
+    let first = open_loop(
+        &[&p[0], &p[1], &p[2]].map(Rc::clone),
+        Vec3::ZERO,
+        &mut front,
+        &mut vec![],
+    );
+    let second = open_loop(
+        &[&p[0], &p[1], &p[3]].map(Rc::clone),
+        Vec3::ZERO,
+        &mut front,
+        &mut vec![],
+    );
+
+    assert_eq!(front.len(), 5);
+    assert_eq!(front.merged(), 1);
+    assert_eq!(first[0].borrow().status(), &EdgeStatus::Active);
+    assert_eq!(second[0].borrow().status(), &EdgeStatus::Boundary);
+
+    // Once the edge leaves the front, another may take its place.
+    front.remove(&first[0]);
+    let third = open_loop(
+        &[&p[0], &p[1], &p[2]].map(Rc::clone),
+        Vec3::ZERO,
+        &mut front,
+        &mut vec![],
+    );
+    assert_eq!(third[0].borrow().status(), &EdgeStatus::Active);
+    assert_eq!(front.merged(), 3);
+}
+
+#[test]
+fn duplicate_edges_are_kept_as_in_cpp() {
+    let p = points(4);
+    let mut front = Front::new(Traversal::Lifo).with_merge_duplicates(false);
+    open_loop(
+        &[&p[0], &p[1], &p[2]].map(Rc::clone),
+        Vec3::ZERO,
+        &mut front,
+        &mut vec![],
+    );
+    let second = open_loop(
+        &[&p[0], &p[1], &p[3]].map(Rc::clone),
+        Vec3::ZERO,
+        &mut front,
+        &mut vec![],
+    );
+
+    assert_eq!(front.len(), 6);
+    assert_eq!(front.merged(), 0);
+    assert_eq!(second[0].borrow().status(), &EdgeStatus::Active);
+}
@@ -0,0 +1,67 @@
+use glam::IVec3;
+use glam::Vec3;
+
+use crate::Point;
+use crate::grid::Grid;
+
+#[test]
+fn bins_every_point() {
+    // A cloud spread over many cells, with duplicate positions.
+    let points: Vec<Point> = (0..5000)
+        .map(|i| {
+            let t = (i % 4000) as f32;
+            Point {
+                pos: Vec3::new(t.sin() * 3.0, (t * 0.37).cos() * 2.0, t * 0.001),
+                normal: Vec3::Z,
+            }
+        })
+        .collect();
+    let grid = Grid::new(&points, 0.1).unwrap();
+
+    assert_eq!(grid.unused_points(), (0..points.len()).collect::<Vec<_>>());
+    for p in &points {
+        let found = grid.point_at(&p.pos).unwrap();
+        assert_eq!(found.borrow().pos, p.pos);
+    }
+}
+
+#[test]
+fn neighbors_by_distance() {
+    let points: Vec<Point> = [0.0, 0.15, -0.05, 0.1, -0.1, 0.5]
+        .into_iter()
+        .map(|x| Point {
+            pos: Vec3::new(x, 0.0, 0.0),
+            normal: Vec3::Z,
+        })
+        .collect();
+    let grid = Grid::new(&points, 0.1).unwrap();
+
+    let found: Vec<f32> = grid
+        .neighbors_by_distance(Vec3::ZERO)
+        .iter()
+        .map(|p| p.borrow().pos.x)
+        .collect();
+    // Ties stay in the order of the cells they are in.
+    assert_eq!(found, [0.0, -0.05, -0.1, 0.1, 0.15]);
+
+    let grid = grid.with_max_neighbors(Some(2));
+    assert_eq!(grid.neighbors_by_distance(Vec3::ZERO).len(), 2);
+}
+
+#[test]
+fn occupancy() {
+    // Two points in one corner cell, one in the opposite corner.
+    let points: Vec<Point> = [Vec3::ZERO, Vec3::splat(0.25), Vec3::new(2.0, 2.0, 1.0)]
+        .into_iter()
+        .map(|pos| Point {
+            pos,
+            normal: Vec3::Z,
+        })
+        .collect();
+    let grid = Grid::new(&points, 0.5).unwrap();
+
+    assert_eq!(grid.bounds(), (Vec3::ZERO, Vec3::new(2.0, 2.0, 1.0)));
+    assert_eq!(grid.dims(), IVec3::new(2, 2, 1));
+    assert_eq!(grid.occupied_cells(), 2);
+    assert_eq!(grid.histogram_points_per_cell(), [2, 1, 1]);
+}
@@ -0,0 +1,77 @@
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::config::Heuristics;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+const ALL_DISABLED: Heuristics = Heuristics {
+    normal_half_space: false,
+    ball_above_triangle: false,
+    existing_inner_edge: false,
+};
+
+#[test]
+fn enabled_by_default() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+    assert_eq!(config.heuristics, Heuristics::default());
+
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    let rejections = reconstruction.report.diagnostics.pivot_rejections;
+    assert!(rejections.normal_half_space > 0);
+    assert!(rejections.existing_inner_edge > 0);
+}
+
+// Without the inner edge check the front keeps re-covering the sphere, until
+// its edges stall. The budget is only a guard.
+#[test]
+fn disabled_checks_reject_nothing() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        heuristics: ALL_DISABLED,
+        max_triangles: Some(5000),
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    assert_eq!(reconstruction.report.budget_exceeded, None);
+    assert!(reconstruction.report.stalled_edges > 0);
+    let rejections = reconstruction.report.diagnostics.pivot_rejections;
+    assert_eq!(rejections.normal_half_space, 0);
+    assert_eq!(rejections.ball_above_triangle, 0);
+    assert_eq!(rejections.existing_inner_edge, 0);
+}
+
+// Normals flipped at random reject valid surface unless the half-space check
+// is disabled.
+#[test]
+fn unoriented_normals() {
+    let cloud: Vec<Point> = create_spherical_cloud(36, 18)
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| Point {
+            pos: p.pos,
+            normal: if i % 3 == 0 { -p.normal } else { p.normal },
+        })
+        .collect();
+    let checked = ReconstructionConfig::new(0.3);
+    let unchecked = ReconstructionConfig {
+        heuristics: Heuristics {
+            normal_half_space: false,
+            ..Heuristics::default()
+        },
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    let with_check = reconstruct_with_config(&cloud, &checked).expect("must generate a mesh");
+    let without_check = reconstruct_with_config(&cloud, &unchecked).expect("must generate a mesh");
+    assert!(
+        with_check
+            .report
+            .diagnostics
+            .pivot_rejections
+            .normal_half_space
+            > 0
+    );
+    assert!(without_check.triangles.len() > with_check.triangles.len());
+}
@@ -0,0 +1,120 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructError;
+use crate::ReconstructionConfig;
+use crate::config::NormalDisagreement;
+use crate::reconstruct_by_label;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+// Two spheres, labelled 1 and 2, with a stray point labelled 2 and two
+// labelled 3.
+fn segmented() -> (Vec<Point>, Vec<u8>) {
+    let mut points = create_spherical_cloud(36, 18);
+    let mut labels = vec![1; points.len()];
+    for p in create_spherical_cloud(36, 18) {
+        points.push(Point {
+            pos: p.pos + Vec3::X * 5.0,
+            normal: p.normal,
+        });
+        labels.push(2);
+    }
+    for (x, label) in [(10.0, 3), (20.0, 3), (30.0, 2)] {
+        points.push(Point {
+            pos: Vec3::X * x,
+            normal: Vec3::Z,
+        });
+        labels.push(label);
+    }
+    (points, labels)
+}
+
+#[test]
+fn one_mesh_per_label() {
+    let (points, labels) = segmented();
+    let config = ReconstructionConfig::new(0.3);
+    let meshes = reconstruct_by_label(&points, &labels, |_| true, &config).unwrap();
+
+    assert_eq!(meshes.keys().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    let sphere = reconstruct_with_config(&create_spherical_cloud(36, 18), &config).unwrap();
+    let first = meshes[&1].as_ref().unwrap();
+    assert_eq!(first.triangles.len(), sphere.triangles.len());
+    let second = meshes[&2].as_ref().unwrap();
+    assert!(
+        second
+            .triangles
+            .iter()
+            .all(|t| t.0.iter().all(|v| v.x > 3.0))
+    );
+    // Too few points for a triangle.
+    assert!(matches!(
+        meshes[&3],
+        Err(ReconstructError::InsufficientPoints { .. })
+    ));
+}
+
+#[test]
+fn filter() {
+    let (points, labels) = segmented();
+    let meshes = reconstruct_by_label(
+        &points,
+        &labels,
+        |label| label == 2,
+        &ReconstructionConfig::new(0.3),
+    )
+    .unwrap();
+    assert_eq!(meshes.keys().copied().collect::<Vec<_>>(), [2]);
+}
+
+#[test]
+fn orphans_index_the_whole_cloud() {
+    let (points, labels) = segmented();
+    let meshes = reconstruct_by_label(
+        &points,
+        &labels,
+        |label| label == 2,
+        &ReconstructionConfig::new(0.3),
+    )
+    .unwrap();
+    assert_eq!(
+        meshes[&2].as_ref().unwrap().report.orphans,
+        [points.len() - 1]
+    );
+}
+
+#[test]
+fn flagged_points_index_the_whole_cloud() {
+    let (mut points, labels) = segmented();
+    // The second sphere with every seventh normal pointing inward.
+    let first = create_spherical_cloud(36, 18).len();
+    for (i, p) in points[first..2 * first].iter_mut().enumerate() {
+        let outward = p.pos - Vec3::X * 5.0;
+        p.normal = if i % 7 == 3 { -outward } else { outward };
+    }
+    let config = ReconstructionConfig {
+        normal_disagreement: NormalDisagreement::Flag,
+        ..ReconstructionConfig::new(0.3)
+    };
+    let meshes = reconstruct_by_label(&points, &labels, |label| label == 2, &config).unwrap();
+    let flagged = &meshes[&2].as_ref().unwrap().report.flagged_points;
+    assert!(!flagged.is_empty());
+    assert!(
+        flagged.iter().all(|&i| i >= first && (i - first) % 7 == 3),
+        "{flagged:?}"
+    );
+}
+
+#[test]
+fn mismatched_labels() {
+    let (points, labels) = segmented();
+    assert!(matches!(
+        reconstruct_by_label(
+            &points,
+            &labels[1..],
+            |_| true,
+            &ReconstructionConfig::new(0.3)
+        ),
+        Err(ReconstructError::MismatchedLabels { .. })
+    ));
+}
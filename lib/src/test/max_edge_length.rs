@@ -0,0 +1,60 @@
+use crate::ReconstructionConfig;
+use crate::config::MaxEdgeLength;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn unlimited_by_default() {
+    assert_eq!(ReconstructionConfig::new(0.3).max_edge_length, None);
+}
+
+#[test]
+fn relative_to_the_radius() {
+    assert!((MaxEdgeLength::Radius(1.5).resolve(0.2) - 0.3).abs() < 1e-6);
+    assert!((MaxEdgeLength::Absolute(0.5).resolve(0.2) - 0.5).abs() < f32::EPSILON);
+}
+
+// No edge spanned by the ball is longer than its diameter.
+#[test]
+fn the_diameter_changes_nothing() {
+    let cloud = create_spherical_cloud(36, 18);
+    let limited = ReconstructionConfig {
+        max_edge_length: Some(MaxEdgeLength::Radius(2.0)),
+        ..ReconstructionConfig::new(0.3)
+    };
+    assert_eq!(
+        format!(
+            "{:?}",
+            reconstruct_with_config(&cloud, &limited).unwrap().triangles
+        ),
+        format!(
+            "{:?}",
+            reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+                .unwrap()
+                .triangles
+        )
+    );
+}
+
+#[test]
+fn long_edges_are_left_open() {
+    let cloud = create_spherical_cloud(36, 18);
+    let max = 0.2;
+    let limited = ReconstructionConfig {
+        max_edge_length: Some(MaxEdgeLength::Absolute(max)),
+        ..ReconstructionConfig::new(0.3)
+    };
+    let reconstruction = reconstruct_with_config(&cloud, &limited).expect("must generate a mesh");
+    assert!(
+        reconstruction
+            .report
+            .diagnostics
+            .pivot_rejections
+            .edge_length
+            > 0
+    );
+    for t in &reconstruction.triangles {
+        let [a, b, c] = t.0;
+        assert!(a.distance(b) <= max && b.distance(c) <= max && c.distance(a) <= max);
+    }
+}
@@ -0,0 +1,49 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn unlimited_by_default() {
+    assert_eq!(ReconstructionConfig::new(0.3).max_neighbors, None);
+}
+
+#[test]
+fn a_generous_cap_changes_nothing() {
+    let cloud = create_spherical_cloud(36, 18);
+    let capped = ReconstructionConfig {
+        max_neighbors: Some(10_000),
+        ..ReconstructionConfig::new(0.3)
+    };
+    assert_eq!(
+        format!(
+            "{:?}",
+            reconstruct_with_config(&cloud, &capped).unwrap().triangles
+        ),
+        format!(
+            "{:?}",
+            reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+                .unwrap()
+                .triangles
+        )
+    );
+}
+
+// A sphere with a dense blob of points on its surface.
+#[test]
+fn caps_a_dense_blob() {
+    let mut cloud = create_spherical_cloud(36, 18);
+    for i in 0..2000 {
+        let offset = Vec3::new((i % 13) as f32, (i % 7) as f32, (i % 11) as f32) * 1e-3;
+        let pos = (Vec3::X + offset).normalize();
+        cloud.push(Point { pos, normal: pos });
+    }
+    let config = ReconstructionConfig {
+        max_neighbors: Some(16),
+        ..ReconstructionConfig::new(0.3)
+    };
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    assert!(reconstruction.triangles.len() > 100);
+}
@@ -1,2 +1,65 @@
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::excessive_precision)]
+#![allow(clippy::unreadable_literal)]
+mod analysis;
+#[cfg(feature = "tokio")]
+mod async_io;
+mod ball_is_empty;
+mod boundary_pass;
+mod budget;
+mod bvh;
+mod canonicalize;
+mod compact;
+mod compat;
+mod components;
 mod compute_ball_center;
+mod context;
+mod curvature;
+mod debug_output;
+#[cfg(feature = "deterministic")]
+mod deterministic;
+mod diagnostics;
+mod diff;
+mod dihedral;
+mod escalation;
+mod extend;
+mod front;
+#[cfg(feature = "unstable")]
+mod glue;
+mod grid;
+mod heuristics;
+mod labels;
+mod max_edge_length;
+mod max_neighbors;
+mod normal_disagreement;
+mod normals;
+mod orient;
+mod orphans;
+mod panic_free;
+mod parallel;
+#[cfg(feature = "unstable")]
+mod pipeline;
+mod point_cloud;
+mod predicates;
+mod preview;
+mod progress;
+#[cfg(feature = "ply")]
+mod quality;
 mod reconstruct;
+mod record_pivots;
+mod remesh;
+#[cfg(feature = "render")]
+mod render;
+#[cfg(feature = "sample-data")]
+mod samples;
+mod sampling;
+mod seed_strategy;
+mod slivers;
+mod spacing;
+mod subdivide;
+mod topology;
+mod trace;
+mod triangle;
+mod units;
+mod winding;
@@ -0,0 +1,110 @@
+use crate::Mesher;
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::config::NormalDisagreement;
+use crate::grid::find_seed_triangle_with;
+use crate::reconstruct_with_config;
+use crate::report::Report;
+use crate::test::reconstruct::create_spherical_cloud;
+
+// The sphere with every seventh normal pointing inward.
+fn misoriented_sphere() -> Vec<Point> {
+    create_spherical_cloud(36, 18)
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| Point {
+            pos: p.pos,
+            normal: if i % 7 == 3 { -p.pos } else { p.pos },
+        })
+        .collect()
+}
+
+// The points left out of the mesh of the sphere with outward normals.
+fn orphans_when_oriented() -> Vec<usize> {
+    let cloud: Vec<Point> = misoriented_sphere()
+        .into_iter()
+        .map(|p| Point { normal: p.pos, ..p })
+        .collect();
+    reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+        .expect("must generate a mesh")
+        .report
+        .orphans
+}
+
+fn report(policy: NormalDisagreement) -> Report {
+    let config = ReconstructionConfig {
+        normal_disagreement: policy,
+        ..ReconstructionConfig::new(0.3)
+    };
+    reconstruct_with_config(&misoriented_sphere(), &config)
+        .expect("must generate a mesh")
+        .report
+}
+
+#[test]
+fn rejected_by_default() {
+    let report = report(NormalDisagreement::default());
+    assert!(report.diagnostics.pivot_rejections.normal_half_space > 0);
+    assert_eq!(report.diagnostics.flagged_normals, 0);
+    assert_eq!(report.diagnostics.flipped_normals, 0);
+    assert!(report.flagged_points.is_empty());
+    // Many misoriented points are left out of the mesh.
+    assert!(report.orphans.len() > orphans_when_oriented().len() + 20);
+}
+
+#[test]
+fn flagged() {
+    let report = report(NormalDisagreement::Flag);
+    assert_eq!(report.diagnostics.pivot_rejections.normal_half_space, 0);
+    assert!(report.diagnostics.flagged_normals >= report.flagged_points.len());
+    assert!(!report.flagged_points.is_empty());
+    assert!(
+        report.flagged_points.iter().all(|i| i % 7 == 3),
+        "{:?}",
+        report.flagged_points
+    );
+    assert_eq!(report.orphans, orphans_when_oriented());
+}
+
+#[test]
+fn flipped() {
+    let report = report(NormalDisagreement::Flip);
+    assert_eq!(report.diagnostics.pivot_rejections.normal_half_space, 0);
+    assert!(report.diagnostics.flipped_normals > 0);
+    assert_eq!(report.diagnostics.flagged_normals, 0);
+    assert_eq!(report.orphans, orphans_when_oriented());
+}
+
+#[test]
+fn flips_update_the_cell_normals() {
+    let cloud = misoriented_sphere();
+    let config = ReconstructionConfig {
+        normal_disagreement: NormalDisagreement::Flip,
+        ..ReconstructionConfig::new(0.3)
+    };
+    let mut emit = |_| {};
+    let mut mesher =
+        Mesher::new(cloud.as_slice(), &config, usize::MAX, &mut emit).expect("usable cloud");
+    let seed = find_seed_triangle_with(
+        &mesher.grid,
+        mesher.radius,
+        config.seed_strategy,
+        &mut mesher.report.diagnostics,
+    )
+    .expect("no seed");
+    mesher.seed(&seed);
+    mesher.expand();
+    assert!(mesher.report.diagnostics.flipped_normals > 0);
+
+    // Bucketing the points again sums their normals afresh.
+    let fresh = mesher.grid.rebucketed(mesher.radius).expect("same grid");
+    let dims = mesher.grid.dims();
+    let cells = usize::try_from(dims.element_product()).expect("a positive count");
+    for offset in 0..cells {
+        let (kept, summed) = (
+            mesher.grid.average_normal(offset),
+            fresh.average_normal(offset),
+        );
+        assert!(kept.distance(summed) < 1e-4, "{offset}: {kept} {summed}");
+    }
+}
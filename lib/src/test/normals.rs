@@ -0,0 +1,133 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::normals::orient_normals_toward;
+use crate::normals::orient_normals_toward_each;
+use crate::reconstruct;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+// The sphere with outward unit normals.
+fn sphere() -> Vec<Point> {
+    create_spherical_cloud(36, 18)
+        .into_iter()
+        .map(|p| Point {
+            pos: p.pos,
+            normal: p.pos.normalize(),
+        })
+        .collect()
+}
+
+#[test]
+fn unit_normals_are_untouched() {
+    let cloud = sphere();
+    let reconstruction = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+        .expect("must generate a mesh");
+    assert_eq!(reconstruction.report.diagnostics.unknown_normals, 0);
+    assert_eq!(reconstruction.report.diagnostics.rescaled_normals, 0);
+}
+
+#[test]
+fn scaled_normals_give_the_same_mesh() {
+    let cloud = sphere();
+    let scaled: Vec<Point> = cloud
+        .iter()
+        .map(|p| Point {
+            pos: p.pos,
+            normal: p.normal * 7.0,
+        })
+        .collect();
+
+    let expected = reconstruct(&cloud, 0.3).expect("must generate a mesh");
+    let reconstruction = reconstruct_with_config(&scaled, &ReconstructionConfig::new(0.3))
+        .expect("must generate a mesh");
+    assert_eq!(
+        reconstruction.report.diagnostics.rescaled_normals,
+        cloud.len()
+    );
+    assert_eq!(
+        format!("{:?}", reconstruction.triangles),
+        format!("{expected:?}")
+    );
+}
+
+// Unknown normals do not reject triangles, so a cloud where every normal is
+// unknown meshes just like one with no normal checks at all.
+#[test]
+fn unknown_normals() {
+    let cloud: Vec<Point> = sphere()
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| Point {
+            pos: p.pos,
+            normal: if i % 2 == 0 { Vec3::ZERO } else { Vec3::NAN },
+        })
+        .collect();
+
+    let reconstruction = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+        .expect("must generate a mesh");
+    let diagnostics = &reconstruction.report.diagnostics;
+    assert_eq!(diagnostics.unknown_normals, cloud.len());
+    assert_eq!(diagnostics.seed_rejections.normal, 0);
+    assert_eq!(diagnostics.pivot_rejections.normal_half_space, 0);
+}
+
+#[test]
+fn oriented_toward_sensor() {
+    // Half the normals of the sphere point inward.
+    let mut cloud: Vec<Point> = sphere()
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| Point {
+            normal: if i % 2 == 0 { p.normal } else { -p.normal },
+            ..p
+        })
+        .collect();
+
+    // From the center, every normal should point inward.
+    let inward = cloud.iter().filter(|p| p.normal.dot(p.pos) < 0.0).count();
+    assert_eq!(
+        orient_normals_toward(&mut cloud, Vec3::ZERO),
+        cloud.len() - inward
+    );
+    assert!(cloud.iter().all(|p| p.normal.dot(p.pos) < 0.0));
+
+    // From far away, only the normals of the near side face the sensor.
+    let sensor = Vec3::new(0.0, 0.0, 100.0);
+    orient_normals_toward(&mut cloud, sensor);
+    assert!(cloud.iter().all(|p| p.normal.dot(sensor - p.pos) >= 0.0));
+    assert_eq!(orient_normals_toward(&mut cloud, sensor), 0);
+}
+
+#[test]
+fn oriented_toward_each_sensor() {
+    let mut cloud = sphere();
+    // Merged from two scans, from inside and outside.
+    let origins: Vec<Vec3> = (0..cloud.len())
+        .map(|i| {
+            if i < cloud.len() / 2 {
+                Vec3::ZERO
+            } else {
+                cloud[i].pos * 2.0
+            }
+        })
+        .collect();
+
+    assert_eq!(
+        orient_normals_toward_each(&mut cloud, &origins),
+        Some(cloud.len() / 2)
+    );
+    for (i, p) in cloud.iter().enumerate() {
+        assert_eq!(p.normal.dot(p.pos) > 0.0, i >= cloud.len() / 2, "{i}");
+    }
+
+    let before: Vec<Vec3> = cloud.iter().map(|p| p.normal).collect();
+    assert_eq!(orient_normals_toward_each(&mut cloud, &origins[1..]), None);
+    assert!(
+        cloud
+            .iter()
+            .zip(before)
+            .all(|(p, normal)| p.normal == normal)
+    );
+}
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use glam::Vec3;
+
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::postprocess::orient;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+// An octahedron, with every face wound outward.
+fn octahedron() -> Vec<Triangle> {
+    let mut faces = vec![];
+    for sx in [-1.0, 1.0] {
+        for sy in [-1.0, 1.0] {
+            for sz in [-1.0, 1.0] {
+                let mut t = Triangle([
+                    Vec3::new(sx, 0.0, 0.0),
+                    Vec3::new(0.0, sy, 0.0),
+                    Vec3::new(0.0, 0.0, sz),
+                ]);
+                if sx * sy * sz < 0.0 {
+                    t.flip();
+                }
+                faces.push(t);
+            }
+        }
+    }
+    faces
+}
+
+fn faces_outward(triangles: &[Triangle]) -> bool {
+    triangles.iter().all(|t| {
+        let centroid = (t.0[0] + t.0[1] + t.0[2]) / 3.0;
+        t.normal().dot(centroid) > 0.0
+    })
+}
+
+// True when no directed edge is traversed twice.
+fn consistent(triangles: &[Triangle]) -> bool {
+    let mut edges = HashSet::new();
+    triangles.iter().all(|t| {
+        (0..3).all(|i| {
+            let (u, v) = (t.0[i], t.0[(i + 1) % 3]);
+            edges.insert((
+                u.to_array().map(f32::to_bits),
+                v.to_array().map(f32::to_bits),
+            ))
+        })
+    })
+}
+
+#[test]
+fn closed_mesh_faces_outward() {
+    let mut triangles = octahedron();
+    assert!(faces_outward(&triangles));
+    triangles.iter_mut().for_each(Triangle::flip);
+    triangles[2].flip();
+    triangles[5].flip();
+
+    let orientation = orient(&mut triangles);
+    assert!(faces_outward(&triangles));
+    assert_eq!(orientation.components, 1);
+    assert_eq!(orientation.closed_components, 1);
+    assert_eq!(orientation.flipped, 6);
+    assert_eq!(orientation.conflicting_edges, 0);
+}
+
+#[test]
+fn open_mesh_is_consistent() {
+    let mut triangles: Vec<Triangle> = octahedron()
+        .into_iter()
+        .filter(|t| t.0.iter().any(|v| v.z > 0.0))
+        .collect();
+    triangles[1].flip();
+    assert!(!consistent(&triangles));
+
+    let orientation = orient(&mut triangles);
+    assert!(consistent(&triangles));
+    assert_eq!(orientation.closed_components, 0);
+}
+
+#[test]
+fn reconstruction() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        orient_outward: true,
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    let orientation = reconstruction
+        .report
+        .orientation
+        .expect("orientation was requested");
+    assert_eq!(orientation.conflicting_edges, 0);
+    assert!(consistent(&reconstruction.triangles));
+}
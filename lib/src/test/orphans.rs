@@ -0,0 +1,54 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::parallel::reconstruct_parallel;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+// A sphere with an extra point far from the surface.
+fn cloud_with_outlier() -> Vec<Point> {
+    let mut cloud = create_spherical_cloud(36, 18);
+    cloud.insert(
+        5,
+        Point {
+            pos: Vec3::new(3.0, 3.0, 3.0),
+            normal: Vec3::Z,
+        },
+    );
+    cloud
+}
+
+fn is_vertex(p: &Point, triangles: &[crate::Triangle]) -> bool {
+    triangles.iter().any(|t| t.0.contains(&p.pos))
+}
+
+#[test]
+fn outlier_is_orphaned() {
+    let cloud = cloud_with_outlier();
+    let config = ReconstructionConfig::new(0.3);
+
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    let report = &reconstruction.report;
+    assert_eq!(report.points, cloud.len());
+    assert!(report.orphans.contains(&5));
+    assert!(report.orphans.is_sorted());
+    assert!(report.orphan_ratio() > 0.0);
+    assert!(report.orphan_ratio() < 0.05);
+
+    for (i, p) in cloud.iter().enumerate() {
+        assert_eq!(
+            report.orphans.contains(&i),
+            !is_vertex(p, &reconstruction.triangles)
+        );
+    }
+}
+
+#[test]
+fn parallel_orphans() {
+    let cloud = cloud_with_outlier();
+    let config = ReconstructionConfig::new(0.3);
+
+    let reconstruction = reconstruct_parallel(&cloud, &config, 4).expect("must generate a mesh");
+    assert!(reconstruction.report.orphans.contains(&5));
+}
@@ -0,0 +1,200 @@
+// Feeds the public API hostile inputs: clouds of infinities, NaNs,
+// duplicates and collinear points, unusable radii, and corrupt files. Each
+// call may fail, but must not panic.
+use core::time::Duration;
+#[cfg(feature = "ply")]
+use std::io::BufReader;
+#[cfg(feature = "ply")]
+use std::io::Cursor;
+
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::analysis::density_stats;
+use crate::analysis::estimate_curvature;
+use crate::compact::CompactCloud;
+use crate::diff::diff;
+use crate::grid::Grid;
+use crate::io::XyzCloud;
+use crate::io::XyzOptions;
+#[cfg(feature = "ply")]
+use crate::io::read_ply;
+use crate::parallel::reconstruct_parallel;
+use crate::postprocess::canonicalize;
+use crate::postprocess::orient;
+use crate::reconstruct_compact_with_config;
+use crate::reconstruct_soa_with_config;
+use crate::reconstruct_with_config;
+use crate::spacing::estimate_radius;
+use crate::test::reconstruct::create_spherical_cloud;
+
+const CASES: u64 = 200;
+
+// A xorshift generator, so that every run tries the same cases.
+struct Rng(u64);
+
+impl Rng {
+    const fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    const fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    fn float(&mut self) -> f32 {
+        const SPECIAL: [f32; 12] = [
+            0.0,
+            -0.0,
+            1.0,
+            -1.0,
+            f32::NAN,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::MAX,
+            f32::MIN_POSITIVE,
+            1e-30,
+            1e30,
+            0.3,
+        ];
+        if self.below(4) == 0 {
+            *self.pick(&SPECIAL)
+        } else {
+            (self.next() >> 40) as f32 / (1 << 23) as f32 - 1.0
+        }
+    }
+
+    fn vec3(&mut self) -> Vec3 {
+        Vec3::new(self.float(), self.float(), self.float())
+    }
+
+    fn cloud(&mut self) -> Vec<Point> {
+        let mut points: Vec<Point> = vec![];
+        for _ in 0..self.below(40) {
+            let point = match self.below(4) {
+                // A duplicate.
+                0 if !points.is_empty() => points[self.below(points.len())].clone(),
+                // On a line.
+                1 => Point {
+                    pos: Vec3::X * self.float(),
+                    normal: Vec3::Z,
+                },
+                _ => Point {
+                    pos: self.vec3(),
+                    normal: self.vec3(),
+                },
+            };
+            points.push(point);
+        }
+        points
+    }
+}
+
+#[test]
+fn reconstruction() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..CASES {
+        let mut points = rng.cloud();
+        // A surface the ball can roll over, with a few points spoiled.
+        if rng.below(4) == 0 {
+            let mut sphere = create_spherical_cloud(12, 6);
+            for p in points.into_iter().take(4) {
+                let at = rng.below(sphere.len());
+                sphere[at] = p;
+            }
+            points = sphere;
+        }
+        let radius = rng.float().abs();
+        let config = ReconstructionConfig {
+            max_triangles: Some(500),
+            time_budget: Some(Duration::from_millis(50)),
+            ..ReconstructionConfig::new(radius)
+        };
+
+        if let Ok(reconstruction) = reconstruct_with_config(&points, &config) {
+            let mut triangles = reconstruction.triangles.clone();
+            orient(&mut triangles);
+            canonicalize(&mut triangles);
+            let _ = diff(&reconstruction.triangles, &triangles, rng.float());
+            let _ = reconstruction.to_indexed().half_edges().boundary_loops();
+            let bvh = reconstruction.build_bvh();
+            let _ = bvh.closest_point(rng.vec3());
+            let _ = bvh.raycast(rng.vec3(), rng.vec3());
+            let _ = bvh.is_visible(rng.vec3(), rng.vec3(), rng.float());
+        }
+
+        let positions: Vec<Vec3> = points.iter().map(|p| p.pos).collect();
+        let normals: Vec<Vec3> = points.iter().skip(rng.below(2)).map(|p| p.normal).collect();
+        let _ = reconstruct_soa_with_config(&positions, &normals, &config);
+        if let Ok(compact) = CompactCloud::new(&points, radius) {
+            let _ = reconstruct_compact_with_config(&compact, &config);
+        }
+        let _ = reconstruct_parallel(&points, &config, rng.below(4));
+        let _ = Grid::new(&points, radius);
+    }
+}
+
+#[test]
+fn analysis() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..CASES {
+        let points = rng.cloud();
+        let _ = estimate_radius(&points, rng.below(10), rng.below(50));
+        let _ = estimate_curvature(&points, rng.below(10));
+        let _ = density_stats(&points);
+    }
+}
+
+#[test]
+fn corrupt_files() {
+    const LINES: [&str; 17] = [
+        "ply\n",
+        "format ascii 1.0\n",
+        "format binary_little_endian 1.0\n",
+        "format binary_big_endian x\n",
+        "format\n",
+        "element vertex 3\n",
+        "element vertex 18446744073709551615\n",
+        "element face -1\n",
+        "element\n",
+        "property float x\n",
+        "property list uchar int vertex_indices\n",
+        "property list\n",
+        "property\n",
+        "property blob y\n",
+        "comment\n",
+        "end_header\n",
+        "1 2 3 nan inf -1e40\n",
+    ];
+    let mut rng = Rng(0xdead_beef_cafe_f00d);
+    let options = XyzOptions {
+        color: Some(rng.below(8)),
+        intensity: Some(rng.below(8)),
+        normal: (rng.below(2) == 0).then(|| rng.below(8)),
+    };
+    for _ in 0..CASES {
+        let mut bytes = vec![];
+        for _ in 0..rng.below(12) {
+            bytes.extend_from_slice(rng.pick(&LINES).as_bytes());
+        }
+        for _ in 0..rng.below(64) {
+            bytes.push(rng.next() as u8);
+        }
+        #[cfg(feature = "ply")]
+        let _ = read_ply(BufReader::new(Cursor::new(&bytes)));
+
+        let mut xyz = XyzCloud::new(&options);
+        for line in String::from_utf8_lossy(&bytes).lines() {
+            let _ = xyz.parse_line(line, &options);
+        }
+        let _ = xyz.finish();
+    }
+}
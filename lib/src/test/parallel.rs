@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::io::load_xyz;
+use crate::parallel::reconstruct_parallel;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+// Triangles as sorted vertex bit patterns, ignoring winding.
+fn faces(triangles: &[Triangle]) -> HashSet<[[u32; 3]; 3]> {
+    triangles
+        .iter()
+        .map(|t| {
+            let mut face = t.0.map(|v| v.to_array().map(f32::to_bits));
+            face.sort_unstable();
+            face
+        })
+        .collect()
+}
+
+#[test]
+fn single_slab_matches_serial() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+
+    let serial = reconstruct_with_config(&cloud, &config)
+        .expect("must generate a mesh")
+        .triangles;
+    let parallel = reconstruct_parallel(&cloud, &config, 8)
+        .expect("must generate a mesh")
+        .triangles;
+    assert_eq!(format!("{parallel:?}"), format!("{serial:?}"));
+}
+
+#[test]
+fn bunny_slabs() {
+    let cloud = load_xyz(&PathBuf::from("../data/bunny.xyz")).expect("Cannot load bunny");
+    let config = ReconstructionConfig::new(0.002);
+
+    let serial = reconstruct_with_config(&cloud, &config)
+        .expect("must generate a mesh")
+        .triangles;
+    let parallel = reconstruct_parallel(&cloud, &config, 4)
+        .expect("must generate a mesh")
+        .triangles;
+
+    // No face is emitted twice.
+    assert_eq!(faces(&parallel).len(), parallel.len());
+
+    // Away from the cuts the slabs reproduce the serial mesh.
+    let shared = faces(&serial).intersection(&faces(&parallel)).count();
+    assert!(
+        shared * 100 > serial.len() * 95,
+        "{shared} of {}",
+        serial.len()
+    );
+}
@@ -0,0 +1,60 @@
+use crate::Triangle;
+use crate::config::Heuristics;
+use crate::pipeline::Front;
+use crate::pipeline::Grid;
+use crate::pipeline::MeshFace;
+use crate::pipeline::Traversal;
+use crate::pipeline::ball_pivot;
+use crate::pipeline::find_reverse_edge_on_front;
+use crate::pipeline::find_seed_triangle;
+use crate::pipeline::glue;
+use crate::pipeline::join;
+use crate::pipeline::not_used;
+use crate::pipeline::on_front;
+use crate::pipeline::open_front;
+use crate::pipeline::output_triangle;
+use crate::reconstruct;
+use crate::report::Diagnostics;
+use crate::report::PivotRejections;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn assembled_pipeline_matches_reconstruct() {
+    let cloud = create_spherical_cloud(36, 18);
+    let radius = 0.3;
+
+    let grid = Grid::new(&cloud, radius).unwrap();
+    let seed =
+        find_seed_triangle(&grid, radius, &mut Diagnostics::default()).expect("must find a seed");
+    let mut front = Front::new(Traversal::Lifo);
+    let mut edges = vec![];
+    let mut triangles: Vec<Triangle> = vec![];
+    let mut rejections = PivotRejections::default();
+    output_triangle(&seed.f, &mut triangles);
+    open_front(&seed, &mut front, &mut edges);
+
+    while let Some(e_ij) = front.active_edge() {
+        let pivot = ball_pivot(&e_ij, &grid, radius, Heuristics::default(), &mut rejections)
+            .filter(|o_k| not_used(&o_k.p.borrow()) || on_front(&o_k.p.borrow()));
+        let Some(o_k) = pivot else {
+            front.mark_boundary(&e_ij);
+            continue;
+        };
+
+        let (a, b) = {
+            let e = e_ij.borrow();
+            (e.a().clone(), e.b().clone())
+        };
+        output_triangle(&MeshFace([a, o_k.p.clone(), b]), &mut triangles);
+        let (e_ik, e_kj) = join(&e_ij, &o_k.p, o_k.center, &mut front, &mut edges);
+        if let Some(e_ki) = find_reverse_edge_on_front(&e_ik) {
+            glue(&e_ik, &e_ki, &mut front);
+        }
+        if let Some(e_jk) = find_reverse_edge_on_front(&e_kj) {
+            glue(&e_kj, &e_jk, &mut front);
+        }
+    }
+
+    let expected = reconstruct(&cloud, radius).expect("must generate a mesh");
+    assert_eq!(format!("{triangles:?}"), format!("{expected:?}"));
+}
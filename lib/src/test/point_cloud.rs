@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use glam::Vec3;
+
+use crate::ReconstructError;
+use crate::ReconstructionConfig;
+use crate::io::XyzCloud;
+use crate::io::load_cloud;
+use crate::io::load_point_cloud;
+use crate::point_cloud::PointCloud;
+use crate::reconstruct_point_cloud;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[cfg(feature = "ply")]
+#[test]
+fn ply_attributes() {
+    let path = std::env::temp_dir()
+        .join("bpa_rs_point_cloud")
+        .join("attributes.ply");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(
+        &path,
+        "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\n\
+         property float z\nproperty float intensity\nproperty uchar red\nproperty uchar green\n\
+         property uchar blue\nproperty list uchar int tags\nelement face 0\n\
+         property list uchar int vertex_indices\nend_header\n\
+         1 2 3 0.5 255 0 0 2 7 8\n4 5 6 0.25 0 255 0 0\n",
+    )
+    .unwrap();
+
+    let cloud = load_point_cloud(&path).expect("a readable cloud");
+    assert_eq!(
+        cloud.positions,
+        [Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0)]
+    );
+    assert_eq!(cloud.normals, None);
+    assert_eq!(cloud.colors, Some(vec![Vec3::X, Vec3::Y]));
+    // Lists are not scalars.
+    assert_eq!(cloud.scalars.len(), 1);
+    assert_eq!(cloud.scalars["intensity"], [0.5, 0.25]);
+    assert_eq!(cloud.normal(1), Vec3::ZERO);
+}
+
+#[test]
+fn xyz_matches_load_cloud() {
+    let path = Path::new("../data/bunny.xyz");
+    let cloud = load_point_cloud(path).expect("Cannot load bunny");
+    let points = load_cloud(path).expect("Cannot load bunny");
+    assert_eq!(cloud, PointCloud::from(points.as_slice()));
+    assert!(cloud.colors.is_none() && cloud.scalars.is_empty());
+    assert_eq!(cloud.to_points().len(), points.len());
+
+    let xyz = XyzCloud {
+        points: points[..2].to_vec(),
+        colors: None,
+        intensities: Some(vec![1.0, 2.0]),
+    };
+    assert_eq!(PointCloud::from(xyz).scalars["intensity"], [1.0, 2.0]);
+}
+
+#[test]
+fn reconstruct() {
+    let points = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+    let expected = reconstruct_with_config(&points, &config).expect("must generate a mesh");
+    let cloud = PointCloud::from(points);
+    let reconstruction = reconstruct_point_cloud(&cloud, &config).expect("must generate a mesh");
+    assert_eq!(
+        format!("{:?}", reconstruction.triangles),
+        format!("{:?}", expected.triangles)
+    );
+
+    // Without normals, nothing is rejected for facing the wrong way.
+    let bare = PointCloud::new(cloud.positions.clone());
+    let reconstruction = reconstruct_point_cloud(&bare, &config).expect("must generate a mesh");
+    assert_eq!(
+        reconstruction.report.diagnostics.unknown_normals,
+        bare.len()
+    );
+
+    let mut short = cloud;
+    short.normals.as_mut().unwrap().pop();
+    assert!(matches!(
+        reconstruct_point_cloud(&short, &config),
+        Err(ReconstructError::MismatchedLengths { .. })
+    ));
+}
+
+#[test]
+fn deduplicated_with_mapping() {
+    let positions = vec![
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 1e-4),
+        Vec3::new(2.0, 0.0, 0.0),
+    ];
+    let mut cloud = PointCloud::new(positions.clone());
+    cloud.normals = Some(vec![Vec3::X, Vec3::Y, Vec3::Z, -Vec3::X, -Vec3::Y]);
+    cloud
+        .scalars
+        .insert(String::from("intensity"), vec![0.0, 0.1, 0.2, 0.3, 0.4]);
+
+    // Only the exact copy is removed.
+    let (exact, dedup) = cloud.deduplicated(0.0);
+    assert_eq!(dedup.mapping, [0, 1, 0, 2, 3]);
+    assert_eq!(dedup.kept, [0, 1, 3, 4]);
+    assert_eq!(dedup.removed(), 1);
+    assert_eq!(exact.len(), 4);
+
+    let (welded, dedup) = cloud.deduplicated(1e-3);
+    assert_eq!(dedup.mapping, [0, 1, 0, 1, 2]);
+    assert_eq!(dedup.kept, [0, 1, 4]);
+    assert_eq!(welded.positions, [positions[0], positions[1], positions[4]]);
+    assert_eq!(welded.normals, Some(vec![Vec3::X, Vec3::Y, -Vec3::Y]));
+    assert_eq!(welded.scalars["intensity"], [0.0, 0.1, 0.4]);
+    // Arrays held by the caller follow the kept points.
+    assert_eq!(dedup.select(&["a", "b", "c", "d", "e"]), ["a", "b", "e"]);
+
+    let (points, dedup_points) = crate::point_cloud::dedup_points(&cloud.to_points(), 1e-3);
+    assert_eq!(dedup_points, dedup);
+    assert_eq!(points.len(), 3);
+}
@@ -0,0 +1,46 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::config::Predicates;
+use crate::reconstruct_with_config;
+
+// A square lattice in the plane, tilted off the axes. Every four neighbours
+// lie on one circle, so the pivot angles tie.
+fn lattice(n: i32) -> Vec<Point> {
+    let (u, v) = (
+        Vec3::new(1.0, 0.3, 0.1).normalize(),
+        Vec3::new(-0.3, 1.0, 0.2).normalize(),
+    );
+    let normal = u.cross(v).normalize();
+    let mut points = vec![];
+    for i in 0..n {
+        for j in 0..n {
+            points.push(Point {
+                pos: Vec3::splat(100.0) + u * i as f32 + v * j as f32,
+                normal,
+            });
+        }
+    }
+    points
+}
+
+#[test]
+fn fast_by_default() {
+    assert_eq!(ReconstructionConfig::new(1.0).predicates, Predicates::Fast);
+}
+
+#[test]
+fn robust_covers_a_lattice() {
+    let config = ReconstructionConfig {
+        predicates: Predicates::Robust,
+        ..ReconstructionConfig::new(0.8)
+    };
+    let reconstruction = reconstruct_with_config(&lattice(20), &config).unwrap();
+    // Two triangles in every square.
+    assert_eq!(reconstruction.triangles.len(), 2 * 19 * 19);
+    for t in &reconstruction.triangles {
+        let [a, b, c] = t.0;
+        assert!((b - a).cross(c - a).length() > 0.5, "{t:?}");
+    }
+}
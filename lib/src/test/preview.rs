@@ -0,0 +1,65 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::ReconstructionConfig;
+use crate::preview::reconstruct_preview;
+use crate::preview::voxel_downsample;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn downsampled_draft() {
+    let cloud = create_spherical_cloud(100, 50);
+    let config = ReconstructionConfig::new(0.1);
+
+    let preview = reconstruct_preview(&cloud, &config, 1000).expect("must generate a mesh");
+    assert!(preview.points.len() <= 1000, "{}", preview.points.len());
+    let voxel_size = preview.voxel_size.expect("the cloud must be downsampled");
+    let report = &preview.reconstruction.report;
+    assert_eq!(report.points, preview.points.len());
+    assert!((report.radius - voxel_size.max(0.1)).abs() < f32::EPSILON);
+    // Most of the sphere is covered.
+    assert!(
+        report.orphan_ratio() < 0.1,
+        "{} orphans",
+        report.orphans.len()
+    );
+    assert!(preview.reconstruction.triangles.len() > preview.points.len());
+}
+
+#[test]
+fn small_clouds_are_kept() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(0.3);
+
+    let preview = reconstruct_preview(&cloud, &config, 1000).expect("must generate a mesh");
+    assert_eq!(preview.voxel_size, None);
+    assert_eq!(preview.points.len(), cloud.len());
+    let full = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    assert_eq!(
+        format!("{:?}", preview.reconstruction.triangles),
+        format!("{:?}", full.triangles)
+    );
+}
+
+#[test]
+fn voxels_are_averaged() {
+    let points = [
+        (Vec3::new(0.1, 0.1, 0.1), Vec3::X),
+        (Vec3::new(0.3, 0.1, 0.1), Vec3::Y),
+        (Vec3::new(1.5, 0.1, 0.1), Vec3::Z),
+        (Vec3::new(0.2, 0.4, 0.1), -Vec3::X),
+    ]
+    .map(|(pos, normal)| Point { pos, normal });
+
+    let downsampled = voxel_downsample(&points, 1.0);
+    assert_eq!(downsampled.len(), 2);
+    assert!(
+        downsampled[0]
+            .pos
+            .abs_diff_eq(Vec3::new(0.2, 0.2, 0.1), 1e-6)
+    );
+    assert_eq!(downsampled[0].normal, Vec3::Y);
+    assert_eq!(downsampled[1].pos, Vec3::new(1.5, 0.1, 0.1));
+    assert_eq!(downsampled[1].normal, Vec3::Z);
+}
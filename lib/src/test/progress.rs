@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::ReconstructionConfig;
+use crate::config::ProgressCallback;
+use crate::reconstruct_with_config;
+use crate::report::Progress;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn memory_is_reported() {
+    let cloud = create_spherical_cloud(100, 50);
+    let seen: Arc<Mutex<Vec<Progress>>> = Arc::default();
+    let config = ReconstructionConfig {
+        progress: Some(ProgressCallback::new({
+            let seen = seen.clone();
+            move |progress| seen.lock().unwrap().push(*progress)
+        })),
+        ..ReconstructionConfig::new(0.1)
+    };
+
+    let report = reconstruct_with_config(&cloud, &config)
+        .expect("must generate a mesh")
+        .report;
+    let seen = seen.lock().unwrap().clone();
+    // Every 4096 pivots, and once at the end.
+    assert_eq!(seen.len(), report.diagnostics.pivots / 4096 + 1);
+    assert!(seen.windows(2).all(|w| w[0].triangles < w[1].triangles));
+    assert!(seen.iter().all(|p| p.points == cloud.len() && p.bytes > 0));
+
+    let last = seen.last().expect("called at the end");
+    assert_eq!(last.triangles, report.triangles);
+    assert_eq!(last.front, 0);
+    // Each triangle adds two edges to the three of the seed.
+    assert_eq!(last.edges, 2 * report.triangles + 1);
+    assert!(seen[0].bytes < last.bytes);
+}
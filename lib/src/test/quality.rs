@@ -0,0 +1,73 @@
+use glam::Vec3;
+
+use crate::Point;
+use crate::Triangle;
+use crate::io::FaceMetric;
+use crate::io::save_quality_ply;
+
+// An equilateral triangle beside a thin one, sharing an edge.
+fn pair() -> Vec<Triangle> {
+    let apex = Vec3::new(0.5, 3.0_f32.sqrt() / 2.0, 0.0);
+    vec![
+        Triangle([Vec3::ZERO, Vec3::X, apex]),
+        Triangle([Vec3::X, Vec3::ZERO, Vec3::new(0.5, -0.05, 0.0)]),
+    ]
+}
+
+#[test]
+fn metrics() {
+    let triangles = pair();
+
+    let aspect = FaceMetric::AspectRatio.evaluate(&triangles);
+    assert!((aspect[0] - 1.0).abs() < 1e-5, "{aspect:?}");
+    assert!(aspect[1] > 5.0, "{aspect:?}");
+    let flat = [Triangle([Vec3::ZERO, Vec3::X, Vec3::X * 2.0])];
+    assert_eq!(FaceMetric::AspectRatio.evaluate(&flat), [f32::INFINITY]);
+
+    let area = FaceMetric::Area.evaluate(&triangles);
+    assert!((area[0] - 3.0_f32.sqrt() / 4.0).abs() < 1e-6, "{area:?}");
+    assert!((area[1] - 0.025).abs() < 1e-6, "{area:?}");
+
+    // A point above the middle of each triangle, and one far above the
+    // equilateral one.
+    let points = [
+        Vec3::new(0.5, 0.3, 0.1),
+        Vec3::new(0.5, 0.3, 0.4),
+        Vec3::new(0.5, -0.02, -0.2),
+    ]
+    .map(|pos| Point {
+        pos,
+        normal: Vec3::Z,
+    });
+    let distance = FaceMetric::Distance(&points).evaluate(&triangles);
+    assert!((distance[0] - 0.4).abs() < 1e-6, "{distance:?}");
+    assert!((distance[1] - 0.2).abs() < 1e-6, "{distance:?}");
+    assert_eq!(FaceMetric::Distance(&[]).evaluate(&triangles), [0.0, 0.0]);
+}
+
+#[test]
+fn colored_ply() {
+    let path = std::env::temp_dir()
+        .join("bpa_rs_quality")
+        .join("pair.quality.ply");
+    save_quality_ply(&path, &pair(), FaceMetric::AspectRatio).expect("write failed");
+    let bytes = std::fs::read(&path).expect("read failed");
+    let end = b"end_header\n";
+    let body = bytes
+        .windows(end.len())
+        .position(|w| w == end)
+        .expect("a header")
+        + end.len();
+    let header = std::str::from_utf8(&bytes[..body]).expect("an ascii header");
+    assert!(header.contains("element vertex 4\n"));
+    assert!(header.contains("element face 2\n"));
+    assert!(header.contains("property uchar red\nproperty uchar green\nproperty uchar blue\n"));
+    assert!(header.contains("property float quality\n"));
+
+    // Each face is a count, three indices, a colour and its value.
+    let faces = &bytes[body + 4 * 3 * 4..];
+    assert_eq!(faces.len(), 2 * (1 + 3 * 4 + 3 + 4));
+    // The best triangle is blue, and the worst red.
+    assert_eq!(faces[13..16], [0, 0, 255]);
+    assert_eq!(faces[20 + 13..20 + 16], [255, 0, 0]);
+}
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::rc::Rc;
 
 use glam::Vec3;
 use insta::assert_debug_snapshot;
@@ -8,7 +9,8 @@ use crate::Triangle;
 use crate::io::load_xyz;
 use crate::reconstruct;
 
-fn create_spherical_cloud(slices: i32, stacks: i32) -> Vec<Point> {
+#[allow(clippy::cast_lossless, clippy::unnecessary_cast)]
+pub(super) fn create_spherical_cloud(slices: i32, stacks: i32) -> Vec<Point> {
     let mut points = vec![Point {
         pos: Vec3::new(0.0, 0.0, -1.0),
         normal: Vec3::new(0.0, 0.0, -1.0),
@@ -39,6 +41,7 @@ fn create_spherical_cloud(slices: i32, stacks: i32) -> Vec<Point> {
     points
 }
 
+#[allow(clippy::ptr_arg, clippy::single_match_else)]
 fn measure_reconstruct(points: &Vec<Point>, radius: f32) -> Option<Vec<Triangle>> {
     let start = std::time::Instant::now();
     let result = reconstruct(points, radius);
@@ -63,6 +66,7 @@ fn measure_reconstruct(points: &Vec<Point>, radius: f32) -> Option<Vec<Triangle>
 }
 
 #[test]
+#[cfg_attr(feature = "deterministic", ignore = "recorded with the platform maths")]
 fn sphere_36_18() {
     let cloud = create_spherical_cloud(36, 18);
     // if let Err(e) = save_points_and_normals(&PathBuf::from("sphere_36_18_cloud.ply"), &cloud) {
@@ -81,6 +85,7 @@ fn sphere_36_18() {
 }
 
 #[test]
+#[cfg_attr(feature = "deterministic", ignore = "recorded with the platform maths")]
 fn sphere_100_50() {
     let cloud = create_spherical_cloud(100, 50);
     // if let Err(e) = save_points_and_normals(&PathBuf::from("sphere_100_50_cloud.ply"), &cloud) {
@@ -193,3 +198,38 @@ fn bunny() {
         }
     }
 }
+
+#[test]
+fn borrowed_points() {
+    let cloud = create_spherical_cloud(36, 18);
+    let expected = format!("{:?}", reconstruct(&cloud, 0.3));
+
+    let shared: Vec<Rc<Point>> = cloud.iter().cloned().map(Rc::new).collect();
+    assert_eq!(format!("{:?}", reconstruct(&shared, 0.3)), expected);
+
+    let refs: Vec<&Point> = cloud.iter().collect();
+    assert_eq!(format!("{:?}", reconstruct(&refs, 0.3)), expected);
+}
+
+#[test]
+fn separate_positions_and_normals() {
+    let cloud = create_spherical_cloud(36, 18);
+    let positions: Vec<Vec3> = cloud.iter().map(|p| p.pos).collect();
+    let normals: Vec<Vec3> = cloud.iter().map(|p| p.normal).collect();
+    assert_eq!(
+        format!("{:?}", crate::reconstruct_soa(&positions, &normals, 0.3)),
+        format!("{:?}", reconstruct(&cloud, 0.3))
+    );
+}
+
+#[test]
+fn duplicate_points() {
+    let cloud = create_spherical_cloud(36, 18);
+    let doubled: Vec<&Point> = cloud.iter().chain(&cloud).collect();
+    let triangles = reconstruct(&doubled, 0.3).unwrap();
+    assert!(!triangles.is_empty());
+    for t in &triangles {
+        let [a, b, c] = t.0;
+        assert!(a != b && b != c && c != a, "{t:?}");
+    }
+}
@@ -0,0 +1,67 @@
+use crate::Reconstruction;
+use crate::ReconstructionConfig;
+use crate::postprocess::SliverOptions;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+const RADIUS: f32 = 0.3;
+
+// Each record's ball rests on the vertices of its triangle.
+fn assert_aligned(reconstruction: &Reconstruction) {
+    let pivots = &reconstruction.report.pivots;
+    assert_eq!(pivots.len(), reconstruction.triangles.len());
+    for (t, pivot) in reconstruction.triangles.iter().zip(pivots) {
+        if let Some(pivot) = pivot {
+            for v in t.0 {
+                assert!((v.distance(pivot.center) - RADIUS).abs() < 1e-4);
+            }
+        }
+    }
+}
+
+#[test]
+fn off_by_default() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig::new(RADIUS);
+    assert!(!config.record_pivots);
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    assert!(reconstruction.report.pivots.is_empty());
+}
+
+#[test]
+fn one_record_per_triangle() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        record_pivots: true,
+        ..ReconstructionConfig::new(RADIUS)
+    };
+    let mut reconstruction =
+        reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    assert_aligned(&reconstruction);
+
+    let pivots = &reconstruction.report.pivots;
+    let seed = pivots[0].expect("the seed is recorded");
+    assert_eq!(seed.angle, None);
+    for pivot in &pivots[1..] {
+        let angle = pivot.expect("every triangle is recorded").angle;
+        assert!(angle.is_some_and(|a| (0.0..core::f32::consts::TAU).contains(&a)));
+    }
+
+    reconstruction.canonicalize();
+    assert_aligned(&reconstruction);
+}
+
+#[test]
+fn records_follow_the_clean_ups() {
+    let cloud = create_spherical_cloud(36, 18);
+    let config = ReconstructionConfig {
+        record_pivots: true,
+        remove_slivers: Some(SliverOptions::default()),
+        keep_largest_component: true,
+        orient_outward: true,
+        ..ReconstructionConfig::new(RADIUS)
+    };
+    let reconstruction = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    assert_aligned(&reconstruction);
+    assert!(reconstruction.report.pivots.iter().any(Option::is_some));
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::postprocess::remesh;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+fn sphere() -> Vec<Triangle> {
+    reconstruct_with_config(
+        &create_spherical_cloud(36, 18),
+        &ReconstructionConfig::new(0.3),
+    )
+    .expect("must generate a mesh")
+    .triangles
+}
+
+fn edge_lengths(triangles: &[Triangle]) -> Vec<f32> {
+    triangles
+        .iter()
+        .flat_map(|t| (0..3).map(|i| t.0[i].distance(t.0[(i + 1) % 3])))
+        .collect()
+}
+
+// The number of faces on each undirected edge.
+fn edge_faces(triangles: &[Triangle]) -> HashMap<[[u32; 3]; 2], usize> {
+    let mut edges = HashMap::new();
+    for t in triangles {
+        for i in 0..3 {
+            let mut edge = [t.0[i], t.0[(i + 1) % 3]].map(|v| v.to_array().map(f32::to_bits));
+            edge.sort_unstable();
+            *edges.entry(edge).or_insert(0) += 1;
+        }
+    }
+    edges
+}
+
+#[test]
+fn uniform_edges() {
+    let mut triangles = sphere();
+    remesh(&mut triangles, 0.1, 5);
+
+    let lengths = edge_lengths(&triangles);
+    let near = lengths
+        .iter()
+        .filter(|&&l| (0.05..=0.15).contains(&l))
+        .count();
+    assert!(near * 10 >= lengths.len() * 9);
+    // Still on the sphere, and still a manifold.
+    assert!(
+        triangles
+            .iter()
+            .all(|t| t.0.iter().all(|v| (v.length() - 1.0).abs() < 0.02))
+    );
+    assert!(edge_faces(&triangles).values().all(|&n| n <= 2));
+}
+
+#[test]
+fn coarser() {
+    let mut triangles = sphere();
+    let before = triangles.len();
+    remesh(&mut triangles, 0.4, 5);
+    assert!(triangles.len() < before / 2);
+    assert!(triangles.iter().all(|t| t.normal().dot(t.0[0]) > 0.0));
+}
+
+// The boundary of a flat square is kept, however its inside is remeshed.
+#[test]
+fn boundary_is_kept() {
+    let corners = [Vec3::ZERO, Vec3::X, Vec3::X + Vec3::Y, Vec3::Y];
+    let mut triangles = vec![
+        Triangle([corners[0], corners[1], corners[2]]),
+        Triangle([corners[0], corners[2], corners[3]]),
+    ];
+    remesh(&mut triangles, 0.2, 3);
+
+    assert!(triangles.len() > 20);
+    assert!(triangles.iter().all(|t| t.0.iter().all(|v| v.z == 0.0
+        && v.x >= 0.0
+        && v.x <= 1.0
+        && v.y >= 0.0
+        && v.y <= 1.0)));
+    for corner in corners {
+        assert!(triangles.iter().any(|t| t.0.contains(&corner)));
+    }
+    let area: f32 = triangles
+        .iter()
+        .map(|t| (t.0[1] - t.0[0]).cross(t.0[2] - t.0[0]).z / 2.0)
+        .sum();
+    assert!((area - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn invalid_length() {
+    let mut triangles = sphere();
+    let before = format!("{triangles:?}");
+    for length in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+        remesh(&mut triangles, length, 3);
+        assert_eq!(format!("{triangles:?}"), before);
+    }
+}
@@ -0,0 +1,130 @@
+use glam::Vec3;
+
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::reconstruct_with_config;
+use crate::render::Camera;
+use crate::render::render_png;
+use crate::test::reconstruct::create_spherical_cloud;
+
+// The width, height and RGB rows of a PNG written by `render_png()`.
+fn decode(png: &[u8]) -> (u32, u32, Vec<u8>) {
+    assert_eq!(
+        png[..8],
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']
+    );
+    let mut at = 8;
+    let (mut width, mut height, mut data) = (0, 0, vec![]);
+    while at < png.len() {
+        let length = u32::from_be_bytes(png[at..at + 4].try_into().unwrap()) as usize;
+        let kind = &png[at + 4..at + 8];
+        let body = &png[at + 8..at + 8 + length];
+        match kind {
+            b"IHDR" => {
+                width = u32::from_be_bytes(body[..4].try_into().unwrap());
+                height = u32::from_be_bytes(body[4..8].try_into().unwrap());
+                assert_eq!(body[8..], [8, 2, 0, 0, 0]);
+            }
+            b"IDAT" => data.extend_from_slice(body),
+            _ => {}
+        }
+        at += 12 + length;
+    }
+    let raw = miniz_oxide::inflate::decompress_to_vec_zlib(&data).expect("valid zlib data");
+    // Drop the filter byte starting each row.
+    let rows = raw
+        .chunks(width as usize * 3 + 1)
+        .flat_map(|row| {
+            assert_eq!(row[0], 0);
+            row[1..].to_vec()
+        })
+        .collect();
+    (width, height, rows)
+}
+
+fn pixel(rgb: &[u8], width: u32, x: u32, y: u32) -> [u8; 3] {
+    let i = (y * width + x) as usize * 3;
+    rgb[i..i + 3].try_into().unwrap()
+}
+
+#[test]
+fn sphere() {
+    let cloud = create_spherical_cloud(36, 18);
+    let reconstruction = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+        .expect("must generate a mesh");
+    let camera = Camera::framing(&reconstruction.triangles, Vec3::new(-1.0, -1.0, -1.0))
+        .expect("a mesh to frame");
+    let png = reconstruction.render_png(&camera, (64, 48));
+    let (width, height, rgb) = decode(&png);
+    assert_eq!((width, height), (64, 48));
+    assert_eq!(rgb.len(), 64 * 48 * 3);
+
+    // The sphere faces the camera at the center, lit head on, and the
+    // corners are background.
+    let center = pixel(&rgb, width, 32, 24);
+    assert!(center[2] > center[0] && center[0] > 150, "{center:?}");
+    assert_eq!(pixel(&rgb, width, 0, 0), [255, 255, 255]);
+    assert_eq!(pixel(&rgb, width, 63, 47), [255, 255, 255]);
+
+    // Inside out, the faces are seen from behind.
+    let mut inverted = reconstruction.triangles;
+    for t in &mut inverted {
+        t.flip();
+    }
+    let (_, _, rgb) = decode(&render_png(&inverted, &camera, (64, 48)));
+    let center = pixel(&rgb, width, 32, 24);
+    assert!(center[0] > center[2], "{center:?}");
+}
+
+#[test]
+fn nearest_face_wins() {
+    // Two squares, one behind the other, whichever is drawn first.
+    let square = |z: f32| {
+        let [a, b, c, d] = [
+            Vec3::new(-1.0, -1.0, z),
+            Vec3::new(1.0, -1.0, z),
+            Vec3::new(1.0, 1.0, z),
+            Vec3::new(-1.0, 1.0, z),
+        ];
+        [Triangle([a, b, c]), Triangle([a, c, d])]
+    };
+    let camera = Camera {
+        eye: Vec3::new(0.0, 0.0, 5.0),
+        target: Vec3::ZERO,
+        up: Vec3::Y,
+        fov_y: core::f32::consts::FRAC_PI_2,
+    };
+    // The near square is wound away from the camera, so it shows its back.
+    let mut near = square(1.0);
+    for t in &mut near {
+        t.flip();
+    }
+    let far = square(0.0);
+    let both: Vec<Triangle> = far.iter().chain(&near).cloned().collect();
+    let reversed: Vec<Triangle> = near.iter().chain(&far).cloned().collect();
+    let (width, _, rgb) = decode(&render_png(&both, &camera, (16, 16)));
+    let (_, _, rgb_reversed) = decode(&render_png(&reversed, &camera, (16, 16)));
+    assert_eq!(rgb, rgb_reversed);
+    let center = pixel(&rgb, width, 8, 8);
+    assert!(center[0] > center[2], "{center:?}");
+}
+
+#[test]
+fn degenerate() {
+    assert!(Camera::framing(&[], Vec3::X).is_none());
+    let t = [Triangle([Vec3::ZERO, Vec3::X, Vec3::Y])];
+    assert!(Camera::framing(&t, Vec3::ZERO).is_none());
+    let camera = Camera::framing(&t, Vec3::NEG_Z).expect("a triangle to frame");
+    assert_eq!(camera.up, Vec3::Y);
+
+    // Images are at least one pixel, and nothing behind the camera is drawn.
+    let (width, height, rgb) = decode(&render_png(&t, &camera, (0, 0)));
+    assert_eq!((width, height), (1, 1));
+    assert_eq!(rgb.len(), 3);
+    let behind = Camera {
+        target: camera.eye * 2.0 - camera.target,
+        ..camera
+    };
+    let (_, _, rgb) = decode(&render_png(&t, &behind, (8, 8)));
+    assert!(rgb.iter().all(|&c| c == 255));
+}
@@ -0,0 +1,38 @@
+use crate::ReconstructionConfig;
+use crate::reconstruct_with_config;
+use crate::samples;
+
+#[test]
+fn bunny() {
+    let cloud = samples::bunny();
+    assert_eq!(cloud.len(), 3595);
+    assert!(
+        cloud
+            .iter()
+            .all(|p| p.pos.is_finite() && p.normal.is_finite())
+    );
+
+    let reconstruction =
+        reconstruct_with_config(&cloud, &ReconstructionConfig::new(samples::BUNNY_RADIUS))
+            .expect("must generate a mesh");
+    // The count is the same for any empty ball tolerance from 1e-5 to 1e-2.
+    // Above 7000 the balls were not tested at all: the absolute tolerance
+    // of the C++ implementation is larger than the squared radius.
+    assert!(reconstruction.triangles.len() > 6800);
+    assert!(reconstruction.report.orphan_ratio() < 0.05);
+}
+
+#[test]
+fn sphere() {
+    let cloud = samples::sphere(36, 18);
+    assert_eq!(cloud.len(), 2 + 36 * 17);
+    for p in &cloud {
+        assert!((p.pos.length() - 1.0).abs() < 1e-6);
+        assert_eq!(p.normal, p.pos);
+    }
+    let reconstruction = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+        .expect("must generate a mesh");
+    assert!(reconstruction.report.orphan_ratio() < 0.01);
+
+    assert_eq!(samples::sphere(0, 0).len(), 2);
+}
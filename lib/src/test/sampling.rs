@@ -0,0 +1,43 @@
+use glam::Vec3;
+
+use crate::Triangle;
+use crate::sampling::sample_surface;
+
+#[test]
+fn density_follows_area() {
+    // Two triangles in the plane z = 0, the second three times the area of
+    // the first.
+    let small = Triangle([Vec3::ZERO, Vec3::X, Vec3::Y]);
+    let large = Triangle([
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(5.0, 0.0, 0.0),
+        Vec3::new(2.0, 1.0, 0.0),
+    ]);
+    let points = sample_surface(&[small, large], 40_000, 7);
+
+    assert_eq!(points.len(), 40_000);
+    assert!(points.iter().all(|p| p.pos.z == 0.0 && p.normal == Vec3::Z));
+    assert!(points.iter().all(|p| p.pos.x >= 0.0 && p.pos.y >= 0.0));
+    let on_small = points.iter().filter(|p| p.pos.x + p.pos.y <= 1.0).count();
+    assert!(on_small.abs_diff(10_000) < 500, "{on_small}");
+}
+
+#[test]
+fn repeatable() {
+    let triangle = Triangle([Vec3::ZERO, Vec3::X, Vec3::Z]);
+    let positions = |seed| -> Vec<Vec3> {
+        sample_surface(core::slice::from_ref(&triangle), 100, seed)
+            .into_iter()
+            .map(|p| p.pos)
+            .collect()
+    };
+    assert_eq!(positions(3), positions(3));
+    assert_ne!(positions(3), positions(4));
+}
+
+#[test]
+fn no_area() {
+    let flat = Triangle([Vec3::ZERO, Vec3::X, Vec3::X * 2.0]);
+    assert!(sample_surface(&[flat], 10, 0).is_empty());
+    assert!(sample_surface(&[], 10, 0).is_empty());
+}
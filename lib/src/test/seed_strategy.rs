@@ -0,0 +1,75 @@
+use glam::Vec3;
+
+use crate::ReconstructionConfig;
+use crate::config::SeedStrategy;
+use crate::grid::Grid;
+use crate::grid::find_seed_triangle_with;
+use crate::reconstruct_with_config;
+use crate::report::Diagnostics;
+use crate::test::reconstruct::create_spherical_cloud;
+
+// The vertices of the seed triangle found with `strategy`.
+fn seed(strategy: SeedStrategy) -> [Vec3; 3] {
+    let cloud = create_spherical_cloud(36, 18);
+    let grid = Grid::new(&cloud, 0.3).unwrap();
+    let seed = find_seed_triangle_with(&grid, 0.3, strategy, &mut Diagnostics::default())
+        .expect("must find a seed");
+    seed.f.0.each_ref().map(|p| p.borrow().pos)
+}
+
+#[test]
+fn scan_order_by_default() {
+    assert_eq!(
+        ReconstructionConfig::new(0.3).seed_strategy,
+        SeedStrategy::ScanOrder
+    );
+}
+
+#[test]
+fn every_strategy_reconstructs() {
+    let cloud = create_spherical_cloud(36, 18);
+    for seed_strategy in [
+        SeedStrategy::ScanOrder,
+        SeedStrategy::DensestCellFirst,
+        SeedStrategy::Random(7),
+        SeedStrategy::Near(Vec3::Z),
+    ] {
+        let config = ReconstructionConfig {
+            seed_strategy,
+            ..ReconstructionConfig::new(0.3)
+        };
+        let reconstruction =
+            reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+        assert!(reconstruction.triangles.len() > 100, "{seed_strategy:?}");
+    }
+}
+
+#[test]
+fn near_a_hint() {
+    for hint in [Vec3::X, Vec3::NEG_Y, Vec3::Z] {
+        for p in seed(SeedStrategy::Near(hint)) {
+            assert!(p.distance(hint) < 0.6, "{p} is far from {hint}");
+        }
+    }
+}
+
+#[test]
+fn random_is_repeatable() {
+    assert_eq!(seed(SeedStrategy::Random(1)), seed(SeedStrategy::Random(1)));
+    assert_ne!(seed(SeedStrategy::Random(1)), seed(SeedStrategy::Random(2)));
+}
+
+#[test]
+fn densest_cell_first() {
+    let cloud = create_spherical_cloud(36, 18);
+    let grid = Grid::new(&cloud, 0.3).unwrap();
+    let seed = find_seed_triangle_with(
+        &grid,
+        0.3,
+        SeedStrategy::DensestCellFirst,
+        &mut Diagnostics::default(),
+    )
+    .expect("must find a seed");
+    // The poles gather a point from every slice.
+    assert!(seed.f.0[0].borrow().pos.z.abs() > 0.9);
+}
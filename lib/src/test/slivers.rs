@@ -0,0 +1,113 @@
+use glam::Vec3;
+
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::postprocess::SliverOptions;
+use crate::postprocess::SliverRemoval;
+use crate::postprocess::orient;
+use crate::postprocess::remove_slivers;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+fn has_vertex(triangles: &[Triangle], v: Vec3) -> bool {
+    triangles.iter().any(|t| t.0.contains(&v))
+}
+
+// A unit square, with a needle along its bottom edge.
+#[test]
+fn needle_is_collapsed() {
+    let [a, b, c, d] = [Vec3::ZERO, Vec3::X, Vec3::X + Vec3::Y, Vec3::Y];
+    let e = Vec3::new(1.0, 0.01, 0.0);
+    let mut triangles = vec![
+        Triangle([a, b, e]),
+        Triangle([a, e, c]),
+        Triangle([a, c, d]),
+    ];
+    let removal = remove_slivers(&mut triangles, SliverOptions::default());
+
+    assert_eq!(removal.collapsed, 1);
+    assert_eq!(removal.flipped, 0);
+    assert_eq!(removal.remaining, 0);
+    assert_eq!(triangles.len(), 2);
+    assert!(!has_vertex(&triangles, b) || !has_vertex(&triangles, e));
+    assert!(triangles.iter().all(|t| t.normal().z > 0.0));
+}
+
+// A kite whose upper half is nearly flat.
+#[test]
+fn cap_is_flipped() {
+    let [a, b] = [Vec3::ZERO, Vec3::X * 2.0];
+    let c = Vec3::new(1.0, 0.05, 0.0);
+    let d = Vec3::new(1.0, -1.0, 0.0);
+    let mut triangles = vec![Triangle([a, b, c]), Triangle([b, a, d])];
+    let removal = remove_slivers(&mut triangles, SliverOptions::default());
+
+    assert_eq!(removal.flipped, 1);
+    assert_eq!(removal.collapsed, 0);
+    assert_eq!(removal.remaining, 0);
+    assert_eq!(triangles.len(), 2);
+    assert!(
+        triangles
+            .iter()
+            .all(|t| t.0.contains(&c) && t.0.contains(&d))
+    );
+    assert!(triangles.iter().all(|t| t.normal().z > 0.0));
+}
+
+// Collapsing the short edge would fold the tetrahedron flat, and every flip
+// would duplicate an edge.
+#[test]
+fn needle_tetrahedron_is_kept() {
+    let [u, v, c, d] = [Vec3::ZERO, Vec3::X * 0.02, Vec3::Y, Vec3::Z];
+    let mut triangles = vec![
+        Triangle([u, c, v]),
+        Triangle([u, v, d]),
+        Triangle([u, d, c]),
+        Triangle([v, c, d]),
+    ];
+    orient(&mut triangles);
+    let removal = remove_slivers(&mut triangles, SliverOptions::default());
+
+    assert_eq!(removal.collapsed, 0);
+    assert_eq!(removal.flipped, 0);
+    assert!(removal.remaining > 0);
+    assert_eq!(triangles.len(), 4);
+}
+
+// Nothing is a sliver without a threshold.
+#[test]
+fn no_thresholds() {
+    let options = SliverOptions {
+        min_quality: 0.0,
+        ..SliverOptions::default()
+    };
+    let cloud = create_spherical_cloud(36, 18);
+    let whole = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3)).unwrap();
+    let mut triangles = whole.triangles.clone();
+    let removal = remove_slivers(&mut triangles, options);
+
+    assert_eq!(removal, SliverRemoval::default());
+    assert_eq!(format!("{triangles:?}"), format!("{:?}", whole.triangles));
+}
+
+#[test]
+fn config() {
+    let cloud = create_spherical_cloud(36, 18);
+    let whole = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3)).unwrap();
+    let config = ReconstructionConfig {
+        remove_slivers: Some(SliverOptions {
+            min_quality: 0.3,
+            ..SliverOptions::default()
+        }),
+        ..ReconstructionConfig::new(0.3)
+    };
+    let cleaned = reconstruct_with_config(&cloud, &config).unwrap();
+    let removal = cleaned.report.slivers.clone().unwrap();
+
+    assert!(removal.collapsed + removal.flipped > 0);
+    assert_eq!(cleaned.report.triangles, cleaned.triangles.len());
+    assert_eq!(
+        cleaned.report.orphans.len(),
+        whole.report.orphans.len() + removal.collapsed
+    );
+}
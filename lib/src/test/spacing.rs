@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use crate::ReconstructionConfig;
+use crate::io::load_cloud;
+use crate::reconstruct_with_config;
+use crate::spacing::estimate_radius;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn sphere() {
+    let cloud = create_spherical_cloud(36, 18);
+
+    let estimate = estimate_radius(&cloud, 8, 100_000).expect("must estimate a radius");
+    assert_eq!(estimate.sampled, cloud.len());
+    assert!(estimate.min <= estimate.typical && estimate.typical <= estimate.max);
+
+    let report = reconstruct_with_config(&cloud, &ReconstructionConfig::new(estimate.typical))
+        .expect("the typical radius must generate a mesh")
+        .report;
+    assert!(report.orphan_ratio() < 0.01, "{report:?}");
+}
+
+#[test]
+fn bunny_sample() {
+    let cloud = load_cloud(Path::new("../data/bunny.xyz")).expect("Cannot load bunny");
+
+    let estimate = estimate_radius(&cloud, 8, 1000).expect("must estimate a radius");
+    assert!(estimate.sampled <= 1000);
+    // The example reconstructs the bunny with a radius of 0.002.
+    assert!(estimate.min > 0.001 && estimate.max < 0.002, "{estimate:?}");
+}
+
+#[test]
+fn too_few_points() {
+    let cloud = create_spherical_cloud(36, 18);
+
+    assert!(estimate_radius(&cloud[..8], 8, 100).is_none());
+    assert!(estimate_radius(&cloud, 0, 100).is_none());
+    assert!(estimate_radius(&cloud, 8, 0).is_none());
+    assert!(estimate_radius(&cloud[..9], 8, 100).is_some());
+}
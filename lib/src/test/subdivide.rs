@@ -0,0 +1,87 @@
+use glam::Vec3;
+
+use crate::Triangle;
+use crate::postprocess::subdivide;
+
+// An octahedron, with every face wound outward.
+fn octahedron() -> Vec<Triangle> {
+    let mut faces = vec![];
+    for sx in [-1.0, 1.0] {
+        for sy in [-1.0, 1.0] {
+            for sz in [-1.0, 1.0] {
+                let mut t = Triangle([
+                    Vec3::new(sx, 0.0, 0.0),
+                    Vec3::new(0.0, sy, 0.0),
+                    Vec3::new(0.0, 0.0, sz),
+                ]);
+                if sx * sy * sz < 0.0 {
+                    t.flip();
+                }
+                faces.push(t);
+            }
+        }
+    }
+    faces
+}
+
+fn has_vertex(triangles: &[Triangle], v: Vec3) -> bool {
+    triangles
+        .iter()
+        .any(|t| t.0.iter().any(|w| w.distance(v) < 1e-6))
+}
+
+#[test]
+fn closed() {
+    let mut triangles = octahedron();
+    subdivide(&mut triangles, 1);
+
+    assert_eq!(triangles.len(), 32);
+    // Each corner has four neighbours, which cancel out.
+    assert!(has_vertex(&triangles, Vec3::X * 0.625));
+    // Each edge point weighs its ends by 3/8 and the opposite corners by 1/8,
+    // which also cancel out.
+    assert!(has_vertex(&triangles, Vec3::new(0.375, 0.375, 0.0)));
+    assert!(
+        triangles
+            .iter()
+            .all(|t| t.normal().dot(t.0[0] + t.0[1] + t.0[2]) > 0.0)
+    );
+}
+
+#[test]
+fn converges_towards_a_smooth_surface() {
+    let mut triangles = octahedron();
+    subdivide(&mut triangles, 4);
+
+    assert_eq!(triangles.len(), 8 * 4_usize.pow(4));
+    let radii: Vec<f32> = triangles
+        .iter()
+        .flat_map(|t| t.0)
+        .map(Vec3::length)
+        .collect();
+    let (min, max) = radii
+        .iter()
+        .fold((f32::MAX, 0.0_f32), |(lo, hi), &r| (lo.min(r), hi.max(r)));
+    assert!(max - min < 0.15);
+}
+
+// A lone triangle is all boundary: its edges are split at their midpoints
+// and its corners, each on two boundary edges, pulled towards them.
+#[test]
+fn boundary() {
+    let [a, b, c] = [Vec3::ZERO, Vec3::X, Vec3::Y];
+    let mut triangles = vec![Triangle([a, b, c])];
+    subdivide(&mut triangles, 1);
+
+    assert_eq!(triangles.len(), 4);
+    assert!(has_vertex(&triangles, (a + b) / 2.0));
+    assert!(has_vertex(&triangles, a * 0.75 + (b + c) * 0.125));
+    assert!(triangles.iter().all(|t| t.normal().z > 0.0));
+}
+
+#[test]
+fn no_levels() {
+    let mut triangles = octahedron();
+    subdivide(&mut triangles, 0);
+    assert_eq!(format!("{triangles:?}"), format!("{:?}", octahedron()));
+}
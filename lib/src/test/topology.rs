@@ -0,0 +1,182 @@
+use glam::Vec3;
+
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+use crate::topology::IndexedMesh;
+
+#[test]
+fn square() {
+    let corner = Vec3::new(1.0, 1.0, 0.0);
+    let triangles = [
+        Triangle([Vec3::ZERO, Vec3::X, corner]),
+        Triangle([Vec3::ZERO, corner, Vec3::Y]),
+    ];
+    let mesh = IndexedMesh::new(&triangles);
+    assert_eq!(mesh.vertices, [Vec3::ZERO, Vec3::X, corner, Vec3::Y]);
+    assert_eq!(mesh.faces, [[0, 1, 2], [0, 2, 3]]);
+    assert_eq!(format!("{:?}", mesh.triangles()), format!("{triangles:?}"));
+
+    let edges = mesh.half_edges();
+    assert_eq!(edges.len(), 6);
+    // The diagonal, 2 -> 0 in the first face and 0 -> 2 in the second.
+    assert_eq!(edges.twin(2), Some(3));
+    assert_eq!(edges.twin(3), Some(2));
+    assert_eq!((0..6).filter(|&h| edges.twin(h).is_none()).count(), 4);
+    assert_eq!((edges.origin(2), edges.target(2)), (2, 0));
+    assert_eq!((edges.next(2), edges.prev(2)), (0, 1));
+
+    let mut ring: Vec<usize> = edges.one_ring(0).collect();
+    ring.sort_unstable();
+    assert_eq!(ring, [1, 2, 3]);
+    let mut ring: Vec<usize> = edges.one_ring(1).collect();
+    ring.sort_unstable();
+    assert_eq!(ring, [0, 2]);
+    assert_eq!(edges.vertex_faces(2).count(), 2);
+    assert_eq!(edges.face_neighbours(0).collect::<Vec<_>>(), [1]);
+}
+
+#[test]
+fn closed_octahedron() {
+    let corners = [Vec3::X, Vec3::Y, Vec3::NEG_X, Vec3::NEG_Y];
+    let triangles: Vec<Triangle> = (0..4)
+        .flat_map(|i| {
+            let (a, b) = (corners[i], corners[(i + 1) % 4]);
+            [Triangle([a, b, Vec3::Z]), Triangle([b, a, Vec3::NEG_Z])]
+        })
+        .collect();
+    let mesh = IndexedMesh::new(&triangles);
+    let edges = mesh.half_edges();
+
+    for h in 0..edges.len() {
+        let twin = edges.twin(h).expect("closed");
+        assert_eq!(edges.twin(twin), Some(h));
+        assert_eq!(edges.origin(twin), edges.target(h));
+    }
+    for v in 0..mesh.vertices.len() {
+        assert_eq!(edges.one_ring(v).count(), 4);
+        assert_eq!(edges.vertex_faces(v).count(), 4);
+    }
+    for f in 0..mesh.faces.len() {
+        assert_eq!(edges.face_neighbours(f).count(), 3);
+    }
+    // Euler's formula for a sphere.
+    let euler = mesh.vertices.len() + mesh.faces.len() - edges.len() / 2;
+    assert_eq!(euler, 2);
+}
+
+#[test]
+fn reconstruction_twins() {
+    let cloud = create_spherical_cloud(36, 18);
+    let mesh = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+        .expect("must generate a mesh")
+        .to_indexed();
+    let edges = mesh.half_edges();
+    for h in 0..edges.len() {
+        if let Some(twin) = edges.twin(h) {
+            assert_eq!(edges.twin(twin), Some(h));
+            assert_eq!(edges.origin(twin), edges.target(h));
+            assert_eq!(edges.target(twin), edges.origin(h));
+        }
+    }
+    // Each vertex is reached from each of its neighbours.
+    for v in 0..mesh.vertices.len() {
+        for u in edges.one_ring(v) {
+            assert!(edges.one_ring(u).any(|w| w == v));
+        }
+    }
+}
+
+#[test]
+fn boundaries() {
+    // A square with a square hole, around the origin.
+    let ring = |r: f32| {
+        [
+            Vec3::new(-r, -r, 0.0),
+            Vec3::new(r, -r, 0.0),
+            Vec3::new(r, r, 0.0),
+            Vec3::new(-r, r, 0.0),
+        ]
+    };
+    let (outer, inner) = (ring(2.0), ring(1.0));
+    let triangles: Vec<Triangle> = (0..4)
+        .flat_map(|i| {
+            let j = (i + 1) % 4;
+            [
+                Triangle([outer[i], outer[j], inner[j]]),
+                Triangle([outer[i], inner[j], inner[i]]),
+            ]
+        })
+        .collect();
+    let mesh = IndexedMesh::new(&triangles);
+    let edges = mesh.half_edges();
+
+    assert_eq!(edges.boundary_edges().count(), 8);
+    assert_eq!(edges.boundary_vertices().count(), 8);
+    let loops = edges.boundary_loops();
+    assert_eq!(loops.len(), 2);
+    for boundary in &loops {
+        assert_eq!(boundary.len(), 4);
+        for (i, &h) in boundary.iter().enumerate() {
+            let following = boundary[(i + 1) % boundary.len()];
+            assert_eq!(edges.target(h), edges.origin(following));
+        }
+    }
+
+    // A closed mesh has no boundary.
+    let closed = IndexedMesh::new(&[
+        Triangle([Vec3::ZERO, Vec3::X, Vec3::Y]),
+        Triangle([Vec3::ZERO, Vec3::Y, Vec3::X]),
+    ])
+    .half_edges();
+    assert_eq!(closed.boundary_edges().count(), 0);
+    assert_eq!(closed.boundary_vertices().count(), 0);
+    assert!(closed.boundary_loops().is_empty());
+}
+
+// The square, with the shared corners of the second triangle jittered by
+// rounding.
+#[test]
+fn welded() {
+    let corner = Vec3::new(1.0, 1.0, 0.0);
+    let jitter = Vec3::splat(1e-6);
+    let triangles = [
+        Triangle([Vec3::ZERO, Vec3::X, corner]),
+        Triangle([Vec3::ZERO + jitter, corner - jitter, Vec3::Y]),
+    ];
+
+    let cracked = IndexedMesh::new(&triangles);
+    assert_eq!(cracked.vertices.len(), 6);
+    assert_eq!(cracked.half_edges().boundary_edges().count(), 6);
+
+    let mesh = IndexedMesh::welded(&triangles, 1e-4);
+    assert_eq!(mesh.vertices, [Vec3::ZERO, Vec3::X, corner, Vec3::Y]);
+    assert_eq!(mesh.faces, [[0, 1, 2], [0, 2, 3]]);
+    assert_eq!(mesh.half_edges().boundary_edges().count(), 4);
+
+    // Too tight a tolerance, or none, joins only identical vertices.
+    assert_eq!(IndexedMesh::welded(&triangles, 1e-7), cracked);
+    assert_eq!(IndexedMesh::welded(&triangles, 0.0), cracked);
+
+    // A triangle narrower than the tolerance keeps its place, degenerate.
+    let sliver = [Triangle([Vec3::ZERO, Vec3::X, Vec3::X + jitter])];
+    assert_eq!(IndexedMesh::welded(&sliver, 1e-4).faces, [[0, 1, 1]]);
+}
+
+// A mesh saved earlier is read back as a triangle soup, and indexed again.
+#[cfg(feature = "stl")]
+#[test]
+fn from_stl() {
+    let cloud = create_spherical_cloud(36, 18);
+    let triangles = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+        .expect("must generate a mesh")
+        .triangles;
+    let path = std::env::temp_dir().join("bpa_rs_topology_from_stl/sphere.stl");
+    crate::io::save_triangles(&path, &triangles).expect("write failed");
+    let loaded = crate::io::load_stl(&path).expect("unreadable stl");
+
+    let mesh = IndexedMesh::from(loaded.as_slice());
+    assert_eq!(mesh, IndexedMesh::new(&triangles));
+    assert_eq!(IndexedMesh::welded(&loaded, 1e-6), mesh);
+}
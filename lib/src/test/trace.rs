@@ -0,0 +1,48 @@
+use crate::ReconstructionConfig;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn every_step_is_traced() {
+    let cloud = create_spherical_cloud(36, 18);
+    let path = std::env::temp_dir().join("bpa_rs_trace/sphere.ndjson");
+    let config = ReconstructionConfig {
+        trace: Some(path.clone()),
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    let report = reconstruct_with_config(&cloud, &config)
+        .expect("must generate a mesh")
+        .report;
+
+    let text = std::fs::read_to_string(&path).expect("no trace written");
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].starts_with(r#"{"step":"seed","triangle":[["#));
+    assert!(lines.iter().all(|l| l.starts_with('{') && l.ends_with('}')));
+    let steps = |step: &str| {
+        let prefix = format!(r#"{{"step":"{step}""#);
+        lines.iter().filter(|l| l.starts_with(&prefix)).count()
+    };
+    assert_eq!(steps("pivot"), report.diagnostics.pivots);
+    assert_eq!(steps("boundary"), report.diagnostics.boundary_edges);
+    assert!(steps("glue") > 0);
+    assert!(text.contains(r#""rejected":"normal_half_space""#));
+    assert!(!text.contains("NaN"));
+}
+
+#[test]
+fn unwritable_trace_is_skipped() {
+    let cloud = create_spherical_cloud(36, 18);
+    // The trace's parent is a file, so the trace cannot be created.
+    let parent = std::env::temp_dir().join("bpa_rs_trace_parent_is_a_file");
+    std::fs::write(&parent, "").unwrap();
+    let config = ReconstructionConfig {
+        trace: Some(parent.join("trace.ndjson")),
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    let traced = reconstruct_with_config(&cloud, &config).expect("must generate a mesh");
+    let untraced = reconstruct_with_config(&cloud, &ReconstructionConfig::new(0.3))
+        .expect("must generate a mesh");
+    assert_eq!(traced.triangles.len(), untraced.triangles.len());
+}
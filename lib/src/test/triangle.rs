@@ -0,0 +1,35 @@
+use glam::Vec3;
+
+use crate::Triangle;
+
+#[test]
+fn arrays() {
+    let raw = [[0.0, 1.0, 2.0], [3.0, 4.0, 5.0], [6.0, 7.0, 8.0]];
+    let t = Triangle::from(raw);
+    assert_eq!(
+        t.0,
+        [
+            Vec3::new(0.0, 1.0, 2.0),
+            Vec3::new(3.0, 4.0, 5.0),
+            Vec3::new(6.0, 7.0, 8.0)
+        ]
+    );
+    assert_eq!(<[[f32; 3]; 3]>::from(t), raw);
+
+    // A mesh flattens to the layout of a vertex buffer.
+    let triangles = vec![Triangle::from(raw); 2];
+    let buffer: Vec<[[f32; 3]; 3]> = triangles.into_iter().map(Into::into).collect();
+    assert_eq!(buffer.as_flattened().as_flattened().len(), 18);
+}
+
+#[test]
+fn iter() {
+    let t = Triangle([Vec3::ZERO, Vec3::X, Vec3::Y]);
+    assert_eq!(t.iter().copied().collect::<Vec<_>>(), t.0);
+    let mut sum = Vec3::ZERO;
+    for v in &t {
+        sum += *v;
+    }
+    assert_eq!(sum, Vec3::new(1.0, 1.0, 0.0));
+    assert_eq!(t.iter().len(), 3);
+}
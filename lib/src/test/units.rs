@@ -0,0 +1,42 @@
+use glam::Vec3;
+
+use crate::ReconstructionConfig;
+use crate::Triangle;
+use crate::provenance::Provenance;
+use crate::units::Unit;
+use crate::units::scale;
+
+#[test]
+fn conversions() {
+    assert!((Unit::Meter.scale_to(Unit::Millimeter) - 1000.0).abs() < 1e-3);
+    assert!((Unit::Inch.scale_to(Unit::Millimeter) - 25.4).abs() < 1e-4);
+    assert!((Unit::Centimeter.scale_to(Unit::Inch) - 1.0 / 2.54).abs() < 1e-6);
+    assert!((Unit::Millimeter.scale_to(Unit::Millimeter) - 1.0).abs() < f32::EPSILON);
+
+    for unit in [Unit::Millimeter, Unit::Centimeter, Unit::Meter, Unit::Inch] {
+        assert_eq!(unit.to_string().parse::<Unit>(), Ok(unit));
+    }
+    assert!("ft".parse::<Unit>().is_err());
+}
+
+#[test]
+fn scale_triangles() {
+    let mut triangles = vec![Triangle([Vec3::X, Vec3::Y, Vec3::new(0.5, 0.25, 2.0)])];
+    scale(&mut triangles, Unit::Meter.scale_to(Unit::Millimeter));
+    assert_eq!(
+        triangles[0].0,
+        [
+            Vec3::new(1000.0, 0.0, 0.0),
+            Vec3::new(0.0, 1000.0, 0.0),
+            Vec3::new(500.0, 250.0, 2000.0)
+        ]
+    );
+}
+
+#[test]
+fn unit_is_recorded() {
+    let provenance = Provenance::new(&ReconstructionConfig::new(0.002)).with_unit(Unit::Millimeter);
+    assert!(provenance.lines().contains(&String::from("unit mm")));
+    let header = String::from_utf8(provenance.stl_header().to_vec()).expect("ascii header");
+    assert!(header.contains(" unit=mm "));
+}
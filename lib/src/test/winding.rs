@@ -0,0 +1,43 @@
+use crate::ReconstructionConfig;
+use crate::config::Winding;
+use crate::parallel::reconstruct_parallel;
+use crate::reconstruct_with_config;
+use crate::test::reconstruct::create_spherical_cloud;
+
+#[test]
+fn clockwise_swaps_vertices() {
+    let cloud = create_spherical_cloud(36, 18);
+    let ccw = ReconstructionConfig::new(0.3);
+    let cw = ReconstructionConfig {
+        winding: Winding::Clockwise,
+        ..ReconstructionConfig::new(0.3)
+    };
+
+    let mut expected = reconstruct_with_config(&cloud, &ccw)
+        .expect("must generate a mesh")
+        .triangles;
+    expected.iter_mut().for_each(crate::Triangle::flip);
+    let triangles = reconstruct_with_config(&cloud, &cw)
+        .expect("must generate a mesh")
+        .triangles;
+    assert_eq!(format!("{triangles:?}"), format!("{expected:?}"));
+}
+
+#[test]
+fn clockwise_parallel() {
+    let cloud = create_spherical_cloud(100, 50);
+    let ccw = ReconstructionConfig::new(0.1);
+    let cw = ReconstructionConfig {
+        winding: Winding::Clockwise,
+        ..ReconstructionConfig::new(0.1)
+    };
+
+    let mut expected = reconstruct_parallel(&cloud, &ccw, 2)
+        .expect("must generate a mesh")
+        .triangles;
+    expected.iter_mut().for_each(crate::Triangle::flip);
+    let triangles = reconstruct_parallel(&cloud, &cw, 2)
+        .expect("must generate a mesh")
+        .triangles;
+    assert_eq!(format!("{triangles:?}"), format!("{expected:?}"));
+}
@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+
+use glam::DVec3;
+use glam::Vec3;
+
+use crate::Triangle;
+
+/// A mesh whose triangles share their vertices.
+///
+/// Triangles keep the order and winding they were given in, so face `i` is
+/// triangle `i`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IndexedMesh {
+    /// The distinct vertices, in the order they are first used.
+    pub vertices: Vec<Vec3>,
+    /// Each triangle as indices into `vertices`.
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl IndexedMesh {
+    /// Joins the triangles at the vertices they share. Only vertices with
+    /// identical coordinates are joined, as they are in a reconstruction.
+    #[must_use]
+    pub fn new(triangles: &[Triangle]) -> Self {
+        let mut index: HashMap<[u32; 3], usize> = HashMap::new();
+        let mut vertices: Vec<Vec3> = vec![];
+        let faces = triangles
+            .iter()
+            .map(|t| {
+                t.0.map(|v| {
+                    *index
+                        .entry(v.to_array().map(f32::to_bits))
+                        .or_insert_with(|| {
+                            vertices.push(v);
+                            vertices.len() - 1
+                        })
+                })
+            })
+            .collect();
+        Self { vertices, faces }
+    }
+
+    /// Joins the triangles at vertices within `tolerance` of each other, so
+    /// that rounding between triangles meant to share a point leaves no
+    /// crack.
+    ///
+    /// Each vertex joins the first vertex used within the tolerance, found
+    /// through a grid of cubes `tolerance` wide. A triangle narrower than
+    /// the tolerance can lose a corner this way; it is kept as a degenerate
+    /// face, so face `i` is still triangle `i`. A tolerance that is not
+    /// positive joins identical vertices only, as [`Self::new()`] does.
+    #[must_use]
+    pub fn welded(triangles: &[Triangle], tolerance: f32) -> Self {
+        if !(tolerance > 0.0 && tolerance.is_finite()) {
+            return Self::new(triangles);
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let cell = |v: Vec3| (v / tolerance).floor().as_i64vec3().to_array();
+        let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+        let mut vertices: Vec<Vec3> = vec![];
+        let faces = triangles
+            .iter()
+            .map(|t| {
+                t.0.map(|v| {
+                    let [x, y, z] = cell(v);
+                    let near = (-1..=1)
+                        .flat_map(|dx| {
+                            (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| [dx, dy, dz]))
+                        })
+                        .filter_map(|[dx, dy, dz]| grid.get(&[x + dx, y + dy, z + dz]))
+                        .flatten()
+                        .copied()
+                        .filter(|&i| vertices[i].distance_squared(v) <= tolerance * tolerance)
+                        .min();
+                    near.unwrap_or_else(|| {
+                        vertices.push(v);
+                        grid.entry([x, y, z]).or_default().push(vertices.len() - 1);
+                        vertices.len() - 1
+                    })
+                })
+            })
+            .collect();
+        Self { vertices, faces }
+    }
+
+    /// The faces as triangles.
+    #[must_use]
+    pub fn triangles(&self) -> Vec<Triangle> {
+        self.faces
+            .iter()
+            .map(|f| Triangle(f.map(|v| self.vertices[v])))
+            .collect()
+    }
+
+    /// The discrete curvature at each vertex, in the order of `vertices`.
+    ///
+    /// The mean curvature comes from the cotangent Laplacian and the
+    /// Gaussian curvature from the angle deficit, both over the mixed
+    /// Voronoi area of the vertex (Meyer et al., 2003). The normal, which
+    /// gives the mean curvature its sign, follows the winding of the faces.
+    /// Both are zero on the boundary, where they are not defined, and at
+    /// vertices whose faces have no area.
+    #[must_use]
+    pub fn curvature(&self) -> Vec<VertexCurvature> {
+        let count = self.vertices.len();
+        let mut area = vec![0.0; count];
+        let mut angles = vec![0.0; count];
+        let mut laplacian = vec![DVec3::ZERO; count];
+        let mut normal = vec![DVec3::ZERO; count];
+        for face in &self.faces {
+            let p = face.map(|v| self.vertices[v].as_dvec3());
+            let cross = (p[1] - p[0]).cross(p[2] - p[0]);
+            let face_area = cross.length() / 2.0;
+            if face_area == 0.0 {
+                continue;
+            }
+            // The angle at each corner, between the edges to the others.
+            let angle = |i: usize| {
+                let (a, b) = (p[(i + 1) % 3] - p[i], p[(i + 2) % 3] - p[i]);
+                a.angle_between(b)
+            };
+            let corner = [angle(0), angle(1), angle(2)];
+            let obtuse = corner
+                .iter()
+                .position(|&a| a > core::f64::consts::FRAC_PI_2);
+            for i in 0..3 {
+                let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+                let (v, cot_j, cot_k) = (face[i], 1.0 / corner[j].tan(), 1.0 / corner[k].tan());
+                angles[v] += corner[i];
+                normal[v] += cross;
+                // The cotangent of the angle opposite each edge weighs it.
+                laplacian[v] += (p[k] - p[i]) * cot_j + (p[j] - p[i]) * cot_k;
+                area[v] += match obtuse {
+                    None => {
+                        p[i].distance_squared(p[k])
+                            .mul_add(cot_j, p[i].distance_squared(p[j]) * cot_k)
+                            / 8.0
+                    }
+                    Some(o) if o == i => face_area / 2.0,
+                    Some(_) => face_area / 4.0,
+                };
+            }
+        }
+
+        let mut boundary = vec![false; count];
+        for v in self.half_edges().boundary_vertices() {
+            boundary[v] = true;
+        }
+        let mut curvature = vec![VertexCurvature::default(); count];
+        for v in 0..count {
+            if area[v] == 0.0 || boundary[v] {
+                continue;
+            }
+            let laplacian = laplacian[v] / (2.0 * area[v]);
+            let mean = -laplacian.dot(normal[v].normalize_or_zero()) / 2.0;
+            let gaussian = (core::f64::consts::TAU - angles[v]) / area[v];
+            #[allow(clippy::cast_possible_truncation)]
+            let (mean, gaussian) = (mean as f32, gaussian as f32);
+            curvature[v] = VertexCurvature { mean, gaussian };
+        }
+        curvature
+    }
+
+    /// Builds the adjacency of the faces. See [`HalfEdges`].
+    #[must_use]
+    pub fn half_edges(&self) -> HalfEdges {
+        HalfEdges::new(self)
+    }
+}
+
+impl From<&[Triangle]> for IndexedMesh {
+    /// As [`IndexedMesh::new()`]. Use [`IndexedMesh::welded()`] for
+    /// triangles whose shared vertices may differ by rounding, such as those
+    /// of a mesh saved in another tool.
+    fn from(triangles: &[Triangle]) -> Self {
+        Self::new(triangles)
+    }
+}
+
+/// The discrete curvature of an [`IndexedMesh`] at a vertex, from
+/// [`IndexedMesh::curvature()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VertexCurvature {
+    /// The mean of the principal curvatures. Positive where the surface
+    /// bends away from its normal, as on a sphere wound outward.
+    pub mean: f32,
+    /// The product of the principal curvatures: positive on domes and bowls,
+    /// negative on saddles and zero on cylinders and planes.
+    pub gaussian: f32,
+}
+
+/// The adjacency of the faces of an [`IndexedMesh`], as half-edges.
+///
+/// Each face has three half-edges, running around it in its winding order:
+/// half-edge `3 * f + c` runs from corner `c` of face `f` to the next
+/// corner. Two half-edges running in opposite directions along the same
+/// edge are twins. An edge on the boundary of the mesh has a half-edge
+/// without a twin, and so does an edge shared by more than two faces, or by
+/// two faces wound in opposite directions.
+#[derive(Clone, Debug)]
+pub struct HalfEdges {
+    faces: Vec<[usize; 3]>,
+    twins: Vec<Option<usize>>,
+    // The half-edges leaving each vertex: those of vertex `v` are
+    // `outgoing[first[v]..first[v + 1]]`.
+    first: Vec<usize>,
+    outgoing: Vec<usize>,
+}
+
+impl HalfEdges {
+    fn new(mesh: &IndexedMesh) -> Self {
+        let faces = mesh.faces.clone();
+        let count = faces.len() * 3;
+        let origin = |h: usize| faces[h / 3][h % 3];
+        let target = |h: usize| faces[h / 3][(h + 1) % 3];
+
+        let mut by_edge: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for h in 0..count {
+            by_edge.entry((origin(h), target(h))).or_default().push(h);
+        }
+        let twins = (0..count)
+            .map(|h| {
+                let along = &by_edge[&(origin(h), target(h))];
+                match by_edge.get(&(target(h), origin(h))).map(Vec::as_slice) {
+                    Some(&[twin]) if along.len() == 1 => Some(twin),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        let mut first = vec![0; mesh.vertices.len() + 1];
+        for h in 0..count {
+            first[origin(h) + 1] += 1;
+        }
+        for v in 0..mesh.vertices.len() {
+            first[v + 1] += first[v];
+        }
+        let mut filled = first.clone();
+        let mut outgoing = vec![0; count];
+        for h in 0..count {
+            let v = origin(h);
+            outgoing[filled[v]] = h;
+            filled[v] += 1;
+        }
+
+        Self {
+            faces,
+            twins,
+            first,
+            outgoing,
+        }
+    }
+
+    /// The number of half-edges, three for each face.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.twins.len()
+    }
+
+    /// True when the mesh has no faces.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.twins.is_empty()
+    }
+
+    /// The vertex half-edge `h` starts at.
+    #[must_use]
+    pub fn origin(&self, h: usize) -> usize {
+        self.faces[h / 3][h % 3]
+    }
+
+    /// The vertex half-edge `h` ends at.
+    #[must_use]
+    pub fn target(&self, h: usize) -> usize {
+        self.faces[h / 3][(h + 1) % 3]
+    }
+
+    /// The face half-edge `h` belongs to.
+    #[must_use]
+    pub const fn face(&self, h: usize) -> usize {
+        h / 3
+    }
+
+    /// The half-edge after `h` around its face.
+    #[must_use]
+    pub const fn next(&self, h: usize) -> usize {
+        h - h % 3 + (h + 1) % 3
+    }
+
+    /// The half-edge before `h` around its face.
+    #[must_use]
+    pub const fn prev(&self, h: usize) -> usize {
+        h - h % 3 + (h + 2) % 3
+    }
+
+    /// The half-edge running the other way along the same edge, in the
+    /// neighbouring face.
+    #[must_use]
+    pub fn twin(&self, h: usize) -> Option<usize> {
+        self.twins[h]
+    }
+
+    /// The half-edges leaving vertex `v`.
+    pub fn outgoing(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        let range = self.first.get(v).zip(self.first.get(v + 1));
+        range
+            .map_or(&[][..], |(&start, &end)| &self.outgoing[start..end])
+            .iter()
+            .copied()
+    }
+
+    /// The faces with a corner at vertex `v`.
+    pub fn vertex_faces(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.outgoing(v).map(|h| self.face(h))
+    }
+
+    /// The vertices joined to vertex `v` by an edge, each once on a
+    /// manifold mesh. They are not in order around the vertex.
+    pub fn one_ring(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.outgoing(v).flat_map(move |h| {
+            // An edge arriving without a twin leaving has no outgoing
+            // half-edge of its own.
+            let arriving = self.prev(h);
+            let unpaired = self.twin(arriving).is_none().then(|| self.origin(arriving));
+            core::iter::once(self.target(h)).chain(unpaired)
+        })
+    }
+
+    /// The faces sharing an edge with face `f`.
+    pub fn face_neighbours(&self, f: usize) -> impl Iterator<Item = usize> + '_ {
+        (3 * f..3 * f + 3).filter_map(|h| self.twin(h).map(|t| self.face(t)))
+    }
+
+    /// The half-edges on the boundary of the mesh, those without a twin.
+    pub fn boundary_edges(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(|&h| self.twin(h).is_none())
+    }
+
+    /// The vertices on the boundary of the mesh, each once, in order.
+    pub fn boundary_vertices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.first.len() - 1).filter(|&v| {
+            self.outgoing(v)
+                .any(|h| self.twin(h).is_none() || self.twin(self.prev(h)).is_none())
+        })
+    }
+
+    /// The boundary edges joined end to end into loops, one around each hole
+    /// and one around the outside of an open surface. Each loop is a list of
+    /// half-edges.
+    ///
+    /// Where a vertex touches more than one loop, the loops may be joined
+    /// there.
+    #[must_use]
+    pub fn boundary_loops(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.len()];
+        let mut loops = vec![];
+        for start in self.boundary_edges() {
+            if visited[start] {
+                continue;
+            }
+            let mut boundary = vec![];
+            let mut h = start;
+            loop {
+                visited[h] = true;
+                boundary.push(h);
+                let Some(following) = self
+                    .outgoing(self.target(h))
+                    .find(|&n| self.twin(n).is_none() && !visited[n])
+                else {
+                    break;
+                };
+                h = following;
+            }
+            loops.push(boundary);
+        }
+        loops
+    }
+}
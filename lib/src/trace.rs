@@ -0,0 +1,190 @@
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::convert::Infallible;
+#[cfg(feature = "std")]
+use std::fmt::Write as _;
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::BufWriter;
+#[cfg(feature = "std")]
+use std::io::Write;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use glam::Vec3;
+
+use crate::grid::GlueCase;
+use crate::grid::SeedResult;
+
+// What became of a point considered while pivoting.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+#[derive(Clone, Copy, Debug)]
+pub enum Outcome {
+    // Rejected for the given reason, named as in `PivotRejections`.
+    Rejected(&'static str),
+    // Passed the checks, with the given pivot angle.
+    Angle(f32),
+}
+
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+#[derive(Clone, Copy, Debug)]
+pub struct Candidate {
+    pub pos: Vec3,
+    pub outcome: Outcome,
+}
+
+// Records the candidate when a trace is being written.
+pub fn note(candidates: &mut Option<&mut Vec<Candidate>>, pos: Vec3, outcome: Outcome) {
+    if let Some(candidates) = candidates.as_deref_mut() {
+        candidates.push(Candidate { pos, outcome });
+    }
+}
+
+// Newline delimited JSON, one object for each step of the algorithm.
+#[cfg(feature = "std")]
+pub struct Trace {
+    writer: BufWriter<File>,
+}
+
+#[cfg(feature = "std")]
+impl Trace {
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+        })
+    }
+
+    pub fn seed(&mut self, seed: &SeedResult) -> std::io::Result<()> {
+        let vertices = seed.f.0.each_ref().map(|p| vec3(p.borrow().pos));
+        writeln!(
+            self.writer,
+            r#"{{"step":"seed","triangle":[{}],"center":{}}}"#,
+            vertices.join(","),
+            vec3(seed.ball_center)
+        )
+    }
+
+    // Writes the pivot around the edge from `a` to `b`, with the candidates
+    // it considered.
+    pub fn pivot(
+        &mut self,
+        a: Vec3,
+        b: Vec3,
+        candidates: &[Candidate],
+        accepted: Option<Vec3>,
+    ) -> std::io::Result<()> {
+        let mut list = String::new();
+        for (i, c) in candidates.iter().enumerate() {
+            if i > 0 {
+                list.push(',');
+            }
+            let _ = match c.outcome {
+                Outcome::Rejected(reason) => {
+                    write!(list, r#"{{"point":{},"rejected":"{reason}"}}"#, vec3(c.pos))
+                }
+                Outcome::Angle(angle) => write!(
+                    list,
+                    r#"{{"point":{},"angle":{}}}"#,
+                    vec3(c.pos),
+                    number(angle)
+                ),
+            };
+        }
+        writeln!(
+            self.writer,
+            r#"{{"step":"pivot","edge":[{},{}],"candidates":[{list}],"accepted":{}}}"#,
+            vec3(a),
+            vec3(b),
+            accepted.map_or_else(|| String::from("null"), vec3)
+        )
+    }
+
+    pub fn glue(&mut self, case: GlueCase) -> std::io::Result<()> {
+        let case = match case {
+            GlueCase::ClosedLoop => "closed_loop",
+            GlueCase::Adjacent => "adjacent",
+            GlueCase::Split => "split",
+        };
+        writeln!(self.writer, r#"{{"step":"glue","case":"{case}"}}"#)
+    }
+
+    pub fn boundary(&mut self, a: Vec3, b: Vec3) -> std::io::Result<()> {
+        writeln!(
+            self.writer,
+            r#"{{"step":"boundary","edge":[{},{}]}}"#,
+            vec3(a),
+            vec3(b)
+        )
+    }
+
+    pub fn stalled(&mut self, a: Vec3, b: Vec3) -> std::io::Result<()> {
+        writeln!(
+            self.writer,
+            r#"{{"step":"stalled","edge":[{},{}]}}"#,
+            vec3(a),
+            vec3(b)
+        )
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+// Traces are written to files, so without `std` none is ever created.
+#[cfg(not(feature = "std"))]
+pub struct Trace(Infallible);
+
+#[cfg(not(feature = "std"))]
+impl Trace {
+    pub const fn seed(&self, _: &SeedResult) -> core::fmt::Result {
+        match self.0 {}
+    }
+
+    pub const fn pivot(
+        &self,
+        _: Vec3,
+        _: Vec3,
+        _: &[Candidate],
+        _: Option<Vec3>,
+    ) -> core::fmt::Result {
+        match self.0 {}
+    }
+
+    pub const fn glue(&self, _: GlueCase) -> core::fmt::Result {
+        match self.0 {}
+    }
+
+    pub const fn boundary(&self, _: Vec3, _: Vec3) -> core::fmt::Result {
+        match self.0 {}
+    }
+
+    pub const fn stalled(&self, _: Vec3, _: Vec3) -> core::fmt::Result {
+        match self.0 {}
+    }
+
+    // Takes `&mut self` to match the writer, for `Mesher::trace`.
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub const fn flush(&mut self) -> core::fmt::Result {
+        match self.0 {}
+    }
+}
+
+// JSON has no representation of NaN or infinity.
+#[cfg(feature = "std")]
+fn number(f: f32) -> String {
+    if f.is_finite() {
+        format!("{f}")
+    } else {
+        String::from("null")
+    }
+}
+
+#[cfg(feature = "std")]
+fn vec3(v: Vec3) -> String {
+    format!("[{},{},{}]", number(v.x), number(v.y), number(v.z))
+}
@@ -0,0 +1,88 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::Triangle;
+
+/// A unit of length.
+///
+/// STL and OBJ files carry no unit, so a mesh is scaled into the unit its
+/// reader expects before it is written. Slicers usually expect millimetres.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Unit {
+    /// Millimetres, `mm`.
+    Millimeter,
+    /// Centimetres, `cm`.
+    Centimeter,
+    /// Metres, `m`.
+    #[default]
+    Meter,
+    /// Inches, `in`.
+    Inch,
+}
+
+impl Unit {
+    /// The length of the unit in millimetres, the smallest unit, so that
+    /// whole conversions such as metres to millimetres are exact.
+    #[must_use]
+    pub const fn millimeters(self) -> f32 {
+        match self {
+            Self::Millimeter => 1.0,
+            Self::Centimeter => 10.0,
+            Self::Meter => 1000.0,
+            Self::Inch => 25.4,
+        }
+    }
+
+    /// The factor converting lengths in this unit into `to`.
+    #[must_use]
+    pub fn scale_to(self, to: Self) -> f32 {
+        self.millimeters() / to.millimeters()
+    }
+
+    /// The abbreviation, as accepted by [`Unit::from_str()`].
+    #[must_use]
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::Millimeter => "mm",
+            Self::Centimeter => "cm",
+            Self::Meter => "m",
+            Self::Inch => "in",
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+impl FromStr for Unit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mm" => Ok(Self::Millimeter),
+            "cm" => Ok(Self::Centimeter),
+            "m" => Ok(Self::Meter),
+            "in" => Ok(Self::Inch),
+            other => Err(format!("unknown unit {other}, expected mm, cm, m or in")),
+        }
+    }
+}
+
+/// Multiplies every vertex by `factor`, as when converting between units
+/// with [`Unit::scale_to()`].
+pub fn scale(triangles: &mut [Triangle], factor: f32) {
+    #[allow(clippy::float_cmp)]
+    if factor == 1.0 {
+        return;
+    }
+    for t in triangles {
+        for v in &mut t.0 {
+            *v *= factor;
+        }
+    }
+}